@@ -0,0 +1,35 @@
+//! The diagnostic items of a crate, indexed in both directions.
+
+use crate::def_id::DefId;
+use crate::HashStableContext;
+use rustc_data_structures::fx::FxHashMap;
+use rustc_data_structures::stable_hasher::{HashStable, StableHasher};
+use rustc_span::Symbol;
+
+#[derive(Debug, Default)]
+pub struct DiagnosticItems {
+    /// The name of each item. An item known under several names maps to the one that sorts
+    /// first, so that the choice doesn't depend on the order the names were collected in.
+    pub id_to_name: FxHashMap<DefId, Symbol>,
+    pub name_to_id: FxHashMap<Symbol, DefId>,
+}
+
+impl DiagnosticItems {
+    /// Registers `def_id` under `name` in both directions. Returns the item previously registered
+    /// under `name`, if any, which is now replaced.
+    pub fn insert(&mut self, name: Symbol, def_id: DefId) -> Option<DefId> {
+        let known_name = self.id_to_name.entry(def_id).or_insert(name);
+        if name.as_str() < known_name.as_str() {
+            *known_name = name;
+        }
+        self.name_to_id.insert(name, def_id)
+    }
+}
+
+impl<CTX: HashStableContext> HashStable<CTX> for DiagnosticItems {
+    #[inline]
+    fn hash_stable(&self, ctx: &mut CTX, hasher: &mut StableHasher) {
+        // `id_to_name` is derived from `name_to_id`.
+        self.name_to_id.hash_stable(ctx, hasher);
+    }
+}
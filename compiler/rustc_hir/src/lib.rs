@@ -18,6 +18,7 @@
 mod arena;
 pub mod def;
 pub mod definitions;
+pub mod diagnostic_items;
 pub use rustc_span::def_id;
 mod hir;
 pub mod hir_id;
@@ -18,6 +18,7 @@
 use rustc_hir::def::{CtorKind, CtorOf, DefKind, Res};
 use rustc_hir::def_id::{CrateNum, DefId, DefIndex, CRATE_DEF_INDEX, LOCAL_CRATE};
 use rustc_hir::definitions::{DefKey, DefPath, DefPathData, DefPathHash};
+use rustc_hir::diagnostic_items::DiagnosticItems;
 use rustc_hir::lang_items;
 use rustc_index::vec::{Idx, IndexVec};
 use rustc_middle::hir::exports::Export;
@@ -1001,17 +1002,15 @@ fn get_lang_items(&self, tcx: TyCtxt<'tcx>) -> &'tcx [(DefId, usize)] {
     }
 
     /// Iterates over the diagnostic items in the given crate.
-    fn get_diagnostic_items(&self) -> FxHashMap<Symbol, DefId> {
-        if self.root.is_proc_macro_crate() {
-            // Proc macro crates do not export any diagnostic-items to the target.
-            Default::default()
-        } else {
-            self.root
-                .diagnostic_items
-                .decode(self)
-                .map(|(name, def_index)| (name, self.local_def_id(def_index)))
-                .collect()
+    fn get_diagnostic_items(&self) -> DiagnosticItems {
+        let mut items = DiagnosticItems::default();
+        // Proc macro crates do not export any diagnostic-items to the target.
+        if !self.root.is_proc_macro_crate() {
+            for (name, def_index) in self.root.diagnostic_items.decode(self) {
+                items.insert(name, self.local_def_id(def_index));
+            }
         }
+        items
     }
 
     /// Iterates over each child of the given item.
@@ -1379,7 +1379,7 @@
     }
 
     /// Returns all diagnostic items defined in all crates.
-    query all_diagnostic_items(_: ()) -> FxHashMap<Symbol, DefId> {
+    query all_diagnostic_items(_: ()) -> DiagnosticItems {
         storage(ArenaCacheSelector<'tcx>)
         eval_always
         desc { "calculating the diagnostic items map" }
@@ -1391,11 +1391,22 @@
     }
 
     /// Returns the diagnostic items defined in a crate.
-    query diagnostic_items(_: CrateNum) -> FxHashMap<Symbol, DefId> {
+    query diagnostic_items(_: CrateNum) -> DiagnosticItems {
         storage(ArenaCacheSelector<'tcx>)
         desc { "calculating the diagnostic items map in a crate" }
     }
 
+    /// Returns the name of the diagnostic item `def_id` is, if it is one.
+    query diagnostic_name(def_id: DefId) -> Option<Symbol> {
+        desc { |tcx| "looking up the diagnostic item name of `{}`", tcx.def_path_str(def_id) }
+    }
+
+    /// Returns the names of the diagnostic items defined in a crate, sorted
+    /// alphabetically.
+    query diagnostic_items_of_crate(_: CrateNum) -> &'tcx [Symbol] {
+        desc { "listing the diagnostic items defined in a crate" }
+    }
+
     query missing_lang_items(_: CrateNum) -> &'tcx [LangItem] {
         desc { "calculating the missing lang items in a crate" }
     }
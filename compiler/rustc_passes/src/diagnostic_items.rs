@@ -12,16 +12,21 @@
 use rustc_ast as ast;
 use rustc_data_structures::fx::FxHashMap;
 use rustc_hir as hir;
+use rustc_hir::diagnostic_items::DiagnosticItems;
 use rustc_hir::itemlikevisit::ItemLikeVisitor;
+use rustc_middle::ty::print::with_no_trimmed_paths;
 use rustc_middle::ty::query::Providers;
 use rustc_middle::ty::TyCtxt;
 use rustc_session::Session;
 use rustc_span::def_id::{CrateNum, DefId, LocalDefId, LOCAL_CRATE};
 use rustc_span::symbol::{sym, Symbol};
 
+#[cfg(test)]
+mod tests;
+
 struct DiagnosticItemCollector<'tcx> {
     // items from this crate
-    items: FxHashMap<Symbol, DefId>,
+    items: DiagnosticItems,
     tcx: TyCtxt<'tcx>,
 }
 
@@ -49,51 +54,66 @@ fn new(tcx: TyCtxt<'tcx>) -> DiagnosticItemCollector<'tcx> {
     }
 
     fn observe_item(&mut self, def_id: LocalDefId) {
-        let hir_id = self.tcx.hir().local_def_id_to_hir_id(def_id);
-        let attrs = self.tcx.hir().attrs(hir_id);
-        if let Some(name) = extract(&self.tcx.sess, attrs) {
+        let tcx = self.tcx;
+        let hir_id = tcx.hir().local_def_id_to_hir_id(def_id);
+        let attrs = tcx.hir().attrs(hir_id);
+        for name in extract(tcx.sess, attrs) {
             // insert into our table
-            collect_item(self.tcx, &mut self.items, name, def_id.to_def_id());
+            collect_item(tcx, &mut self.items, name, def_id.to_def_id());
         }
     }
 }
 
-fn collect_item(
-    tcx: TyCtxt<'_>,
-    items: &mut FxHashMap<Symbol, DefId>,
-    name: Symbol,
-    item_def_id: DefId,
-) {
+fn collect_item(tcx: TyCtxt<'_>, items: &mut DiagnosticItems, name: Symbol, item_def_id: DefId) {
     // Check for duplicates.
-    if let Some(original_def_id) = items.insert(name, item_def_id) {
-        if original_def_id != item_def_id {
-            let mut err = match tcx.hir().span_if_local(item_def_id) {
-                Some(span) => tcx.sess.struct_span_err(
-                    span,
-                    &format!("duplicate diagnostic item found: `{}`.", name),
-                ),
-                None => tcx.sess.struct_err(&format!(
-                    "duplicate diagnostic item in crate `{}`: `{}`.",
-                    tcx.crate_name(item_def_id.krate),
-                    name
-                )),
-            };
-            if let Some(span) = tcx.hir().span_if_local(original_def_id) {
-                err.span_note(span, "the diagnostic item is first defined here");
-            } else {
-                err.note(&format!(
-                    "the diagnostic item is first defined in crate `{}`.",
-                    tcx.crate_name(original_def_id.krate)
-                ));
-            }
-            err.emit();
+    if let Some(original_def_id) = insert_item(items, name, item_def_id) {
+        let mut err = match tcx.hir().span_if_local(item_def_id) {
+            Some(span) => tcx
+                .sess
+                .struct_span_err(span, &format!("duplicate diagnostic item found: `{}`.", name)),
+            None => tcx.sess.struct_err(&format!(
+                "duplicate diagnostic item in crate `{}`: `{}`.",
+                tcx.crate_name(item_def_id.krate),
+                name
+            )),
+        };
+        if let Some(span) = tcx.hir().span_if_local(original_def_id) {
+            err.span_note(span, "the diagnostic item is first defined here");
+        } else {
+            let original_path = with_no_trimmed_paths(|| tcx.def_path_str(original_def_id));
+            err.note(&format!(
+                "the diagnostic item is first defined as `{}` in crate `{}`.",
+                original_path,
+                tcx.crate_name(original_def_id.krate)
+            ));
+            err.help(&format!(
+                "diagnostic item names are shared with all dependencies; \
+                 rename this one, or namespace it with a prefix such as `{}_{}`",
+                tcx.crate_name(item_def_id.krate),
+                name
+            ));
         }
+        err.emit();
     }
 }
 
-/// Extract the first `rustc_diagnostic_item = "$name"` out of a list of attributes.
-fn extract(sess: &Session, attrs: &[ast::Attribute]) -> Option<Symbol> {
-    attrs.iter().find_map(|attr| {
+/// Registers `item_def_id` under `name`. Returns the other item that already had the name, if
+/// any, which is a duplicate to report; registering an item under one of its own names again is
+/// not.
+fn insert_item(items: &mut DiagnosticItems, name: Symbol, item_def_id: DefId) -> Option<DefId> {
+    items.insert(name, item_def_id).filter(|&original_def_id| original_def_id != item_def_id)
+}
+
+/// Extract every `rustc_diagnostic_item = "$name"` out of a list of attributes.
+///
+/// An item may carry the attribute more than once to be known under several names, for example
+/// to keep the old name working while lints migrate to a new one. Registering the same `DefId`
+/// twice is not a duplicate, so `collect_item` only complains if a name is taken by another item.
+fn extract<'a>(
+    sess: &'a Session,
+    attrs: &'a [ast::Attribute],
+) -> impl Iterator<Item = Symbol> + 'a {
+    attrs.iter().filter_map(move |attr| {
         if sess.check_name(attr, sym::rustc_diagnostic_item) {
             attr.value_str()
         } else {
@@ -103,7 +123,7 @@ fn extract(sess: &Session, attrs: &[ast::Attribute]) -> Option<Symbol> {
 }
 
 /// Traverse and collect the diagnostic items in the current
-fn diagnostic_items<'tcx>(tcx: TyCtxt<'tcx>, cnum: CrateNum) -> FxHashMap<Symbol, DefId> {
+fn diagnostic_items<'tcx>(tcx: TyCtxt<'tcx>, cnum: CrateNum) -> DiagnosticItems {
     assert_eq!(cnum, LOCAL_CRATE);
 
     // Initialize the collector.
@@ -120,13 +140,13 @@ fn diagnostic_items<'tcx>(tcx: TyCtxt<'tcx>, cnum: CrateNum) -> FxHashMap<Symbol
 }
 
 /// Traverse and collect all the diagnostic items in all crates.
-fn all_diagnostic_items<'tcx>(tcx: TyCtxt<'tcx>, (): ()) -> FxHashMap<Symbol, DefId> {
+fn all_diagnostic_items<'tcx>(tcx: TyCtxt<'tcx>, (): ()) -> DiagnosticItems {
     // Initialize the collector.
-    let mut collector = FxHashMap::default();
+    let mut collector = DiagnosticItems::default();
 
     // Collect diagnostic items in other crates.
     for &cnum in tcx.crates().iter().chain(std::iter::once(&LOCAL_CRATE)) {
-        for (&name, &def_id) in tcx.diagnostic_items(cnum).iter() {
+        for (&name, &def_id) in tcx.diagnostic_items(cnum).name_to_id.iter() {
             collect_item(tcx, &mut collector, name, def_id);
         }
     }
@@ -134,7 +154,27 @@ fn all_diagnostic_items<'tcx>(tcx: TyCtxt<'tcx>, (): ()) -> FxHashMap<Symbol, De
     collector
 }
 
+/// List the names of the diagnostic items defined in a single crate.
+fn diagnostic_items_of_crate<'tcx>(tcx: TyCtxt<'tcx>, cnum: CrateNum) -> &'tcx [Symbol] {
+    tcx.arena.alloc_from_iter(sorted_names(&tcx.diagnostic_items(cnum).name_to_id))
+}
+
+/// Look up the name of a diagnostic item from its `DefId`, without going through all the items.
+fn diagnostic_name<'tcx>(tcx: TyCtxt<'tcx>, def_id: DefId) -> Option<Symbol> {
+    tcx.diagnostic_items(def_id.krate).id_to_name.get(&def_id).copied()
+}
+
+/// Sort by the names themselves rather than by `Symbol`, whose order depends on when each
+/// name happened to be interned, so that the result is the same in every session.
+fn sorted_names(items: &FxHashMap<Symbol, DefId>) -> Vec<Symbol> {
+    let mut names: Vec<Symbol> = items.keys().copied().collect();
+    names.sort_by_cached_key(|name| name.as_str());
+    names
+}
+
 pub fn provide(providers: &mut Providers) {
     providers.diagnostic_items = diagnostic_items;
     providers.all_diagnostic_items = all_diagnostic_items;
+    providers.diagnostic_items_of_crate = diagnostic_items_of_crate;
+    providers.diagnostic_name = diagnostic_name;
 }
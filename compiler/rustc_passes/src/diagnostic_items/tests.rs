@@ -0,0 +1,88 @@
+use super::*;
+use rustc_ast::attr::{mk_attr_outer, mk_name_value_item_str};
+use rustc_errors::registry::Registry;
+use rustc_session::config::Options;
+use rustc_session::{build_session, DiagnosticOutput};
+use rustc_span::def_id::DefIndex;
+use rustc_span::symbol::Ident;
+use rustc_span::{with_default_session_globals, DUMMY_SP};
+
+#[test]
+fn test_sorted_names() {
+    with_default_session_globals(|| {
+        // Intern the names in reverse order, so that sorting by `Symbol` would get it wrong.
+        let second = Symbol::intern("zz_second_diagnostic_item");
+        let first = Symbol::intern("aa_first_diagnostic_item");
+        assert!(second < first);
+
+        let mut items = FxHashMap::default();
+        items.insert(second, DefId { krate: LOCAL_CRATE, index: DefIndex::from_u32(1) });
+        items.insert(first, DefId { krate: LOCAL_CRATE, index: DefIndex::from_u32(2) });
+        assert_eq!(sorted_names(&items), [first, second]);
+    })
+}
+
+#[test]
+fn test_insert_keeps_both_directions() {
+    with_default_session_globals(|| {
+        let iter = DefId { krate: LOCAL_CRATE, index: DefIndex::from_u32(1) };
+        let other = DefId { krate: LOCAL_CRATE, index: DefIndex::from_u32(2) };
+        let zz_name = Symbol::intern("zz_iterator");
+        let aa_name = Symbol::intern("aa_iterator");
+
+        let mut items = DiagnosticItems::default();
+        assert_eq!(items.insert(zz_name, iter), None);
+        assert_eq!(items.insert(aa_name, iter), None);
+        assert_eq!(items.name_to_id[&zz_name], iter);
+        assert_eq!(items.name_to_id[&aa_name], iter);
+        // Whichever order the aliases come in, the same one is picked.
+        assert_eq!(items.id_to_name[&iter], aa_name);
+
+        // Taking a name that's already in use reports the previous owner.
+        assert_eq!(items.insert(zz_name, other), Some(iter));
+        assert_eq!(items.id_to_name[&other], zz_name);
+    })
+}
+
+fn diagnostic_item(name: &str) -> ast::Attribute {
+    let ident = Ident::with_dummy_span(sym::rustc_diagnostic_item);
+    mk_attr_outer(mk_name_value_item_str(ident, Symbol::intern(name), DUMMY_SP))
+}
+
+#[test]
+fn test_extract_and_insert() {
+    with_default_session_globals(|| {
+        let sess = build_session(
+            Options::default(),
+            None,
+            Registry::new(&[]),
+            DiagnosticOutput::Default,
+            Default::default(),
+            None,
+            None,
+        );
+        let iter = DefId { krate: LOCAL_CRATE, index: DefIndex::from_u32(1) };
+        let other = DefId { krate: LOCAL_CRATE, index: DefIndex::from_u32(2) };
+        let mut items = DiagnosticItems::default();
+
+        // Two names on one item, among unrelated attributes.
+        let doc = mk_attr_outer(mk_name_value_item_str(
+            Ident::with_dummy_span(sym::doc),
+            Symbol::intern("An iterator."),
+            DUMMY_SP,
+        ));
+        let attrs = [diagnostic_item("Iterator"), doc, diagnostic_item("IteratorOld")];
+        let names: Vec<_> = extract(&sess, &attrs).collect();
+        assert_eq!(names, [Symbol::intern("Iterator"), Symbol::intern("IteratorOld")]);
+        for &name in &names {
+            assert_eq!(insert_item(&mut items, name, iter), None);
+        }
+        // Seeing the same item again, as when merging crates, is not a duplicate either.
+        assert_eq!(insert_item(&mut items, names[0], iter), None);
+
+        // Another item taking one of those names collides with the first.
+        let attrs = [diagnostic_item("IteratorOld")];
+        let names: Vec<_> = extract(&sess, &attrs).collect();
+        assert_eq!(insert_item(&mut items, names[0], other), Some(iter));
+    })
+}
@@ -21,7 +21,25 @@
 mod skip_while;
 mod step_by;
 mod take;
+mod take_crc32;
+mod take_ema;
+mod take_flat;
+mod take_hashing;
+mod take_heartbeat;
+mod take_interleaved;
+mod take_into_ring;
+mod take_monotonic;
+mod take_pairs;
+mod take_peekable;
+mod take_poll;
+mod take_records;
+mod take_sequenced;
+mod take_summarizing;
+mod take_then_chain;
+mod take_validated;
 mod take_while;
+mod take_with_breaker;
+mod take_with_progress;
 mod zip;
 
 pub use self::{
@@ -48,6 +66,60 @@
 #[unstable(feature = "iter_map_while", reason = "recently added", issue = "68537")]
 pub use self::map_while::MapWhile;
 
+#[unstable(feature = "iter_take_pairs", issue = "none")]
+pub use self::take_pairs::TakePairs;
+
+#[unstable(feature = "iter_take_then_chain", issue = "none")]
+pub use self::take_then_chain::TakeThenChain;
+
+#[unstable(feature = "iter_take_heartbeat", issue = "none")]
+pub use self::take_heartbeat::TakeHeartbeat;
+
+#[unstable(feature = "iter_take_hashing", issue = "none")]
+pub use self::take_hashing::TakeHashing;
+
+#[unstable(feature = "iter_take_records", issue = "none")]
+pub use self::take_records::TakeRecords;
+
+#[unstable(feature = "iter_take_poll", issue = "none")]
+pub use self::take_poll::TakePoll;
+
+#[unstable(feature = "iter_take_flat", issue = "none")]
+pub use self::take_flat::TakeFlat;
+
+#[unstable(feature = "iter_take_peekable", issue = "none")]
+pub use self::take_peekable::TakePeekable;
+
+#[unstable(feature = "iter_take_summarizing", issue = "none")]
+pub use self::take_summarizing::{Summary, TakeSummarizing};
+
+#[unstable(feature = "iter_take_interleaved", issue = "none")]
+pub use self::take_interleaved::TakeInterleaved;
+
+#[unstable(feature = "iter_take_with_progress", issue = "none")]
+pub use self::take_with_progress::TakeWithProgress;
+
+#[unstable(feature = "iter_take_ema", issue = "none")]
+pub use self::take_ema::TakeEma;
+
+#[unstable(feature = "iter_take_monotonic", issue = "none")]
+pub use self::take_monotonic::{OrderingViolation, TakeMonotonic};
+
+#[unstable(feature = "iter_take_into_ring", issue = "none")]
+pub use self::take_into_ring::RingFill;
+
+#[unstable(feature = "iter_take_sequenced", issue = "none")]
+pub use self::take_sequenced::TakeSequenced;
+
+#[unstable(feature = "iter_take_validated", issue = "none")]
+pub use self::take_validated::{InvalidPolicy, TakeValidated};
+
+#[unstable(feature = "iter_take_with_breaker", issue = "none")]
+pub use self::take_with_breaker::TakeWithBreaker;
+
+#[unstable(feature = "iter_take_crc32", issue = "none")]
+pub use self::take_crc32::TakeCrc32;
+
 #[unstable(feature = "trusted_random_access", issue = "none")]
 pub use self::zip::TrustedRandomAccess;
 
@@ -24,6 +24,182 @@ impl<I> Take<I> {
     pub(in crate::iter) fn new(iter: I, n: usize) -> Take<I> {
         Take { iter, n }
     }
+
+    /// Returns the number of elements this iterator may still yield.
+    ///
+    /// This is the cap passed to [`take`] minus the number of elements
+    /// consumed so far. It is an upper bound: the underlying iterator may run
+    /// out sooner. Together with [`with_remaining`] this allows a `Take` to be
+    /// checkpointed and resumed later; see there for details.
+    ///
+    /// [`take`]: Iterator::take
+    /// [`with_remaining`]: Take::with_remaining
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iter_take_resume)]
+    ///
+    /// let mut iter = (0..10).take(4);
+    /// assert_eq!(iter.remaining(), 4);
+    /// iter.next();
+    /// assert_eq!(iter.remaining(), 3);
+    /// ```
+    #[inline]
+    #[unstable(feature = "iter_take_resume", issue = "none")]
+    pub fn remaining(&self) -> usize {
+        self.n
+    }
+
+    /// Creates a `Take` that yields at most `n` more elements of `iter`.
+    ///
+    /// This is the same as `iter.take(n)`, and exists to resume a `Take`
+    /// whose [`remaining`] count was saved earlier. The workflow is:
+    ///
+    /// 1. Save `take.remaining()`, together with whatever is needed to rebuild
+    ///    the underlying iterator at its current position (an offset into a
+    ///    file, a database cursor, ...).
+    /// 2. Later, rebuild the underlying iterator at that position and pass it
+    ///    here along with the saved count.
+    ///
+    /// The resumed `Take` then yields exactly what the original would have
+    /// yielded had it not been interrupted, provided the rebuilt iterator
+    /// produces the same elements as the original one would have.
+    ///
+    /// Only a single `usize` needs to be persisted for the `Take` itself, so no
+    /// serialization framework is required: for example, store it with
+    /// [`u64::to_le_bytes`] and read it back with [`u64::from_le_bytes`].
+    ///
+    /// [`remaining`]: Take::remaining
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iter_take_resume)]
+    /// use std::iter::Take;
+    ///
+    /// let data = [1, 2, 3, 4, 5, 6];
+    ///
+    /// let mut batch = data.iter().take(4);
+    /// assert_eq!(batch.next(), Some(&1));
+    /// // Checkpoint: one element consumed, three left in the batch.
+    /// let (offset, remaining) = (1, batch.remaining());
+    ///
+    /// let resumed = Take::with_remaining(data[offset..].iter(), remaining);
+    /// assert_eq!(resumed.collect::<Vec<_>>(), [&2, &3, &4]);
+    /// ```
+    #[inline]
+    #[unstable(feature = "iter_take_resume", issue = "none")]
+    pub fn with_remaining(iter: I, n: usize) -> Take<I> {
+        Take::new(iter, n)
+    }
+
+    /// Consumes the `Take`, returning the underlying iterator.
+    ///
+    /// The iterator is returned at its current position. Any [`remaining`]
+    /// count is lost, so the returned iterator is no longer limited.
+    ///
+    /// [`remaining`]: Take::remaining
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iter_take_into_inner)]
+    ///
+    /// let mut take = vec![1, 2, 3, 4].into_iter().take(2);
+    /// assert_eq!(take.next(), Some(1));
+    ///
+    /// let rest = take.into_inner();
+    /// assert_eq!(rest.collect::<Vec<_>>(), [2, 3, 4]);
+    /// ```
+    #[inline]
+    #[unstable(feature = "iter_take_into_inner", issue = "none")]
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+
+    /// Gets a reference to the underlying iterator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iter_take_into_inner)]
+    ///
+    /// let mut take = [1, 2, 3, 4].iter().take(2);
+    /// take.next();
+    /// assert_eq!(take.get_ref().as_slice(), [2, 3, 4]);
+    /// ```
+    #[inline]
+    #[unstable(feature = "iter_take_into_inner", issue = "none")]
+    pub fn get_ref(&self) -> &I {
+        &self.iter
+    }
+
+    /// Gets a mutable reference to the underlying iterator.
+    ///
+    /// Elements taken from the underlying iterator this way don't count
+    /// towards the limit of this `Take`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iter_take_into_inner)]
+    ///
+    /// let mut take = (0..10).take(2);
+    /// assert_eq!(take.get_mut().next(), Some(0));
+    /// assert_eq!(take.collect::<Vec<_>>(), [1, 2]);
+    /// ```
+    #[inline]
+    #[unstable(feature = "iter_take_into_inner", issue = "none")]
+    pub fn get_mut(&mut self) -> &mut I {
+        &mut self.iter
+    }
+}
+
+impl<I: Iterator> Take<I> {
+    /// Consumes up to the [`remaining`] number of elements, splitting them
+    /// into two collections by a predicate.
+    ///
+    /// This is the same as [`Iterator::partition`], but the cap applies to the
+    /// number of elements consumed in total, not to each collection. Each
+    /// collection reserves space for half of the elements that are known to
+    /// be coming, so that together they never reserve more than is needed.
+    ///
+    /// [`remaining`]: Take::remaining
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iter_partition_take)]
+    ///
+    /// let (even, odd): (Vec<i32>, Vec<i32>) = (0..10).take(5).partition_take(|&n| n % 2 == 0);
+    ///
+    /// assert_eq!(even, [0, 2, 4]);
+    /// assert_eq!(odd, [1, 3]);
+    /// ```
+    #[unstable(feature = "iter_partition_take", issue = "none")]
+    pub fn partition_take<B, F>(self, mut predicate: F) -> (B, B)
+    where
+        B: Default + Extend<I::Item>,
+        F: FnMut(&I::Item) -> bool,
+    {
+        let mut matches: B = Default::default();
+        let mut non_matches: B = Default::default();
+
+        let (lower, _) = self.size_hint();
+        matches.extend_reserve(lower / 2);
+        non_matches.extend_reserve(lower / 2);
+
+        self.fold((), |(), x| {
+            if predicate(&x) {
+                matches.extend_one(x);
+            } else {
+                non_matches.extend_one(x);
+            }
+        });
+
+        (matches, non_matches)
+    }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
@@ -57,6 +233,21 @@ fn nth(&mut self, n: usize) -> Option<I::Item> {
         }
     }
 
+    #[inline]
+    fn advance_by(&mut self, n: usize) -> Result<(), usize> {
+        let min = cmp::min(self.n, n);
+        let advanced = match self.iter.advance_by(min) {
+            Ok(()) => min,
+            Err(k) => k,
+        };
+        self.n -= advanced;
+        if advanced < n {
+            Err(advanced)
+        } else {
+            Ok(())
+        }
+    }
+
     #[inline]
     fn size_hint(&self) -> (usize, Option<usize>) {
         if self.n == 0 {
@@ -119,6 +310,11 @@ fn ok<B, T>(mut f: impl FnMut(B, T) -> B) -> impl FnMut(B, T) -> Result<B, !> {
         self.try_fold(init, ok(fold)).unwrap()
     }
 
+    #[inline]
+    fn count(self) -> usize {
+        SpecTakeCount::spec_count(self)
+    }
+
     unsafe fn __iterator_get_unchecked(&mut self, idx: usize) -> <I as Iterator>::Item
     where
         Self: TrustedRandomAccess,
@@ -177,6 +373,24 @@ fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
         }
     }
 
+    #[inline]
+    fn advance_back_by(&mut self, n: usize) -> Result<(), usize> {
+        // The elements of `iter` past the first `self.n` are outside the
+        // window, so skip them first without counting them.
+        let trim = self.iter.len().saturating_sub(self.n);
+        let skip = trim.saturating_add(n);
+        let advanced = match self.iter.advance_back_by(skip) {
+            Ok(()) => skip - trim,
+            Err(k) => k.saturating_sub(trim),
+        };
+        self.n -= advanced;
+        if advanced < n {
+            Err(advanced)
+        } else {
+            Ok(())
+        }
+    }
+
     #[inline]
     fn try_rfold<Acc, Fold, R>(&mut self, init: Acc, fold: Fold) -> R
     where
@@ -232,3 +446,30 @@ unsafe impl<I> TrustedRandomAccess for Take<I>
 {
     const MAY_HAVE_SIDE_EFFECT: bool = I::MAY_HAVE_SIDE_EFFECT;
 }
+
+// Take::count specialization trait
+#[doc(hidden)]
+trait SpecTakeCount {
+    fn spec_count(self) -> usize;
+}
+
+impl<I: Iterator> SpecTakeCount for Take<I> {
+    #[inline]
+    default fn spec_count(self) -> usize {
+        // `fold` already stops at the limit.
+        self.fold(0, |count, _| count + 1)
+    }
+}
+
+impl<I: Iterator + TrustedRandomAccess> SpecTakeCount for Take<I> {
+    #[inline]
+    fn spec_count(self) -> usize {
+        // The elements only need to be produced if doing so has side effects;
+        // otherwise the exact size of `iter` is all that matters.
+        if I::MAY_HAVE_SIDE_EFFECT {
+            self.fold(0, |count, _| count + 1)
+        } else {
+            cmp::min(self.n, self.iter.size())
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use crate::hash::Hasher;
+use crate::iter::{FusedIterator, TakeHashing};
+
+/// A [`Hasher`] computing the CRC-32 checksum used by Ethernet, zlib and PNG
+/// (the IEEE 802.3 polynomial, reflected, with an initial value and final XOR
+/// of all ones).
+#[derive(Clone, Debug)]
+struct Crc32 {
+    // The running remainder, before the final XOR.
+    crc: u32,
+}
+
+impl Crc32 {
+    fn new() -> Crc32 {
+        Crc32 { crc: !0 }
+    }
+}
+
+/// The CRC of every byte value, for processing a byte at a time.
+static CRC32_TABLE: [u32; 256] = crc32_table();
+
+const fn crc32_table() -> [u32; 256] {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ 0xedb8_8320 } else { crc >> 1 };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+impl Hasher for Crc32 {
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.crc = CRC32_TABLE[((self.crc ^ byte as u32) & 0xff) as usize] ^ (self.crc >> 8);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        !self.crc as u64
+    }
+}
+
+/// An iterator that yields at most `n` bytes of `iter`, computing their
+/// CRC-32 checksum.
+///
+/// This `struct` is created by the [`take_crc32`] method on [`Iterator`]. See
+/// its documentation for more.
+///
+/// [`take_crc32`]: Iterator::take_crc32
+/// [`Iterator`]: trait.Iterator.html
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[unstable(feature = "iter_take_crc32", issue = "none")]
+pub struct TakeCrc32<I> {
+    inner: TakeHashing<I, Crc32>,
+}
+
+impl<I> TakeCrc32<I> {
+    pub(in crate::iter) fn new(iter: I, n: usize) -> TakeCrc32<I> {
+        TakeCrc32 { inner: TakeHashing::new(iter, n, Crc32::new()) }
+    }
+
+    /// Returns the CRC-32 of the `n` bytes taken, or `None` if fewer than `n`
+    /// bytes have been yielded so far.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iter_take_crc32)]
+    ///
+    /// let mut it = b"123456789".iter().copied().take_crc32(9);
+    /// assert_eq!(it.finish_crc(), None);
+    /// assert_eq!(it.by_ref().count(), 9);
+    /// assert_eq!(it.finish_crc(), Some(0xcbf4_3926));
+    /// ```
+    #[unstable(feature = "iter_take_crc32", issue = "none")]
+    pub fn finish_crc(&self) -> Option<u32> {
+        self.inner.finish().map(|crc| crc as u32)
+    }
+}
+
+#[unstable(feature = "iter_take_crc32", issue = "none")]
+impl<I: Iterator<Item = u8>> Iterator for TakeCrc32<I> {
+    type Item = u8;
+
+    #[inline]
+    fn next(&mut self) -> Option<u8> {
+        self.inner.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+#[unstable(feature = "iter_take_crc32", issue = "none")]
+impl<I: ExactSizeIterator<Item = u8>> ExactSizeIterator for TakeCrc32<I> {}
+
+#[unstable(feature = "iter_take_crc32", issue = "none")]
+impl<I: FusedIterator<Item = u8>> FusedIterator for TakeCrc32<I> {}
@@ -0,0 +1,80 @@
+use crate::iter::FusedIterator;
+
+/// An iterator that yields the exponential moving average of at most `n`
+/// elements of `iter`.
+///
+/// This `struct` is created by the [`take_ema`] method on [`Iterator`]. See
+/// its documentation for more.
+///
+/// [`take_ema`]: Iterator::take_ema
+/// [`Iterator`]: trait.Iterator.html
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[unstable(feature = "iter_take_ema", issue = "none")]
+pub struct TakeEma<I> {
+    iter: I,
+    n: usize,
+    alpha: f64,
+    prev: Option<f64>,
+}
+
+impl<I> TakeEma<I> {
+    pub(in crate::iter) fn new(iter: I, n: usize, alpha: f64) -> TakeEma<I> {
+        TakeEma { iter, n, alpha: alpha.clamp(0.0, 1.0), prev: None }
+    }
+}
+
+#[unstable(feature = "iter_take_ema", issue = "none")]
+impl<I> Iterator for TakeEma<I>
+where
+    I: Iterator,
+    I::Item: Into<f64>,
+{
+    type Item = f64;
+
+    #[inline]
+    fn next(&mut self) -> Option<f64> {
+        if self.n == 0 {
+            return None;
+        }
+        let x = self.iter.next()?.into();
+        self.n -= 1;
+        let ema = match self.prev {
+            None => x,
+            Some(prev) => self.alpha * x + (1.0 - self.alpha) * prev,
+        };
+        self.prev = Some(ema);
+        Some(ema)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.n == 0 {
+            return (0, Some(0));
+        }
+
+        let (lower, upper) = self.iter.size_hint();
+        let lower = lower.min(self.n);
+        let upper = match upper {
+            Some(x) if x < self.n => Some(x),
+            _ => Some(self.n),
+        };
+        (lower, upper)
+    }
+}
+
+#[unstable(feature = "iter_take_ema", issue = "none")]
+impl<I> ExactSizeIterator for TakeEma<I>
+where
+    I: ExactSizeIterator,
+    I::Item: Into<f64>,
+{
+}
+
+#[unstable(feature = "iter_take_ema", issue = "none")]
+impl<I> FusedIterator for TakeEma<I>
+where
+    I: FusedIterator,
+    I::Item: Into<f64>,
+{
+}
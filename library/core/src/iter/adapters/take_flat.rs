@@ -0,0 +1,121 @@
+use crate::fmt;
+use crate::iter::FusedIterator;
+
+/// An iterator that flattens an iterator of iterables, yielding at most `n`
+/// inner elements in total.
+///
+/// This `struct` is created by the [`take_flat`] method on [`Iterator`]. See
+/// its documentation for more.
+///
+/// [`take_flat`]: Iterator::take_flat
+/// [`Iterator`]: trait.Iterator.html
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[unstable(feature = "iter_take_flat", issue = "none")]
+pub struct TakeFlat<I: Iterator<Item: IntoIterator>> {
+    iter: I,
+    inner: Option<<I::Item as IntoIterator>::IntoIter>,
+    n: usize,
+}
+
+impl<I: Iterator<Item: IntoIterator>> TakeFlat<I> {
+    pub(in crate::iter) fn new(iter: I, n: usize) -> TakeFlat<I> {
+        TakeFlat { iter, inner: None, n }
+    }
+
+    /// Returns a mutable reference to the inner iterator currently being
+    /// drained, if any.
+    ///
+    /// Once the cap has been reached in the middle of an inner iterator, this
+    /// gives access to its remaining elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iter_take_flat)]
+    /// let groups = vec![vec![1, 2, 3], vec![4, 5]];
+    /// let mut it = groups.into_iter().take_flat(4);
+    /// assert_eq!(it.by_ref().collect::<Vec<_>>(), [1, 2, 3, 4]);
+    /// assert_eq!(it.inner_mut().and_then(|inner| inner.next()), Some(5));
+    /// ```
+    #[unstable(feature = "iter_take_flat", issue = "none")]
+    pub fn inner_mut(&mut self) -> Option<&mut <I::Item as IntoIterator>::IntoIter> {
+        self.inner.as_mut()
+    }
+
+    /// Consumes the adapter, returning the outer iterator and the inner
+    /// iterator currently being drained, if any.
+    ///
+    /// Together they hold every element that has not been yielded yet: the
+    /// rest of the current inner iterator, followed by the iterables still in
+    /// the outer one.
+    #[unstable(feature = "iter_take_flat", issue = "none")]
+    pub fn into_parts(self) -> (I, Option<<I::Item as IntoIterator>::IntoIter>) {
+        (self.iter, self.inner)
+    }
+}
+
+#[unstable(feature = "iter_take_flat", issue = "none")]
+impl<I> Clone for TakeFlat<I>
+where
+    I: Iterator<Item: IntoIterator<IntoIter: Clone>> + Clone,
+{
+    fn clone(&self) -> Self {
+        TakeFlat { iter: self.iter.clone(), inner: self.inner.clone(), n: self.n }
+    }
+}
+
+#[unstable(feature = "iter_take_flat", issue = "none")]
+impl<I> fmt::Debug for TakeFlat<I>
+where
+    I: Iterator<Item: IntoIterator<IntoIter: fmt::Debug>> + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TakeFlat")
+            .field("iter", &self.iter)
+            .field("inner", &self.inner)
+            .field("n", &self.n)
+            .finish()
+    }
+}
+
+#[unstable(feature = "iter_take_flat", issue = "none")]
+impl<I: Iterator<Item: IntoIterator>> Iterator for TakeFlat<I> {
+    type Item = <I::Item as IntoIterator>::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.n != 0 {
+            if let Some(inner) = &mut self.inner {
+                if let Some(item) = inner.next() {
+                    self.n -= 1;
+                    return Some(item);
+                }
+                self.inner = None;
+            }
+            match self.iter.next() {
+                Some(next) => self.inner = Some(next.into_iter()),
+                None => self.n = 0,
+            }
+        }
+        None
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.n == 0 {
+            return (0, Some(0));
+        }
+
+        let (lower, upper) = self.inner.as_ref().map_or((0, Some(0)), |inner| inner.size_hint());
+        // Unless the outer iterator is known to be empty, it may supply any
+        // number of further elements, up to the cap.
+        let upper = match (upper, self.iter.size_hint()) {
+            (Some(x), (0, Some(0))) if x < self.n => Some(x),
+            _ => Some(self.n),
+        };
+        (lower.min(self.n), upper)
+    }
+}
+
+#[unstable(feature = "iter_take_flat", issue = "none")]
+impl<I: Iterator<Item: IntoIterator>> FusedIterator for TakeFlat<I> {}
@@ -0,0 +1,109 @@
+use crate::hash::Hasher;
+use crate::iter::FusedIterator;
+
+/// An iterator that yields at most `n` bytes of `iter`, feeding each one into
+/// a [`Hasher`].
+///
+/// This `struct` is created by the [`take_hashing`] method on [`Iterator`].
+/// See its documentation for more.
+///
+/// [`take_hashing`]: Iterator::take_hashing
+/// [`Iterator`]: trait.Iterator.html
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[unstable(feature = "iter_take_hashing", issue = "none")]
+pub struct TakeHashing<I, H> {
+    iter: I,
+    n: usize,
+    hasher: H,
+}
+
+impl<I, H> TakeHashing<I, H> {
+    pub(in crate::iter) fn new(iter: I, n: usize, hasher: H) -> TakeHashing<I, H> {
+        TakeHashing { iter, n, hasher }
+    }
+
+    /// Returns the hash of the `n` bytes taken, or `None` if fewer than `n`
+    /// bytes have been yielded so far.
+    ///
+    /// Like [`Hasher::finish`], this does not reset the hasher, so it can be
+    /// called more than once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iter_take_hashing)]
+    /// use std::collections::hash_map::DefaultHasher;
+    ///
+    /// let mut it = [1u8, 2, 3].iter().copied().take_hashing(2, DefaultHasher::new());
+    /// assert_eq!(it.next(), Some(1));
+    /// assert_eq!(it.finish(), None);
+    /// assert_eq!(it.next(), Some(2));
+    /// assert!(it.finish().is_some());
+    /// ```
+    #[unstable(feature = "iter_take_hashing", issue = "none")]
+    pub fn finish(&self) -> Option<u64>
+    where
+        H: Hasher,
+    {
+        if self.n == 0 { Some(self.hasher.finish()) } else { None }
+    }
+
+    /// Returns a reference to the hasher, whatever number of bytes it has
+    /// seen so far.
+    #[unstable(feature = "iter_take_hashing", issue = "none")]
+    pub fn hasher(&self) -> &H {
+        &self.hasher
+    }
+}
+
+#[unstable(feature = "iter_take_hashing", issue = "none")]
+impl<I, H> Iterator for TakeHashing<I, H>
+where
+    I: Iterator<Item = u8>,
+    H: Hasher,
+{
+    type Item = u8;
+
+    #[inline]
+    fn next(&mut self) -> Option<u8> {
+        if self.n == 0 {
+            return None;
+        }
+        let byte = self.iter.next()?;
+        self.n -= 1;
+        self.hasher.write_u8(byte);
+        Some(byte)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.n == 0 {
+            return (0, Some(0));
+        }
+
+        let (lower, upper) = self.iter.size_hint();
+        let lower = lower.min(self.n);
+        let upper = match upper {
+            Some(x) if x < self.n => Some(x),
+            _ => Some(self.n),
+        };
+        (lower, upper)
+    }
+}
+
+#[unstable(feature = "iter_take_hashing", issue = "none")]
+impl<I, H> ExactSizeIterator for TakeHashing<I, H>
+where
+    I: ExactSizeIterator<Item = u8>,
+    H: Hasher,
+{
+}
+
+#[unstable(feature = "iter_take_hashing", issue = "none")]
+impl<I, H> FusedIterator for TakeHashing<I, H>
+where
+    I: FusedIterator<Item = u8>,
+    H: Hasher,
+{
+}
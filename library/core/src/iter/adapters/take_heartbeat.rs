@@ -0,0 +1,107 @@
+use crate::cmp;
+use crate::fmt;
+use crate::iter::FusedIterator;
+use crate::num::NonZeroUsize;
+
+/// An iterator that yields at most `n` elements of `iter`, inserting a
+/// heartbeat element after every `every` of them.
+///
+/// This `struct` is created by the [`take_heartbeat`] method on [`Iterator`].
+/// See its documentation for more.
+///
+/// [`take_heartbeat`]: Iterator::take_heartbeat
+/// [`Iterator`]: trait.Iterator.html
+#[derive(Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[unstable(feature = "iter_take_heartbeat", issue = "none")]
+pub struct TakeHeartbeat<I, F> {
+    iter: I,
+    n: usize,
+    every: NonZeroUsize,
+    // Real elements yielded since the last heartbeat.
+    since: usize,
+    make: F,
+}
+
+impl<I, F> TakeHeartbeat<I, F> {
+    pub(in crate::iter) fn new(
+        iter: I,
+        n: usize,
+        every: NonZeroUsize,
+        make: F,
+    ) -> TakeHeartbeat<I, F> {
+        TakeHeartbeat { iter, n, every, since: 0, make }
+    }
+}
+
+#[unstable(feature = "iter_take_heartbeat", issue = "none")]
+impl<I: fmt::Debug, F> fmt::Debug for TakeHeartbeat<I, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TakeHeartbeat")
+            .field("iter", &self.iter)
+            .field("n", &self.n)
+            .field("every", &self.every)
+            .finish()
+    }
+}
+
+#[unstable(feature = "iter_take_heartbeat", issue = "none")]
+impl<I, F> Iterator for TakeHeartbeat<I, F>
+where
+    I: Iterator,
+    F: FnMut() -> I::Item,
+{
+    type Item = I::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<I::Item> {
+        if self.since == self.every.get() {
+            self.since = 0;
+            return Some((self.make)());
+        }
+        if self.n == 0 {
+            return None;
+        }
+        let item = self.iter.next()?;
+        self.n -= 1;
+        self.since += 1;
+        Some(item)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let every = self.every.get();
+        let pending = (self.since == every) as usize;
+        let since = if pending == 1 { 0 } else { self.since };
+        // Heartbeats that `real` more real elements would trigger. The last,
+        // partial stretch of `real % every` elements triggers one more if it
+        // brings `since` up to `every`. That is checked against
+        // `every - since`, which can't underflow as `since < every`, rather
+        // than by adding to `since`, which could overflow.
+        let heartbeats = |real: usize| real / every + (real % every >= every - since) as usize;
+
+        let (lower, upper) = if self.n == 0 {
+            (0, Some(0))
+        } else {
+            let (lower, upper) = self.iter.size_hint();
+            let lower = cmp::min(lower, self.n);
+            let upper = match upper {
+                Some(x) if x < self.n => Some(x),
+                _ => Some(self.n),
+            };
+            (lower, upper)
+        };
+
+        let lower = lower.saturating_add(heartbeats(lower)).saturating_add(pending);
+        let upper = upper.and_then(|x| x.checked_add(heartbeats(x))?.checked_add(pending));
+        (lower, upper)
+    }
+}
+
+#[unstable(feature = "iter_take_heartbeat", issue = "none")]
+impl<I, F> FusedIterator for TakeHeartbeat<I, F>
+where
+    I: FusedIterator,
+    F: FnMut() -> I::Item,
+{
+}
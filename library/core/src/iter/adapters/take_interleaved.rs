@@ -0,0 +1,87 @@
+use crate::iter::FusedIterator;
+
+/// An iterator that alternates between two iterators, yielding at most `n`
+/// elements in total.
+///
+/// This `struct` is created by the [`take_interleaved`] method on
+/// [`Iterator`]. See its documentation for more.
+///
+/// [`take_interleaved`]: Iterator::take_interleaved
+/// [`Iterator`]: trait.Iterator.html
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[unstable(feature = "iter_take_interleaved", issue = "none")]
+pub struct TakeInterleaved<A, B> {
+    // Each side is set to `None` once it is exhausted, as in `Chain`, so it
+    // is never polled again.
+    a: Option<A>,
+    b: Option<B>,
+    n: usize,
+    a_next: bool,
+}
+
+impl<A, B> TakeInterleaved<A, B> {
+    pub(in crate::iter) fn new(a: A, b: B, n: usize) -> TakeInterleaved<A, B> {
+        TakeInterleaved { a: Some(a), b: Some(b), n, a_next: true }
+    }
+}
+
+fn next_or_clear<I: Iterator>(opt: &mut Option<I>) -> Option<I::Item> {
+    let x = opt.as_mut()?.next();
+    if x.is_none() {
+        *opt = None;
+    }
+    x
+}
+
+#[unstable(feature = "iter_take_interleaved", issue = "none")]
+impl<A, B> Iterator for TakeInterleaved<A, B>
+where
+    A: Iterator,
+    B: Iterator<Item = A::Item>,
+{
+    type Item = A::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<A::Item> {
+        if self.n == 0 {
+            return None;
+        }
+        let item = if self.a_next {
+            next_or_clear(&mut self.a).or_else(|| next_or_clear(&mut self.b))
+        } else {
+            next_or_clear(&mut self.b).or_else(|| next_or_clear(&mut self.a))
+        }?;
+        self.a_next = !self.a_next;
+        self.n -= 1;
+        Some(item)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.n == 0 {
+            return (0, Some(0));
+        }
+
+        let (a_lower, a_upper) = self.a.as_ref().map_or((0, Some(0)), Iterator::size_hint);
+        let (b_lower, b_upper) = self.b.as_ref().map_or((0, Some(0)), Iterator::size_hint);
+        let lower = a_lower.saturating_add(b_lower).min(self.n);
+        let upper = match (a_upper, b_upper) {
+            (Some(x), Some(y)) => x.checked_add(y),
+            _ => None,
+        };
+        let upper = match upper {
+            Some(x) if x < self.n => Some(x),
+            _ => Some(self.n),
+        };
+        (lower, upper)
+    }
+}
+
+#[unstable(feature = "iter_take_interleaved", issue = "none")]
+impl<A, B> FusedIterator for TakeInterleaved<A, B>
+where
+    A: Iterator,
+    B: Iterator<Item = A::Item>,
+{
+}
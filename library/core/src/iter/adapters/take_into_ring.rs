@@ -0,0 +1,80 @@
+use crate::mem::{self, MaybeUninit};
+use crate::ops::Range;
+
+/// Where the elements written by [`take_into_ring`] ended up in the buffer.
+///
+/// The valid elements start at [`head`] and run for [`len`] slots, wrapping
+/// around to the start of the buffer if they reach its end. Those slots are
+/// initialized and owned by the caller, who is responsible for reading or
+/// dropping them; all other slots are left as they were.
+///
+/// [`take_into_ring`]: Iterator::take_into_ring
+/// [`head`]: RingFill::head
+/// [`len`]: RingFill::len
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[unstable(feature = "iter_take_into_ring", issue = "none")]
+pub struct RingFill {
+    head: usize,
+    len: usize,
+    cap: usize,
+}
+
+impl RingFill {
+    pub(in crate::iter) fn fill<I: Iterator>(
+        iter: I,
+        buf: &mut [MaybeUninit<I::Item>],
+    ) -> RingFill {
+        let cap = buf.len();
+        let mut ring = RingFill { head: 0, len: 0, cap };
+        for item in iter {
+            if ring.len < cap {
+                buf[ring.len] = MaybeUninit::new(item);
+                ring.len += 1;
+            } else if cap > 0 {
+                let oldest = mem::replace(&mut buf[ring.head], MaybeUninit::new(item));
+                ring.head += 1;
+                if ring.head == cap {
+                    ring.head = 0;
+                }
+                // SAFETY: the buffer is full, so every slot, including the one
+                // just overwritten, held an element written by this loop. It is
+                // dropped only now so that the ring is consistent if that panics.
+                drop(unsafe { oldest.assume_init() });
+            }
+        }
+        ring
+    }
+
+    /// Returns the index of the oldest element kept.
+    #[unstable(feature = "iter_take_into_ring", issue = "none")]
+    pub fn head(&self) -> usize {
+        self.head
+    }
+
+    /// Returns the number of elements kept.
+    #[unstable(feature = "iter_take_into_ring", issue = "none")]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no element was kept.
+    #[unstable(feature = "iter_take_into_ring", issue = "none")]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the index ranges of the buffer holding the elements kept,
+    /// from oldest to newest.
+    ///
+    /// The elements in the first range are older than those in the second.
+    /// The second range is empty unless the elements wrap around the end of
+    /// the buffer.
+    #[unstable(feature = "iter_take_into_ring", issue = "none")]
+    pub fn ranges(&self) -> (Range<usize>, Range<usize>) {
+        if self.cap - self.head >= self.len {
+            (self.head..self.head + self.len, 0..0)
+        } else {
+            (self.head..self.cap, 0..self.len - (self.cap - self.head))
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use crate::cmp::Ordering;
+use crate::fmt;
+use crate::iter::FusedIterator;
+
+/// The error yielded by a [`TakeMonotonic`] for the first element that is out
+/// of order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[unstable(feature = "iter_take_monotonic", issue = "none")]
+pub struct OrderingViolation<T> {
+    index: usize,
+    previous: T,
+    current: T,
+}
+
+impl<T> OrderingViolation<T> {
+    /// Returns the position of the out-of-order element in the source,
+    /// counting from zero.
+    #[unstable(feature = "iter_take_monotonic", issue = "none")]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the element just before the out-of-order one.
+    #[unstable(feature = "iter_take_monotonic", issue = "none")]
+    pub fn previous(&self) -> &T {
+        &self.previous
+    }
+
+    /// Returns the out-of-order element, which compares less than
+    /// [`previous`](OrderingViolation::previous).
+    #[unstable(feature = "iter_take_monotonic", issue = "none")]
+    pub fn current(&self) -> &T {
+        &self.current
+    }
+
+    /// Returns the previous and the out-of-order element, in that order.
+    #[unstable(feature = "iter_take_monotonic", issue = "none")]
+    pub fn into_elements(self) -> (T, T) {
+        (self.previous, self.current)
+    }
+}
+
+/// An iterator that yields at most `n` elements of `iter`, checking that they
+/// are in order.
+///
+/// This `struct` is created by the [`take_monotonic`] method on [`Iterator`].
+/// See its documentation for more.
+///
+/// [`take_monotonic`]: Iterator::take_monotonic
+/// [`Iterator`]: trait.Iterator.html
+#[derive(Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[unstable(feature = "iter_take_monotonic", issue = "none")]
+pub struct TakeMonotonic<I: Iterator, F> {
+    iter: I,
+    n: usize,
+    cmp: F,
+    previous: Option<I::Item>,
+    index: usize,
+}
+
+impl<I: Iterator, F> TakeMonotonic<I, F> {
+    pub(in crate::iter) fn new(iter: I, n: usize, cmp: F) -> TakeMonotonic<I, F> {
+        TakeMonotonic { iter, n, cmp, previous: None, index: 0 }
+    }
+}
+
+#[unstable(feature = "iter_take_monotonic", issue = "none")]
+impl<I: fmt::Debug + Iterator, F> fmt::Debug for TakeMonotonic<I, F>
+where
+    I::Item: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TakeMonotonic")
+            .field("iter", &self.iter)
+            .field("n", &self.n)
+            .field("previous", &self.previous)
+            .finish()
+    }
+}
+
+#[unstable(feature = "iter_take_monotonic", issue = "none")]
+impl<I, F> Iterator for TakeMonotonic<I, F>
+where
+    I: Iterator,
+    I::Item: Clone,
+    F: FnMut(&I::Item, &I::Item) -> Ordering,
+{
+    type Item = Result<I::Item, OrderingViolation<I::Item>>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.n == 0 {
+            return None;
+        }
+        let item = self.iter.next()?;
+        let index = self.index;
+        self.index += 1;
+        if let Some(previous) = &self.previous {
+            if (self.cmp)(previous, &item) == Ordering::Greater {
+                // Stop for good: once the order is broken, nothing after the
+                // violation can be trusted.
+                self.n = 0;
+                let previous = self.previous.take().unwrap();
+                return Some(Err(OrderingViolation { index, previous, current: item }));
+            }
+        }
+        self.n -= 1;
+        self.previous = Some(item.clone());
+        Some(Ok(item))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.n == 0 {
+            return (0, Some(0));
+        }
+
+        // Any element may break the order and end iteration, so only the
+        // next one is certain to be yielded, be it in order or not.
+        let (lower, upper) = self.iter.size_hint();
+        let lower = (lower > 0) as usize;
+        let upper = match upper {
+            Some(x) if x < self.n => Some(x),
+            _ => Some(self.n),
+        };
+        (lower, upper)
+    }
+}
+
+#[unstable(feature = "iter_take_monotonic", issue = "none")]
+impl<I, F> FusedIterator for TakeMonotonic<I, F>
+where
+    I: FusedIterator,
+    I::Item: Clone,
+    F: FnMut(&I::Item, &I::Item) -> Ordering,
+{
+}
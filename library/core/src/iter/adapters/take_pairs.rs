@@ -0,0 +1,77 @@
+use crate::iter::FusedIterator;
+
+/// An iterator over overlapping pairs of consecutive elements of `iter`,
+/// yielding at most `n` pairs.
+///
+/// This `struct` is created by the [`take_pairs`] method on [`Iterator`]. See
+/// its documentation for more.
+///
+/// [`take_pairs`]: Iterator::take_pairs
+/// [`Iterator`]: trait.Iterator.html
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[unstable(feature = "iter_take_pairs", issue = "none")]
+pub struct TakePairs<I: Iterator> {
+    iter: I,
+    prev: Option<I::Item>,
+    n: usize,
+}
+
+impl<I: Iterator> TakePairs<I> {
+    pub(in crate::iter) fn new(iter: I, n: usize) -> TakePairs<I> {
+        TakePairs { iter, prev: None, n }
+    }
+}
+
+#[unstable(feature = "iter_take_pairs", issue = "none")]
+impl<I> Iterator for TakePairs<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    type Item = (I::Item, I::Item);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.n == 0 {
+            return None;
+        }
+        let prev = match self.prev.take() {
+            Some(prev) => prev,
+            None => self.iter.next()?,
+        };
+        let cur = self.iter.next()?;
+        self.n -= 1;
+        // The last pair hands out `cur` without keeping a copy around.
+        if self.n != 0 {
+            self.prev = Some(cur.clone());
+        }
+        Some((prev, cur))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.n == 0 {
+            return (0, Some(0));
+        }
+
+        let carried = self.prev.is_some() as usize;
+        let (lower, upper) = self.iter.size_hint();
+        let pairs = |len: usize| len.saturating_add(carried).saturating_sub(1);
+
+        let lower = pairs(lower).min(self.n);
+        let upper = match upper {
+            Some(x) if pairs(x) < self.n => Some(pairs(x)),
+            _ => Some(self.n),
+        };
+        (lower, upper)
+    }
+}
+
+#[unstable(feature = "iter_take_pairs", issue = "none")]
+impl<I> FusedIterator for TakePairs<I>
+where
+    I: FusedIterator,
+    I::Item: Clone,
+{
+}
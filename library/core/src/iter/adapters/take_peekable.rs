@@ -0,0 +1,124 @@
+use crate::iter::FusedIterator;
+
+/// An iterator that yields at most `n` elements, with a `peek()` that does
+/// not count against that limit.
+///
+/// This `struct` is created by the [`take_peekable`] method on [`Iterator`].
+/// See its documentation for more.
+///
+/// [`take_peekable`]: Iterator::take_peekable
+/// [`Iterator`]: trait.Iterator.html
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[unstable(feature = "iter_take_peekable", issue = "none")]
+pub struct TakePeekable<I: Iterator> {
+    iter: I,
+    n: usize,
+    /// Remember a peeked value, even if it was None.
+    peeked: Option<Option<I::Item>>,
+}
+
+impl<I: Iterator> TakePeekable<I> {
+    pub(in crate::iter) fn new(iter: I, n: usize) -> TakePeekable<I> {
+        TakePeekable { iter, n, peeked: None }
+    }
+
+    /// Returns a reference to the next element without advancing the
+    /// iterator or using up any of the remaining limit.
+    ///
+    /// The first call pulls the element from the underlying iterator and
+    /// keeps it in a one-element lookahead buffer; later calls return the
+    /// buffered element until [`next`] hands it out. Once the limit has been
+    /// reached, this returns `None` without touching the underlying iterator,
+    /// so peeking never pulls an element that would not be yielded.
+    ///
+    /// [`next`]: Iterator::next
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iter_take_peekable)]
+    ///
+    /// let mut iter = [1, 2, 3].iter().take_peekable(2);
+    ///
+    /// assert_eq!(iter.peek(), Some(&&1));
+    /// assert_eq!(iter.peek(), Some(&&1));
+    /// assert_eq!(iter.len(), 2);
+    ///
+    /// assert_eq!(iter.next(), Some(&1));
+    /// assert_eq!(iter.peek(), Some(&&2));
+    /// assert_eq!(iter.next(), Some(&2));
+    ///
+    /// // The limit is reached, so `3` is neither peeked nor yielded.
+    /// assert_eq!(iter.peek(), None);
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    #[inline]
+    #[unstable(feature = "iter_take_peekable", issue = "none")]
+    pub fn peek(&mut self) -> Option<&I::Item> {
+        self.peek_mut().map(|item| &*item)
+    }
+
+    /// Returns a mutable reference to the next element without advancing the
+    /// iterator or using up any of the remaining limit.
+    ///
+    /// Like [`peek`], this fills the one-element lookahead buffer if it is
+    /// empty. Changes made through the returned reference are seen by the
+    /// following call to [`next`].
+    ///
+    /// [`peek`]: TakePeekable::peek
+    /// [`next`]: Iterator::next
+    #[inline]
+    #[unstable(feature = "iter_take_peekable", issue = "none")]
+    pub fn peek_mut(&mut self) -> Option<&mut I::Item> {
+        if self.n == 0 {
+            return None;
+        }
+        let iter = &mut self.iter;
+        self.peeked.get_or_insert_with(|| iter.next()).as_mut()
+    }
+}
+
+#[unstable(feature = "iter_take_peekable", issue = "none")]
+impl<I: Iterator> Iterator for TakePeekable<I> {
+    type Item = I::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<I::Item> {
+        if self.n == 0 {
+            return None;
+        }
+        let item = match self.peeked.take() {
+            Some(v) => v,
+            None => self.iter.next(),
+        }?;
+        self.n -= 1;
+        Some(item)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.n == 0 {
+            return (0, Some(0));
+        }
+
+        let peek_len = match self.peeked {
+            Some(None) => return (0, Some(0)),
+            Some(Some(_)) => 1,
+            None => 0,
+        };
+        let (lower, upper) = self.iter.size_hint();
+        let lower = lower.saturating_add(peek_len).min(self.n);
+        let upper = match upper.and_then(|x| x.checked_add(peek_len)) {
+            Some(x) if x < self.n => Some(x),
+            _ => Some(self.n),
+        };
+        (lower, upper)
+    }
+}
+
+#[unstable(feature = "iter_take_peekable", issue = "none")]
+impl<I: ExactSizeIterator> ExactSizeIterator for TakePeekable<I> {}
+
+#[unstable(feature = "iter_take_peekable", issue = "none")]
+impl<I: FusedIterator> FusedIterator for TakePeekable<I> {}
@@ -0,0 +1,99 @@
+use crate::iter::FusedIterator;
+use crate::task::Poll;
+
+/// An iterator over a not-always-ready source that yields at most `n` ready
+/// elements, and can be driven with [`poll_next`](TakePoll::poll_next).
+///
+/// This `struct` is created by the [`take_poll`] method on [`Iterator`]. See
+/// its documentation for more.
+///
+/// [`take_poll`]: Iterator::take_poll
+/// [`Iterator`]: trait.Iterator.html
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[unstable(feature = "iter_take_poll", issue = "none")]
+pub struct TakePoll<I> {
+    iter: I,
+    n: usize,
+}
+
+impl<I> TakePoll<I> {
+    pub(in crate::iter) fn new(iter: I, n: usize) -> TakePoll<I> {
+        TakePoll { iter, n }
+    }
+
+    /// Attempts to pull out the next ready element.
+    ///
+    /// Returns [`Poll::Pending`] if the source is not ready yet,
+    /// `Poll::Ready(Some(item))` for a ready element, and `Poll::Ready(None)`
+    /// once `n` elements have been yielded or the source is exhausted. After
+    /// that, every call returns `Poll::Ready(None)` without touching the
+    /// source again.
+    ///
+    /// Returning [`Poll::Pending`] does not arrange for anyone to be woken up;
+    /// see [`Iterator::take_poll`] for what the caller is expected to do.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iter_take_poll)]
+    /// use std::task::Poll;
+    ///
+    /// let mut it = [Some(1), None, Some(2)].iter().copied().take_poll(5);
+    /// assert_eq!(it.poll_next(), Poll::Ready(Some(1)));
+    /// assert_eq!(it.poll_next(), Poll::Pending);
+    /// assert_eq!(it.poll_next(), Poll::Ready(Some(2)));
+    /// assert_eq!(it.poll_next(), Poll::Ready(None));
+    /// ```
+    #[unstable(feature = "iter_take_poll", issue = "none")]
+    pub fn poll_next<T>(&mut self) -> Poll<Option<T>>
+    where
+        I: Iterator<Item = Option<T>>,
+    {
+        match self.next() {
+            Some(Some(item)) => Poll::Ready(Some(item)),
+            Some(None) => Poll::Pending,
+            None => Poll::Ready(None),
+        }
+    }
+}
+
+#[unstable(feature = "iter_take_poll", issue = "none")]
+impl<I, T> Iterator for TakePoll<I>
+where
+    I: Iterator<Item = Option<T>>,
+{
+    type Item = Option<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Option<T>> {
+        if self.n == 0 {
+            return None;
+        }
+        match self.iter.next() {
+            Some(Some(item)) => {
+                self.n -= 1;
+                Some(Some(item))
+            }
+            Some(None) => Some(None),
+            None => {
+                self.n = 0;
+                None
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.n == 0 {
+            return (0, Some(0));
+        }
+        // Any number of not-ready markers may come before the `n` elements,
+        // so only an exhausted source bounds the length.
+        let (_, upper) = self.iter.size_hint();
+        (0, upper)
+    }
+}
+
+#[unstable(feature = "iter_take_poll", issue = "none")]
+impl<I, T> FusedIterator for TakePoll<I> where I: Iterator<Item = Option<T>> {}
@@ -0,0 +1,100 @@
+use crate::iter::FusedIterator;
+
+/// An iterator over at most `n` fixed-width records of `W` bytes each, read
+/// from a byte iterator.
+///
+/// This `struct` is created by the [`take_records`] method on [`Iterator`].
+/// See its documentation for more.
+///
+/// [`take_records`]: Iterator::take_records
+/// [`Iterator`]: trait.Iterator.html
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[unstable(feature = "iter_take_records", issue = "none")]
+pub struct TakeRecords<I, const W: usize> {
+    iter: I,
+    n: usize,
+    remainder: [u8; W],
+    remainder_len: usize,
+}
+
+impl<I, const W: usize> TakeRecords<I, W> {
+    pub(in crate::iter) fn new(iter: I, n: usize) -> TakeRecords<I, W> {
+        TakeRecords { iter, n, remainder: [0; W], remainder_len: 0 }
+    }
+
+    /// Returns the bytes of a trailing partial record, if the source ran out
+    /// in the middle of one.
+    ///
+    /// The partial record is never yielded, so this is the place to look for
+    /// it when a truncated stream should be treated as an error. The returned
+    /// slice is empty if every record was complete.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iter_take_records)]
+    /// let bytes = [1, 2, 3, 4, 5];
+    /// let mut records = bytes.iter().copied().take_records::<2>(10);
+    /// assert_eq!(records.by_ref().count(), 2);
+    /// assert_eq!(records.remainder(), [5]);
+    /// ```
+    #[unstable(feature = "iter_take_records", issue = "none")]
+    pub fn remainder(&self) -> &[u8] {
+        &self.remainder[..self.remainder_len]
+    }
+}
+
+#[unstable(feature = "iter_take_records", issue = "none")]
+impl<I, const W: usize> Iterator for TakeRecords<I, W>
+where
+    I: Iterator<Item = u8>,
+{
+    type Item = [u8; W];
+
+    #[inline]
+    fn next(&mut self) -> Option<[u8; W]> {
+        if self.n == 0 {
+            return None;
+        }
+        let mut record = [0; W];
+        for (i, byte) in record.iter_mut().enumerate() {
+            match self.iter.next() {
+                Some(b) => *byte = b,
+                None => {
+                    self.remainder = record;
+                    self.remainder_len = i;
+                    self.n = 0;
+                    return None;
+                }
+            }
+        }
+        self.n -= 1;
+        Some(record)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if W == 0 {
+            return (self.n, Some(self.n));
+        }
+
+        let (lower, upper) = self.iter.size_hint();
+        let lower = (lower / W).min(self.n);
+        let upper = match upper {
+            Some(x) if x / W < self.n => Some(x / W),
+            _ => Some(self.n),
+        };
+        (lower, upper)
+    }
+}
+
+#[unstable(feature = "iter_take_records", issue = "none")]
+impl<I, const W: usize> ExactSizeIterator for TakeRecords<I, W>
+where
+    I: ExactSizeIterator<Item = u8>,
+{
+}
+
+#[unstable(feature = "iter_take_records", issue = "none")]
+impl<I, const W: usize> FusedIterator for TakeRecords<I, W> where I: Iterator<Item = u8> {}
@@ -0,0 +1,85 @@
+use crate::iter::FusedIterator;
+
+/// An iterator that yields at most `n` elements of `iter`, each tagged with
+/// a sequence number.
+///
+/// This `struct` is created by the [`take_sequenced`] method on [`Iterator`].
+/// See its documentation for more.
+///
+/// [`take_sequenced`]: Iterator::take_sequenced
+/// [`Iterator`]: trait.Iterator.html
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[unstable(feature = "iter_take_sequenced", issue = "none")]
+pub struct TakeSequenced<I> {
+    iter: I,
+    n: usize,
+    next_seq: u64,
+}
+
+impl<I> TakeSequenced<I> {
+    pub(in crate::iter) fn new(iter: I, n: usize, start: u64) -> TakeSequenced<I> {
+        TakeSequenced { iter, n, next_seq: start }
+    }
+
+    /// Returns the sequence number the next element will be tagged with.
+    ///
+    /// Saving this once the elements yielded so far have been processed, and
+    /// passing it as `start` to [`take_sequenced`] after a restart, keeps the
+    /// numbering going without gaps or repeats.
+    ///
+    /// [`take_sequenced`]: Iterator::take_sequenced
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iter_take_sequenced)]
+    ///
+    /// let mut it = ["a", "b", "c"].iter().take_sequenced(2, 100);
+    /// assert_eq!(it.next_seq(), 100);
+    /// assert_eq!(it.next(), Some((100, &"a")));
+    /// assert_eq!(it.next_seq(), 101);
+    /// ```
+    #[unstable(feature = "iter_take_sequenced", issue = "none")]
+    pub fn next_seq(&self) -> u64 {
+        self.next_seq
+    }
+}
+
+#[unstable(feature = "iter_take_sequenced", issue = "none")]
+impl<I: Iterator> Iterator for TakeSequenced<I> {
+    type Item = (u64, I::Item);
+
+    #[inline]
+    fn next(&mut self) -> Option<(u64, I::Item)> {
+        if self.n == 0 {
+            return None;
+        }
+        let item = self.iter.next()?;
+        self.n -= 1;
+        let seq = self.next_seq;
+        self.next_seq = seq.saturating_add(1);
+        Some((seq, item))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.n == 0 {
+            return (0, Some(0));
+        }
+
+        let (lower, upper) = self.iter.size_hint();
+        let lower = lower.min(self.n);
+        let upper = match upper {
+            Some(x) if x < self.n => Some(x),
+            _ => Some(self.n),
+        };
+        (lower, upper)
+    }
+}
+
+#[unstable(feature = "iter_take_sequenced", issue = "none")]
+impl<I: ExactSizeIterator> ExactSizeIterator for TakeSequenced<I> {}
+
+#[unstable(feature = "iter_take_sequenced", issue = "none")]
+impl<I: FusedIterator> FusedIterator for TakeSequenced<I> {}
@@ -0,0 +1,156 @@
+use crate::iter::FusedIterator;
+use crate::ops::Add;
+
+/// Running statistics over the elements yielded by a [`TakeSummarizing`].
+///
+/// This `struct` is returned by [`TakeSummarizing::summary`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[unstable(feature = "iter_take_summarizing", issue = "none")]
+pub struct Summary<T> {
+    min: T,
+    max: T,
+    count: usize,
+    sum: T,
+}
+
+impl<T: Copy> Summary<T> {
+    /// Returns the smallest element yielded.
+    #[unstable(feature = "iter_take_summarizing", issue = "none")]
+    pub fn min(&self) -> T {
+        self.min
+    }
+
+    /// Returns the largest element yielded.
+    #[unstable(feature = "iter_take_summarizing", issue = "none")]
+    pub fn max(&self) -> T {
+        self.max
+    }
+
+    /// Returns the number of elements yielded.
+    #[unstable(feature = "iter_take_summarizing", issue = "none")]
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns the sum of the elements yielded.
+    #[unstable(feature = "iter_take_summarizing", issue = "none")]
+    pub fn sum(&self) -> T {
+        self.sum
+    }
+}
+
+/// An iterator that yields at most `n` elements of `iter` while keeping
+/// running statistics over them.
+///
+/// This `struct` is created by the [`take_summarizing`] method on
+/// [`Iterator`]. See its documentation for more.
+///
+/// [`take_summarizing`]: Iterator::take_summarizing
+/// [`Iterator`]: trait.Iterator.html
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[unstable(feature = "iter_take_summarizing", issue = "none")]
+pub struct TakeSummarizing<I: Iterator> {
+    iter: I,
+    n: usize,
+    summary: Option<Summary<I::Item>>,
+}
+
+impl<I: Iterator> TakeSummarizing<I> {
+    pub(in crate::iter) fn new(iter: I, n: usize) -> TakeSummarizing<I> {
+        TakeSummarizing { iter, n, summary: None }
+    }
+
+    /// Returns the statistics over the elements yielded so far, or `None` if
+    /// no element has been yielded yet.
+    ///
+    /// The statistics are updated as each element is yielded, so this is
+    /// cheap to call at any point, including in the middle of iteration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iter_take_summarizing)]
+    ///
+    /// let mut it = [3, 1, 4, 1, 5].iter().copied().take_summarizing(4);
+    /// assert!(it.summary().is_none());
+    ///
+    /// assert_eq!(it.next(), Some(3));
+    /// assert_eq!(it.summary().unwrap().max(), 3);
+    ///
+    /// it.by_ref().for_each(drop);
+    /// let summary = it.summary().unwrap();
+    /// assert_eq!((summary.min(), summary.max()), (1, 4));
+    /// assert_eq!((summary.count(), summary.sum()), (4, 9));
+    /// ```
+    #[unstable(feature = "iter_take_summarizing", issue = "none")]
+    pub fn summary(&self) -> Option<Summary<I::Item>>
+    where
+        I::Item: Copy,
+    {
+        self.summary
+    }
+}
+
+#[unstable(feature = "iter_take_summarizing", issue = "none")]
+impl<I> Iterator for TakeSummarizing<I>
+where
+    I: Iterator,
+    I::Item: Copy + PartialOrd + Add<Output = I::Item>,
+{
+    type Item = I::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<I::Item> {
+        if self.n == 0 {
+            return None;
+        }
+        let item = self.iter.next()?;
+        self.n -= 1;
+        match &mut self.summary {
+            None => self.summary = Some(Summary { min: item, max: item, count: 1, sum: item }),
+            Some(summary) => {
+                if item < summary.min {
+                    summary.min = item;
+                }
+                if item > summary.max {
+                    summary.max = item;
+                }
+                summary.count += 1;
+                summary.sum = summary.sum + item;
+            }
+        }
+        Some(item)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.n == 0 {
+            return (0, Some(0));
+        }
+
+        let (lower, upper) = self.iter.size_hint();
+        let lower = lower.min(self.n);
+        let upper = match upper {
+            Some(x) if x < self.n => Some(x),
+            _ => Some(self.n),
+        };
+        (lower, upper)
+    }
+}
+
+#[unstable(feature = "iter_take_summarizing", issue = "none")]
+impl<I> ExactSizeIterator for TakeSummarizing<I>
+where
+    I: ExactSizeIterator,
+    I::Item: Copy + PartialOrd + Add<Output = I::Item>,
+{
+}
+
+#[unstable(feature = "iter_take_summarizing", issue = "none")]
+impl<I> FusedIterator for TakeSummarizing<I>
+where
+    I: FusedIterator,
+    I::Item: Copy + PartialOrd + Add<Output = I::Item>,
+{
+}
@@ -0,0 +1,86 @@
+use crate::iter::FusedIterator;
+
+/// An iterator that yields up to `n` elements of one iterator and, if that
+/// cap is reached, continues with the elements of another.
+///
+/// This `struct` is created by the [`take_then_chain`] method on [`Iterator`].
+/// See its documentation for more.
+///
+/// [`take_then_chain`]: Iterator::take_then_chain
+/// [`Iterator`]: trait.Iterator.html
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[unstable(feature = "iter_take_then_chain", issue = "none")]
+pub struct TakeThenChain<I, J> {
+    // Both fields are "fused" by setting them to `None`: `iter` once the cap is
+    // reached, and both of them once `iter` runs dry before the cap.
+    iter: Option<I>,
+    fallback: Option<J>,
+    n: usize,
+}
+
+impl<I, J> TakeThenChain<I, J> {
+    pub(in crate::iter) fn new(iter: I, n: usize, fallback: J) -> TakeThenChain<I, J> {
+        TakeThenChain { iter: Some(iter), fallback: Some(fallback), n }
+    }
+}
+
+#[unstable(feature = "iter_take_then_chain", issue = "none")]
+impl<I, J> Iterator for TakeThenChain<I, J>
+where
+    I: Iterator,
+    J: Iterator<Item = I::Item>,
+{
+    type Item = I::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<I::Item> {
+        if let Some(iter) = &mut self.iter {
+            if self.n != 0 {
+                return match iter.next() {
+                    Some(item) => {
+                        self.n -= 1;
+                        Some(item)
+                    }
+                    None => {
+                        // Running out early is not the same as hitting the
+                        // cap, so the fallback is never consulted.
+                        self.iter = None;
+                        self.fallback = None;
+                        None
+                    }
+                };
+            }
+            self.iter = None;
+        }
+        self.fallback.as_mut()?.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (fb_lower, fb_upper) = match &self.fallback {
+            Some(fallback) => fallback.size_hint(),
+            None => (0, Some(0)),
+        };
+        let iter = match &self.iter {
+            Some(iter) if self.n != 0 => iter,
+            _ => return (fb_lower, fb_upper),
+        };
+
+        let (lower, upper) = iter.size_hint();
+        let lower = if lower >= self.n { self.n.saturating_add(fb_lower) } else { lower };
+        let upper = match upper {
+            Some(x) if x < self.n => Some(x),
+            _ => fb_upper.and_then(|x| x.checked_add(self.n)),
+        };
+        (lower, upper)
+    }
+}
+
+#[unstable(feature = "iter_take_then_chain", issue = "none")]
+impl<I, J> FusedIterator for TakeThenChain<I, J>
+where
+    I: Iterator,
+    J: FusedIterator<Item = I::Item>,
+{
+}
@@ -0,0 +1,120 @@
+use crate::fmt;
+use crate::iter::FusedIterator;
+
+/// What a [`TakeValidated`] does with an element that fails validation.
+///
+/// In both cases, invalid elements don't count toward the limit of
+/// [`take_validated`]: it is the number of valid elements yielded.
+///
+/// [`take_validated`]: Iterator::take_validated
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[unstable(feature = "iter_take_validated", issue = "none")]
+pub enum InvalidPolicy {
+    /// Drop the invalid element and its error, and carry on with the next
+    /// element.
+    Skip,
+    /// Yield the error of the invalid element and stop.
+    Stop,
+}
+
+/// An iterator that validates the elements of `iter`, yielding at most `n`
+/// valid ones.
+///
+/// This `struct` is created by the [`take_validated`] method on [`Iterator`].
+/// See its documentation for more.
+///
+/// [`take_validated`]: Iterator::take_validated
+/// [`Iterator`]: trait.Iterator.html
+#[derive(Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[unstable(feature = "iter_take_validated", issue = "none")]
+pub struct TakeValidated<I, F> {
+    iter: I,
+    n: usize,
+    validator: F,
+    policy: InvalidPolicy,
+}
+
+impl<I, F> TakeValidated<I, F> {
+    pub(in crate::iter) fn new(
+        iter: I,
+        n: usize,
+        validator: F,
+        policy: InvalidPolicy,
+    ) -> TakeValidated<I, F> {
+        TakeValidated { iter, n, validator, policy }
+    }
+}
+
+#[unstable(feature = "iter_take_validated", issue = "none")]
+impl<I: fmt::Debug, F> fmt::Debug for TakeValidated<I, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TakeValidated")
+            .field("iter", &self.iter)
+            .field("n", &self.n)
+            .field("policy", &self.policy)
+            .finish()
+    }
+}
+
+#[unstable(feature = "iter_take_validated", issue = "none")]
+impl<I, F, E> Iterator for TakeValidated<I, F>
+where
+    I: Iterator,
+    F: FnMut(&I::Item) -> Result<(), E>,
+{
+    type Item = Result<I::Item, E>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Result<I::Item, E>> {
+        if self.n == 0 {
+            return None;
+        }
+        loop {
+            let item = self.iter.next()?;
+            match (self.validator)(&item) {
+                Ok(()) => {
+                    self.n -= 1;
+                    return Some(Ok(item));
+                }
+                Err(err) => match self.policy {
+                    InvalidPolicy::Skip => {}
+                    InvalidPolicy::Stop => {
+                        self.n = 0;
+                        return Some(Err(err));
+                    }
+                },
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.n == 0 {
+            return (0, Some(0));
+        }
+
+        let (lower, upper) = self.iter.size_hint();
+        // Any element may be invalid. When skipping, that leaves no lower
+        // bound; when stopping, the first element is yielded either way.
+        let lower = match self.policy {
+            InvalidPolicy::Skip => 0,
+            InvalidPolicy::Stop => lower.min(1),
+        };
+        // An error is only yielded while fewer than `n` valid elements have
+        // been, so at most `n` elements are yielded in total.
+        let upper = match upper {
+            Some(x) if x < self.n => Some(x),
+            _ => Some(self.n),
+        };
+        (lower, upper)
+    }
+}
+
+#[unstable(feature = "iter_take_validated", issue = "none")]
+impl<I, F, E> FusedIterator for TakeValidated<I, F>
+where
+    I: FusedIterator,
+    F: FnMut(&I::Item) -> Result<(), E>,
+{
+}
@@ -0,0 +1,97 @@
+use crate::iter::FusedIterator;
+
+/// An iterator over the results of `iter` that stops after `n` successes or
+/// after too many errors in a row.
+///
+/// This `struct` is created by the [`take_with_breaker`] method on
+/// [`Iterator`]. See its documentation for more.
+///
+/// [`take_with_breaker`]: Iterator::take_with_breaker
+/// [`Iterator`]: trait.Iterator.html
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[unstable(feature = "iter_take_with_breaker", issue = "none")]
+pub struct TakeWithBreaker<I> {
+    iter: I,
+    n: usize,
+    max_consecutive_errors: usize,
+    consecutive_errors: usize,
+    tripped: bool,
+}
+
+impl<I> TakeWithBreaker<I> {
+    pub(in crate::iter) fn new(
+        iter: I,
+        n: usize,
+        max_consecutive_errors: usize,
+    ) -> TakeWithBreaker<I> {
+        // A threshold of zero would trip before the first error is even seen,
+        // which can't happen, so it behaves like a threshold of one.
+        let max_consecutive_errors = max_consecutive_errors.max(1);
+        TakeWithBreaker { iter, n, max_consecutive_errors, consecutive_errors: 0, tripped: false }
+    }
+
+    /// Returns `true` if the iterator stopped because of too many errors in
+    /// a row, as opposed to reaching its limit or running out of elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iter_take_with_breaker)]
+    ///
+    /// let results = [Ok(1), Err("down"), Err("down"), Ok(2)];
+    /// let mut it = results.iter().copied().take_with_breaker(5, 2);
+    /// assert_eq!(it.by_ref().count(), 3);
+    /// assert!(it.is_tripped());
+    /// ```
+    #[unstable(feature = "iter_take_with_breaker", issue = "none")]
+    pub fn is_tripped(&self) -> bool {
+        self.tripped
+    }
+}
+
+#[unstable(feature = "iter_take_with_breaker", issue = "none")]
+impl<I, T, E> Iterator for TakeWithBreaker<I>
+where
+    I: Iterator<Item = Result<T, E>>,
+{
+    type Item = Result<T, E>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Result<T, E>> {
+        if self.n == 0 || self.tripped {
+            return None;
+        }
+        let item = self.iter.next()?;
+        match item {
+            Ok(_) => {
+                self.n -= 1;
+                self.consecutive_errors = 0;
+            }
+            Err(_) => {
+                self.consecutive_errors += 1;
+                if self.consecutive_errors == self.max_consecutive_errors {
+                    self.tripped = true;
+                }
+            }
+        }
+        Some(item)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.n == 0 || self.tripped {
+            return (0, Some(0));
+        }
+
+        // Errors don't count toward `n`, so only the source bounds how many
+        // elements are yielded. It takes at least `n` successes or enough
+        // errors to trip the breaker to stop early, though.
+        let (lower, upper) = self.iter.size_hint();
+        let errors_left = self.max_consecutive_errors - self.consecutive_errors;
+        (lower.min(self.n).min(errors_left), upper)
+    }
+}
+
+#[unstable(feature = "iter_take_with_breaker", issue = "none")]
+impl<I, T, E> FusedIterator for TakeWithBreaker<I> where I: FusedIterator<Item = Result<T, E>> {}
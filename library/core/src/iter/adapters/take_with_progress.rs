@@ -0,0 +1,61 @@
+use crate::iter::FusedIterator;
+
+/// An iterator that yields at most `n` elements of `iter`, each paired with
+/// the fraction of `n` completed.
+///
+/// This `struct` is created by the [`take_with_progress`] method on
+/// [`Iterator`]. See its documentation for more.
+///
+/// [`take_with_progress`]: Iterator::take_with_progress
+/// [`Iterator`]: trait.Iterator.html
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[unstable(feature = "iter_take_with_progress", issue = "none")]
+pub struct TakeWithProgress<I> {
+    iter: I,
+    n: usize,
+    yielded: usize,
+}
+
+impl<I> TakeWithProgress<I> {
+    pub(in crate::iter) fn new(iter: I, n: usize) -> TakeWithProgress<I> {
+        TakeWithProgress { iter, n, yielded: 0 }
+    }
+}
+
+#[unstable(feature = "iter_take_with_progress", issue = "none")]
+impl<I: Iterator> Iterator for TakeWithProgress<I> {
+    type Item = (f32, I::Item);
+
+    #[inline]
+    fn next(&mut self) -> Option<(f32, I::Item)> {
+        if self.yielded == self.n {
+            return None;
+        }
+        let item = self.iter.next()?;
+        self.yielded += 1;
+        Some((self.yielded as f32 / self.n as f32, item))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.n - self.yielded;
+        if remaining == 0 {
+            return (0, Some(0));
+        }
+
+        let (lower, upper) = self.iter.size_hint();
+        let lower = lower.min(remaining);
+        let upper = match upper {
+            Some(x) if x < remaining => Some(x),
+            _ => Some(remaining),
+        };
+        (lower, upper)
+    }
+}
+
+#[unstable(feature = "iter_take_with_progress", issue = "none")]
+impl<I: ExactSizeIterator> ExactSizeIterator for TakeWithProgress<I> {}
+
+#[unstable(feature = "iter_take_with_progress", issue = "none")]
+impl<I: FusedIterator> FusedIterator for TakeWithProgress<I> {}
@@ -3,14 +3,20 @@
 // can't split that into multiple files.
 
 use crate::cmp::{self, Ordering};
-use crate::ops::{ControlFlow, Try};
+use crate::hash::Hasher;
+use crate::mem::MaybeUninit;
+use crate::num::NonZeroUsize;
+use crate::ops::{Add, ControlFlow, Try};
 
 use super::super::TrustedRandomAccess;
 use super::super::{Chain, Cloned, Copied, Cycle, Enumerate, Filter, FilterMap, Fuse};
 use super::super::{FlatMap, Flatten};
 use super::super::{FromIterator, Intersperse, IntersperseWith, Product, Sum, Zip};
 use super::super::{
-    Inspect, Map, MapWhile, Peekable, Rev, Scan, Skip, SkipWhile, StepBy, Take, TakeWhile,
+    Inspect, InvalidPolicy, Map, MapWhile, Peekable, Rev, RingFill, Scan, Skip, SkipWhile, StepBy,
+    Take, TakeCrc32, TakeEma, TakeFlat, TakeHashing, TakeHeartbeat, TakeInterleaved, TakeMonotonic,
+    TakePairs, TakePeekable, TakePoll, TakeRecords, TakeSequenced, TakeSummarizing, TakeThenChain,
+    TakeValidated, TakeWhile, TakeWithBreaker, TakeWithProgress,
 };
 
 fn _assert_is_object_safe(_: &dyn Iterator<Item = ()>) {}
@@ -1292,6 +1298,828 @@ fn take(self, n: usize) -> Take<Self>
         Take::new(self, n)
     }
 
+    /// Creates an iterator that yields overlapping pairs `(prev, cur)` of
+    /// consecutive elements, stopping after `n` pairs.
+    ///
+    /// Every element except the first and the last one of the yielded window
+    /// appears in two pairs: once as `cur` and once as `prev` of the following
+    /// pair. To hand it out twice, the adapter clones it when it is yielded as
+    /// `cur`, so each pair costs one [`Clone`] of the item, except the `n`-th
+    /// pair which gives its second element away without keeping a copy.
+    ///
+    /// At least two elements are needed to produce a pair; an iterator with
+    /// fewer elements yields nothing.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(iter_take_pairs)]
+    ///
+    /// let mut iter = (0..5).take_pairs(2);
+    ///
+    /// assert_eq!(iter.next(), Some((0, 1)));
+    /// assert_eq!(iter.next(), Some((1, 2)));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    ///
+    /// Computing deltas between consecutive readings:
+    ///
+    /// ```
+    /// #![feature(iter_take_pairs)]
+    ///
+    /// let readings = [10, 13, 19, 20];
+    /// let deltas: Vec<i32> =
+    ///     readings.iter().take_pairs(usize::MAX).map(|(a, b)| b - a).collect();
+    /// assert_eq!(deltas, [3, 6, 1]);
+    /// ```
+    #[inline]
+    #[unstable(feature = "iter_take_pairs", issue = "none")]
+    fn take_pairs(self, n: usize) -> TakePairs<Self>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+    {
+        TakePairs::new(self, n)
+    }
+
+    /// Creates an iterator that yields the first `n` elements of this
+    /// iterator and then, once that cap is reached, every element of
+    /// `fallback`.
+    ///
+    /// The fallback only takes over when the cap is hit. If this iterator is
+    /// exhausted before yielding `n` elements, the returned iterator simply
+    /// ends there and `fallback` is never touched: running dry means there was
+    /// nothing more to take, not that the primary source was over budget.
+    ///
+    /// Once the cap is reached the original iterator is dropped without being
+    /// advanced any further.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(iter_take_then_chain)]
+    ///
+    /// let primary = [1, 2, 3, 4];
+    /// let fallback = [10, 20];
+    ///
+    /// let v: Vec<_> = primary.iter().take_then_chain(2, fallback.iter()).collect();
+    /// assert_eq!(v, [&1, &2, &10, &20]);
+    /// ```
+    ///
+    /// Exhausting the primary iterator does not switch to the fallback:
+    ///
+    /// ```
+    /// #![feature(iter_take_then_chain)]
+    ///
+    /// let primary = [1, 2];
+    /// let fallback = [10, 20];
+    ///
+    /// let v: Vec<_> = primary.iter().take_then_chain(5, fallback.iter()).collect();
+    /// assert_eq!(v, [&1, &2]);
+    /// ```
+    #[inline]
+    #[unstable(feature = "iter_take_then_chain", issue = "none")]
+    fn take_then_chain<J>(self, n: usize, fallback: J) -> TakeThenChain<Self, J::IntoIter>
+    where
+        Self: Sized,
+        J: IntoIterator<Item = Self::Item>,
+    {
+        TakeThenChain::new(self, n, fallback.into_iter())
+    }
+
+    /// Takes up to `N` elements from the iterator into an array, returning the
+    /// array and the number of elements written.
+    ///
+    /// The first `min(N, len)` slots of the array are filled from the
+    /// iterator, where `len` is the number of elements it yields; any slots
+    /// after that hold [`Default::default()`]. The iterator is advanced at most
+    /// `N` times, and nothing is allocated, so this is usable where there is no
+    /// heap.
+    ///
+    /// To keep using the iterator afterwards, call this on [`by_ref`].
+    ///
+    /// [`by_ref`]: Iterator::by_ref
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(iter_take_array)]
+    ///
+    /// let (array, len) = (1..=5).take_array::<3>();
+    /// assert_eq!(array, [1, 2, 3]);
+    /// assert_eq!(len, 3);
+    ///
+    /// let (array, len) = (1..=2).take_array::<4>();
+    /// assert_eq!(&array[..len], [1, 2]);
+    /// assert_eq!(array, [1, 2, 0, 0]);
+    /// ```
+    ///
+    /// Taking several batches from the same iterator:
+    ///
+    /// ```
+    /// #![feature(iter_take_array)]
+    ///
+    /// let mut iter = "hello world".bytes();
+    /// let (first, _) = iter.by_ref().take_array::<5>();
+    /// assert_eq!(&first, b"hello");
+    /// let (rest, len) = iter.take_array::<8>();
+    /// assert_eq!(&rest[..len], b" world");
+    /// ```
+    #[inline]
+    #[unstable(feature = "iter_take_array", issue = "none")]
+    fn take_array<const N: usize>(self) -> ([Self::Item; N], usize)
+    where
+        Self: Sized,
+        Self::Item: Default,
+    {
+        let mut array = [(); N].map(|()| Default::default());
+        let mut len = 0;
+        // `zip` only pulls from `self` once there is a slot left to fill.
+        for (slot, item) in array.iter_mut().zip(self) {
+            *slot = item;
+            len += 1;
+        }
+        (array, len)
+    }
+
+    /// Creates an iterator that yields at most `n` elements, inserting an
+    /// element produced by `make` after every `every` of them.
+    ///
+    /// The inserted heartbeat elements do not count towards `n`. A heartbeat
+    /// always follows a complete group of `every` elements, including the last
+    /// group if the iterator stops exactly at a group boundary, but a final
+    /// partial group is not followed by one. In particular a heartbeat is never
+    /// yielded unless at least one real element came before it.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(iter_take_heartbeat)]
+    /// use std::num::NonZeroUsize;
+    ///
+    /// let every = NonZeroUsize::new(2).unwrap();
+    /// let v: Vec<_> = (1..=5).take_heartbeat(10, every, || 0).collect();
+    /// assert_eq!(v, [1, 2, 0, 3, 4, 0, 5]);
+    ///
+    /// // Only the real elements count towards `n`.
+    /// let v: Vec<_> = (1..=5).take_heartbeat(3, every, || 0).collect();
+    /// assert_eq!(v, [1, 2, 0, 3]);
+    /// ```
+    #[inline]
+    #[unstable(feature = "iter_take_heartbeat", issue = "none")]
+    fn take_heartbeat<F>(self, n: usize, every: NonZeroUsize, make: F) -> TakeHeartbeat<Self, F>
+    where
+        Self: Sized,
+        F: FnMut() -> Self::Item,
+    {
+        TakeHeartbeat::new(self, n, every, make)
+    }
+
+    /// Creates an iterator that yields the first `n` bytes unchanged while
+    /// feeding each of them into `hasher`.
+    ///
+    /// This is meant for framed binary protocols in which a payload of known
+    /// length is followed by a checksum: the payload can be consumed and
+    /// checked in a single pass. Once exactly `n` bytes have been yielded,
+    /// [`TakeHashing::finish`] returns the hash of the whole payload. If the
+    /// source runs out first, it keeps returning `None`, so a short frame is
+    /// never mistaken for a complete one.
+    ///
+    /// Each byte is fed with [`Hasher::write_u8`], so the result matches
+    /// writing the same bytes one at a time. Note that for some hashers,
+    /// [`DefaultHasher`] among them, this differs from a single
+    /// [`Hasher::write`] of the whole slice.
+    ///
+    /// [`DefaultHasher`]: ../../std/collections/hash_map/struct.DefaultHasher.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iter_take_hashing)]
+    /// use std::collections::hash_map::DefaultHasher;
+    /// use std::hash::Hasher;
+    ///
+    /// let frame = [0xde, 0xad, 0xbe, 0xef, 0x42];
+    /// let mut bytes = frame.iter().copied();
+    ///
+    /// let mut payload = bytes.by_ref().take_hashing(4, DefaultHasher::new());
+    /// let data: Vec<u8> = payload.by_ref().collect();
+    /// assert_eq!(data, [0xde, 0xad, 0xbe, 0xef]);
+    ///
+    /// let mut expected = DefaultHasher::new();
+    /// data.iter().for_each(|&b| expected.write_u8(b));
+    /// assert_eq!(payload.finish(), Some(expected.finish()));
+    ///
+    /// // The checksum byte is left in the source.
+    /// assert_eq!(bytes.next(), Some(0x42));
+    /// ```
+    #[inline]
+    #[unstable(feature = "iter_take_hashing", issue = "none")]
+    fn take_hashing<H>(self, n: usize, hasher: H) -> TakeHashing<Self, H>
+    where
+        Self: Sized + Iterator<Item = u8>,
+        H: Hasher,
+    {
+        TakeHashing::new(self, n, hasher)
+    }
+
+    /// Creates an iterator that groups a byte stream into at most `n`
+    /// fixed-width records of `W` bytes each.
+    ///
+    /// Each record is yielded as a `[u8; W]` array, which makes it convenient
+    /// to decode fixed-size packet headers or table rows with functions such
+    /// as [`u32::from_be_bytes`]. The iterator stops after `n` records, or as
+    /// soon as the source runs out.
+    ///
+    /// # Trailing partial records
+    ///
+    /// If the source runs out in the middle of a record, that partial record
+    /// is dropped rather than yielded or padded. Its bytes are kept, though,
+    /// and can be retrieved with [`TakeRecords::remainder`] once the iterator
+    /// has returned `None`. Callers that consider a truncated stream an error
+    /// should check that the remainder is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iter_take_records)]
+    ///
+    /// let bytes = [0, 0, 0, 1, 0, 0, 0, 2, 0, 0];
+    /// let mut records = bytes.iter().copied().take_records::<4>(8);
+    ///
+    /// let values: Vec<u32> = records.by_ref().map(u32::from_be_bytes).collect();
+    /// assert_eq!(values, [1, 2]);
+    ///
+    /// // The last two bytes did not make up a whole record.
+    /// assert_eq!(records.remainder(), [0, 0]);
+    /// ```
+    #[inline]
+    #[unstable(feature = "iter_take_records", issue = "none")]
+    fn take_records<const W: usize>(self, n: usize) -> TakeRecords<Self, W>
+    where
+        Self: Sized + Iterator<Item = u8>,
+    {
+        TakeRecords::new(self, n)
+    }
+
+    /// Creates an iterator over a source that is not always ready, which
+    /// stops after `n` ready elements and can be driven in a poll-like way.
+    ///
+    /// The source reports readiness through an [`Option`] wrapper around each
+    /// element: `Some(item)` is a ready element, while `None` means "nothing
+    /// available right now, try again later", as a nonblocking reader would
+    /// report `WouldBlock`. The end of the source is, as usual, signalled by
+    /// [`next`] returning `None`.
+    ///
+    /// The returned iterator passes these markers through unchanged, counting
+    /// only ready elements towards `n`. Its [`poll_next`] method translates
+    /// them into the shape expected by async code: [`Poll::Pending`] when the
+    /// source is not ready, `Poll::Ready(Some(item))` for an element and
+    /// `Poll::Ready(None)` at the end.
+    ///
+    /// This is a building block for bridging blocking-style sources into an
+    /// async context, not an implementation of an async stream trait.
+    ///
+    /// # Integration contract
+    ///
+    /// * A not-ready marker never counts towards `n` and never ends
+    ///   iteration, so a source that is never ready makes [`poll_next`]
+    ///   return [`Poll::Pending`] forever.
+    /// * Returning [`Poll::Pending`] does not register a [`Waker`]. Whoever
+    ///   polls must arrange to be woken when the source may have become
+    ///   ready, for instance by waiting for the underlying file descriptor to
+    ///   become readable, before polling again.
+    /// * After `Poll::Ready(None)` the source is never polled again.
+    ///
+    /// [`next`]: Iterator::next
+    /// [`poll_next`]: TakePoll::poll_next
+    /// [`Poll::Pending`]: crate::task::Poll::Pending
+    /// [`Waker`]: crate::task::Waker
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iter_take_poll)]
+    /// use std::task::Poll;
+    ///
+    /// // `None` marks a moment at which the source had nothing to offer.
+    /// let source = vec![None, Some('a'), None, None, Some('b'), Some('c')];
+    /// let mut it = source.into_iter().take_poll(2);
+    ///
+    /// let mut received = Vec::new();
+    /// loop {
+    ///     match it.poll_next() {
+    ///         Poll::Ready(Some(c)) => received.push(c),
+    ///         Poll::Ready(None) => break,
+    ///         // A real caller would wait for readiness here.
+    ///         Poll::Pending => continue,
+    ///     }
+    /// }
+    /// assert_eq!(received, ['a', 'b']);
+    /// ```
+    #[inline]
+    #[unstable(feature = "iter_take_poll", issue = "none")]
+    fn take_poll<T>(self, n: usize) -> TakePoll<Self>
+    where
+        Self: Sized + Iterator<Item = Option<T>>,
+    {
+        TakePoll::new(self, n)
+    }
+
+    /// Creates an iterator that flattens nested iterables, yielding at most
+    /// `n` inner elements in total.
+    ///
+    /// This yields the same elements as `self.flatten().take(n)`, stopping in
+    /// the middle of an inner iterator if that is where the cap is reached.
+    /// Unlike that combination, the partially consumed inner iterator is not
+    /// lost: [`TakeFlat::inner_mut`] and [`TakeFlat::into_parts`] give access
+    /// to it, so the remaining elements can still be processed. Only as many
+    /// outer elements are pulled as are needed to produce `n` inner ones.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iter_take_flat)]
+    ///
+    /// let packets = vec![vec![1, 2, 3], vec![4, 5], vec![6]];
+    /// let mut it = packets.into_iter().take_flat(4);
+    /// assert_eq!(it.by_ref().collect::<Vec<_>>(), [1, 2, 3, 4]);
+    ///
+    /// let (rest, inner) = it.into_parts();
+    /// assert_eq!(inner.map(Iterator::collect::<Vec<_>>), Some(vec![5]));
+    /// assert_eq!(rest.collect::<Vec<_>>(), [vec![6]]);
+    /// ```
+    #[inline]
+    #[unstable(feature = "iter_take_flat", issue = "none")]
+    fn take_flat(self, n: usize) -> TakeFlat<Self>
+    where
+        Self: Sized,
+        Self::Item: IntoIterator,
+    {
+        TakeFlat::new(self, n)
+    }
+
+    /// Creates an iterator that yields at most `n` elements and can peek at
+    /// the next one without using up any of that limit.
+    ///
+    /// Peeking needs a one-element lookahead buffer. Combining [`take`] and
+    /// [`peekable`] keeps that buffer on one side of the limit or the other:
+    /// with `peekable().take(n)`, `take` cannot see the buffer, and with
+    /// `take(n).peekable()`, peeking pulls an element through `take` and
+    /// counts it against `n` before it has been yielded. This adapter keeps
+    /// the buffer and the limit together, so [`TakePeekable::peek`] only ever
+    /// fills the buffer, and the remaining limit goes down only when
+    /// [`next`] actually hands an element out. Once `n` elements have been
+    /// yielded, peeking returns `None` without pulling anything more from the
+    /// source.
+    ///
+    /// [`take`]: Iterator::take
+    /// [`peekable`]: Iterator::peekable
+    /// [`next`]: Iterator::next
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iter_take_peekable)]
+    ///
+    /// let mut words = ["a", "b", "", "c"].iter().take_peekable(3);
+    /// let mut line = String::new();
+    /// while let Some(word) = words.next() {
+    ///     line.push_str(word);
+    ///     if words.peek().map_or(false, |next| !next.is_empty()) {
+    ///         line.push(' ');
+    ///     }
+    /// }
+    /// assert_eq!(line, "a b");
+    /// ```
+    #[inline]
+    #[unstable(feature = "iter_take_peekable", issue = "none")]
+    fn take_peekable(self, n: usize) -> TakePeekable<Self>
+    where
+        Self: Sized,
+    {
+        TakePeekable::new(self, n)
+    }
+
+    /// Creates an iterator that yields at most `n` elements while keeping
+    /// running statistics over them: the minimum, maximum, count and sum.
+    ///
+    /// The elements are yielded unchanged. The statistics are computed
+    /// incrementally as each element goes by, so once iteration is done
+    /// [`TakeSummarizing::summary`] describes everything that was yielded
+    /// without a second pass over the data, which may no longer be
+    /// available.
+    ///
+    /// The sum is accumulated with [`Add`], starting from the first element,
+    /// so it overflows exactly when adding up the elements by hand would.
+    /// Elements that are not comparable, such as a floating-point NaN after
+    /// the first element, never replace the current minimum or maximum.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iter_take_summarizing)]
+    ///
+    /// let readings = [2.5, 3.0, 1.5, 4.0, 9.9];
+    /// let mut it = readings.iter().copied().take_summarizing(4);
+    /// let loaded: Vec<f64> = it.by_ref().collect();
+    /// assert_eq!(loaded.len(), 4);
+    ///
+    /// let summary = it.summary().unwrap();
+    /// assert_eq!(summary.count(), 4);
+    /// assert_eq!(summary.min(), 1.5);
+    /// assert_eq!(summary.max(), 4.0);
+    /// assert_eq!(summary.sum() / summary.count() as f64, 2.75);
+    /// ```
+    #[inline]
+    #[unstable(feature = "iter_take_summarizing", issue = "none")]
+    fn take_summarizing(self, n: usize) -> TakeSummarizing<Self>
+    where
+        Self: Sized,
+        Self::Item: Copy + PartialOrd + Add<Output = Self::Item>,
+    {
+        TakeSummarizing::new(self, n)
+    }
+
+    /// Creates an iterator that takes elements from this iterator and
+    /// `other` in turn, yielding at most `n` elements in total.
+    ///
+    /// The first element comes from this iterator, the second from `other`,
+    /// and so on, alternating fairly between the two. Once one of them is
+    /// exhausted, the rest of the elements come from the other one alone, so
+    /// iteration only ends early if both run out before `n` elements have
+    /// been yielded. An exhausted iterator is never polled again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iter_take_interleaved)]
+    ///
+    /// let a = [1, 2, 3];
+    /// let b = [4, 5];
+    ///
+    /// let v: Vec<_> = a.iter().take_interleaved(&b, 4).collect();
+    /// assert_eq!(v, [&1, &4, &2, &5]);
+    ///
+    /// // `b` runs out first, after which `a` is drained on its own.
+    /// let v: Vec<_> = a.iter().take_interleaved(&b, 10).collect();
+    /// assert_eq!(v, [&1, &4, &2, &5, &3]);
+    /// ```
+    #[inline]
+    #[unstable(feature = "iter_take_interleaved", issue = "none")]
+    fn take_interleaved<J>(self, other: J, n: usize) -> TakeInterleaved<Self, J::IntoIter>
+    where
+        Self: Sized,
+        J: IntoIterator<Item = Self::Item>,
+    {
+        TakeInterleaved::new(self, other.into_iter(), n)
+    }
+
+    /// Creates an iterator that yields at most `n` elements, each paired
+    /// with how far along the `n` elements it is, as a fraction.
+    ///
+    /// The `i`-th element yielded, counting from 1, comes paired with
+    /// `i / n`, so the fractions run over `(0, 1]` and the `n`-th element
+    /// gets exactly `1.0`. This is handy for driving a progress bar. If the
+    /// source runs out early, the last fraction yielded is less than `1.0`.
+    /// If `n` is zero, nothing is yielded.
+    ///
+    /// The fractions are computed as `f32`, which is precise enough for
+    /// display, but for very large `n` consecutive elements may get the same
+    /// fraction.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iter_take_with_progress)]
+    ///
+    /// let files = ["a.txt", "b.txt", "c.txt", "d.txt", "e.txt"];
+    /// let mut progress = Vec::new();
+    /// for (done, file) in files.iter().take_with_progress(4) {
+    ///     progress.push(format!("{} {:.0}%", file, done * 100.0));
+    /// }
+    /// assert_eq!(progress, ["a.txt 25%", "b.txt 50%", "c.txt 75%", "d.txt 100%"]);
+    /// ```
+    #[inline]
+    #[unstable(feature = "iter_take_with_progress", issue = "none")]
+    fn take_with_progress(self, n: usize) -> TakeWithProgress<Self>
+    where
+        Self: Sized,
+    {
+        TakeWithProgress::new(self, n)
+    }
+
+    /// Creates an iterator that yields the exponential moving average of at
+    /// most `n` elements, each converted to `f64`.
+    ///
+    /// The first value yielded is the first element itself. Every later value
+    /// is `alpha * x + (1.0 - alpha) * prev`, where `x` is the new element and
+    /// `prev` is the value yielded before it. The average is updated as each
+    /// element is pulled, so no elements are buffered. A larger `alpha` makes
+    /// the average follow new elements more closely: `1.0` yields the
+    /// elements unchanged, while `0.0` keeps yielding the first element.
+    ///
+    /// `alpha` is clamped to the range `[0.0, 1.0]`. A NaN `alpha` is kept
+    /// as is, so every value after the first is NaN.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iter_take_ema)]
+    ///
+    /// let readings = [10u8, 20, 20, 40, 99];
+    /// let smoothed: Vec<f64> = readings.iter().copied().take_ema(4, 0.5).collect();
+    /// assert_eq!(smoothed, [10.0, 15.0, 17.5, 28.75]);
+    /// ```
+    #[inline]
+    #[unstable(feature = "iter_take_ema", issue = "none")]
+    fn take_ema(self, n: usize, alpha: f64) -> TakeEma<Self>
+    where
+        Self: Sized,
+        Self::Item: Into<f64>,
+    {
+        TakeEma::new(self, n, alpha)
+    }
+
+    /// Creates an iterator that yields at most `n` elements, checking with
+    /// `cmp` that each one is not less than the one before it.
+    ///
+    /// Elements are yielded wrapped in [`Ok`] for as long as they are in
+    /// order, that is, as long as `cmp(previous, current)` is not
+    /// [`Ordering::Greater`]. The first element that breaks the order is
+    /// yielded as an [`Err`] holding an [`OrderingViolation`] with its index
+    /// and both elements, and the iterator stops right after it, without
+    /// pulling anything more from the source. This makes it easy to reject
+    /// corrupt input as soon as it shows up.
+    ///
+    /// Equal elements are allowed, so the order checked is non-decreasing.
+    /// To require a strictly increasing order, have `cmp` return
+    /// [`Ordering::Greater`] for equal elements as well. To check a
+    /// non-increasing order, reverse the comparison.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iter_take_monotonic)]
+    ///
+    /// let timestamps = [3, 5, 5, 4, 9];
+    /// let mut it = timestamps.iter().take_monotonic(5, |a, b| a.cmp(b));
+    ///
+    /// assert_eq!(it.next(), Some(Ok(&3)));
+    /// assert_eq!(it.next(), Some(Ok(&5)));
+    /// assert_eq!(it.next(), Some(Ok(&5)));
+    ///
+    /// let violation = it.next().unwrap().unwrap_err();
+    /// assert_eq!(violation.index(), 3);
+    /// assert_eq!((violation.previous(), violation.current()), (&&5, &&4));
+    ///
+    /// // Nothing is yielded after a violation.
+    /// assert_eq!(it.next(), None);
+    /// ```
+    ///
+    /// [`OrderingViolation`]: crate::iter::OrderingViolation
+    #[inline]
+    #[unstable(feature = "iter_take_monotonic", issue = "none")]
+    fn take_monotonic<F>(self, n: usize, cmp: F) -> TakeMonotonic<Self, F>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+        F: FnMut(&Self::Item, &Self::Item) -> Ordering,
+    {
+        TakeMonotonic::new(self, n, cmp)
+    }
+
+    /// Consumes the iterator, writing its elements into the ring buffer
+    /// `buf` and keeping the most recent `buf.len()` of them.
+    ///
+    /// Elements are written to `buf` in order, starting at index 0. Once the
+    /// buffer is full, each new element overwrites, and drops, the oldest one
+    /// kept, so the buffer always holds the latest elements seen. No memory
+    /// is allocated, which makes this a building block for tailing a stream
+    /// in fixed memory. If `buf` is empty, every element is dropped.
+    ///
+    /// The returned [`RingFill`] tells where the elements kept are: they
+    /// start at [`RingFill::head`] and may wrap around the end of the buffer,
+    /// and [`RingFill::ranges`] gives them as two index ranges. These slots
+    /// are initialized, and it is up to the caller to read or drop their
+    /// elements. The initial contents of `buf` are treated as uninitialized
+    /// and are never dropped.
+    ///
+    /// This consumes the whole iterator, so it never returns if the iterator
+    /// is infinite.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iter_take_into_ring)]
+    /// use std::mem::MaybeUninit;
+    ///
+    /// let mut buf = [MaybeUninit::uninit(); 4];
+    /// let ring = (1..=6).take_into_ring(&mut buf);
+    /// assert_eq!(ring.len(), 4);
+    ///
+    /// let (older, newer) = ring.ranges();
+    /// assert_eq!((older.clone(), newer.clone()), (2..4, 0..2));
+    ///
+    /// // SAFETY: `RingFill` says these slots are initialized.
+    /// let last: Vec<i32> =
+    ///     older.chain(newer).map(|i| unsafe { buf[i].assume_init() }).collect();
+    /// assert_eq!(last, [3, 4, 5, 6]);
+    /// ```
+    #[unstable(feature = "iter_take_into_ring", issue = "none")]
+    fn take_into_ring(self, buf: &mut [MaybeUninit<Self::Item>]) -> RingFill
+    where
+        Self: Sized,
+    {
+        RingFill::fill(self, buf)
+    }
+
+    /// Creates an iterator that yields at most `n` elements, each paired
+    /// with a sequence number counting up from `start`.
+    ///
+    /// The first element yielded is tagged with `start`, the next with
+    /// `start + 1`, and so on. Unlike the count of [`enumerate`], the numbering
+    /// doesn't have to start at zero: persist [`TakeSequenced::next_seq`]
+    /// along with the processed elements, and pass it back as `start` after a
+    /// restart, so that every element ever processed keeps a unique number.
+    ///
+    /// [`enumerate`]: Iterator::enumerate
+    ///
+    /// # Overflow Behavior
+    ///
+    /// Sequence numbers saturate at [`u64::MAX`] instead of wrapping: once it
+    /// is reached, every further element is tagged with [`u64::MAX`] as
+    /// well. Numbers are only unique below that, which is never a concern in
+    /// practice when counting up from a small `start`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iter_take_sequenced)]
+    ///
+    /// let events = ["boot", "login", "logout"];
+    /// let mut first_run = events.iter().take_sequenced(2, 1);
+    /// assert_eq!(first_run.next(), Some((1, &"boot")));
+    /// assert_eq!(first_run.next(), Some((2, &"login")));
+    /// assert_eq!(first_run.next(), None);
+    /// let checkpoint = first_run.next_seq();
+    ///
+    /// // After a restart, the numbering picks up where it left off.
+    /// let mut second_run = events[2..].iter().take_sequenced(2, checkpoint);
+    /// assert_eq!(second_run.next(), Some((3, &"logout")));
+    /// ```
+    #[inline]
+    #[unstable(feature = "iter_take_sequenced", issue = "none")]
+    fn take_sequenced(self, n: usize, start: u64) -> TakeSequenced<Self>
+    where
+        Self: Sized,
+    {
+        TakeSequenced::new(self, n, start)
+    }
+
+    /// Creates an iterator that runs `validator` on each element and yields
+    /// at most `n` valid elements.
+    ///
+    /// Valid elements are yielded as `Ok`. What happens to an element for
+    /// which `validator` returns an error is up to `policy`:
+    ///
+    /// - With [`InvalidPolicy::Skip`], the element and its error are
+    ///   dropped, and the iterator carries on with the next element. Skipped
+    ///   elements don't count toward `n`, so up to `n` valid elements are
+    ///   still yielded, however many invalid ones come between them.
+    /// - With [`InvalidPolicy::Stop`], the error is yielded as `Err`, and the
+    ///   iterator stops. The error doesn't count toward `n` either, but it is
+    ///   only ever yielded while fewer than `n` valid elements have been, so
+    ///   at most `n` elements are yielded in total. Once `n` valid elements
+    ///   have been yielded, no further element is pulled or validated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iter_take_validated)]
+    /// use std::iter::InvalidPolicy;
+    ///
+    /// let check = |x: &i32| if *x >= 0 { Ok(()) } else { Err(format!("{} is negative", x)) };
+    /// let records = [1, -2, 3, -4, 5];
+    ///
+    /// let mut skipping = records.iter().copied().take_validated(3, check, InvalidPolicy::Skip);
+    /// assert_eq!(skipping.next(), Some(Ok(1)));
+    /// assert_eq!(skipping.next(), Some(Ok(3)));
+    /// assert_eq!(skipping.next(), Some(Ok(5)));
+    /// assert_eq!(skipping.next(), None);
+    ///
+    /// let mut stopping = records.iter().copied().take_validated(3, check, InvalidPolicy::Stop);
+    /// assert_eq!(stopping.next(), Some(Ok(1)));
+    /// assert_eq!(stopping.next(), Some(Err("-2 is negative".to_string())));
+    /// assert_eq!(stopping.next(), None);
+    /// ```
+    #[inline]
+    #[unstable(feature = "iter_take_validated", issue = "none")]
+    fn take_validated<F, E>(
+        self,
+        n: usize,
+        validator: F,
+        policy: InvalidPolicy,
+    ) -> TakeValidated<Self, F>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> Result<(), E>,
+    {
+        TakeValidated::new(self, n, validator, policy)
+    }
+
+    /// Creates an iterator over results that yields at most `n` `Ok` values,
+    /// and trips like a circuit breaker after `max_consecutive_errors`
+    /// errors in a row.
+    ///
+    /// Errors are yielded as they come, so isolated failures can be handled
+    /// or logged without ending the stream, and they don't count toward `n`.
+    /// Each error increments a counter, and each `Ok` value resets it to
+    /// zero, so only a run of errors with no success in between trips the
+    /// breaker. The error that brings the counter up to
+    /// `max_consecutive_errors` is still yielded, and then the iterator
+    /// stops, as reported by [`TakeWithBreaker::is_tripped`]. A
+    /// `max_consecutive_errors` of zero behaves like one, tripping on the
+    /// first error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iter_take_with_breaker)]
+    ///
+    /// let results = [Ok(1), Err("timeout"), Ok(2), Err("refused"), Err("refused"), Ok(3)];
+    /// let mut it = results.iter().copied().take_with_breaker(10, 2);
+    ///
+    /// // A single error is passed through, and the next success resets the count.
+    /// assert_eq!(it.next(), Some(Ok(1)));
+    /// assert_eq!(it.next(), Some(Err("timeout")));
+    /// assert_eq!(it.next(), Some(Ok(2)));
+    ///
+    /// // Two errors in a row trip the breaker.
+    /// assert_eq!(it.next(), Some(Err("refused")));
+    /// assert_eq!(it.next(), Some(Err("refused")));
+    /// assert_eq!(it.next(), None);
+    /// assert!(it.is_tripped());
+    /// ```
+    #[inline]
+    #[unstable(feature = "iter_take_with_breaker", issue = "none")]
+    fn take_with_breaker<T, E>(
+        self,
+        n: usize,
+        max_consecutive_errors: usize,
+    ) -> TakeWithBreaker<Self>
+    where
+        Self: Sized + Iterator<Item = Result<T, E>>,
+    {
+        TakeWithBreaker::new(self, n, max_consecutive_errors)
+    }
+
+    /// Creates an iterator that yields at most `n` bytes, computing their
+    /// CRC-32 checksum along the way.
+    ///
+    /// The checksum is the common IEEE 802.3 variant used by Ethernet, zlib,
+    /// gzip and PNG. Once `n` bytes have been yielded, [`finish_crc`] returns
+    /// it, so that a region of known length can be verified against an
+    /// expected checksum while it is being read. This is [`take_hashing`]
+    /// with a CRC-32 hasher.
+    ///
+    /// [`finish_crc`]: TakeCrc32::finish_crc
+    /// [`take_hashing`]: Iterator::take_hashing
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iter_take_crc32)]
+    ///
+    /// let record = b"hello world\x0d\x4a\x11\x85";
+    /// let mut bytes = record.iter().copied();
+    ///
+    /// let mut payload = bytes.by_ref().take_crc32(11);
+    /// let data: Vec<u8> = payload.by_ref().collect();
+    /// assert_eq!(data, b"hello world");
+    ///
+    /// // The expected checksum follows the payload, big-endian.
+    /// let mut expected = [0; 4];
+    /// expected.iter_mut().for_each(|b| *b = bytes.next().unwrap());
+    /// assert_eq!(payload.finish_crc(), Some(u32::from_be_bytes(expected)));
+    /// ```
+    #[inline]
+    #[unstable(feature = "iter_take_crc32", issue = "none")]
+    fn take_crc32(self, n: usize) -> TakeCrc32<Self>
+    where
+        Self: Sized + Iterator<Item = u8>,
+    {
+        TakeCrc32::new(self, n)
+    }
+
     /// An iterator adaptor similar to [`fold`] that holds internal state and
     /// produces a new iterator.
     ///
@@ -17,7 +17,25 @@
 mod skip_while;
 mod step_by;
 mod take;
+mod take_crc32;
+mod take_ema;
+mod take_flat;
+mod take_hashing;
+mod take_heartbeat;
+mod take_interleaved;
+mod take_into_ring;
+mod take_monotonic;
+mod take_pairs;
+mod take_peekable;
+mod take_poll;
+mod take_records;
+mod take_sequenced;
+mod take_summarizing;
+mod take_then_chain;
+mod take_validated;
 mod take_while;
+mod take_with_breaker;
+mod take_with_progress;
 mod zip;
 
 use core::cell::Cell;
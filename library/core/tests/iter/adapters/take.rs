@@ -73,6 +73,110 @@ fn test_iterator_take_nth_back() {
     assert_eq!(it.nth_back(1), None);
 }
 
+#[test]
+fn test_iterator_take_advance_by() {
+    let mut take = (0..10).take(3);
+    assert_eq!(take.advance_by(2), Ok(()));
+    assert_eq!(take.next(), Some(2));
+    assert_eq!(take.advance_by(1), Err(0));
+
+    let mut take = (0..10).take(3);
+    assert_eq!(take.advance_by(0), Ok(()));
+    assert_eq!(take.advance_by(5), Err(3));
+    assert_eq!(take.next(), None);
+
+    // The take limit is not hit, but the underlying iterator runs out.
+    let mut it = 0..2;
+    let mut take = it.by_ref().take(5);
+    assert_eq!(take.advance_by(4), Err(2));
+    assert_eq!(take.size_hint(), (0, Some(0)));
+    assert_eq!(take.remaining(), 3);
+    assert_eq!(it.next(), None);
+
+    // Nothing past the limit is consumed from the underlying iterator.
+    let mut it = 0..10;
+    assert_eq!(it.by_ref().take(4).advance_by(usize::MAX), Err(4));
+    assert_eq!(it.next(), Some(4));
+}
+
+#[test]
+fn test_iterator_take_advance_back_by() {
+    let xs = [0, 1, 2, 3, 4];
+
+    // The take limit is smaller than the slice.
+    let mut take = xs.iter().take(3);
+    assert_eq!(take.advance_back_by(1), Ok(()));
+    assert_eq!(take.next_back(), Some(&1));
+    assert_eq!(take.advance_back_by(2), Err(1));
+    assert_eq!(take.next_back(), None);
+    assert_eq!(take.next(), None);
+
+    // The take limit is equal to the length of the slice.
+    let mut take = xs.iter().take(5);
+    assert_eq!(take.advance_back_by(0), Ok(()));
+    assert_eq!(take.advance_back_by(2), Ok(()));
+    assert_eq!(take.len(), 3);
+    assert_eq!(take.next_back(), Some(&2));
+    assert_eq!(take.advance_back_by(3), Err(2));
+
+    // The take limit is larger than the slice.
+    let mut take = xs.iter().take(8);
+    assert_eq!(take.advance_back_by(3), Ok(()));
+    assert_eq!(take.next_back(), Some(&1));
+    assert_eq!(take.next(), Some(&0));
+    assert_eq!(take.advance_back_by(1), Err(0));
+
+    let mut take = xs.iter().take(8);
+    assert_eq!(take.advance_back_by(usize::MAX), Err(5));
+    assert_eq!(take.next(), None);
+}
+
+#[test]
+fn test_iterator_take_count() {
+    // Not `TrustedRandomAccess`, so the elements are counted one by one.
+    assert_eq!((0..).take(5).count(), 5);
+    assert_eq!((0..).take(0).count(), 0);
+
+    assert_eq!((0..10).take(5).count(), 5);
+    assert_eq!((0..3).take(5).count(), 3);
+    let xs = [0, 1, 2, 3, 4];
+    let mut it = xs.iter().take(4);
+    it.next();
+    assert_eq!(it.count(), 3);
+
+    // Side effects still happen for every element within the limit.
+    let mut calls = 0;
+    let count = (0..10)
+        .map(|x| {
+            calls += 1;
+            x
+        })
+        .take(5)
+        .count();
+    assert_eq!(count, 5);
+    assert_eq!(calls, 5);
+}
+
+#[test]
+fn test_iterator_take_partition_take() {
+    let (even, odd): (Vec<i32>, Vec<i32>) = (0..10).take(6).partition_take(|&n| n % 2 == 0);
+    assert_eq!(even, [0, 2, 4]);
+    assert_eq!(odd, [1, 3, 5]);
+    assert_eq!(even.len() + odd.len(), 6);
+
+    // The cap counts elements already taken.
+    let mut take = (0..10).take(6);
+    take.next();
+    let (even, odd): (Vec<i32>, Vec<i32>) = take.partition_take(|&n| n % 2 == 0);
+    assert_eq!(even, [2, 4]);
+    assert_eq!(odd, [1, 3, 5]);
+
+    // The underlying iterator runs out first.
+    let (small, large): (Vec<i32>, Vec<i32>) = (0..3).take(6).partition_take(|&n| n < 1);
+    assert_eq!(small, [0]);
+    assert_eq!(large, [1, 2]);
+}
+
 #[test]
 fn test_iterator_take_short() {
     let xs = [0, 1, 2, 3];
@@ -124,3 +228,37 @@ fn test_take_try_folds() {
     assert_eq!(iter.try_for_each(Err), Err(2));
     assert_eq!(iter.try_for_each(Err), Ok(()));
 }
+
+#[test]
+fn test_take_remaining() {
+    let mut it = (0..10).take(3);
+    assert_eq!(it.remaining(), 3);
+    it.next();
+    assert_eq!(it.remaining(), 2);
+    it.by_ref().for_each(drop);
+    assert_eq!(it.remaining(), 0);
+
+    // `remaining` is only an upper bound on what is left.
+    let it = (0..2).take(5);
+    assert_eq!(it.remaining(), 5);
+}
+
+#[test]
+fn test_take_persist_and_resume() {
+    let data: Vec<u32> = (100..120).collect();
+    let uninterrupted: Vec<_> = data.iter().skip(2).take(12).collect();
+
+    // Consume part of the batch, then checkpoint the position and cap.
+    let mut batch = data.iter().skip(2).take(12);
+    let mut output: Vec<_> = batch.by_ref().take(5).collect();
+    let checkpoint = (2 + output.len(), (batch.remaining() as u64).to_le_bytes());
+    drop(batch);
+
+    // Rebuild the inner iterator from scratch and resume with the saved cap.
+    let (offset, saved) = checkpoint;
+    let remaining = u64::from_le_bytes(saved) as usize;
+    let resumed = Take::with_remaining(data[offset..].iter(), remaining);
+    output.extend(resumed);
+
+    assert_eq!(output, uninterrupted);
+}
@@ -0,0 +1,38 @@
+use core::iter::*;
+
+// A bit-at-a-time implementation to check the table-driven one against.
+fn crc32_reference(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xedb8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+#[test]
+fn test_take_crc32() {
+    let data: Vec<u8> = (0..1000u32).map(|i| (i * 7 + i / 3) as u8).collect();
+    for &n in &[0, 1, 9, 255, 256, 999] {
+        let mut it = data.iter().copied().take_crc32(n);
+        let taken: Vec<u8> = it.by_ref().collect();
+        assert_eq!(taken, data[..n]);
+        assert_eq!(it.finish_crc(), Some(crc32_reference(&data[..n])), "n = {}", n);
+    }
+
+    assert_eq!(b"123456789".iter().copied().take_crc32(9).finish_crc(), None);
+    let mut it = b"123456789".iter().copied().take_crc32(9);
+    it.by_ref().for_each(drop);
+    assert_eq!(it.finish_crc(), Some(0xcbf4_3926));
+}
+
+#[test]
+fn test_take_crc32_short_source() {
+    let mut it = [1u8, 2, 3].iter().copied().take_crc32(5);
+    assert_eq!(it.size_hint(), (3, Some(3)));
+    assert_eq!(it.by_ref().count(), 3);
+    assert_eq!(it.finish_crc(), None);
+}
@@ -0,0 +1,50 @@
+use core::iter::*;
+
+#[test]
+fn test_take_ema_step() {
+    // A step from 0 to 1: with `alpha = 0.5`, the average closes half of the
+    // remaining gap on every step, so it is `1 - 0.5^k` after `k` ones.
+    let step = repeat(0u8).take(4).chain(repeat(1u8));
+    let ema: Vec<f64> = step.take_ema(12, 0.5).collect();
+    assert_eq!(ema.len(), 12);
+    assert_eq!(ema[..4], [0.0, 0.0, 0.0, 0.0]);
+    for (k, &value) in ema[4..].iter().enumerate() {
+        assert_eq!(value, 1.0 - 0.5f64.powi(k as i32 + 1));
+    }
+    assert!(ema.windows(2).all(|w| w[0] <= w[1]));
+    assert!(1.0 - ema[11] < 0.01);
+
+    // A smaller `alpha` converges more slowly.
+    let step = repeat(0u8).take(4).chain(repeat(1u8));
+    let slow = step.take_ema(12, 0.1).last().unwrap();
+    assert!(slow < ema[11]);
+    assert!((slow - (1.0 - 0.9f64.powi(8))).abs() < 1e-12);
+}
+
+#[test]
+fn test_take_ema_alpha_bounds() {
+    let xs = [1.0f32, 5.0, 3.0];
+    let first: Vec<f64> = xs.iter().copied().take_ema(3, 0.0).collect();
+    assert_eq!(first, [1.0, 1.0, 1.0]);
+    let same: Vec<f64> = xs.iter().copied().take_ema(3, 1.0).collect();
+    assert_eq!(same, [1.0, 5.0, 3.0]);
+
+    // Out-of-range values are clamped.
+    let clamped: Vec<f64> = xs.iter().copied().take_ema(3, -2.0).collect();
+    assert_eq!(clamped, first);
+    let clamped: Vec<f64> = xs.iter().copied().take_ema(3, 7.5).collect();
+    assert_eq!(clamped, same);
+}
+
+#[test]
+fn test_take_ema_limit() {
+    let mut it = (1..10u32).take_ema(2, 0.5);
+    assert_eq!(it.len(), 2);
+    assert_eq!(it.next(), Some(1.0));
+    assert_eq!(it.next(), Some(1.5));
+    assert_eq!(it.next(), None);
+
+    let mut it = (1..10u32).take_ema(0, 0.5);
+    assert_eq!(it.size_hint(), (0, Some(0)));
+    assert_eq!(it.next(), None);
+}
@@ -0,0 +1,49 @@
+use core::iter::*;
+
+#[test]
+fn test_take_flat() {
+    let groups = [[1, 2, 3].to_vec(), [4, 5].to_vec()];
+    let mut it = groups.iter().take_flat(4);
+    assert_eq!(it.by_ref().copied().collect::<Vec<_>>(), [1, 2, 3, 4]);
+    assert_eq!(it.next(), None);
+    // The second group stopped right before its last element.
+    assert_eq!(it.inner_mut().unwrap().next(), Some(&5));
+}
+
+#[test]
+fn test_take_flat_into_parts() {
+    let groups = [[1, 2], [3, 4], [5, 6]];
+    let mut it = groups.iter().take_flat(2);
+    assert_eq!(it.by_ref().collect::<Vec<_>>(), [&1, &2]);
+    let (mut rest, inner) = it.into_parts();
+    // The cap fell on a group boundary, so nothing more was pulled.
+    assert_eq!(inner.unwrap().next(), None);
+    assert_eq!(rest.next(), Some(&[3, 4]));
+
+    let v: Vec<_> = groups.iter().take_flat(10).copied().collect();
+    assert_eq!(v, [1, 2, 3, 4, 5, 6]);
+    assert_eq!(groups.iter().take_flat(0).next(), None);
+
+    let empty_groups: [&[i32]; 3] = [&[], &[7], &[]];
+    let v: Vec<_> = empty_groups.iter().copied().take_flat(5).collect();
+    assert_eq!(v, [&7]);
+}
+
+#[test]
+fn test_take_flat_size_hint() {
+    let groups = [[1, 2, 3], [4, 5, 6]];
+    let mut it = groups.iter().take_flat(4);
+    assert_eq!(it.size_hint(), (0, Some(4)));
+    it.next();
+    assert_eq!(it.size_hint(), (2, Some(3)));
+    it.next();
+    it.next();
+    assert_eq!(it.size_hint(), (0, Some(1)));
+    it.next();
+    assert_eq!(it.size_hint(), (0, Some(0)));
+
+    // Once the outer iterator is empty, the inner one bounds the length.
+    let mut it = [[1, 2, 3]].iter().take_flat(10);
+    it.next();
+    assert_eq!(it.size_hint(), (2, Some(2)));
+}
@@ -0,0 +1,44 @@
+use core::hash::Hasher;
+use core::iter::*;
+use std::collections::hash_map::DefaultHasher;
+
+fn hash_bytes(bytes: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    bytes.iter().for_each(|&b| hasher.write_u8(b));
+    hasher.finish()
+}
+
+#[test]
+fn test_take_hashing() {
+    let data: Vec<u8> = (0..=255).collect();
+    let mut it = data.iter().copied().take_hashing(100, DefaultHasher::new());
+    let taken: Vec<u8> = it.by_ref().collect();
+    assert_eq!(taken, data[..100]);
+    assert_eq!(it.finish(), Some(hash_bytes(&data[..100])));
+    // Finishing does not reset the hasher.
+    assert_eq!(it.finish(), Some(hash_bytes(&data[..100])));
+    assert_eq!(it.next(), None);
+}
+
+#[test]
+fn test_take_hashing_short_source() {
+    let mut it = [1u8, 2, 3].iter().copied().take_hashing(5, DefaultHasher::new());
+    assert_eq!(it.finish(), None);
+    assert_eq!(it.by_ref().count(), 3);
+    assert_eq!(it.finish(), None);
+    assert_eq!(it.hasher().finish(), hash_bytes(&[1, 2, 3]));
+
+    let it = empty().take_hashing(0, DefaultHasher::new());
+    assert_eq!(it.finish(), Some(hash_bytes(&[])));
+}
+
+#[test]
+fn test_take_hashing_size_hint() {
+    let mut it = [0u8; 10].iter().copied().take_hashing(4, DefaultHasher::new());
+    assert_eq!(it.len(), 4);
+    it.next();
+    assert_eq!(it.len(), 3);
+
+    let it = [0u8; 2].iter().copied().take_hashing(4, DefaultHasher::new());
+    assert_eq!(it.size_hint(), (2, Some(2)));
+}
@@ -0,0 +1,57 @@
+use core::iter::*;
+use core::num::NonZeroUsize;
+
+const HEARTBEAT: i32 = -1;
+
+fn every(k: usize) -> NonZeroUsize {
+    NonZeroUsize::new(k).unwrap()
+}
+
+#[test]
+fn test_take_heartbeat() {
+    let v: Vec<_> = (0..5).take_heartbeat(10, every(2), || HEARTBEAT).collect();
+    assert_eq!(v, [0, 1, -1, 2, 3, -1, 4]);
+
+    // A group that ends exactly at the cap still gets its heartbeat.
+    let v: Vec<_> = (0..5).take_heartbeat(4, every(2), || HEARTBEAT).collect();
+    assert_eq!(v, [0, 1, -1, 2, 3, -1]);
+
+    let v: Vec<_> = (0..5).take_heartbeat(3, every(2), || HEARTBEAT).collect();
+    assert_eq!(v, [0, 1, -1, 2]);
+
+    let v: Vec<_> = (0..5).take_heartbeat(0, every(2), || HEARTBEAT).collect();
+    assert_eq!(v, []);
+
+    let v: Vec<_> = (0..3).take_heartbeat(10, every(1), || HEARTBEAT).collect();
+    assert_eq!(v, [0, -1, 1, -1, 2, -1]);
+}
+
+#[test]
+fn test_take_heartbeat_counts_real_items() {
+    let mut made = 0;
+    let real = (0..5)
+        .take_heartbeat(4, every(2), || {
+            made += 1;
+            HEARTBEAT
+        })
+        .filter(|&x| x != HEARTBEAT)
+        .count();
+    assert_eq!(real, 4);
+    assert_eq!(made, 2);
+}
+
+#[test]
+fn test_take_heartbeat_size_hint() {
+    let mut it = (0..5).take_heartbeat(10, every(2), || HEARTBEAT);
+    assert_eq!(it.size_hint(), (7, Some(7)));
+    it.next();
+    it.next();
+    assert_eq!(it.size_hint(), (5, Some(5)));
+    it.next();
+    assert_eq!(it.size_hint(), (4, Some(4)));
+
+    let it = (0..5).take_heartbeat(4, every(2), || HEARTBEAT);
+    assert_eq!(it.size_hint(), (6, Some(6)));
+    let it = (0..).take_heartbeat(usize::MAX, every(2), || HEARTBEAT);
+    assert_eq!(it.size_hint(), (usize::MAX, None));
+}
@@ -0,0 +1,32 @@
+use core::iter::*;
+
+#[test]
+fn test_take_interleaved() {
+    let a = [1, 2, 3];
+    let b = [4, 5];
+
+    let it = a.iter().copied().take_interleaved(b.iter().copied(), 4);
+    assert_eq!(it.size_hint(), (4, Some(4)));
+    assert_eq!(it.collect::<Vec<_>>(), [1, 4, 2, 5]);
+
+    let it = a.iter().copied().take_interleaved(b.iter().copied(), 10);
+    assert_eq!(it.size_hint(), (5, Some(5)));
+    assert_eq!(it.collect::<Vec<_>>(), [1, 4, 2, 5, 3]);
+
+    // Whichever side runs out first, the other is drained.
+    let it = b.iter().copied().take_interleaved(a.iter().copied(), 10);
+    assert_eq!(it.collect::<Vec<_>>(), [4, 1, 5, 2, 3]);
+
+    let it = empty().take_interleaved(a.iter().copied(), 2);
+    assert_eq!(it.collect::<Vec<_>>(), [1, 2]);
+    assert_eq!(a.iter().take_interleaved(&b, 0).next(), None);
+}
+
+#[test]
+fn test_take_interleaved_does_not_repoll_exhausted() {
+    // An iterator that yields again after returning `None`.
+    let mut flaky = [Some(1), None, Some(2)].iter().copied();
+    let mut it = from_fn(move || flaky.next().flatten()).take_interleaved(10..13, 10);
+    assert_eq!(it.by_ref().collect::<Vec<_>>(), [1, 10, 11, 12]);
+    assert_eq!(it.next(), None);
+}
@@ -0,0 +1,66 @@
+use core::cell::Cell;
+use core::iter::*;
+use core::mem::MaybeUninit;
+
+fn kept<T: Copy>(buf: &[MaybeUninit<T>], ring: RingFill) -> Vec<T> {
+    let (older, newer) = ring.ranges();
+    older.chain(newer).map(|i| unsafe { buf[i].assume_init() }).collect()
+}
+
+#[test]
+fn test_take_into_ring_shorter_than_buffer() {
+    let mut buf = [MaybeUninit::uninit(); 5];
+    let ring = [1, 2, 3].iter().copied().take_into_ring(&mut buf);
+    assert_eq!((ring.head(), ring.len()), (0, 3));
+    assert_eq!(ring.ranges(), (0..3, 0..0));
+    assert_eq!(kept(&buf, ring), [1, 2, 3]);
+
+    let ring = empty::<i32>().take_into_ring(&mut buf);
+    assert!(ring.is_empty());
+    assert_eq!(ring.ranges(), (0..0, 0..0));
+}
+
+#[test]
+fn test_take_into_ring_keeps_most_recent() {
+    let mut buf = [MaybeUninit::uninit(); 5];
+    let ring = (1..=8).take_into_ring(&mut buf);
+    assert_eq!((ring.head(), ring.len()), (3, 5));
+    assert_eq!(ring.ranges(), (3..5, 0..3));
+    assert_eq!(kept(&buf, ring), [4, 5, 6, 7, 8]);
+
+    // Filling the buffer exactly, or a whole number of times over, leaves
+    // the oldest element at the start.
+    let ring = (1..=10).take_into_ring(&mut buf);
+    assert_eq!(ring.ranges(), (0..5, 0..0));
+    assert_eq!(kept(&buf, ring), [6, 7, 8, 9, 10]);
+
+    let ring = (1..=3).take_into_ring(&mut []);
+    assert!(ring.is_empty());
+}
+
+#[test]
+fn test_take_into_ring_drops_overwritten() {
+    struct Counted<'a>(&'a Cell<usize>);
+    impl Drop for Counted<'_> {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    let drops = Cell::new(0);
+    let mut buf: [MaybeUninit<Counted<'_>>; 3] =
+        [MaybeUninit::uninit(), MaybeUninit::uninit(), MaybeUninit::uninit()];
+    let ring = repeat_with(|| Counted(&drops)).take(7).take_into_ring(&mut buf);
+    assert_eq!(drops.get(), 4);
+
+    let (older, newer) = ring.ranges();
+    for i in older.chain(newer) {
+        unsafe { buf[i].as_mut_ptr().drop_in_place() };
+    }
+    assert_eq!(drops.get(), 7);
+
+    // Nothing is kept in an empty buffer.
+    let ring = repeat_with(|| Counted(&drops)).take(2).take_into_ring(&mut []);
+    assert!(ring.is_empty());
+    assert_eq!(drops.get(), 9);
+}
@@ -0,0 +1,51 @@
+use core::cmp::Ordering;
+use core::iter::*;
+
+#[test]
+fn test_take_monotonic_sorted() {
+    let xs = [1, 2, 2, 3, 7, 8];
+    let checked: Vec<_> = xs.iter().take_monotonic(5, |a, b| a.cmp(b)).collect();
+    assert_eq!(checked, [Ok(&1), Ok(&2), Ok(&2), Ok(&3), Ok(&7)]);
+
+    // Reversing the comparison checks for a non-increasing order instead.
+    let mut it = (0..4).rev().take_monotonic(10, |a, b| b.cmp(a));
+    assert_eq!(it.size_hint(), (1, Some(4)));
+    assert!(it.by_ref().all(|x| x.is_ok()));
+    assert_eq!(it.next(), None);
+}
+
+#[test]
+fn test_take_monotonic_inversion() {
+    let xs = [10, 20, 30, 25, 40, 50];
+    let mut it = xs.iter().copied().take_monotonic(6, |a, b| a.cmp(b));
+    // Only four elements come out, so no more than one can be promised.
+    assert_eq!(it.size_hint(), (1, Some(6)));
+    assert_eq!(it.next(), Some(Ok(10)));
+    assert_eq!(it.next(), Some(Ok(20)));
+    assert_eq!(it.next(), Some(Ok(30)));
+
+    let violation = it.next().unwrap().unwrap_err();
+    assert_eq!(violation.index(), 3);
+    assert_eq!(*violation.previous(), 30);
+    assert_eq!(*violation.current(), 25);
+    assert_eq!(violation.into_elements(), (30, 25));
+
+    assert_eq!(it.size_hint(), (0, Some(0)));
+    assert_eq!(it.next(), None);
+}
+
+#[test]
+fn test_take_monotonic_strict_and_limit() {
+    // Treating equal elements as out of order checks a strictly increasing
+    // order.
+    let strict = |a: &i32, b: &i32| if a < b { Ordering::Less } else { Ordering::Greater };
+    let xs = [1, 2, 2, 3];
+    let results: Vec<_> = xs.iter().copied().take_monotonic(4, strict).collect();
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[2].unwrap_err().index(), 2);
+
+    // An inversion past the limit is never seen.
+    let xs = [1, 2, 0];
+    let results: Vec<_> = xs.iter().copied().take_monotonic(2, strict).collect();
+    assert_eq!(results, [Ok(1), Ok(2)]);
+}
@@ -0,0 +1,32 @@
+use core::iter::*;
+
+#[test]
+fn test_take_pairs() {
+    let v: Vec<_> = (0..5).take_pairs(2).collect();
+    assert_eq!(v, [(0, 1), (1, 2)]);
+
+    let v: Vec<_> = (0..5).take_pairs(10).collect();
+    assert_eq!(v, [(0, 1), (1, 2), (2, 3), (3, 4)]);
+
+    assert_eq!((0..5).take_pairs(0).next(), None);
+    assert_eq!((0..1).take_pairs(3).next(), None);
+    assert_eq!(empty::<i32>().take_pairs(3).next(), None);
+}
+
+#[test]
+fn test_take_pairs_size_hint() {
+    let mut it = (0..5).take_pairs(2);
+    assert_eq!(it.size_hint(), (2, Some(2)));
+    it.next();
+    assert_eq!(it.size_hint(), (1, Some(1)));
+    it.next();
+    assert_eq!(it.size_hint(), (0, Some(0)));
+
+    let mut it = (0..3).take_pairs(10);
+    assert_eq!(it.size_hint(), (2, Some(2)));
+    it.next();
+    assert_eq!(it.size_hint(), (1, Some(1)));
+
+    assert_eq!((0..1).take_pairs(10).size_hint(), (0, Some(0)));
+    assert_eq!((0..).take_pairs(10).size_hint(), (10, Some(10)));
+}
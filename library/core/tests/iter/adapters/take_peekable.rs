@@ -0,0 +1,42 @@
+use core::iter::*;
+
+#[test]
+fn test_take_peekable_peek_keeps_limit() {
+    let mut it = (0..10).take_peekable(3);
+    for _ in 0..5 {
+        assert_eq!(it.peek(), Some(&0));
+        assert_eq!(it.len(), 3);
+    }
+    assert_eq!(it.next(), Some(0));
+    assert_eq!(it.len(), 2);
+
+    assert_eq!(it.peek(), Some(&1));
+    assert_eq!(it.peek(), Some(&1));
+    assert_eq!(it.len(), 2);
+    assert_eq!(it.next(), Some(1));
+
+    if let Some(x) = it.peek_mut() {
+        *x *= 10;
+    }
+    assert_eq!(it.len(), 1);
+    assert_eq!(it.next(), Some(20));
+    assert_eq!(it.len(), 0);
+    assert_eq!(it.peek(), None);
+    assert_eq!(it.next(), None);
+}
+
+#[test]
+fn test_take_peekable_does_not_pull_past_limit() {
+    let mut source = 0..10;
+    let mut it = source.by_ref().take_peekable(2);
+    assert_eq!(it.by_ref().collect::<Vec<_>>(), [0, 1]);
+    assert_eq!(it.peek(), None);
+    assert_eq!(source.next(), Some(2));
+
+    let mut it = (0..1).take_peekable(5);
+    assert_eq!(it.size_hint(), (1, Some(1)));
+    assert_eq!(it.next(), Some(0));
+    assert_eq!(it.peek(), None);
+    assert_eq!(it.size_hint(), (0, Some(0)));
+    assert_eq!(it.next(), None);
+}
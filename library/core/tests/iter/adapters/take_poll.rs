@@ -0,0 +1,49 @@
+use core::iter::*;
+use core::task::Poll;
+
+#[test]
+fn test_take_poll_pending_then_ready() {
+    let source = [None, None, Some(1), None, Some(2), Some(3), Some(4)];
+    let mut it = source.iter().copied().take_poll(3);
+    assert_eq!(it.poll_next(), Poll::Pending);
+    assert_eq!(it.poll_next(), Poll::Pending);
+    assert_eq!(it.poll_next(), Poll::Ready(Some(1)));
+    assert_eq!(it.poll_next(), Poll::Pending);
+    assert_eq!(it.poll_next(), Poll::Ready(Some(2)));
+    assert_eq!(it.poll_next(), Poll::Ready(Some(3)));
+    assert_eq!(it.poll_next(), Poll::Ready(None));
+    assert_eq!(it.poll_next(), Poll::Ready(None));
+}
+
+#[test]
+fn test_take_poll_source_exhausted() {
+    let mut source = [Some(1), None].iter().copied();
+    let mut it = source.by_ref().take_poll(5);
+    assert_eq!(it.poll_next(), Poll::Ready(Some(1)));
+    assert_eq!(it.poll_next(), Poll::Pending);
+    assert_eq!(it.poll_next(), Poll::Ready(None));
+
+    let mut it = empty::<Option<i32>>().take_poll(0);
+    assert_eq!(it.poll_next(), Poll::Ready(None));
+}
+
+#[test]
+fn test_take_poll_as_iterator() {
+    // Markers pass through and don't count towards `n`.
+    let source = [None, Some(1), None, Some(2), Some(3)];
+    let v: Vec<_> = source.iter().copied().take_poll(2).collect();
+    assert_eq!(v, [None, Some(1), None, Some(2)]);
+
+    let mut source = [Some(1), Some(2), Some(3)].iter().copied();
+    assert_eq!(source.by_ref().take_poll(2).count(), 2);
+    // Nothing past the `n`-th element is consumed.
+    assert_eq!(source.next(), Some(Some(3)));
+}
+
+#[test]
+fn test_take_poll_size_hint() {
+    let it = [Some(1), None, Some(2)].iter().copied().take_poll(1);
+    assert_eq!(it.size_hint(), (0, Some(3)));
+    let it = [Some(1)].iter().copied().take_poll(0);
+    assert_eq!(it.size_hint(), (0, Some(0)));
+}
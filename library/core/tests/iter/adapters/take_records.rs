@@ -0,0 +1,48 @@
+use core::iter::*;
+
+#[test]
+fn test_take_records_exact_multiple() {
+    let bytes: Vec<u8> = (0..12).collect();
+    let mut it = bytes.iter().copied().take_records::<4>(10);
+    assert_eq!(it.next(), Some([0, 1, 2, 3]));
+    assert_eq!(it.next(), Some([4, 5, 6, 7]));
+    assert_eq!(it.next(), Some([8, 9, 10, 11]));
+    assert_eq!(it.next(), None);
+    assert_eq!(it.remainder(), []);
+}
+
+#[test]
+fn test_take_records_trailing_partial() {
+    let bytes: Vec<u8> = (0..7).collect();
+    let mut it = bytes.iter().copied().take_records::<3>(10);
+    assert_eq!(it.by_ref().collect::<Vec<_>>(), [[0, 1, 2], [3, 4, 5]]);
+    assert_eq!(it.remainder(), [6]);
+    assert_eq!(it.next(), None);
+}
+
+#[test]
+fn test_take_records_stops_at_n() {
+    let mut source = 0..=255u8;
+    let mut it = source.by_ref().take_records::<2>(2);
+    assert_eq!(it.next(), Some([0, 1]));
+    assert_eq!(it.next(), Some([2, 3]));
+    assert_eq!(it.next(), None);
+    assert_eq!(it.remainder(), []);
+    // Nothing past the last record is consumed.
+    assert_eq!(source.next(), Some(4));
+}
+
+#[test]
+fn test_take_records_size_hint() {
+    let bytes = [0u8; 10];
+    let mut it = bytes.iter().copied().take_records::<4>(5);
+    assert_eq!(it.len(), 2);
+    it.next();
+    assert_eq!(it.len(), 1);
+
+    let it = bytes.iter().copied().take_records::<4>(1);
+    assert_eq!(it.len(), 1);
+
+    let it = bytes.iter().copied().take_records::<0>(3);
+    assert_eq!(it.size_hint(), (3, Some(3)));
+}
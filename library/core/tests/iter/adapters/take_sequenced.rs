@@ -0,0 +1,43 @@
+use core::iter::*;
+
+#[test]
+fn test_take_sequenced() {
+    let mut it = (10..20).take_sequenced(3, 0);
+    assert_eq!(it.len(), 3);
+    assert_eq!(it.by_ref().collect::<Vec<_>>(), [(0, 10), (1, 11), (2, 12)]);
+    assert_eq!(it.next(), None);
+    assert_eq!(it.next_seq(), 3);
+}
+
+#[test]
+fn test_take_sequenced_restart() {
+    let source = || 0..10;
+
+    // Process a first batch, then "crash" after persisting the checkpoint.
+    let mut run = source().take_sequenced(4, 1000);
+    let first: Vec<_> = run.by_ref().collect();
+    assert_eq!(first, [(1000, 0), (1001, 1), (1002, 2), (1003, 3)]);
+    let checkpoint = run.next_seq();
+    assert_eq!(checkpoint, 1004);
+
+    // Resume the source after the processed elements, from the checkpoint.
+    let mut run = source().skip(first.len()).take_sequenced(10, checkpoint);
+    let second: Vec<_> = run.by_ref().collect();
+    assert_eq!(second.len(), 6);
+    assert_eq!(second[0], (1004, 4));
+    assert_eq!(second[5], (1009, 9));
+    assert_eq!(run.next_seq(), 1010);
+
+    // The ids across both runs are consecutive.
+    let ids: Vec<u64> = first.iter().chain(&second).map(|&(id, _)| id).collect();
+    assert_eq!(ids, (1000..1010).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_take_sequenced_saturates() {
+    let mut it = (0..5).take_sequenced(5, u64::MAX - 1);
+    assert_eq!(it.next(), Some((u64::MAX - 1, 0)));
+    assert_eq!(it.next(), Some((u64::MAX, 1)));
+    assert_eq!(it.next(), Some((u64::MAX, 2)));
+    assert_eq!(it.next_seq(), u64::MAX);
+}
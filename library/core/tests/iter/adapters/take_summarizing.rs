@@ -0,0 +1,39 @@
+use core::iter::*;
+
+#[test]
+fn test_take_summarizing_matches_manual() {
+    let data = [17, -4, 23, 8, 8, -11, 42, 5, 0, 99];
+    for n in 1..=data.len() + 2 {
+        let mut it = data.iter().copied().take_summarizing(n);
+        let taken: Vec<i32> = it.by_ref().collect();
+        let prefix = &data[..n.min(data.len())];
+        assert_eq!(taken, prefix);
+
+        let summary = it.summary().unwrap();
+        assert_eq!(summary.count(), prefix.len());
+        assert_eq!(summary.min(), *prefix.iter().min().unwrap());
+        assert_eq!(summary.max(), *prefix.iter().max().unwrap());
+        assert_eq!(summary.sum(), prefix.iter().sum::<i32>());
+    }
+}
+
+#[test]
+fn test_take_summarizing_empty() {
+    let mut it = (0..10).take_summarizing(0);
+    assert_eq!(it.next(), None);
+    assert_eq!(it.summary(), None);
+
+    let mut it = empty::<u8>().take_summarizing(3);
+    assert_eq!(it.next(), None);
+    assert_eq!(it.summary(), None);
+}
+
+#[test]
+fn test_take_summarizing_floats() {
+    let mut it = [0.5, f64::NAN, -2.0, 1.5].iter().copied().take_summarizing(4);
+    assert_eq!(it.by_ref().count(), 4);
+    let summary = it.summary().unwrap();
+    assert_eq!(summary.min(), -2.0);
+    assert_eq!(summary.max(), 1.5);
+    assert!(summary.sum().is_nan());
+}
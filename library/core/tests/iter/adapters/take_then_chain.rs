@@ -0,0 +1,44 @@
+use core::iter::*;
+
+#[test]
+fn test_take_then_chain_cap_reached() {
+    let v: Vec<_> = (0..10).take_then_chain(3, 100..102).collect();
+    assert_eq!(v, [0, 1, 2, 100, 101]);
+
+    // Yielding exactly `n` elements counts as reaching the cap.
+    let v: Vec<_> = (0..3).take_then_chain(3, 100..102).collect();
+    assert_eq!(v, [0, 1, 2, 100, 101]);
+
+    let v: Vec<_> = (0..3).take_then_chain(0, 100..102).collect();
+    assert_eq!(v, [100, 101]);
+}
+
+#[test]
+fn test_take_then_chain_exhausted() {
+    let v: Vec<_> = (0..2).take_then_chain(3, 100..102).collect();
+    assert_eq!(v, [0, 1]);
+
+    let mut it = empty::<i32>().take_then_chain(1, 100..102);
+    assert_eq!(it.next(), None);
+    assert_eq!(it.next(), None);
+}
+
+#[test]
+fn test_take_then_chain_does_not_overdraw_primary() {
+    let mut primary = 0..10;
+    let v: Vec<_> = primary.by_ref().take_then_chain(2, once(100)).collect();
+    assert_eq!(v, [0, 1, 100]);
+    assert_eq!(primary.next(), Some(2));
+}
+
+#[test]
+fn test_take_then_chain_size_hint() {
+    let mut it = (0..10).take_then_chain(3, 100..102);
+    assert_eq!(it.size_hint(), (5, Some(5)));
+    it.nth(2);
+    assert_eq!(it.size_hint(), (2, Some(2)));
+
+    assert_eq!((0..2).take_then_chain(3, 100..102).size_hint(), (2, Some(2)));
+    assert_eq!((0..).take_then_chain(3, 100..).size_hint(), (usize::MAX, None));
+    assert_eq!((0..10).filter(|_| true).take_then_chain(3, 100..102).size_hint(), (0, Some(5)));
+}
@@ -0,0 +1,48 @@
+use core::iter::*;
+
+fn even(x: &u32) -> Result<(), u32> {
+    if x % 2 == 0 { Ok(()) } else { Err(*x) }
+}
+
+#[test]
+fn test_take_validated_skip() {
+    let records = [0, 1, 2, 3, 5, 7, 4, 6, 8];
+    let mut it = records.iter().copied().take_validated(4, even, InvalidPolicy::Skip);
+    assert_eq!(it.size_hint(), (0, Some(4)));
+    assert_eq!(it.by_ref().collect::<Vec<_>>(), [Ok(0), Ok(2), Ok(4), Ok(6)]);
+    assert_eq!(it.next(), None);
+
+    // The invalid elements between the valid ones don't count toward the
+    // limit, and nothing past the last valid element is pulled.
+    let mut source = records.iter().copied();
+    assert_eq!(source.by_ref().take_validated(3, even, InvalidPolicy::Skip).count(), 3);
+    assert_eq!(source.next(), Some(6));
+
+    // Running out of elements isn't an error.
+    let it = [1, 2, 3].iter().copied().take_validated(5, even, InvalidPolicy::Skip);
+    assert_eq!(it.collect::<Vec<_>>(), [Ok(2)]);
+}
+
+#[test]
+fn test_take_validated_stop() {
+    let records = [0, 2, 3, 4, 5];
+    let mut it = records.iter().copied().take_validated(4, even, InvalidPolicy::Stop);
+    assert_eq!(it.size_hint(), (1, Some(4)));
+    assert_eq!(it.by_ref().collect::<Vec<_>>(), [Ok(0), Ok(2), Err(3)]);
+    assert_eq!(it.next(), None);
+    assert_eq!(it.size_hint(), (0, Some(0)));
+
+    // The limit is reached before the invalid element, which is never
+    // validated.
+    let mut validated = Vec::new();
+    let it = records.iter().copied().take_validated(
+        2,
+        |x: &u32| {
+            validated.push(*x);
+            even(x)
+        },
+        InvalidPolicy::Stop,
+    );
+    assert_eq!(it.collect::<Vec<_>>(), [Ok(0), Ok(2)]);
+    assert_eq!(validated, [0, 2]);
+}
@@ -0,0 +1,39 @@
+use core::iter::*;
+
+#[test]
+fn test_take_with_breaker_interspersed_errors() {
+    let results = [Ok(1), Err(1), Ok(2), Err(2), Err(3), Ok(3), Err(4), Ok(4), Ok(5)];
+    let mut it = results.iter().copied().take_with_breaker(4, 3);
+    assert_eq!(
+        it.by_ref().collect::<Vec<_>>(),
+        [Ok(1), Err(1), Ok(2), Err(2), Err(3), Ok(3), Err(4), Ok(4)]
+    );
+    assert_eq!(it.next(), None);
+    assert!(!it.is_tripped());
+
+    // Running out of elements doesn't trip the breaker either.
+    let mut it = [Err(0), Err(0), Ok(0)].iter().copied().take_with_breaker(5, 3);
+    assert_eq!(it.by_ref().count(), 3);
+    assert!(!it.is_tripped());
+}
+
+#[test]
+fn test_take_with_breaker_trips() {
+    let results = [Ok(1), Err(1), Err(2), Ok(2), Err(3), Err(4), Err(5), Ok(3)];
+    let mut source = results.iter().copied();
+    let mut it = source.by_ref().take_with_breaker(10, 3);
+    assert_eq!(it.size_hint(), (3, Some(8)));
+    assert_eq!(
+        it.by_ref().collect::<Vec<_>>(),
+        [Ok(1), Err(1), Err(2), Ok(2), Err(3), Err(4), Err(5)]
+    );
+    assert!(it.is_tripped());
+    assert_eq!(it.next(), None);
+    assert_eq!(it.size_hint(), (0, Some(0)));
+    assert_eq!(source.next(), Some(Ok(3)));
+
+    // A threshold of zero trips on the first error, like one does.
+    let mut it = [Ok(1), Err(1), Ok(2)].iter().copied().take_with_breaker(10, 0);
+    assert_eq!(it.by_ref().collect::<Vec<_>>(), [Ok(1), Err(1)]);
+    assert!(it.is_tripped());
+}
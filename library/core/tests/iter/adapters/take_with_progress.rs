@@ -0,0 +1,25 @@
+use core::iter::*;
+
+#[test]
+fn test_take_with_progress() {
+    let mut it = (10..20).take_with_progress(4);
+    assert_eq!(it.len(), 4);
+    assert_eq!(it.next(), Some((0.25, 10)));
+    assert_eq!(it.len(), 3);
+    assert_eq!(it.by_ref().collect::<Vec<_>>(), [(0.5, 11), (0.75, 12), (1.0, 13)]);
+    assert_eq!(it.next(), None);
+
+    // The last fraction is exactly 1.0 even when `n` has no exact reciprocal.
+    let (last, _) = (0..).take_with_progress(7).last().unwrap();
+    assert_eq!(last, 1.0);
+}
+
+#[test]
+fn test_take_with_progress_short_or_empty() {
+    let fractions: Vec<_> = (0..2).take_with_progress(4).map(|(f, _)| f).collect();
+    assert_eq!(fractions, [0.25, 0.5]);
+
+    let mut it = (0..2).take_with_progress(0);
+    assert_eq!(it.size_hint(), (0, Some(0)));
+    assert_eq!(it.next(), None);
+}
@@ -472,3 +472,26 @@ fn test_collect() {
     let b: Vec<isize> = a.iter().cloned().collect();
     assert!(a == b);
 }
+
+#[test]
+fn test_take_array() {
+    // Source longer than `N`: only `N` elements are pulled.
+    let mut it = 0..10;
+    let (array, len) = it.by_ref().take_array::<4>();
+    assert_eq!(array, [0, 1, 2, 3]);
+    assert_eq!(len, 4);
+    assert_eq!(it.next(), Some(4));
+
+    // Source shorter than `N`: the tail is left as `Default::default()`.
+    let (array, len) = (1..3).take_array::<5>();
+    assert_eq!(array, [1, 2, 0, 0, 0]);
+    assert_eq!(len, 2);
+
+    let (array, len) = (0..3).map(Some).take_array::<0>();
+    assert_eq!(array, []);
+    assert_eq!(len, 0);
+
+    let (array, len) = core::iter::empty::<Option<i32>>().take_array::<2>();
+    assert_eq!(array, [None, None]);
+    assert_eq!(len, 0);
+}
@@ -54,6 +54,27 @@
 #![feature(iter_is_partitioned)]
 #![feature(iter_order_by)]
 #![feature(iter_map_while)]
+#![feature(iter_take_pairs)]
+#![feature(iter_take_then_chain)]
+#![feature(iter_take_array)]
+#![feature(iter_take_heartbeat)]
+#![feature(iter_take_resume)]
+#![feature(iter_partition_take)]
+#![feature(iter_take_hashing)]
+#![feature(iter_take_records)]
+#![feature(iter_take_poll)]
+#![feature(iter_take_flat)]
+#![feature(iter_take_peekable)]
+#![feature(iter_take_summarizing)]
+#![feature(iter_take_interleaved)]
+#![feature(iter_take_with_progress)]
+#![feature(iter_take_ema)]
+#![feature(iter_take_monotonic)]
+#![feature(iter_take_into_ring)]
+#![feature(iter_take_sequenced)]
+#![feature(iter_take_validated)]
+#![feature(iter_take_with_breaker)]
+#![feature(iter_take_crc32)]
 #![feature(const_mut_refs)]
 #![feature(const_pin)]
 #![feature(const_slice_from_raw_parts)]
@@ -584,6 +584,10 @@ pub fn set_permissions(&self, perm: Permissions) -> io::Result<()> {
     }
 }
 
+/// Allows extension traits within `std`.
+#[unstable(feature = "sealed", issue = "none")]
+impl crate::sealed::Sealed for File {}
+
 impl AsInner<fs_imp::File> for File {
     fn as_inner(&self) -> &fs_imp::File {
         &self.inner
@@ -1349,6 +1353,12 @@ fn next(&mut self) -> Option<io::Result<DirEntry>> {
     }
 }
 
+impl FromInner<fs_imp::ReadDir> for ReadDir {
+    fn from_inner(inner: fs_imp::ReadDir) -> ReadDir {
+        ReadDir(inner)
+    }
+}
+
 impl DirEntry {
     /// Returns the full path to the file that this entry represents.
     ///
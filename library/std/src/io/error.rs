@@ -191,6 +191,34 @@ pub enum ErrorKind {
     /// to allocate enough memory.
     #[stable(feature = "out_of_memory_error", since = "1.54.0")]
     OutOfMemory,
+
+    /// The remote host is not reachable.
+    #[unstable(feature = "io_error_network", issue = "none")]
+    HostUnreachable,
+
+    /// The network containing the remote host is not reachable.
+    #[unstable(feature = "io_error_network", issue = "none")]
+    NetworkUnreachable,
+
+    /// The local network interface the operation needs is down.
+    #[unstable(feature = "io_error_network", issue = "none")]
+    NetworkDown,
+
+    /// The underlying storage is full.
+    #[unstable(feature = "io_error_storage", issue = "none")]
+    StorageFull,
+
+    /// The user's quota of disk blocks or inodes on the filesystem is used up.
+    #[unstable(feature = "io_error_storage", issue = "none")]
+    QuotaExceeded,
+
+    /// A file grew larger than the filesystem or the process allows.
+    #[unstable(feature = "io_error_storage", issue = "none")]
+    FileTooLarge,
+
+    /// The filesystem is mounted read-only, so it cannot be modified.
+    #[unstable(feature = "io_error_storage", issue = "none")]
+    ReadOnlyFilesystem,
 }
 
 impl ErrorKind {
@@ -216,6 +244,13 @@ pub(crate) fn as_str(&self) -> &'static str {
             ErrorKind::UnexpectedEof => "unexpected end of file",
             ErrorKind::Unsupported => "unsupported",
             ErrorKind::OutOfMemory => "out of memory",
+            ErrorKind::HostUnreachable => "host unreachable",
+            ErrorKind::NetworkUnreachable => "network unreachable",
+            ErrorKind::NetworkDown => "network down",
+            ErrorKind::StorageFull => "no storage space",
+            ErrorKind::QuotaExceeded => "quota exceeded",
+            ErrorKind::FileTooLarge => "file too large",
+            ErrorKind::ReadOnlyFilesystem => "read-only filesystem",
         }
     }
 }
@@ -67,3 +67,28 @@ fn test_const() {
     assert!(format!("{:?}", E).contains("\"hello\""));
     assert!(format!("{:?}", E).contains("NotFound"));
 }
+
+#[test]
+#[cfg(unix)]
+fn test_network_error_kinds() {
+    let kind = |errno| Error::from_raw_os_error(errno).kind();
+    assert_eq!(kind(libc::EHOSTUNREACH), ErrorKind::HostUnreachable);
+    assert_eq!(kind(libc::ENETUNREACH), ErrorKind::NetworkUnreachable);
+    assert_eq!(kind(libc::ENETDOWN), ErrorKind::NetworkDown);
+    assert_eq!(kind(libc::ENETRESET), ErrorKind::ConnectionReset);
+    assert_eq!(ErrorKind::HostUnreachable.as_str(), "host unreachable");
+}
+
+#[test]
+#[cfg(unix)]
+fn test_storage_error_kinds() {
+    let kind = |errno| Error::from_raw_os_error(errno).kind();
+    assert_eq!(kind(libc::ENOSPC), ErrorKind::StorageFull);
+    assert_eq!(kind(libc::EDQUOT), ErrorKind::QuotaExceeded);
+    assert_eq!(kind(libc::EFBIG), ErrorKind::FileTooLarge);
+    assert_eq!(kind(libc::EROFS), ErrorKind::ReadOnlyFilesystem);
+    assert_eq!(ErrorKind::StorageFull.as_str(), "no storage space");
+
+    // Anything unknown is still `Other`.
+    assert_eq!(kind(i32::MAX), ErrorKind::Other);
+}
@@ -0,0 +1,1006 @@
+#![doc = include_str!("../../../core/src/iter/docs.md")]
+//!
+//! # Standard library extensions
+//!
+//! Besides everything from [`core::iter`], this module provides
+//! [`IteratorExt`], which adds iterator adapters that depend on functionality
+//! only available in the standard library, such as clocks, threads and
+//! channels.
+
+#![stable(feature = "rust1", since = "1.0.0")]
+
+#[cfg(test)]
+mod tests;
+
+mod take_approx_dedup;
+mod take_balanced;
+mod take_bytes_bounded;
+mod take_cached;
+mod take_cancellable;
+mod take_cow;
+mod take_delayed;
+mod take_fanout;
+mod take_frames;
+mod take_microbatched;
+mod take_permitted;
+mod take_prefetched;
+mod take_recording;
+mod take_reporting;
+mod take_rolling_median;
+mod take_scheduled_batches;
+mod take_sorted;
+mod take_tee_write;
+mod take_within;
+
+#[doc(inline)]
+#[stable(feature = "rust1", since = "1.0.0")]
+pub use core::iter::*;
+
+#[unstable(feature = "iter_take_approx_dedup", issue = "none")]
+pub use self::take_approx_dedup::TakeApproxDedup;
+#[unstable(feature = "iter_take_balanced", issue = "none")]
+pub use self::take_balanced::TakeBalanced;
+#[unstable(feature = "iter_take_bytes_bounded", issue = "none")]
+pub use self::take_bytes_bounded::TakeBytesBounded;
+#[unstable(feature = "iter_take_cached", issue = "none")]
+pub use self::take_cached::TakeCached;
+#[unstable(feature = "iter_take_cancellable", issue = "none")]
+pub use self::take_cancellable::{StopReason, TakeCancellable};
+#[unstable(feature = "iter_take_cow", issue = "none")]
+pub use self::take_cow::TakeCow;
+#[unstable(feature = "iter_take_delayed", issue = "none")]
+pub use self::take_delayed::TakeDelayed;
+#[unstable(feature = "iter_take_fanout", issue = "none")]
+pub use self::take_fanout::{FanoutPolicy, TakeFanout};
+#[unstable(feature = "iter_take_frames", issue = "none")]
+pub use self::take_frames::{PrefixKind, TakeFrames};
+#[unstable(feature = "iter_take_microbatched", issue = "none")]
+pub use self::take_microbatched::TakeMicrobatched;
+#[unstable(feature = "iter_take_permitted", issue = "none")]
+pub use self::take_permitted::TakePermitted;
+#[unstable(feature = "iter_take_prefetched", issue = "none")]
+pub use self::take_prefetched::TakePrefetched;
+#[unstable(feature = "iter_take_recording", issue = "none")]
+pub use self::take_recording::TakeRecording;
+#[unstable(feature = "iter_take_reporting", issue = "none")]
+pub use self::take_reporting::TakeReporting;
+#[unstable(feature = "iter_take_rolling_median", issue = "none")]
+pub use self::take_rolling_median::TakeRollingMedian;
+#[unstable(feature = "iter_take_scheduled_batches", issue = "none")]
+pub use self::take_scheduled_batches::TakeScheduledBatches;
+#[unstable(feature = "iter_take_sorted", issue = "none")]
+pub use self::take_sorted::{TakeLargest, TakeSmallest};
+#[unstable(feature = "iter_take_tee_write", issue = "none")]
+pub use self::take_tee_write::TakeTeeWrite;
+#[unstable(feature = "iter_take_within", issue = "none")]
+pub use self::take_within::TakeWithin;
+
+use crate::hash::Hash;
+use crate::io::Write;
+use crate::sync::atomic::AtomicBool;
+use crate::sync::{Arc, Semaphore};
+use crate::sync::mpsc::{Sender, SyncSender};
+use crate::time::Duration;
+
+/// Extension methods for [`Iterator`] that need the standard library.
+///
+/// This trait is implemented for every [`Iterator`], so bringing it into
+/// scope is all that is needed to use its methods.
+#[unstable(feature = "iter_std_ext", issue = "none")]
+pub trait IteratorExt: Iterator {
+    /// Creates an iterator that yields at most `n` elements, and stops early
+    /// once a total time `budget` has been spent.
+    ///
+    /// The clock starts on the first call to [`next`] and is read from the
+    /// monotonic [`Instant`] clock. Before each element is requested from the
+    /// underlying iterator, the time elapsed so far is compared to `budget`:
+    /// once it is used up, iteration ends even if fewer than `n` elements have
+    /// been yielded. The check happens before an element is pulled, so no
+    /// element is ever produced and then thrown away, but an element that is
+    /// slow to produce can overrun the budget by however long it takes.
+    ///
+    /// This is useful for bounded-effort batch processing, where a batch should
+    /// be cut short rather than hold up the caller.
+    ///
+    /// [`next`]: Iterator::next
+    /// [`Instant`]: crate::time::Instant
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iter_std_ext, iter_take_within)]
+    /// use std::iter::IteratorExt;
+    /// use std::time::Duration;
+    ///
+    /// let batch: Vec<_> = (0..).take_within(100, Duration::from_secs(60)).collect();
+    /// assert_eq!(batch.len(), 100);
+    ///
+    /// let batch: Vec<_> = (0..).take_within(100, Duration::ZERO).collect();
+    /// assert!(batch.is_empty());
+    /// ```
+    #[inline]
+    #[unstable(feature = "iter_take_within", issue = "none")]
+    fn take_within(self, n: usize, budget: Duration) -> TakeWithin<Self>
+    where
+        Self: Sized,
+    {
+        TakeWithin::new(self, n, budget)
+    }
+
+    /// Creates an iterator that yields at most `n` elements and sends a
+    /// `(done, total)` progress update over `tx` for each one.
+    ///
+    /// `total` is `n`, or the upper bound of this iterator's [`size_hint`] if
+    /// that is smaller. An update is sent for every element yielded; there is
+    /// no throttling, so consumers that redraw on each message may want to
+    /// coalesce them on the receiving side.
+    ///
+    /// Once iteration is finished the sender is dropped, disconnecting the
+    /// receiver. If the source ran out before `total` elements, a final
+    /// `(done, done)` update is sent first, so the last message received
+    /// always describes a completed task. Dropping the adapter before it is
+    /// finished drops the sender without a final update.
+    ///
+    /// Send errors are ignored: if the receiver has gone away, elements are
+    /// still yielded as usual.
+    ///
+    /// [`size_hint`]: Iterator::size_hint
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iter_std_ext, iter_take_reporting)]
+    /// use std::iter::IteratorExt;
+    /// use std::sync::mpsc::channel;
+    ///
+    /// let (tx, rx) = channel();
+    /// let sum: i32 = (1..=10).take_reporting(3, tx).sum();
+    /// assert_eq!(sum, 6);
+    ///
+    /// let progress: Vec<_> = rx.iter().collect();
+    /// assert_eq!(progress, [(1, 3), (2, 3), (3, 3)]);
+    /// ```
+    #[inline]
+    #[unstable(feature = "iter_take_reporting", issue = "none")]
+    fn take_reporting(self, n: usize, tx: Sender<(usize, usize)>) -> TakeReporting<Self>
+    where
+        Self: Sized,
+    {
+        TakeReporting::new(self, n, tx)
+    }
+
+    /// Creates an iterator that yields at most `n` elements as
+    /// [`Cow::Borrowed`], for iterators over references such as
+    /// [`slice::Iter`].
+    ///
+    /// This lets code downstream of a `take` decide per element whether a
+    /// borrow is enough or an owned value is needed, calling
+    /// [`Cow::into_owned`] only in the latter case. No element is cloned up
+    /// front, so taking from a slice of expensive-to-clone values is
+    /// zero-copy. The adapter keeps the double-ended and exact-size
+    /// properties of the source, so it can be reversed or have its length
+    /// queried like a plain [`take`].
+    ///
+    /// Only iterators that yield references can lend their elements out.
+    /// Iterators that produce owned values have nothing to borrow from, so for
+    /// those the equivalent is `iter.take(n).map(Cow::Owned)`, which simply
+    /// moves each value into a [`Cow::Owned`].
+    ///
+    /// [`take`]: Iterator::take
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iter_std_ext, iter_take_cow)]
+    /// use std::borrow::Cow;
+    /// use std::iter::IteratorExt;
+    ///
+    /// let names = [String::from("ferris"), String::from("corro"), String::from("bors")];
+    ///
+    /// let mut taken = names.iter().take_cow(2);
+    /// let first = taken.next().unwrap();
+    /// assert!(matches!(first, Cow::Borrowed(_)));
+    ///
+    /// // Ownership is only paid for when it is actually needed.
+    /// let mut second = taken.next().unwrap();
+    /// second.to_mut().push('!');
+    /// assert_eq!(*second, "corro!");
+    /// assert_eq!(names[1], "corro");
+    /// assert_eq!(taken.next(), None);
+    /// ```
+    #[inline]
+    #[unstable(feature = "iter_take_cow", issue = "none")]
+    fn take_cow<'a, T>(self, n: usize) -> TakeCow<Self>
+    where
+        Self: Sized + Iterator<Item = &'a T>,
+        T: ToOwned + ?Sized + 'a,
+    {
+        TakeCow::new(self, n)
+    }
+
+    /// Creates an iterator that yields the `n` smallest elements of the
+    /// source, in ascending order.
+    ///
+    /// Unlike [`take`], which yields the first `n` elements by position, this
+    /// selects by value: the whole source is consumed on the first call to
+    /// [`next`], keeping only the `n` smallest elements seen so far in a
+    /// bounded binary heap. Over a source of `m` elements this takes
+    /// O(*m* log *n*) time and O(*n*) space, which is cheaper than collecting
+    /// and sorting everything when `n` is much smaller than `m`.
+    ///
+    /// If the source has fewer than `n` elements, all of them are yielded.
+    /// Elements that compare equal are yielded in an unspecified order. If
+    /// `n` is 0, the source is not touched at all.
+    ///
+    /// See [`take_largest`] for the opposite selection.
+    ///
+    /// [`take`]: Iterator::take
+    /// [`next`]: Iterator::next
+    /// [`take_largest`]: IteratorExt::take_largest
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iter_std_ext, iter_take_sorted)]
+    /// use std::iter::IteratorExt;
+    ///
+    /// let v = [5, 1, 8, 3, 9, 2, 7];
+    /// let smallest: Vec<_> = v.iter().copied().take_smallest(3).collect();
+    /// assert_eq!(smallest, [1, 2, 3]);
+    /// ```
+    #[inline]
+    #[unstable(feature = "iter_take_sorted", issue = "none")]
+    fn take_smallest(self, n: usize) -> TakeSmallest<Self>
+    where
+        Self: Sized,
+        Self::Item: Ord,
+    {
+        TakeSmallest::new(self, n)
+    }
+
+    /// Creates an iterator that yields the `n` largest elements of the
+    /// source, in descending order.
+    ///
+    /// This is the "top N" counterpart of [`take_smallest`], with the same
+    /// O(*m* log *n*) time and O(*n*) space bounds over a source of `m`
+    /// elements. The largest element is yielded first.
+    ///
+    /// [`take_smallest`]: IteratorExt::take_smallest
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iter_std_ext, iter_take_sorted)]
+    /// use std::iter::IteratorExt;
+    ///
+    /// let v = [5, 1, 8, 3, 9, 2, 7];
+    /// let largest: Vec<_> = v.iter().copied().take_largest(3).collect();
+    /// assert_eq!(largest, [9, 8, 7]);
+    /// ```
+    #[inline]
+    #[unstable(feature = "iter_take_sorted", issue = "none")]
+    fn take_largest(self, n: usize) -> TakeLargest<Self>
+    where
+        Self: Sized,
+        Self::Item: Ord,
+    {
+        TakeLargest::new(self, n)
+    }
+
+    /// Creates an iterator that yields at most `n` elements, also sending a
+    /// copy of each to the `secondary` channel.
+    ///
+    /// The elements are yielded to the caller unchanged; the secondary channel
+    /// is a best-effort tap, for instance feeding a log shipper alongside the
+    /// main processing. What happens when the bounded `secondary` channel is
+    /// full is decided by `policy`:
+    ///
+    /// * [`FanoutPolicy::Block`] waits for room, so the secondary receiver
+    ///   sees every element, at the cost of slowing the caller down to its
+    ///   pace.
+    /// * [`FanoutPolicy::DropNewest`] discards the copy that does not fit and
+    ///   yields the element anyway, so the caller is never held up.
+    ///
+    /// # Drop accounting
+    ///
+    /// [`TakeFanout::dropped`] counts the yielded elements whose copy never
+    /// reached the secondary channel. Under [`FanoutPolicy::DropNewest`] that
+    /// includes every copy rejected because the channel was full. Under both
+    /// policies, once the secondary receiver has been dropped, no further
+    /// copies are made and every subsequent element is counted as dropped;
+    /// this is not an error, and the caller keeps receiving elements.
+    ///
+    /// The sender is dropped as soon as iteration is finished, so a receiver
+    /// looping over the channel stops once it has seen the last copy.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iter_std_ext, iter_take_fanout)]
+    /// use std::iter::{FanoutPolicy, IteratorExt};
+    /// use std::sync::mpsc::sync_channel;
+    ///
+    /// let (tx, rx) = sync_channel(2);
+    /// let mut it = (1..=10).take_fanout(4, tx, FanoutPolicy::DropNewest);
+    /// let primary: Vec<_> = it.by_ref().collect();
+    /// assert_eq!(primary, [1, 2, 3, 4]);
+    ///
+    /// // Nobody drained the secondary channel, so only two copies fit.
+    /// assert_eq!(rx.iter().collect::<Vec<_>>(), [1, 2]);
+    /// assert_eq!(it.dropped(), 2);
+    /// ```
+    #[inline]
+    #[unstable(feature = "iter_take_fanout", issue = "none")]
+    fn take_fanout(
+        self,
+        n: usize,
+        secondary: SyncSender<Self::Item>,
+        policy: FanoutPolicy,
+    ) -> TakeFanout<Self>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+    {
+        TakeFanout::new(self, n, secondary, policy)
+    }
+
+    /// Creates an iterator that yields at most `n` elements, recording a
+    /// clone of each so the exact sequence can be replayed afterwards.
+    ///
+    /// The elements are yielded unchanged. Once they have been consumed,
+    /// [`TakeRecording::replay`] returns a fresh iterator over what was
+    /// yielded, which is handy for inspecting what a consumer actually saw
+    /// when debugging it.
+    ///
+    /// Every yielded element is cloned and kept until the adapter is dropped,
+    /// so recording `n` elements costs `n` clones and memory for up to `n`
+    /// elements, on top of whatever the consumer keeps itself.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iter_std_ext, iter_take_recording)]
+    /// use std::iter::IteratorExt;
+    ///
+    /// let mut it = (1..).map(|x| x * x).take_recording(4);
+    /// let sum: u32 = it.by_ref().sum();
+    /// assert_eq!(sum, 30);
+    ///
+    /// let seen: Vec<_> = it.replay().collect();
+    /// assert_eq!(seen, [1, 4, 9, 16]);
+    /// ```
+    #[inline]
+    #[unstable(feature = "iter_take_recording", issue = "none")]
+    fn take_recording(self, n: usize) -> TakeRecording<Self>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+    {
+        TakeRecording::new(self, n)
+    }
+
+    /// Creates an iterator that yields `transform(item)` for at most `n`
+    /// elements, caching the results in a small least-recently-used cache
+    /// keyed by `key(&item)`.
+    ///
+    /// When an element's key is already in the cache, a clone of the cached
+    /// result is yielded and `transform` is not called. Otherwise `transform`
+    /// is called and its result is stored under that key. This is useful when
+    /// the transformation is expensive and the same keys keep recurring close
+    /// together in the stream.
+    ///
+    /// The cache holds at most `capacity` results. When a new result is
+    /// stored into a full cache, the entry whose key was least recently seen
+    /// is evicted first; both a cache hit and a fresh insertion count as
+    /// seeing a key. Eviction scans the whole cache, so `capacity` is meant to
+    /// be small. A `capacity` of zero disables caching, so `transform` is
+    /// called for every element.
+    ///
+    /// The cache assumes that `transform` gives the same result for elements
+    /// with the same key; an element whose key hits the cache yields the
+    /// result computed for an earlier element.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iter_std_ext, iter_take_cached)]
+    /// use std::iter::IteratorExt;
+    ///
+    /// let mut calls = 0;
+    /// let words = ["apple", "avocado", "banana", "apricot", "blueberry", "cherry"];
+    /// let initials: Vec<_> = words
+    ///     .iter()
+    ///     .take_cached(
+    ///         5,
+    ///         |word| word.as_bytes()[0],
+    ///         |word| {
+    ///             calls += 1;
+    ///             word[..1].to_uppercase()
+    ///         },
+    ///         2,
+    ///     )
+    ///     .collect();
+    /// assert_eq!(initials, ["A", "A", "B", "A", "B"]);
+    /// assert_eq!(calls, 2);
+    /// ```
+    #[inline]
+    #[unstable(feature = "iter_take_cached", issue = "none")]
+    fn take_cached<K, V, Fk, Ft>(
+        self,
+        n: usize,
+        key: Fk,
+        transform: Ft,
+        capacity: usize,
+    ) -> TakeCached<Self, K, V, Fk, Ft>
+    where
+        Self: Sized,
+        K: Hash + Eq,
+        V: Clone,
+        Fk: FnMut(&Self::Item) -> K,
+        Ft: FnMut(Self::Item) -> V,
+    {
+        TakeCached::new(self, n, key, transform, capacity)
+    }
+
+    /// Creates an iterator that splits a byte stream into at most `n`
+    /// length-prefixed frames.
+    ///
+    /// Each frame on the wire is a length prefix, encoded as selected by
+    /// `prefix`, followed by that many bytes of payload. The iterator yields
+    /// the payload of each frame as a [`Vec<u8>`], without the prefix. It
+    /// stops after `n` frames, or once the source runs out.
+    ///
+    /// The payload is read without allocating the announced length up
+    /// front, so a corrupt or hostile prefix cannot trigger a huge allocation
+    /// by itself; the frame only grows as bytes actually arrive.
+    ///
+    /// # Truncated frames
+    ///
+    /// If the source runs out in the middle of a frame, whether in its prefix
+    /// or its payload, that frame is dropped rather than yielded and the
+    /// iterator stops. The same happens if a [`PrefixKind::Varint`] prefix is
+    /// longer than ten bytes or overflows a `u64`. The bytes of the dropped
+    /// frame, prefix included, can be retrieved with
+    /// [`TakeFrames::remainder`] once the iterator has returned `None`.
+    /// Callers that consider a truncated stream an error should check that
+    /// the remainder is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iter_std_ext, iter_take_frames)]
+    /// use std::iter::{IteratorExt, PrefixKind};
+    ///
+    /// let bytes = [0, 2, b'h', b'i', 0, 0, 0, 3, b'y', b'o'];
+    /// let mut frames = bytes.iter().copied().take_frames(8, PrefixKind::U16Be);
+    ///
+    /// assert_eq!(frames.next(), Some(b"hi".to_vec()));
+    /// assert_eq!(frames.next(), Some(Vec::new()));
+    /// // The last frame announced three bytes but only two arrived.
+    /// assert_eq!(frames.next(), None);
+    /// assert_eq!(frames.remainder(), [0, 3, b'y', b'o']);
+    /// ```
+    #[inline]
+    #[unstable(feature = "iter_take_frames", issue = "none")]
+    fn take_frames(self, n: usize, prefix: PrefixKind) -> TakeFrames<Self>
+    where
+        Self: Sized + Iterator<Item = u8>,
+    {
+        TakeFrames::new(self, n, prefix)
+    }
+
+    /// Creates an iterator that groups at most `n` elements into batches of
+    /// up to `batch_size` elements, flushing a batch early once it is
+    /// `max_delay` old.
+    ///
+    /// A batch starts when its first element arrives, and is yielded as soon
+    /// as it holds `batch_size` elements, or as soon as an element arrives
+    /// `max_delay` or more after the batch started, whichever comes first.
+    /// Time is read from the monotonic [`Instant`] clock. This is the usual
+    /// micro-batching policy: a busy source fills whole batches, while a slow
+    /// one still gets its elements passed on in a timely manner.
+    ///
+    /// The delay can only be checked between elements, so a batch is
+    /// flushed late if the next element takes long to arrive. When the
+    /// underlying iterator runs out or `n` elements have been taken, the
+    /// last batch is yielded even if it is not full. No batch is ever empty.
+    ///
+    /// [`Instant`]: crate::time::Instant
+    ///
+    /// # Panics
+    ///
+    /// Panics if `batch_size` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iter_std_ext, iter_take_microbatched)]
+    /// use std::iter::IteratorExt;
+    /// use std::time::Duration;
+    ///
+    /// let batches: Vec<_> = (1..).take_microbatched(7, 3, Duration::from_secs(60)).collect();
+    /// assert_eq!(batches, [vec![1, 2, 3], vec![4, 5, 6], vec![7]]);
+    /// ```
+    #[unstable(feature = "iter_take_microbatched", issue = "none")]
+    fn take_microbatched(
+        self,
+        n: usize,
+        batch_size: usize,
+        max_delay: Duration,
+    ) -> TakeMicrobatched<Self>
+    where
+        Self: Sized,
+    {
+        TakeMicrobatched::new(self, n, batch_size, max_delay)
+    }
+
+    /// Creates an iterator that yields at most `n` elements, skipping those
+    /// whose key was probably yielded before.
+    ///
+    /// Unlike deduplicating with a [`HashSet`], the keys yielded so far are
+    /// not stored, only remembered in a [Bloom filter] sized for
+    /// `expected_items` keys and a false positive rate of `fp_rate`. It
+    /// takes up about `1.44 * log2(1 / fp_rate)` bits per expected key, so
+    /// about 10 bits for a rate of 1%, however large the keys themselves
+    /// are. It is allocated up front, and doesn't grow.
+    ///
+    /// The filter makes errors in one direction only:
+    ///
+    /// - It never yields two elements with the same key. Keys that compare
+    ///   equal must hash the same, as for a [`HashSet`], and then a repeated
+    ///   key is always recognized.
+    /// - It may skip an element whose key wasn't yielded before, a false
+    ///   positive. As long as at most `expected_items` keys have been
+    ///   yielded, each new key is skipped with a probability of at most
+    ///   about `fp_rate`. The probability grows as more keys are yielded
+    ///   beyond that, so `expected_items` should be at least `n`.
+    ///
+    /// The hash function is seeded randomly, like that of a [`HashSet`], so
+    /// which elements fall victim to false positives differs between runs.
+    ///
+    /// [`HashSet`]: crate::collections::HashSet
+    /// [Bloom filter]: https://en.wikipedia.org/wiki/Bloom_filter
+    ///
+    /// # Panics
+    ///
+    /// Panics if `fp_rate` is not strictly between 0 and 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iter_std_ext, iter_take_approx_dedup)]
+    /// use std::iter::IteratorExt;
+    ///
+    /// let visits = ["/", "/about", "/", "/blog", "/about", "/contact"];
+    /// let pages: Vec<_> = visits.iter().take_approx_dedup(3, |page| **page, 1000, 1e-6).collect();
+    /// assert_eq!(pages, [&"/", &"/about", &"/blog"]);
+    /// ```
+    #[inline]
+    #[unstable(feature = "iter_take_approx_dedup", issue = "none")]
+    fn take_approx_dedup<K, F>(
+        self,
+        n: usize,
+        key: F,
+        expected_items: usize,
+        fp_rate: f64,
+    ) -> TakeApproxDedup<Self, F>
+    where
+        Self: Sized,
+        K: Hash,
+        F: FnMut(&Self::Item) -> K,
+    {
+        TakeApproxDedup::new(self, n, key, expected_items, fp_rate)
+    }
+
+    /// Creates an iterator that yields at most `n` elements, and stops early
+    /// once `cancel` is set.
+    ///
+    /// The flag is checked with [`Ordering::Relaxed`] before each element is
+    /// pulled from the underlying iterator, so setting it from another thread
+    /// stops the iteration cleanly, between two elements, at the next call to
+    /// [`next`]. An element that is already being produced when the flag is
+    /// set is still yielded. [`TakeCancellable::stop_reason`] tells whether
+    /// the iterator stopped because it was cancelled, reached its limit, or
+    /// ran out of elements.
+    ///
+    /// Once it has stopped, the iterator keeps returning `None`, even if
+    /// `cancel` is cleared again.
+    ///
+    /// [`Ordering::Relaxed`]: crate::sync::atomic::Ordering::Relaxed
+    /// [`next`]: Iterator::next
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iter_std_ext, iter_take_cancellable)]
+    /// use std::iter::{IteratorExt, StopReason};
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    /// use std::sync::Arc;
+    ///
+    /// let cancel = Arc::new(AtomicBool::new(false));
+    /// let mut jobs = (1..).take_cancellable(100, cancel.clone());
+    /// assert_eq!(jobs.next(), Some(1));
+    /// assert_eq!(jobs.next(), Some(2));
+    ///
+    /// // Typically set by another thread, such as a signal handler's.
+    /// cancel.store(true, Ordering::Relaxed);
+    /// assert_eq!(jobs.next(), None);
+    /// assert_eq!(jobs.stop_reason(), Some(StopReason::Cancelled));
+    /// ```
+    #[inline]
+    #[unstable(feature = "iter_take_cancellable", issue = "none")]
+    fn take_cancellable(self, n: usize, cancel: Arc<AtomicBool>) -> TakeCancellable<Self>
+    where
+        Self: Sized,
+    {
+        TakeCancellable::new(self, n, cancel)
+    }
+
+    /// Creates an iterator that yields at most `n` bytes, writing a copy of
+    /// each byte it yields to `sink`.
+    ///
+    /// The bytes are yielded unchanged, so the sink receives exactly the
+    /// prefix of the underlying iterator that was consumed. Writes go through
+    /// a [`BufWriter`], which is flushed once the iterator reaches its limit
+    /// or runs out of bytes, and again when the sink is taken back with
+    /// [`TakeTeeWrite::into_sink`].
+    ///
+    /// Since [`next`] yields plain bytes, write errors can't be reported as
+    /// they happen. Instead, the first one is kept and can be checked with
+    /// [`TakeTeeWrite::error`] after iterating, or is returned by
+    /// [`TakeTeeWrite::into_sink`].
+    ///
+    /// [`BufWriter`]: crate::io::BufWriter
+    /// [`next`]: Iterator::next
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(iter_std_ext, iter_take_tee_write)]
+    /// use std::fs::File;
+    /// use std::io::{self, Read};
+    /// use std::iter::IteratorExt;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let input = File::open("input.bin")?;
+    ///     let log = File::create("consumed.bin")?;
+    ///
+    ///     let mut bytes = input.bytes().map(Result::unwrap).take_tee_write(16, log);
+    ///     let header: Vec<u8> = bytes.by_ref().collect();
+    ///     if let Some(e) = bytes.error() {
+    ///         eprintln!("failed to log the header: {}", e);
+    ///     }
+    ///     println!("read a {}-byte header", header.len());
+    ///     Ok(())
+    /// }
+    /// ```
+    #[inline]
+    #[unstable(feature = "iter_take_tee_write", issue = "none")]
+    fn take_tee_write<W>(self, n: usize, sink: W) -> TakeTeeWrite<Self, W>
+    where
+        Self: Sized + Iterator<Item = u8>,
+        W: Write,
+    {
+        TakeTeeWrite::new(self, n, sink)
+    }
+
+    /// Creates an iterator that yields at most `n` elements, which are pulled
+    /// from the underlying iterator ahead of time on a background thread.
+    ///
+    /// This helps when each call to [`next`] on the underlying iterator has
+    /// to wait for something, such as a channel or a network request: the
+    /// worker thread keeps up to `depth` elements ready, so the consumer only
+    /// waits when it gets ahead of the worker. With a `depth` of zero nothing
+    /// is buffered, but the worker still fetches the next element while the
+    /// consumer is busy with the current one.
+    ///
+    /// The underlying iterator is moved to a new thread, which is spawned
+    /// right away and calls [`next`] on it at most `n` times. Elements are
+    /// yielded in the order the underlying iterator produced them.
+    ///
+    /// When the adapter is dropped before reaching its limit, the worker
+    /// stops as soon as it tries to hand over its next element. Beyond the
+    /// elements already prefetched, at most one more is pulled from the
+    /// underlying iterator, and all of them are dropped unseen.
+    /// The adapter does not wait for the worker to stop when dropped, since
+    /// the underlying iterator may block indefinitely. If the underlying
+    /// iterator panics, the panic is propagated to the consumer by the call
+    /// to [`next`] that would have returned the missing element.
+    ///
+    /// [`next`]: Iterator::next
+    ///
+    /// # Panics
+    ///
+    /// Panics if the worker thread can't be spawned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iter_std_ext, iter_take_prefetched)]
+    /// use std::iter::IteratorExt;
+    /// use std::sync::mpsc::channel;
+    /// use std::thread;
+    ///
+    /// let (tx, rx) = channel();
+    /// thread::spawn(move || {
+    ///     for i in 0..10 {
+    ///         tx.send(i).unwrap();
+    ///     }
+    /// });
+    ///
+    /// let first: Vec<_> = rx.into_iter().take_prefetched(5, 2).collect();
+    /// assert_eq!(first, [0, 1, 2, 3, 4]);
+    /// ```
+    #[inline]
+    #[unstable(feature = "iter_take_prefetched", issue = "none")]
+    fn take_prefetched(self, n: usize, depth: usize) -> TakePrefetched<Self::Item>
+    where
+        Self: Sized + Send + 'static,
+        Self::Item: Send + 'static,
+    {
+        TakePrefetched::new(self, n, depth)
+    }
+
+    /// Creates an iterator that takes at most `n` elements and yields, after
+    /// each one, the median of the last `window` elements.
+    ///
+    /// The window is kept split into two ordered halves, so each step takes
+    /// O(log `window`) time however large the window is. Since the elements
+    /// are only required to be [`Ord`], not to support arithmetic, the median
+    /// of an even number of elements is the lower of the two middle ones.
+    ///
+    /// While the window is filling up, the median is taken over the elements
+    /// seen so far, so one median is yielded for each element taken, starting
+    /// with the first. Callers that only want medians over full windows can
+    /// [`skip`] the first `window - 1` of them.
+    ///
+    /// [`skip`]: Iterator::skip
+    ///
+    /// # Panics
+    ///
+    /// Panics if `window` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iter_std_ext, iter_take_rolling_median)]
+    /// use std::iter::IteratorExt;
+    ///
+    /// // A single outlier doesn't move a rolling median much.
+    /// let latencies = [12, 11, 13, 250, 12, 14, 11];
+    /// let medians: Vec<_> = latencies.iter().take_rolling_median(7, 3).collect();
+    /// assert_eq!(medians, [&12, &11, &12, &13, &13, &14, &12]);
+    ///
+    /// // Only the medians over full windows.
+    /// let full: Vec<_> = latencies.iter().take_rolling_median(7, 3).skip(2).collect();
+    /// assert_eq!(full, [&12, &13, &13, &14, &12]);
+    /// ```
+    #[inline]
+    #[unstable(feature = "iter_take_rolling_median", issue = "none")]
+    fn take_rolling_median(self, n: usize, window: usize) -> TakeRollingMedian<Self>
+    where
+        Self: Sized,
+        Self::Item: Ord + Clone,
+    {
+        TakeRollingMedian::new(self, n, window)
+    }
+
+    /// Creates an iterator that yields at most `n` elements, sleeping before
+    /// each one for as long as `delay` says.
+    ///
+    /// `delay` is called with each element as it comes out of the underlying
+    /// iterator, and the current thread then sleeps for the returned duration
+    /// before the element is yielded. A zero duration skips the sleep. As with
+    /// [`thread::sleep`], the sleep may last longer than asked, but never
+    /// shorter.
+    ///
+    /// This models a per-element processing cost, which is useful for
+    /// simulating realistic load in tests and benchmarks.
+    ///
+    /// [`thread::sleep`]: crate::thread::sleep
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iter_std_ext, iter_take_delayed)]
+    /// use std::iter::IteratorExt;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// let start = Instant::now();
+    /// let sizes: Vec<u64> =
+    ///     [1, 2, 3, 4].iter().copied().take_delayed(3, |&n| Duration::from_millis(n)).collect();
+    /// assert_eq!(sizes, [1, 2, 3]);
+    /// assert!(start.elapsed() >= Duration::from_millis(6));
+    /// ```
+    #[inline]
+    #[unstable(feature = "iter_take_delayed", issue = "none")]
+    fn take_delayed<F>(self, n: usize, delay: F) -> TakeDelayed<Self, F>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> Duration,
+    {
+        TakeDelayed::new(self, n, delay)
+    }
+
+    /// Creates an iterator that groups at most `n` elements into batches
+    /// whose sizes are taken in turn from `sizes`.
+    ///
+    /// Each batch holds as many elements as the next size in the schedule
+    /// asks for. Sizes of zero are skipped, so no batch is ever empty. The
+    /// last batch is short if the underlying iterator runs out or `n`
+    /// elements have been taken before it is full.
+    ///
+    /// If the schedule runs out first, iteration stops: the elements left
+    /// over are not yielded, and stay in the underlying iterator. Pass a
+    /// schedule ending in [`repeat`] to keep going with a fixed size instead.
+    ///
+    /// [`repeat`]: crate::iter::repeat
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iter_std_ext, iter_take_scheduled_batches)]
+    /// use std::iter::{self, IteratorExt};
+    ///
+    /// let batches: Vec<_> = (0..).take_scheduled_batches(6, [2, 3, 1]).collect();
+    /// assert_eq!(batches, [vec![0, 1], vec![2, 3, 4], vec![5]]);
+    ///
+    /// // Headers of a fixed size, then fixed-size records.
+    /// let sizes = iter::once(3).chain(iter::repeat(2));
+    /// let batches: Vec<_> = (0..).take_scheduled_batches(8, sizes).collect();
+    /// assert_eq!(batches, [vec![0, 1, 2], vec![3, 4], vec![5, 6], vec![7]]);
+    /// ```
+    #[inline]
+    #[unstable(feature = "iter_take_scheduled_batches", issue = "none")]
+    fn take_scheduled_batches<S>(
+        self,
+        n: usize,
+        sizes: S,
+    ) -> TakeScheduledBatches<Self, S::IntoIter>
+    where
+        Self: Sized,
+        S: IntoIterator<Item = usize>,
+    {
+        TakeScheduledBatches::new(self, n, sizes.into_iter())
+    }
+
+    /// Creates an iterator that yields at most `n` elements, taking a permit
+    /// from `sem` for each of them.
+    ///
+    /// Before pulling each element from the underlying iterator, [`next`]
+    /// takes a permit with [`Semaphore::acquire`], blocking the current
+    /// thread until one is available. The permit then belongs to the caller,
+    /// who gives it back with [`Semaphore::release`] once done with the
+    /// element, from whichever thread processed it. This bounds the number
+    /// of elements being worked on at once to the number of permits of
+    /// `sem`, which may be shared with other iterators and code.
+    ///
+    /// When the underlying iterator runs out, the permit taken to look for
+    /// another element is released again. Forgetting to release the permit
+    /// of an element leaks it for good, and once all permits have leaked,
+    /// [`next`] blocks forever.
+    ///
+    /// [`next`]: Iterator::next
+    /// [`Semaphore::acquire`]: crate::sync::Semaphore::acquire
+    /// [`Semaphore::release`]: crate::sync::Semaphore::release
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iter_std_ext, iter_take_permitted, semaphore)]
+    /// use std::iter::IteratorExt;
+    /// use std::sync::{Arc, Semaphore};
+    /// use std::thread;
+    ///
+    /// // At most four jobs run at the same time.
+    /// let sem = Arc::new(Semaphore::new(4));
+    /// let mut handles = Vec::new();
+    /// for job in (0..).take_permitted(10, Arc::clone(&sem)) {
+    ///     let sem = Arc::clone(&sem);
+    ///     handles.push(thread::spawn(move || {
+    ///         println!("running job {}", job);
+    ///         sem.release();
+    ///     }));
+    /// }
+    /// for handle in handles {
+    ///     handle.join().unwrap();
+    /// }
+    /// ```
+    #[inline]
+    #[unstable(feature = "iter_take_permitted", issue = "none")]
+    fn take_permitted(self, n: usize, sem: Arc<Semaphore>) -> TakePermitted<Self>
+    where
+        Self: Sized,
+    {
+        TakePermitted::new(self, n, sem)
+    }
+
+    /// Creates an iterator that spreads at most `n` elements over `buckets`
+    /// groups of roughly equal total weight, then yields the groups.
+    ///
+    /// The first call to [`next`] takes up to `n` elements from the
+    /// underlying iterator and weighs each with `weight`. Every element goes
+    /// to the bucket whose total weight is the lowest at the time, the first
+    /// such bucket if several are tied, and the buckets are then yielded in
+    /// order. Exactly `buckets` of them are always yielded, so some may be
+    /// empty when there are fewer elements than buckets.
+    ///
+    /// This is a greedy heuristic, not an optimal partition: elements are
+    /// placed in the order they come in and never moved afterwards. It
+    /// balances best when the heavy elements come first, so sorting by
+    /// decreasing weight beforehand is worthwhile when that's cheap. Totals
+    /// saturate at `u64::MAX` instead of overflowing.
+    ///
+    /// [`next`]: Iterator::next
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buckets` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iter_std_ext, iter_take_balanced)]
+    /// use std::iter::IteratorExt;
+    ///
+    /// // Share out jobs, weighed by their expected running time.
+    /// let jobs = [8, 5, 4, 3, 3, 1];
+    /// let workers: Vec<_> = jobs.iter().take_balanced(6, 2, |job| **job).collect();
+    /// assert_eq!(workers, [vec![&8, &3, &1], vec![&5, &4, &3]]);
+    /// ```
+    #[inline]
+    #[unstable(feature = "iter_take_balanced", issue = "none")]
+    fn take_balanced<F>(self, n: usize, buckets: usize, weight: F) -> TakeBalanced<Self, F>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> u64,
+    {
+        TakeBalanced::new(self, n, buckets, weight)
+    }
+
+    /// Creates an iterator that yields at most `n` elements, and stops early
+    /// once their total size would exceed `max_bytes`.
+    ///
+    /// The size of each element is given by `size`, which may be its length,
+    /// its serialized size or any other estimate of the memory it takes up.
+    /// Elements are yielded as long as fewer than `n` have been yielded and
+    /// the sizes yielded so far, plus that of the next element, add up to at
+    /// most `max_bytes`. This bounds memory use even when a few huge elements
+    /// would slip under a limit on their count alone.
+    ///
+    /// The element that would go over the budget has to be taken from the
+    /// underlying iterator to be measured. It is not yielded, and iteration
+    /// ends there even if smaller elements come after it. The adapter keeps
+    /// it instead, so that it can be recovered with
+    /// [`TakeBytesBounded::into_rejected`], for example to start the next
+    /// batch with it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iter_std_ext, iter_take_bytes_bounded)]
+    /// use std::iter::IteratorExt;
+    ///
+    /// let records = ["abc", "de", "fghij", "k"];
+    ///
+    /// // The byte budget runs out first.
+    /// let batch: Vec<_> = records.iter().take_bytes_bounded(10, 8, |r| r.len()).collect();
+    /// assert_eq!(batch, [&"abc", &"de"]);
+    ///
+    /// // The count runs out first.
+    /// let batch: Vec<_> = records.iter().take_bytes_bounded(1, 8, |r| r.len()).collect();
+    /// assert_eq!(batch, [&"abc"]);
+    /// ```
+    #[inline]
+    #[unstable(feature = "iter_take_bytes_bounded", issue = "none")]
+    fn take_bytes_bounded<F>(self, n: usize, max_bytes: usize, size: F) -> TakeBytesBounded<Self, F>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> usize,
+    {
+        TakeBytesBounded::new(self, n, max_bytes, size)
+    }
+}
+
+#[unstable(feature = "iter_std_ext", issue = "none")]
+impl<I: Iterator + ?Sized> IteratorExt for I {}
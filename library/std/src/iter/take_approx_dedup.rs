@@ -0,0 +1,139 @@
+use crate::collections::hash_map::RandomState;
+use crate::f64::consts::LN_2;
+use crate::fmt;
+use crate::hash::{BuildHasher, Hash, Hasher};
+use crate::iter::FusedIterator;
+
+/// A Bloom filter over the keys yielded so far.
+#[derive(Clone)]
+struct Bloom {
+    bits: Vec<u64>,
+    num_bits: u64,
+    num_hashes: u32,
+    hasher: RandomState,
+}
+
+impl Bloom {
+    fn new(expected_items: usize, fp_rate: f64) -> Bloom {
+        assert!(fp_rate > 0.0 && fp_rate < 1.0, "false positive rate must be between 0 and 1");
+        // The textbook optimum: `m = -n ln p / (ln 2)^2` bits and
+        // `k = m / n * ln 2` hash functions.
+        let expected = expected_items.max(1) as f64;
+        let num_bits = (-expected * fp_rate.ln() / (LN_2 * LN_2)).ceil().max(64.0) as u64;
+        let num_hashes = (num_bits as f64 / expected * LN_2).round().max(1.0) as u32;
+        let words = (num_bits + 63) / 64;
+        Bloom { bits: vec![0; words as usize], num_bits, num_hashes, hasher: RandomState::new() }
+    }
+
+    /// Adds `key` to the filter, returning whether it probably was in it
+    /// already.
+    fn insert<K: Hash + ?Sized>(&mut self, key: &K) -> bool {
+        let mut hasher = self.hasher.build_hasher();
+        key.hash(&mut hasher);
+        let hash = hasher.finish();
+
+        // Derive all `k` positions from one hash, as `h1 + i * h2`, which is
+        // as good as `k` independent hashes for a Bloom filter. `h2` is odd so
+        // that the positions don't collapse onto a single one.
+        let h1 = hash;
+        let h2 = hash.rotate_left(32) | 1;
+        let mut present = true;
+        for i in 0..self.num_hashes {
+            let bit = h1.wrapping_add(h2.wrapping_mul(i as u64)) % self.num_bits;
+            let (word, mask) = ((bit / 64) as usize, 1 << (bit % 64));
+            if self.bits[word] & mask == 0 {
+                present = false;
+                self.bits[word] |= mask;
+            }
+        }
+        present
+    }
+}
+
+/// An iterator that yields at most `n` elements of `iter`, skipping those
+/// whose key was probably seen before.
+///
+/// This `struct` is created by the [`take_approx_dedup`] method on
+/// [`IteratorExt`]. See its documentation for more.
+///
+/// [`take_approx_dedup`]: super::IteratorExt::take_approx_dedup
+/// [`IteratorExt`]: super::IteratorExt
+#[derive(Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[unstable(feature = "iter_take_approx_dedup", issue = "none")]
+pub struct TakeApproxDedup<I, F> {
+    iter: I,
+    n: usize,
+    key: F,
+    seen: Bloom,
+}
+
+impl<I, F> TakeApproxDedup<I, F> {
+    pub(super) fn new(
+        iter: I,
+        n: usize,
+        key: F,
+        expected_items: usize,
+        fp_rate: f64,
+    ) -> TakeApproxDedup<I, F> {
+        TakeApproxDedup { iter, n, key, seen: Bloom::new(expected_items, fp_rate) }
+    }
+}
+
+#[unstable(feature = "iter_take_approx_dedup", issue = "none")]
+impl<I: fmt::Debug, F> fmt::Debug for TakeApproxDedup<I, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TakeApproxDedup")
+            .field("iter", &self.iter)
+            .field("n", &self.n)
+            .field("num_bits", &self.seen.num_bits)
+            .field("num_hashes", &self.seen.num_hashes)
+            .finish()
+    }
+}
+
+#[unstable(feature = "iter_take_approx_dedup", issue = "none")]
+impl<I, K, F> Iterator for TakeApproxDedup<I, F>
+where
+    I: Iterator,
+    K: Hash,
+    F: FnMut(&I::Item) -> K,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        if self.n == 0 {
+            return None;
+        }
+        loop {
+            let item = self.iter.next()?;
+            if !self.seen.insert(&(self.key)(&item)) {
+                self.n -= 1;
+                return Some(item);
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.n == 0 {
+            return (0, Some(0));
+        }
+
+        // Any element may be skipped as a duplicate.
+        let upper = match self.iter.size_hint().1 {
+            Some(x) if x < self.n => Some(x),
+            _ => Some(self.n),
+        };
+        (0, upper)
+    }
+}
+
+#[unstable(feature = "iter_take_approx_dedup", issue = "none")]
+impl<I, K, F> FusedIterator for TakeApproxDedup<I, F>
+where
+    I: FusedIterator,
+    K: Hash,
+    F: FnMut(&I::Item) -> K,
+{
+}
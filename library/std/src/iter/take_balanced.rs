@@ -0,0 +1,124 @@
+use crate::cmp::Reverse;
+use crate::collections::BinaryHeap;
+use crate::fmt;
+use crate::iter::FusedIterator;
+use crate::vec;
+
+/// An iterator that spreads at most `n` elements of `iter` over a fixed
+/// number of buckets of roughly equal total weight, and yields the buckets.
+///
+/// This `struct` is created by the [`take_balanced`] method on
+/// [`IteratorExt`]. See its documentation for more.
+///
+/// [`take_balanced`]: super::IteratorExt::take_balanced
+/// [`IteratorExt`]: super::IteratorExt
+#[derive(Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[unstable(feature = "iter_take_balanced", issue = "none")]
+pub struct TakeBalanced<I: Iterator, F> {
+    state: State<I, F, I::Item>,
+}
+
+#[derive(Clone)]
+enum State<I, F, T> {
+    /// The source has not been consumed yet.
+    Pending { iter: I, n: usize, buckets: usize, weight: F },
+    /// The filled buckets, in order.
+    Balanced(vec::IntoIter<Vec<T>>),
+}
+
+impl<I: Iterator, F> TakeBalanced<I, F> {
+    pub(super) fn new(iter: I, n: usize, buckets: usize, weight: F) -> TakeBalanced<I, F> {
+        assert!(buckets != 0, "number of buckets must be non-zero");
+        TakeBalanced { state: State::Pending { iter, n, buckets, weight } }
+    }
+}
+
+impl<I, F> TakeBalanced<I, F>
+where
+    I: Iterator,
+    F: FnMut(&I::Item) -> u64,
+{
+    /// Consumes the source on first use, handing each element to the bucket
+    /// that is lightest at the time.
+    fn balanced(&mut self) -> &mut vec::IntoIter<Vec<I::Item>> {
+        if let State::Pending { iter, n, buckets, weight } = &mut self.state {
+            let mut filled: Vec<Vec<I::Item>> = (0..*buckets).map(|_| Vec::new()).collect();
+            // A min-heap of `(total weight, index)`, so that ties go to the
+            // bucket that comes first.
+            let mut lightest: BinaryHeap<_> = (0..*buckets).map(|i| Reverse((0u64, i))).collect();
+            for item in iter.take(*n) {
+                let w = weight(&item);
+                let mut top = lightest.peek_mut().unwrap();
+                let Reverse((total, i)) = *top;
+                filled[i].push(item);
+                *top = Reverse((total.saturating_add(w), i));
+            }
+            self.state = State::Balanced(filled.into_iter());
+        }
+        match &mut self.state {
+            State::Balanced(filled) => filled,
+            State::Pending { .. } => unreachable!(),
+        }
+    }
+}
+
+#[unstable(feature = "iter_take_balanced", issue = "none")]
+impl<I, F> fmt::Debug for TakeBalanced<I, F>
+where
+    I: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.state {
+            State::Pending { iter, n, buckets, .. } => f
+                .debug_struct("TakeBalanced")
+                .field("iter", iter)
+                .field("n", n)
+                .field("buckets", buckets)
+                .finish(),
+            State::Balanced(filled) => {
+                f.debug_struct("TakeBalanced").field("buckets", &filled.as_slice()).finish()
+            }
+        }
+    }
+}
+
+#[unstable(feature = "iter_take_balanced", issue = "none")]
+impl<I, F> Iterator for TakeBalanced<I, F>
+where
+    I: Iterator,
+    F: FnMut(&I::Item) -> u64,
+{
+    type Item = Vec<I::Item>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Vec<I::Item>> {
+        self.balanced().next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match &self.state {
+            // Every bucket is yielded, even an empty one.
+            State::Pending { buckets, .. } => (*buckets, Some(*buckets)),
+            State::Balanced(filled) => filled.size_hint(),
+        }
+    }
+}
+
+#[unstable(feature = "iter_take_balanced", issue = "none")]
+impl<I, F> ExactSizeIterator for TakeBalanced<I, F>
+where
+    I: Iterator,
+    F: FnMut(&I::Item) -> u64,
+{
+}
+
+#[unstable(feature = "iter_take_balanced", issue = "none")]
+impl<I, F> FusedIterator for TakeBalanced<I, F>
+where
+    I: Iterator,
+    F: FnMut(&I::Item) -> u64,
+{
+}
@@ -0,0 +1,111 @@
+use crate::cmp;
+use crate::fmt;
+use crate::iter::FusedIterator;
+
+/// An iterator that yields at most `n` elements of `iter`, as long as their
+/// total size stays within a byte budget.
+///
+/// This `struct` is created by the [`take_bytes_bounded`] method on
+/// [`IteratorExt`]. See its documentation for more.
+///
+/// [`take_bytes_bounded`]: super::IteratorExt::take_bytes_bounded
+/// [`IteratorExt`]: super::IteratorExt
+#[derive(Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[unstable(feature = "iter_take_bytes_bounded", issue = "none")]
+pub struct TakeBytesBounded<I: Iterator, F> {
+    iter: I,
+    n: usize,
+    remaining_bytes: usize,
+    size: F,
+    rejected: Option<I::Item>,
+}
+
+impl<I: Iterator, F> TakeBytesBounded<I, F> {
+    pub(super) fn new(iter: I, n: usize, max_bytes: usize, size: F) -> TakeBytesBounded<I, F> {
+        TakeBytesBounded { iter, n, remaining_bytes: max_bytes, size, rejected: None }
+    }
+
+    /// Consumes the adapter, returning the element that went over the byte
+    /// budget, if iteration ended because of one.
+    ///
+    /// That element had to be taken from the underlying iterator to be
+    /// measured, so this is the only way to get it back.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iter_std_ext, iter_take_bytes_bounded)]
+    /// use std::iter::IteratorExt;
+    ///
+    /// let records = ["abc", "de", "fghij", "k"];
+    /// let mut it = records.iter().take_bytes_bounded(10, 8, |r| r.len());
+    /// assert_eq!(it.by_ref().count(), 2);
+    /// assert_eq!(it.into_rejected(), Some(&"fghij"));
+    /// ```
+    #[unstable(feature = "iter_take_bytes_bounded", issue = "none")]
+    pub fn into_rejected(self) -> Option<I::Item> {
+        self.rejected
+    }
+}
+
+#[unstable(feature = "iter_take_bytes_bounded", issue = "none")]
+impl<I, F> fmt::Debug for TakeBytesBounded<I, F>
+where
+    I: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TakeBytesBounded")
+            .field("iter", &self.iter)
+            .field("n", &self.n)
+            .field("remaining_bytes", &self.remaining_bytes)
+            .field("rejected", &self.rejected)
+            .finish()
+    }
+}
+
+#[unstable(feature = "iter_take_bytes_bounded", issue = "none")]
+impl<I, F> Iterator for TakeBytesBounded<I, F>
+where
+    I: Iterator,
+    F: FnMut(&I::Item) -> usize,
+{
+    type Item = I::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<I::Item> {
+        if self.n == 0 {
+            return None;
+        }
+        let item = self.iter.next()?;
+        let size = (self.size)(&item);
+        if size > self.remaining_bytes {
+            self.n = 0;
+            self.rejected = Some(item);
+            return None;
+        }
+        self.remaining_bytes -= size;
+        self.n -= 1;
+        Some(item)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.n == 0 {
+            return (0, Some(0));
+        }
+
+        // Any element may be too large for what's left of the budget.
+        let (_, upper) = self.iter.size_hint();
+        (0, Some(upper.map_or(self.n, |x| cmp::min(x, self.n))))
+    }
+}
+
+#[unstable(feature = "iter_take_bytes_bounded", issue = "none")]
+impl<I, F> FusedIterator for TakeBytesBounded<I, F>
+where
+    I: FusedIterator,
+    F: FnMut(&I::Item) -> usize,
+{
+}
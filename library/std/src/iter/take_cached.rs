@@ -0,0 +1,131 @@
+use crate::collections::HashMap;
+use crate::fmt;
+use crate::hash::Hash;
+use crate::iter::FusedIterator;
+
+/// An iterator that yields at most `n` transformed elements of `iter`,
+/// caching the results of the transformation by key.
+///
+/// This `struct` is created by the [`take_cached`] method on
+/// [`IteratorExt`]. See its documentation for more.
+///
+/// [`take_cached`]: super::IteratorExt::take_cached
+/// [`IteratorExt`]: super::IteratorExt
+#[derive(Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[unstable(feature = "iter_take_cached", issue = "none")]
+pub struct TakeCached<I, K, V, Fk, Ft> {
+    iter: I,
+    n: usize,
+    key: Fk,
+    transform: Ft,
+    capacity: usize,
+    // Each entry remembers the tick of its last use; the entry with the
+    // smallest tick is the least recently used one.
+    cache: HashMap<K, (V, u64)>,
+    tick: u64,
+}
+
+impl<I, K, V, Fk, Ft> TakeCached<I, K, V, Fk, Ft> {
+    pub(super) fn new(
+        iter: I,
+        n: usize,
+        key: Fk,
+        transform: Ft,
+        capacity: usize,
+    ) -> TakeCached<I, K, V, Fk, Ft> {
+        TakeCached { iter, n, key, transform, capacity, cache: HashMap::new(), tick: 0 }
+    }
+}
+
+#[unstable(feature = "iter_take_cached", issue = "none")]
+impl<I: fmt::Debug, K, V, Fk, Ft> fmt::Debug for TakeCached<I, K, V, Fk, Ft> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TakeCached")
+            .field("iter", &self.iter)
+            .field("n", &self.n)
+            .field("capacity", &self.capacity)
+            .field("cached", &self.cache.len())
+            .finish()
+    }
+}
+
+#[unstable(feature = "iter_take_cached", issue = "none")]
+impl<I, K, V, Fk, Ft> Iterator for TakeCached<I, K, V, Fk, Ft>
+where
+    I: Iterator,
+    K: Hash + Eq,
+    V: Clone,
+    Fk: FnMut(&I::Item) -> K,
+    Ft: FnMut(I::Item) -> V,
+{
+    type Item = V;
+
+    fn next(&mut self) -> Option<V> {
+        if self.n == 0 {
+            return None;
+        }
+        let item = self.iter.next()?;
+        self.n -= 1;
+
+        if self.capacity == 0 {
+            return Some((self.transform)(item));
+        }
+
+        self.tick += 1;
+        let key = (self.key)(&item);
+        if let Some((value, last_used)) = self.cache.get_mut(&key) {
+            *last_used = self.tick;
+            return Some(value.clone());
+        }
+
+        let value = (self.transform)(item);
+        if self.cache.len() == self.capacity {
+            // The cache is meant to be small, so scanning it for the least
+            // recently used entry is cheaper than keeping a recency list.
+            // Ticks are unique, so this evicts exactly one entry.
+            if let Some(oldest) = self.cache.values().map(|&(_, last_used)| last_used).min() {
+                self.cache.retain(|_, &mut (_, last_used)| last_used != oldest);
+            }
+        }
+        self.cache.insert(key, (value.clone(), self.tick));
+        Some(value)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.n == 0 {
+            return (0, Some(0));
+        }
+
+        let (lower, upper) = self.iter.size_hint();
+        let lower = lower.min(self.n);
+        let upper = match upper {
+            Some(x) if x < self.n => Some(x),
+            _ => Some(self.n),
+        };
+        (lower, upper)
+    }
+}
+
+#[unstable(feature = "iter_take_cached", issue = "none")]
+impl<I, K, V, Fk, Ft> ExactSizeIterator for TakeCached<I, K, V, Fk, Ft>
+where
+    I: ExactSizeIterator,
+    K: Hash + Eq,
+    V: Clone,
+    Fk: FnMut(&I::Item) -> K,
+    Ft: FnMut(I::Item) -> V,
+{
+}
+
+#[unstable(feature = "iter_take_cached", issue = "none")]
+impl<I, K, V, Fk, Ft> FusedIterator for TakeCached<I, K, V, Fk, Ft>
+where
+    I: FusedIterator,
+    K: Hash + Eq,
+    V: Clone,
+    Fk: FnMut(&I::Item) -> K,
+    Ft: FnMut(I::Item) -> V,
+{
+}
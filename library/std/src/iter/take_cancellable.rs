@@ -0,0 +1,107 @@
+use crate::iter::FusedIterator;
+use crate::sync::atomic::{AtomicBool, Ordering};
+use crate::sync::Arc;
+
+/// Why a [`TakeCancellable`] stopped, as returned by
+/// [`TakeCancellable::stop_reason`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[unstable(feature = "iter_take_cancellable", issue = "none")]
+pub enum StopReason {
+    /// The cancellation flag was set.
+    Cancelled,
+    /// `n` elements were yielded.
+    Limit,
+    /// The underlying iterator ran out of elements.
+    Exhausted,
+}
+
+/// An iterator that yields at most `n` elements of `iter`, and stops early
+/// once a cancellation flag is set.
+///
+/// This `struct` is created by the [`take_cancellable`] method on
+/// [`IteratorExt`]. See its documentation for more.
+///
+/// [`take_cancellable`]: super::IteratorExt::take_cancellable
+/// [`IteratorExt`]: super::IteratorExt
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[unstable(feature = "iter_take_cancellable", issue = "none")]
+pub struct TakeCancellable<I> {
+    iter: I,
+    n: usize,
+    cancel: Arc<AtomicBool>,
+    stopped: Option<StopReason>,
+}
+
+impl<I> TakeCancellable<I> {
+    pub(super) fn new(iter: I, n: usize, cancel: Arc<AtomicBool>) -> TakeCancellable<I> {
+        TakeCancellable { iter, n, cancel, stopped: None }
+    }
+
+    /// Returns why the iterator stopped, or `None` if it hasn't yet.
+    ///
+    /// The reason is recorded by the call to [`next`] that returns `None`
+    /// for the first time, and doesn't change afterwards. In particular, an
+    /// iterator that has yielded `n` elements only reports
+    /// [`StopReason::Limit`] once [`next`] has been called one more time.
+    ///
+    /// [`next`]: Iterator::next
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iter_std_ext, iter_take_cancellable)]
+    /// use std::iter::{IteratorExt, StopReason};
+    /// use std::sync::atomic::AtomicBool;
+    /// use std::sync::Arc;
+    ///
+    /// let mut it = (0..3).take_cancellable(5, Arc::new(AtomicBool::new(false)));
+    /// assert_eq!(it.by_ref().count(), 3);
+    /// assert_eq!(it.stop_reason(), Some(StopReason::Exhausted));
+    /// ```
+    #[unstable(feature = "iter_take_cancellable", issue = "none")]
+    pub fn stop_reason(&self) -> Option<StopReason> {
+        self.stopped
+    }
+}
+
+#[unstable(feature = "iter_take_cancellable", issue = "none")]
+impl<I: Iterator> Iterator for TakeCancellable<I> {
+    type Item = I::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<I::Item> {
+        if self.stopped.is_some() {
+            return None;
+        }
+        let reason = if self.n == 0 {
+            StopReason::Limit
+        } else if self.cancel.load(Ordering::Relaxed) {
+            StopReason::Cancelled
+        } else if let Some(item) = self.iter.next() {
+            self.n -= 1;
+            return Some(item);
+        } else {
+            StopReason::Exhausted
+        };
+        self.stopped = Some(reason);
+        None
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.stopped.is_some() || self.n == 0 {
+            return (0, Some(0));
+        }
+
+        // Cancellation may come at any time.
+        let upper = match self.iter.size_hint().1 {
+            Some(x) if x < self.n => Some(x),
+            _ => Some(self.n),
+        };
+        (0, upper)
+    }
+}
+
+#[unstable(feature = "iter_take_cancellable", issue = "none")]
+impl<I: Iterator> FusedIterator for TakeCancellable<I> {}
@@ -0,0 +1,83 @@
+use crate::borrow::Cow;
+use crate::iter::{FusedIterator, Take, TrustedLen};
+
+/// An iterator that yields at most `n` borrowed elements of `iter`, wrapped
+/// in [`Cow::Borrowed`].
+///
+/// This `struct` is created by the [`take_cow`] method on [`IteratorExt`].
+/// See its documentation for more.
+///
+/// [`take_cow`]: super::IteratorExt::take_cow
+/// [`IteratorExt`]: super::IteratorExt
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[unstable(feature = "iter_take_cow", issue = "none")]
+pub struct TakeCow<I> {
+    iter: Take<I>,
+}
+
+impl<I: Iterator> TakeCow<I> {
+    pub(super) fn new(iter: I, n: usize) -> TakeCow<I> {
+        TakeCow { iter: iter.take(n) }
+    }
+}
+
+#[unstable(feature = "iter_take_cow", issue = "none")]
+impl<'a, I, T> Iterator for TakeCow<I>
+where
+    I: Iterator<Item = &'a T>,
+    T: ToOwned + ?Sized + 'a,
+{
+    type Item = Cow<'a, T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Cow<'a, T>> {
+        self.iter.next().map(Cow::Borrowed)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Cow<'a, T>> {
+        self.iter.nth(n).map(Cow::Borrowed)
+    }
+}
+
+#[unstable(feature = "iter_take_cow", issue = "none")]
+impl<'a, I, T> DoubleEndedIterator for TakeCow<I>
+where
+    I: DoubleEndedIterator<Item = &'a T> + ExactSizeIterator,
+    T: ToOwned + ?Sized + 'a,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Cow<'a, T>> {
+        self.iter.next_back().map(Cow::Borrowed)
+    }
+}
+
+#[unstable(feature = "iter_take_cow", issue = "none")]
+impl<'a, I, T> ExactSizeIterator for TakeCow<I>
+where
+    I: ExactSizeIterator<Item = &'a T>,
+    T: ToOwned + ?Sized + 'a,
+{
+}
+
+#[unstable(feature = "iter_take_cow", issue = "none")]
+impl<'a, I, T> FusedIterator for TakeCow<I>
+where
+    I: FusedIterator<Item = &'a T>,
+    T: ToOwned + ?Sized + 'a,
+{
+}
+
+#[unstable(feature = "trusted_len", issue = "37572")]
+unsafe impl<'a, I, T> TrustedLen for TakeCow<I>
+where
+    I: TrustedLen<Item = &'a T>,
+    T: ToOwned + ?Sized + 'a,
+{
+}
@@ -0,0 +1,88 @@
+use crate::fmt;
+use crate::iter::FusedIterator;
+use crate::thread;
+use crate::time::Duration;
+
+/// An iterator that yields at most `n` elements of `iter`, sleeping for a
+/// computed delay before each one.
+///
+/// This `struct` is created by the [`take_delayed`] method on
+/// [`IteratorExt`]. See its documentation for more.
+///
+/// [`take_delayed`]: super::IteratorExt::take_delayed
+/// [`IteratorExt`]: super::IteratorExt
+#[derive(Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[unstable(feature = "iter_take_delayed", issue = "none")]
+pub struct TakeDelayed<I, F> {
+    iter: I,
+    n: usize,
+    delay: F,
+}
+
+impl<I, F> TakeDelayed<I, F> {
+    pub(super) fn new(iter: I, n: usize, delay: F) -> TakeDelayed<I, F> {
+        TakeDelayed { iter, n, delay }
+    }
+}
+
+#[unstable(feature = "iter_take_delayed", issue = "none")]
+impl<I: fmt::Debug, F> fmt::Debug for TakeDelayed<I, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TakeDelayed").field("iter", &self.iter).field("n", &self.n).finish()
+    }
+}
+
+#[unstable(feature = "iter_take_delayed", issue = "none")]
+impl<I, F> Iterator for TakeDelayed<I, F>
+where
+    I: Iterator,
+    F: FnMut(&I::Item) -> Duration,
+{
+    type Item = I::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<I::Item> {
+        if self.n == 0 {
+            return None;
+        }
+        let item = self.iter.next()?;
+        self.n -= 1;
+        let delay = (self.delay)(&item);
+        if delay != Duration::ZERO {
+            thread::sleep(delay);
+        }
+        Some(item)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.n == 0 {
+            return (0, Some(0));
+        }
+
+        let (lower, upper) = self.iter.size_hint();
+        let lower = lower.min(self.n);
+        let upper = match upper {
+            Some(x) if x < self.n => Some(x),
+            _ => Some(self.n),
+        };
+        (lower, upper)
+    }
+}
+
+#[unstable(feature = "iter_take_delayed", issue = "none")]
+impl<I, F> ExactSizeIterator for TakeDelayed<I, F>
+where
+    I: ExactSizeIterator,
+    F: FnMut(&I::Item) -> Duration,
+{
+}
+
+#[unstable(feature = "iter_take_delayed", issue = "none")]
+impl<I, F> FusedIterator for TakeDelayed<I, F>
+where
+    I: FusedIterator,
+    F: FnMut(&I::Item) -> Duration,
+{
+}
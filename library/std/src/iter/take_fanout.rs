@@ -0,0 +1,132 @@
+use crate::iter::FusedIterator;
+use crate::sync::mpsc::{SyncSender, TrySendError};
+
+/// What [`take_fanout`] does when its secondary channel is full.
+///
+/// [`take_fanout`]: super::IteratorExt::take_fanout
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[unstable(feature = "iter_take_fanout", issue = "none")]
+pub enum FanoutPolicy {
+    /// Wait for the secondary receiver to make room, so every element reaches
+    /// it. A slow secondary consumer slows down the primary one.
+    Block,
+    /// Drop the copy that does not fit and carry on, so the primary consumer
+    /// is never held up. Dropped copies are counted by
+    /// [`TakeFanout::dropped`].
+    DropNewest,
+}
+
+/// An iterator that yields at most `n` elements of `iter`, sending a copy of
+/// each to a secondary channel.
+///
+/// This `struct` is created by the [`take_fanout`] method on [`IteratorExt`].
+/// See its documentation for more.
+///
+/// [`take_fanout`]: super::IteratorExt::take_fanout
+/// [`IteratorExt`]: super::IteratorExt
+#[derive(Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[unstable(feature = "iter_take_fanout", issue = "none")]
+pub struct TakeFanout<I: Iterator> {
+    iter: I,
+    n: usize,
+    // Dropped once iteration is finished or the receiver hangs up.
+    secondary: Option<SyncSender<I::Item>>,
+    policy: FanoutPolicy,
+    dropped: usize,
+}
+
+impl<I: Iterator> TakeFanout<I> {
+    pub(super) fn new(
+        iter: I,
+        n: usize,
+        secondary: SyncSender<I::Item>,
+        policy: FanoutPolicy,
+    ) -> TakeFanout<I> {
+        TakeFanout { iter, n, secondary: Some(secondary), policy, dropped: 0 }
+    }
+
+    /// Returns the number of yielded elements whose copy did not reach the
+    /// secondary channel.
+    ///
+    /// This counts copies dropped because the channel was full under
+    /// [`FanoutPolicy::DropNewest`], and every copy after the secondary
+    /// receiver was disconnected, under either policy.
+    #[unstable(feature = "iter_take_fanout", issue = "none")]
+    pub fn dropped(&self) -> usize {
+        self.dropped
+    }
+}
+
+#[unstable(feature = "iter_take_fanout", issue = "none")]
+impl<I> Iterator for TakeFanout<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    type Item = I::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<I::Item> {
+        if self.n == 0 {
+            self.secondary = None;
+            return None;
+        }
+        let item = match self.iter.next() {
+            Some(item) => item,
+            None => {
+                self.n = 0;
+                self.secondary = None;
+                return None;
+            }
+        };
+        self.n -= 1;
+
+        if let Some(secondary) = &self.secondary {
+            let copy = item.clone();
+            let sent = match self.policy {
+                FanoutPolicy::Block => {
+                    secondary.send(copy).map_err(|e| TrySendError::Disconnected(e.0))
+                }
+                FanoutPolicy::DropNewest => secondary.try_send(copy),
+            };
+            match sent {
+                Ok(()) => {}
+                Err(TrySendError::Full(_)) => self.dropped += 1,
+                Err(TrySendError::Disconnected(_)) => {
+                    self.dropped += 1;
+                    self.secondary = None;
+                }
+            }
+        } else {
+            self.dropped += 1;
+        }
+        if self.n == 0 {
+            self.secondary = None;
+        }
+        Some(item)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.n == 0 {
+            return (0, Some(0));
+        }
+
+        let (lower, upper) = self.iter.size_hint();
+        let lower = lower.min(self.n);
+        let upper = match upper {
+            Some(x) if x < self.n => Some(x),
+            _ => Some(self.n),
+        };
+        (lower, upper)
+    }
+}
+
+#[unstable(feature = "iter_take_fanout", issue = "none")]
+impl<I> FusedIterator for TakeFanout<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+}
@@ -0,0 +1,197 @@
+use crate::convert::TryFrom;
+use crate::iter::FusedIterator;
+
+/// The encoding of the length prefix in front of each frame read by
+/// [`take_frames`].
+///
+/// [`take_frames`]: super::IteratorExt::take_frames
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[unstable(feature = "iter_take_frames", issue = "none")]
+pub enum PrefixKind {
+    /// A two-byte big-endian length.
+    U16Be,
+    /// A two-byte little-endian length.
+    U16Le,
+    /// A four-byte big-endian length.
+    U32Be,
+    /// A four-byte little-endian length.
+    U32Le,
+    /// An unsigned LEB128 variable-length integer, as used by Protocol
+    /// Buffers: seven bits per byte, least significant group first, with the
+    /// high bit set on every byte but the last. At most ten bytes are
+    /// accepted, enough for any `u64`.
+    Varint,
+}
+
+/// The longest prefix any [`PrefixKind`] can have.
+const MAX_PREFIX_LEN: usize = 10;
+
+enum Decoded {
+    NeedMore,
+    Len(u64),
+    Invalid,
+}
+
+impl PrefixKind {
+    /// The smallest number of bytes a prefix of this kind takes up.
+    fn min_len(self) -> usize {
+        match self {
+            PrefixKind::U16Be | PrefixKind::U16Le => 2,
+            PrefixKind::U32Be | PrefixKind::U32Le => 4,
+            PrefixKind::Varint => 1,
+        }
+    }
+
+    fn decode(self, bytes: &[u8]) -> Decoded {
+        if bytes.len() < self.min_len() {
+            return Decoded::NeedMore;
+        }
+        match self {
+            PrefixKind::U16Be => Decoded::Len(u16::from_be_bytes([bytes[0], bytes[1]]).into()),
+            PrefixKind::U16Le => Decoded::Len(u16::from_le_bytes([bytes[0], bytes[1]]).into()),
+            PrefixKind::U32Be => {
+                Decoded::Len(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]).into())
+            }
+            PrefixKind::U32Le => {
+                Decoded::Len(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]).into())
+            }
+            PrefixKind::Varint => {
+                let last = bytes[bytes.len() - 1];
+                if last & 0x80 != 0 {
+                    return if bytes.len() == MAX_PREFIX_LEN {
+                        Decoded::Invalid
+                    } else {
+                        Decoded::NeedMore
+                    };
+                }
+                // The tenth byte only has room for the top bit of a `u64`.
+                if bytes.len() == MAX_PREFIX_LEN && last > 1 {
+                    return Decoded::Invalid;
+                }
+                let len = bytes
+                    .iter()
+                    .enumerate()
+                    .fold(0, |len, (i, &b)| len | u64::from(b & 0x7f) << (7 * i));
+                Decoded::Len(len)
+            }
+        }
+    }
+}
+
+/// An iterator over at most `n` length-prefixed frames read from a byte
+/// iterator.
+///
+/// This `struct` is created by the [`take_frames`] method on
+/// [`IteratorExt`]. See its documentation for more.
+///
+/// [`take_frames`]: super::IteratorExt::take_frames
+/// [`IteratorExt`]: super::IteratorExt
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[unstable(feature = "iter_take_frames", issue = "none")]
+pub struct TakeFrames<I> {
+    iter: I,
+    n: usize,
+    prefix: PrefixKind,
+    remainder: Vec<u8>,
+}
+
+impl<I> TakeFrames<I> {
+    pub(super) fn new(iter: I, n: usize, prefix: PrefixKind) -> TakeFrames<I> {
+        TakeFrames { iter, n, prefix, remainder: Vec::new() }
+    }
+
+    /// Returns the bytes of a trailing incomplete frame, including its
+    /// prefix, if the source ran out in the middle of one.
+    ///
+    /// The incomplete frame is never yielded, so this is the place to look
+    /// for it when a truncated stream should be treated as an error. The
+    /// bytes of an invalid varint prefix end up here as well. The returned
+    /// slice is empty if every frame read was complete.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iter_std_ext, iter_take_frames)]
+    /// use std::iter::{IteratorExt, PrefixKind};
+    ///
+    /// let bytes = [1, b'a', 3, b'b', b'c'];
+    /// let mut frames = bytes.iter().copied().take_frames(10, PrefixKind::Varint);
+    /// assert_eq!(frames.by_ref().count(), 1);
+    /// assert_eq!(frames.remainder(), [3, b'b', b'c']);
+    /// ```
+    #[unstable(feature = "iter_take_frames", issue = "none")]
+    pub fn remainder(&self) -> &[u8] {
+        &self.remainder
+    }
+}
+
+#[unstable(feature = "iter_take_frames", issue = "none")]
+impl<I> Iterator for TakeFrames<I>
+where
+    I: Iterator<Item = u8>,
+{
+    type Item = Vec<u8>;
+
+    fn next(&mut self) -> Option<Vec<u8>> {
+        if self.n == 0 {
+            return None;
+        }
+
+        let mut prefix = [0; MAX_PREFIX_LEN];
+        let mut prefix_len = 0;
+        let len = loop {
+            match self.iter.next() {
+                Some(b) => prefix[prefix_len] = b,
+                None => {
+                    self.n = 0;
+                    self.remainder.extend_from_slice(&prefix[..prefix_len]);
+                    return None;
+                }
+            }
+            prefix_len += 1;
+            match self.prefix.decode(&prefix[..prefix_len]) {
+                Decoded::NeedMore => {}
+                Decoded::Len(len) => break len,
+                Decoded::Invalid => {
+                    self.n = 0;
+                    self.remainder.extend_from_slice(&prefix[..prefix_len]);
+                    return None;
+                }
+            }
+        };
+
+        // A length that doesn't fit in a `usize` can't be read in full, so
+        // just read until the source runs out. The frame isn't allocated up
+        // front, so a bogus length doesn't cause a huge allocation by itself.
+        let len = usize::try_from(len).unwrap_or(usize::MAX);
+        let mut frame = Vec::new();
+        frame.extend(self.iter.by_ref().take(len));
+        if frame.len() < len {
+            self.n = 0;
+            self.remainder.extend_from_slice(&prefix[..prefix_len]);
+            self.remainder.append(&mut frame);
+            return None;
+        }
+
+        self.n -= 1;
+        Some(frame)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.n == 0 {
+            return (0, Some(0));
+        }
+
+        // Even an empty frame takes up the bytes of its prefix.
+        let upper = match self.iter.size_hint().1 {
+            Some(x) if x / self.prefix.min_len() < self.n => Some(x / self.prefix.min_len()),
+            _ => Some(self.n),
+        };
+        (0, upper)
+    }
+}
+
+#[unstable(feature = "iter_take_frames", issue = "none")]
+impl<I> FusedIterator for TakeFrames<I> where I: Iterator<Item = u8> {}
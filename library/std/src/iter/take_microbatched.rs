@@ -0,0 +1,93 @@
+use crate::iter::FusedIterator;
+use crate::time::{Duration, Instant};
+
+/// An iterator that groups at most `n` elements of `iter` into batches,
+/// flushed by size or by age.
+///
+/// This `struct` is created by the [`take_microbatched`] method on
+/// [`IteratorExt`]. See its documentation for more.
+///
+/// [`take_microbatched`]: super::IteratorExt::take_microbatched
+/// [`IteratorExt`]: super::IteratorExt
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[unstable(feature = "iter_take_microbatched", issue = "none")]
+pub struct TakeMicrobatched<I> {
+    iter: I,
+    n: usize,
+    batch_size: usize,
+    max_delay: Duration,
+}
+
+impl<I> TakeMicrobatched<I> {
+    pub(super) fn new(
+        iter: I,
+        n: usize,
+        batch_size: usize,
+        max_delay: Duration,
+    ) -> TakeMicrobatched<I> {
+        assert!(batch_size != 0, "batch size must be non-zero");
+        TakeMicrobatched { iter, n, batch_size, max_delay }
+    }
+}
+
+#[unstable(feature = "iter_take_microbatched", issue = "none")]
+impl<I: Iterator> Iterator for TakeMicrobatched<I> {
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Vec<I::Item>> {
+        if self.n == 0 {
+            return None;
+        }
+        let first = match self.iter.next() {
+            Some(item) => item,
+            None => {
+                self.n = 0;
+                return None;
+            }
+        };
+        // The batch starts with its first element, not when it was asked for,
+        // so waiting on an idle source doesn't count against its delay.
+        let start = Instant::now();
+        // Only reserve room for elements the source says it has, as the caps
+        // may be far larger than any batch will ever be.
+        let expected = self.iter.size_hint().0.saturating_add(1);
+        let mut batch = Vec::with_capacity(self.batch_size.min(self.n).min(expected));
+        batch.push(first);
+        self.n -= 1;
+
+        while self.n > 0 && batch.len() < self.batch_size && start.elapsed() < self.max_delay {
+            match self.iter.next() {
+                Some(item) => {
+                    batch.push(item);
+                    self.n -= 1;
+                }
+                None => {
+                    self.n = 0;
+                    break;
+                }
+            }
+        }
+        Some(batch)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.n == 0 {
+            return (0, Some(0));
+        }
+
+        // Any element starts a batch, and a batch may be flushed by its delay
+        // after any number of elements, down to a single one.
+        let (lower, upper) = self.iter.size_hint();
+        let lower = if lower > 0 { 1 } else { 0 };
+        let upper = match upper {
+            Some(x) if x < self.n => Some(x),
+            _ => Some(self.n),
+        };
+        (lower, upper)
+    }
+}
+
+#[unstable(feature = "iter_take_microbatched", issue = "none")]
+impl<I: FusedIterator> FusedIterator for TakeMicrobatched<I> {}
@@ -0,0 +1,67 @@
+use crate::iter::FusedIterator;
+use crate::sync::{Arc, Semaphore};
+
+/// An iterator that yields at most `n` elements of `iter`, taking a permit
+/// from a semaphore for each of them.
+///
+/// This `struct` is created by the [`take_permitted`] method on
+/// [`IteratorExt`]. See its documentation for more.
+///
+/// [`take_permitted`]: super::IteratorExt::take_permitted
+/// [`IteratorExt`]: super::IteratorExt
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[unstable(feature = "iter_take_permitted", issue = "none")]
+pub struct TakePermitted<I> {
+    iter: I,
+    n: usize,
+    sem: Arc<Semaphore>,
+}
+
+impl<I> TakePermitted<I> {
+    pub(super) fn new(iter: I, n: usize, sem: Arc<Semaphore>) -> TakePermitted<I> {
+        TakePermitted { iter, n, sem }
+    }
+}
+
+#[unstable(feature = "iter_take_permitted", issue = "none")]
+impl<I: Iterator> Iterator for TakePermitted<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        if self.n == 0 {
+            return None;
+        }
+        self.sem.acquire();
+        match self.iter.next() {
+            Some(item) => {
+                self.n -= 1;
+                Some(item)
+            }
+            None => {
+                // Nothing to hand the permit over with.
+                self.sem.release();
+                self.n = 0;
+                None
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.n == 0 {
+            return (0, Some(0));
+        }
+
+        let (lower, upper) = self.iter.size_hint();
+        let lower = lower.min(self.n);
+        let upper = match upper {
+            Some(x) if x < self.n => Some(x),
+            _ => Some(self.n),
+        };
+        (lower, upper)
+    }
+}
+
+#[unstable(feature = "iter_take_permitted", issue = "none")]
+impl<I: FusedIterator> FusedIterator for TakePermitted<I> {}
@@ -0,0 +1,83 @@
+use crate::iter::FusedIterator;
+use crate::panic;
+use crate::sync::mpsc::{sync_channel, Receiver};
+use crate::thread::{self, JoinHandle};
+
+/// An iterator that yields at most `n` elements of another iterator, which
+/// a background thread pulls ahead of time.
+///
+/// This `struct` is created by the [`take_prefetched`] method on
+/// [`IteratorExt`]. See its documentation for more.
+///
+/// [`take_prefetched`]: super::IteratorExt::take_prefetched
+/// [`IteratorExt`]: super::IteratorExt
+#[derive(Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[unstable(feature = "iter_take_prefetched", issue = "none")]
+pub struct TakePrefetched<T> {
+    // Both are dropped once the worker is done, which makes this fused.
+    rx: Option<Receiver<T>>,
+    worker: Option<JoinHandle<()>>,
+    n: usize,
+}
+
+impl<T: Send + 'static> TakePrefetched<T> {
+    pub(super) fn new<I>(iter: I, n: usize, depth: usize) -> TakePrefetched<T>
+    where
+        I: Iterator<Item = T> + Send + 'static,
+    {
+        let (tx, rx) = sync_channel(depth);
+        let worker = thread::spawn(move || {
+            for item in iter.take(n) {
+                // The receiver is gone, so nobody wants the rest.
+                if tx.send(item).is_err() {
+                    break;
+                }
+            }
+        });
+        TakePrefetched { rx: Some(rx), worker: Some(worker), n }
+    }
+
+    fn finish(&mut self) {
+        self.n = 0;
+        self.rx = None;
+        if let Some(worker) = self.worker.take() {
+            if let Err(payload) = worker.join() {
+                panic::resume_unwind(payload);
+            }
+        }
+    }
+}
+
+#[unstable(feature = "iter_take_prefetched", issue = "none")]
+impl<T: Send + 'static> Iterator for TakePrefetched<T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        let item = self.rx.as_ref()?.recv().ok();
+        match item {
+            Some(item) => {
+                self.n -= 1;
+                if self.n == 0 {
+                    self.finish();
+                }
+                Some(item)
+            }
+            // The worker hung up, either because the underlying iterator ran
+            // out or because it panicked.
+            None => {
+                self.finish();
+                None
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.n))
+    }
+}
+
+#[unstable(feature = "iter_take_prefetched", issue = "none")]
+impl<T: Send + 'static> FusedIterator for TakePrefetched<T> {}
@@ -0,0 +1,115 @@
+use crate::iter::{Cloned, FusedIterator};
+use crate::slice;
+
+/// An iterator that yields at most `n` elements of `iter`, keeping a copy of
+/// each so the sequence can be replayed.
+///
+/// This `struct` is created by the [`take_recording`] method on
+/// [`IteratorExt`]. See its documentation for more.
+///
+/// [`take_recording`]: super::IteratorExt::take_recording
+/// [`IteratorExt`]: super::IteratorExt
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[unstable(feature = "iter_take_recording", issue = "none")]
+pub struct TakeRecording<I: Iterator> {
+    iter: I,
+    n: usize,
+    recorded: Vec<I::Item>,
+}
+
+impl<I: Iterator> TakeRecording<I> {
+    pub(super) fn new(iter: I, n: usize) -> TakeRecording<I> {
+        TakeRecording { iter, n, recorded: Vec::new() }
+    }
+
+    /// Returns a fresh iterator over clones of the elements yielded so far,
+    /// in the order they were yielded.
+    ///
+    /// This can be called any number of times, including while iteration is
+    /// still in progress, in which case only the elements yielded up to that
+    /// point are replayed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iter_std_ext, iter_take_recording)]
+    /// use std::iter::IteratorExt;
+    ///
+    /// let mut it = "abc".chars().take_recording(2);
+    /// assert_eq!(it.next(), Some('a'));
+    /// assert_eq!(it.replay().collect::<String>(), "a");
+    /// assert_eq!(it.next(), Some('b'));
+    /// assert_eq!(it.replay().collect::<String>(), "ab");
+    /// ```
+    #[unstable(feature = "iter_take_recording", issue = "none")]
+    pub fn replay(&self) -> Cloned<slice::Iter<'_, I::Item>>
+    where
+        I::Item: Clone,
+    {
+        self.recorded.iter().cloned()
+    }
+
+    /// Returns the elements yielded so far, in the order they were yielded.
+    #[unstable(feature = "iter_take_recording", issue = "none")]
+    pub fn recorded(&self) -> &[I::Item] {
+        &self.recorded
+    }
+
+    /// Consumes the adapter, returning the elements yielded so far.
+    #[unstable(feature = "iter_take_recording", issue = "none")]
+    pub fn into_recorded(self) -> Vec<I::Item> {
+        self.recorded
+    }
+}
+
+#[unstable(feature = "iter_take_recording", issue = "none")]
+impl<I> Iterator for TakeRecording<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    type Item = I::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<I::Item> {
+        if self.n == 0 {
+            return None;
+        }
+        let item = self.iter.next()?;
+        self.n -= 1;
+        self.recorded.push(item.clone());
+        Some(item)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.n == 0 {
+            return (0, Some(0));
+        }
+
+        let (lower, upper) = self.iter.size_hint();
+        let lower = lower.min(self.n);
+        let upper = match upper {
+            Some(x) if x < self.n => Some(x),
+            _ => Some(self.n),
+        };
+        (lower, upper)
+    }
+}
+
+#[unstable(feature = "iter_take_recording", issue = "none")]
+impl<I> ExactSizeIterator for TakeRecording<I>
+where
+    I: ExactSizeIterator,
+    I::Item: Clone,
+{
+}
+
+#[unstable(feature = "iter_take_recording", issue = "none")]
+impl<I> FusedIterator for TakeRecording<I>
+where
+    I: FusedIterator,
+    I::Item: Clone,
+{
+}
@@ -0,0 +1,81 @@
+use crate::iter::FusedIterator;
+use crate::sync::mpsc::Sender;
+
+/// An iterator that yields at most `n` elements of `iter`, reporting its
+/// progress over a channel.
+///
+/// This `struct` is created by the [`take_reporting`] method on
+/// [`IteratorExt`]. See its documentation for more.
+///
+/// [`take_reporting`]: super::IteratorExt::take_reporting
+/// [`IteratorExt`]: super::IteratorExt
+#[derive(Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[unstable(feature = "iter_take_reporting", issue = "none")]
+pub struct TakeReporting<I> {
+    iter: I,
+    n: usize,
+    done: usize,
+    total: usize,
+    // Dropped as soon as iteration is finished, which disconnects the receiver.
+    tx: Option<Sender<(usize, usize)>>,
+}
+
+impl<I: Iterator> TakeReporting<I> {
+    pub(super) fn new(iter: I, n: usize, tx: Sender<(usize, usize)>) -> TakeReporting<I> {
+        let total = match iter.size_hint() {
+            (_, Some(upper)) if upper < n => upper,
+            _ => n,
+        };
+        TakeReporting { iter, n, done: 0, total, tx: Some(tx) }
+    }
+}
+
+#[unstable(feature = "iter_take_reporting", issue = "none")]
+impl<I: Iterator> Iterator for TakeReporting<I> {
+    type Item = I::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<I::Item> {
+        if self.done < self.n {
+            if let Some(item) = self.iter.next() {
+                self.done += 1;
+                if let Some(tx) = &self.tx {
+                    // A disconnected receiver just means nobody is watching.
+                    let _ = tx.send((self.done, self.total));
+                }
+                if self.done == self.n {
+                    self.tx = None;
+                }
+                return Some(item);
+            }
+        }
+
+        if let Some(tx) = self.tx.take() {
+            // Skip the final update if the last one already said the same thing.
+            if self.done != self.total || self.done == 0 {
+                let _ = tx.send((self.done, self.done));
+            }
+        }
+        None
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.n - self.done;
+        if n == 0 {
+            return (0, Some(0));
+        }
+
+        let (lower, upper) = self.iter.size_hint();
+        let lower = crate::cmp::min(lower, n);
+        let upper = match upper {
+            Some(x) if x < n => Some(x),
+            _ => Some(n),
+        };
+        (lower, upper)
+    }
+}
+
+#[unstable(feature = "iter_take_reporting", issue = "none")]
+impl<I: FusedIterator> FusedIterator for TakeReporting<I> {}
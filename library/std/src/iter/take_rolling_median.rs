@@ -0,0 +1,176 @@
+use crate::collections::{BTreeMap, VecDeque};
+use crate::iter::FusedIterator;
+
+/// A multiset of elements, stored as a count per distinct value, so that the
+/// smallest and largest elements can be found, added and removed in
+/// logarithmic time.
+#[derive(Clone, Debug)]
+struct Half<T> {
+    counts: BTreeMap<T, usize>,
+    len: usize,
+}
+
+impl<T: Ord + Clone> Half<T> {
+    fn new() -> Half<T> {
+        Half { counts: BTreeMap::new(), len: 0 }
+    }
+
+    fn insert(&mut self, item: T) {
+        *self.counts.entry(item).or_insert(0) += 1;
+        self.len += 1;
+    }
+
+    fn remove(&mut self, item: &T) {
+        let count = self.counts.get_mut(item).expect("removed element is missing");
+        *count -= 1;
+        if *count == 0 {
+            self.counts.remove(item);
+        }
+        self.len -= 1;
+    }
+
+    fn first(&self) -> Option<&T> {
+        self.counts.keys().next()
+    }
+
+    fn last(&self) -> Option<&T> {
+        self.counts.keys().next_back()
+    }
+
+    fn pop_first(&mut self) -> T {
+        let item = self.first().unwrap().clone();
+        self.remove(&item);
+        item
+    }
+
+    fn pop_last(&mut self) -> T {
+        let item = self.last().unwrap().clone();
+        self.remove(&item);
+        item
+    }
+}
+
+/// An iterator that yields the median of a sliding window over at most `n`
+/// elements of `iter`.
+///
+/// This `struct` is created by the [`take_rolling_median`] method on
+/// [`IteratorExt`]. See its documentation for more.
+///
+/// [`take_rolling_median`]: super::IteratorExt::take_rolling_median
+/// [`IteratorExt`]: super::IteratorExt
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[unstable(feature = "iter_take_rolling_median", issue = "none")]
+pub struct TakeRollingMedian<I: Iterator> {
+    iter: I,
+    n: usize,
+    size: usize,
+    // The elements in the window, oldest first.
+    window: VecDeque<I::Item>,
+    // The window split in two: every element of `lower` is at most every
+    // element of `upper`, and `lower` holds the extra element when the
+    // window has an odd length. The median is the largest element of `lower`.
+    lower: Half<I::Item>,
+    upper: Half<I::Item>,
+}
+
+impl<I: Iterator> TakeRollingMedian<I>
+where
+    I::Item: Ord + Clone,
+{
+    pub(super) fn new(iter: I, n: usize, size: usize) -> TakeRollingMedian<I> {
+        assert!(size != 0, "window size must be non-zero");
+        TakeRollingMedian {
+            iter,
+            n,
+            size,
+            window: VecDeque::new(),
+            lower: Half::new(),
+            upper: Half::new(),
+        }
+    }
+
+    fn insert(&mut self, item: I::Item) {
+        match self.lower.last() {
+            Some(max) if item > *max => self.upper.insert(item),
+            _ => self.lower.insert(item),
+        }
+        self.rebalance();
+    }
+
+    fn remove(&mut self, item: &I::Item) {
+        // Equal elements may sit on both sides of the split, but they are
+        // interchangeable, so removing any one of them will do.
+        match self.lower.last() {
+            Some(max) if *item <= *max => self.lower.remove(item),
+            _ => self.upper.remove(item),
+        }
+        self.rebalance();
+    }
+
+    fn rebalance(&mut self) {
+        if self.lower.len > self.upper.len + 1 {
+            let item = self.lower.pop_last();
+            self.upper.insert(item);
+        } else if self.upper.len > self.lower.len {
+            let item = self.upper.pop_first();
+            self.lower.insert(item);
+        }
+    }
+}
+
+#[unstable(feature = "iter_take_rolling_median", issue = "none")]
+impl<I> Iterator for TakeRollingMedian<I>
+where
+    I: Iterator,
+    I::Item: Ord + Clone,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        if self.n == 0 {
+            return None;
+        }
+        let item = self.iter.next()?;
+        self.n -= 1;
+
+        if self.window.len() == self.size {
+            let oldest = self.window.pop_front().unwrap();
+            self.remove(&oldest);
+        }
+        self.window.push_back(item.clone());
+        self.insert(item);
+        self.lower.last().cloned()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.n == 0 {
+            return (0, Some(0));
+        }
+
+        let (lower, upper) = self.iter.size_hint();
+        let lower = lower.min(self.n);
+        let upper = match upper {
+            Some(x) if x < self.n => Some(x),
+            _ => Some(self.n),
+        };
+        (lower, upper)
+    }
+}
+
+#[unstable(feature = "iter_take_rolling_median", issue = "none")]
+impl<I> ExactSizeIterator for TakeRollingMedian<I>
+where
+    I: ExactSizeIterator,
+    I::Item: Ord + Clone,
+{
+}
+
+#[unstable(feature = "iter_take_rolling_median", issue = "none")]
+impl<I> FusedIterator for TakeRollingMedian<I>
+where
+    I: FusedIterator,
+    I::Item: Ord + Clone,
+{
+}
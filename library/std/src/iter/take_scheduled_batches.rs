@@ -0,0 +1,78 @@
+use crate::cmp;
+use crate::iter::FusedIterator;
+
+/// An iterator that groups at most `n` elements of `iter` into batches whose
+/// sizes follow a schedule.
+///
+/// This `struct` is created by the [`take_scheduled_batches`] method on
+/// [`IteratorExt`]. See its documentation for more.
+///
+/// [`take_scheduled_batches`]: super::IteratorExt::take_scheduled_batches
+/// [`IteratorExt`]: super::IteratorExt
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[unstable(feature = "iter_take_scheduled_batches", issue = "none")]
+pub struct TakeScheduledBatches<I, S> {
+    iter: I,
+    n: usize,
+    sizes: S,
+}
+
+impl<I, S> TakeScheduledBatches<I, S> {
+    pub(super) fn new(iter: I, n: usize, sizes: S) -> TakeScheduledBatches<I, S> {
+        TakeScheduledBatches { iter, n, sizes }
+    }
+}
+
+#[unstable(feature = "iter_take_scheduled_batches", issue = "none")]
+impl<I: Iterator, S: Iterator<Item = usize>> Iterator for TakeScheduledBatches<I, S> {
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Vec<I::Item>> {
+        if self.n == 0 {
+            return None;
+        }
+        let size = match self.sizes.find(|&size| size != 0) {
+            Some(size) => cmp::min(size, self.n),
+            None => {
+                self.n = 0;
+                return None;
+            }
+        };
+
+        // A schedule may ask for far more than the source has to give, so
+        // only reserve room for what it says it has.
+        let mut batch = Vec::with_capacity(cmp::min(size, self.iter.size_hint().0));
+        batch.extend(self.iter.by_ref().take(size));
+        if batch.len() < size {
+            // The underlying iterator ran out.
+            self.n = 0;
+        } else {
+            self.n -= size;
+        }
+        if batch.is_empty() { None } else { Some(batch) }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.n == 0 {
+            return (0, Some(0));
+        }
+
+        // The schedule may run out before any batch is yielded, and every
+        // batch holds at least one element and uses up one size.
+        let (_, upper) = self.iter.size_hint();
+        let upper = match upper {
+            Some(x) if x < self.n => x,
+            _ => self.n,
+        };
+        let upper = match self.sizes.size_hint() {
+            (_, Some(x)) => cmp::min(x, upper),
+            (_, None) => upper,
+        };
+        (0, Some(upper))
+    }
+}
+
+#[unstable(feature = "iter_take_scheduled_batches", issue = "none")]
+impl<I: FusedIterator, S: Iterator<Item = usize>> FusedIterator for TakeScheduledBatches<I, S> {}
@@ -0,0 +1,140 @@
+use crate::cmp::Reverse;
+use crate::collections::BinaryHeap;
+use crate::iter::FusedIterator;
+use crate::vec;
+
+/// An iterator that yields the `n` smallest elements of `iter` in ascending
+/// order.
+///
+/// This `struct` is created by the [`take_smallest`] method on
+/// [`IteratorExt`]. See its documentation for more.
+///
+/// [`take_smallest`]: super::IteratorExt::take_smallest
+/// [`IteratorExt`]: super::IteratorExt
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[unstable(feature = "iter_take_sorted", issue = "none")]
+pub struct TakeSmallest<I: Iterator> {
+    state: State<I, I::Item>,
+}
+
+/// An iterator that yields the `n` largest elements of `iter` in descending
+/// order.
+///
+/// This `struct` is created by the [`take_largest`] method on
+/// [`IteratorExt`]. See its documentation for more.
+///
+/// [`take_largest`]: super::IteratorExt::take_largest
+/// [`IteratorExt`]: super::IteratorExt
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[unstable(feature = "iter_take_sorted", issue = "none")]
+pub struct TakeLargest<I: Iterator> {
+    state: State<I, Reverse<I::Item>>,
+}
+
+#[derive(Clone, Debug)]
+enum State<I, T> {
+    /// The source has not been consumed yet.
+    Pending { iter: I, n: usize },
+    /// The selected elements, in the order they are yielded.
+    Sorted(vec::IntoIter<T>),
+}
+
+impl<I: Iterator> TakeSmallest<I> {
+    pub(super) fn new(iter: I, n: usize) -> TakeSmallest<I> {
+        TakeSmallest { state: State::Pending { iter, n } }
+    }
+}
+
+impl<I: Iterator> TakeLargest<I> {
+    pub(super) fn new(iter: I, n: usize) -> TakeLargest<I> {
+        TakeLargest { state: State::Pending { iter, n } }
+    }
+}
+
+impl<I: Iterator, T: Ord> State<I, T> {
+    /// Consumes the source on first use, keeping the `n` smallest `T`s that
+    /// `f` maps its elements to.
+    fn sorted(&mut self, f: impl FnMut(I::Item) -> T) -> &mut vec::IntoIter<T> {
+        if let State::Pending { iter, n } = self {
+            let n = *n;
+            // A max-heap of the `n` smallest values seen so far: anything that
+            // is not smaller than its top can never make the cut.
+            let mut heap = BinaryHeap::new();
+            // Like `take(0)`, leave the source alone when nothing is wanted.
+            if n > 0 {
+                for x in iter.map(f) {
+                    if heap.len() < n {
+                        heap.push(x);
+                    } else if let Some(mut top) = heap.peek_mut() {
+                        if x < *top {
+                            *top = x;
+                        }
+                    }
+                }
+            }
+            *self = State::Sorted(heap.into_sorted_vec().into_iter());
+        }
+        match self {
+            State::Sorted(sorted) => sorted,
+            State::Pending { .. } => unreachable!(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            State::Pending { iter, n } => {
+                let (lower, upper) = iter.size_hint();
+                let upper = match upper {
+                    Some(upper) => upper.min(*n),
+                    None => *n,
+                };
+                (lower.min(*n), Some(upper))
+            }
+            State::Sorted(sorted) => sorted.size_hint(),
+        }
+    }
+}
+
+#[unstable(feature = "iter_take_sorted", issue = "none")]
+impl<I: Iterator> Iterator for TakeSmallest<I>
+where
+    I::Item: Ord,
+{
+    type Item = I::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<I::Item> {
+        self.state.sorted(|x| x).next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.state.size_hint()
+    }
+}
+
+#[unstable(feature = "iter_take_sorted", issue = "none")]
+impl<I: Iterator> Iterator for TakeLargest<I>
+where
+    I::Item: Ord,
+{
+    type Item = I::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<I::Item> {
+        self.state.sorted(Reverse).next().map(|Reverse(x)| x)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.state.size_hint()
+    }
+}
+
+#[unstable(feature = "iter_take_sorted", issue = "none")]
+impl<I: Iterator> FusedIterator for TakeSmallest<I> where I::Item: Ord {}
+
+#[unstable(feature = "iter_take_sorted", issue = "none")]
+impl<I: Iterator> FusedIterator for TakeLargest<I> where I::Item: Ord {}
@@ -0,0 +1,138 @@
+use crate::io::{self, BufWriter, Write};
+use crate::iter::FusedIterator;
+
+/// An iterator that yields at most `n` bytes of `iter`, writing a copy of
+/// each to a sink.
+///
+/// This `struct` is created by the [`take_tee_write`] method on
+/// [`IteratorExt`]. See its documentation for more.
+///
+/// [`take_tee_write`]: super::IteratorExt::take_tee_write
+/// [`IteratorExt`]: super::IteratorExt
+#[derive(Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[unstable(feature = "iter_take_tee_write", issue = "none")]
+pub struct TakeTeeWrite<I, W: Write> {
+    iter: I,
+    n: usize,
+    sink: BufWriter<W>,
+    error: Option<io::Error>,
+}
+
+impl<I, W: Write> TakeTeeWrite<I, W> {
+    pub(super) fn new(iter: I, n: usize, sink: W) -> TakeTeeWrite<I, W> {
+        TakeTeeWrite { iter, n, sink: BufWriter::new(sink), error: None }
+    }
+
+    /// Returns the first error hit while writing to the sink, if any.
+    ///
+    /// Once a write has failed, nothing more is written to the sink, but the
+    /// iterator keeps yielding bytes as usual. The sink is flushed when the
+    /// iterator reaches its limit or runs out of bytes, so an error from that
+    /// final flush shows up here only after the last call to [`next`].
+    ///
+    /// [`next`]: Iterator::next
+    #[unstable(feature = "iter_take_tee_write", issue = "none")]
+    pub fn error(&self) -> Option<&io::Error> {
+        self.error.as_ref()
+    }
+
+    /// Flushes the sink and returns it.
+    ///
+    /// If any write to the sink failed, the first error is returned instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iter_std_ext, iter_take_tee_write)]
+    /// use std::iter::IteratorExt;
+    ///
+    /// let mut it = b"hello world".iter().copied().take_tee_write(5, Vec::new());
+    /// it.next();
+    /// it.next();
+    /// assert_eq!(it.into_sink().unwrap(), b"he");
+    /// ```
+    #[unstable(feature = "iter_take_tee_write", issue = "none")]
+    pub fn into_sink(self) -> io::Result<W> {
+        if let Some(e) = self.error {
+            return Err(e);
+        }
+        self.sink.into_inner().map_err(io::Error::from)
+    }
+
+    fn write(&mut self, byte: u8) {
+        if self.error.is_none() {
+            self.error = self.sink.write_all(&[byte]).err();
+        }
+    }
+
+    fn flush(&mut self) {
+        if self.error.is_none() {
+            self.error = self.sink.flush().err();
+        }
+    }
+}
+
+#[unstable(feature = "iter_take_tee_write", issue = "none")]
+impl<I, W> Iterator for TakeTeeWrite<I, W>
+where
+    I: Iterator<Item = u8>,
+    W: Write,
+{
+    type Item = u8;
+
+    #[inline]
+    fn next(&mut self) -> Option<u8> {
+        if self.n == 0 {
+            return None;
+        }
+        match self.iter.next() {
+            Some(byte) => {
+                self.n -= 1;
+                self.write(byte);
+                if self.n == 0 {
+                    self.flush();
+                }
+                Some(byte)
+            }
+            None => {
+                self.n = 0;
+                self.flush();
+                None
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.n == 0 {
+            return (0, Some(0));
+        }
+
+        let (lower, upper) = self.iter.size_hint();
+        let lower = lower.min(self.n);
+        let upper = match upper {
+            Some(x) if x < self.n => Some(x),
+            _ => Some(self.n),
+        };
+        (lower, upper)
+    }
+}
+
+#[unstable(feature = "iter_take_tee_write", issue = "none")]
+impl<I, W> ExactSizeIterator for TakeTeeWrite<I, W>
+where
+    I: ExactSizeIterator<Item = u8>,
+    W: Write,
+{
+}
+
+// The limit drops to zero when the underlying iterator runs out, so this is
+// fused whether or not `I` is.
+#[unstable(feature = "iter_take_tee_write", issue = "none")]
+impl<I, W> FusedIterator for TakeTeeWrite<I, W>
+where
+    I: Iterator<Item = u8>,
+    W: Write,
+{
+}
@@ -0,0 +1,64 @@
+use crate::iter::FusedIterator;
+use crate::time::{Duration, Instant};
+
+/// An iterator that yields at most `n` elements of `iter` within a total time
+/// budget.
+///
+/// This `struct` is created by the [`take_within`] method on [`IteratorExt`].
+/// See its documentation for more.
+///
+/// [`take_within`]: super::IteratorExt::take_within
+/// [`IteratorExt`]: super::IteratorExt
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[unstable(feature = "iter_take_within", issue = "none")]
+pub struct TakeWithin<I> {
+    iter: I,
+    n: usize,
+    budget: Duration,
+    start: Option<Instant>,
+}
+
+impl<I> TakeWithin<I> {
+    pub(super) fn new(iter: I, n: usize, budget: Duration) -> TakeWithin<I> {
+        TakeWithin { iter, n, budget, start: None }
+    }
+}
+
+#[unstable(feature = "iter_take_within", issue = "none")]
+impl<I: Iterator> Iterator for TakeWithin<I> {
+    type Item = I::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<I::Item> {
+        if self.n == 0 {
+            return None;
+        }
+        let start = *self.start.get_or_insert_with(Instant::now);
+        if start.elapsed() >= self.budget {
+            self.n = 0;
+            return None;
+        }
+        let item = self.iter.next()?;
+        self.n -= 1;
+        Some(item)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.n == 0 {
+            return (0, Some(0));
+        }
+
+        // The budget may run out at any point, so nothing is guaranteed.
+        let (_, upper) = self.iter.size_hint();
+        let upper = match upper {
+            Some(x) if x < self.n => Some(x),
+            _ => Some(self.n),
+        };
+        (0, upper)
+    }
+}
+
+#[unstable(feature = "iter_take_within", issue = "none")]
+impl<I: FusedIterator> FusedIterator for TakeWithin<I> {}
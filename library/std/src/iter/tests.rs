@@ -0,0 +1,704 @@
+use super::{FanoutPolicy, IteratorExt, PrefixKind, StopReason};
+use crate::borrow::Cow;
+use crate::sync::mpsc::{channel, sync_channel};
+use crate::thread;
+use crate::time::{Duration, Instant};
+
+#[test]
+fn take_within_stops_after_n() {
+    let v: Vec<_> = (0..).take_within(5, Duration::from_secs(60)).collect();
+    assert_eq!(v, [0, 1, 2, 3, 4]);
+
+    let v: Vec<_> = (0..3).take_within(5, Duration::from_secs(60)).collect();
+    assert_eq!(v, [0, 1, 2]);
+}
+
+#[test]
+fn take_within_stops_after_budget() {
+    let slow = (0..).inspect(|_| thread::sleep(Duration::from_millis(10)));
+
+    let start = Instant::now();
+    let taken = slow.take_within(1000, Duration::from_millis(50)).count();
+    let elapsed = start.elapsed();
+
+    assert!(taken >= 1, "took {} items", taken);
+    assert!(taken < 1000, "took {} items", taken);
+    // Generous bound: sleeps can overshoot on a loaded machine.
+    assert!(elapsed < Duration::from_secs(5), "took {:?}", elapsed);
+}
+
+#[test]
+fn take_within_zero_budget() {
+    let mut it = (0..).take_within(5, Duration::ZERO);
+    assert_eq!(it.next(), None);
+    assert_eq!(it.size_hint(), (0, Some(0)));
+}
+
+#[test]
+fn take_within_size_hint() {
+    let it = (0..10).take_within(5, Duration::from_secs(60));
+    assert_eq!(it.size_hint(), (0, Some(5)));
+    let it = (0..3).take_within(5, Duration::from_secs(60));
+    assert_eq!(it.size_hint(), (0, Some(3)));
+}
+
+#[test]
+fn take_reporting_progress() {
+    let (tx, rx) = channel();
+    let v: Vec<_> = (0..10).take_reporting(4, tx).collect();
+    assert_eq!(v, [0, 1, 2, 3]);
+    let progress: Vec<_> = rx.iter().collect();
+    assert_eq!(progress, [(1, 4), (2, 4), (3, 4), (4, 4)]);
+
+    // A source known to be shorter than `n` sets the total.
+    let (tx, rx) = channel();
+    assert_eq!((0..3).take_reporting(5, tx).count(), 3);
+    let progress: Vec<_> = rx.iter().collect();
+    assert_eq!(progress, [(1, 3), (2, 3), (3, 3)]);
+}
+
+#[test]
+fn take_reporting_final_update() {
+    // Running dry before the expected total sends a closing update.
+    let (tx, rx) = channel();
+    assert_eq!((0..4).filter(|x| x % 2 == 0).take_reporting(5, tx).count(), 2);
+    let progress: Vec<_> = rx.iter().collect();
+    assert_eq!(progress, [(1, 4), (2, 4), (2, 2)]);
+
+    let (tx, rx) = channel();
+    assert_eq!(crate::iter::empty::<i32>().take_reporting(5, tx).count(), 0);
+    let progress: Vec<_> = rx.iter().collect();
+    assert_eq!(progress, [(0, 0)]);
+}
+
+#[test]
+fn take_reporting_receiver_dropped() {
+    let (tx, rx) = channel();
+    drop(rx);
+    assert_eq!((0..10).take_reporting(4, tx).count(), 4);
+}
+
+#[test]
+fn take_cow_borrows_from_slice() {
+    let v = [String::from("a"), String::from("b"), String::from("c")];
+    let mut it = v.iter().take_cow(2);
+    assert_eq!(it.len(), 2);
+    match it.next() {
+        Some(Cow::Borrowed(s)) => assert!(crate::ptr::eq(s, &v[0])),
+        other => panic!("expected a borrowed element, got {:?}", other),
+    }
+    let owned = it.next().unwrap().into_owned();
+    assert_eq!(owned, "b");
+    assert_eq!(it.next(), None);
+
+    let back: Vec<_> = v.iter().take_cow(2).rev().collect();
+    assert_eq!(back, [Cow::Borrowed(&v[1]), Cow::Borrowed(&v[0])]);
+    assert!(back.iter().all(|c| matches!(c, Cow::Borrowed(_))));
+}
+
+#[test]
+fn take_cow_unsized() {
+    let words = ["x", "yy", "zzz"];
+    let v: Vec<Cow<'_, str>> = words.iter().copied().take_cow(5).collect();
+    assert_eq!(v, ["x", "yy", "zzz"]);
+}
+
+#[test]
+fn take_smallest_selects_by_value() {
+    let v = [42, 7, 19, 3, 88, 7, 61, 0, 25, 14];
+    let mut sorted = v.to_vec();
+    sorted.sort();
+    for n in 0..=v.len() + 2 {
+        let smallest: Vec<_> = v.iter().copied().take_smallest(n).collect();
+        assert_eq!(smallest, sorted[..n.min(v.len())]);
+    }
+
+    let mut it = v.iter().copied().take_smallest(3);
+    assert_eq!(it.size_hint(), (3, Some(3)));
+    assert_eq!(it.next(), Some(0));
+    assert_eq!(it.size_hint(), (2, Some(2)));
+
+    // Like `take(0)`, asking for nothing leaves the source alone.
+    let mut source = v.iter().copied();
+    assert_eq!(source.by_ref().take_smallest(0).next(), None);
+    assert_eq!(source.by_ref().take_largest(0).next(), None);
+    assert_eq!(source.next(), Some(42));
+}
+
+#[test]
+fn take_largest_selects_by_value() {
+    let v = [42, 7, 19, 3, 88, 7, 61, 0, 25, 14];
+    let mut sorted = v.to_vec();
+    sorted.sort_by(|a, b| b.cmp(a));
+    for n in 0..=v.len() + 2 {
+        let largest: Vec<_> = v.iter().copied().take_largest(n).collect();
+        assert_eq!(largest, sorted[..n.min(v.len())]);
+    }
+
+    let words = ["pear", "fig", "apple", "kiwi"];
+    let top: Vec<_> = words.iter().take_largest(2).collect();
+    assert_eq!(top, [&"pear", &"kiwi"]);
+}
+
+#[test]
+fn take_fanout_block() {
+    let (tx, rx) = sync_channel(1);
+    let secondary = thread::spawn(move || rx.iter().collect::<Vec<_>>());
+
+    let mut it = (0..100).take_fanout(20, tx, FanoutPolicy::Block);
+    let primary: Vec<_> = it.by_ref().collect();
+    assert_eq!(primary, (0..20).collect::<Vec<_>>());
+    assert_eq!(it.dropped(), 0);
+    // Blocking delivers every element, in order.
+    assert_eq!(secondary.join().unwrap(), primary);
+}
+
+#[test]
+fn take_fanout_drop_newest() {
+    let (tx, rx) = sync_channel(2);
+    let mut it = (0..100).take_fanout(5, tx, FanoutPolicy::DropNewest);
+    assert_eq!(it.next(), Some(0));
+    assert_eq!(it.next(), Some(1));
+    assert_eq!(it.next(), Some(2));
+    assert_eq!(it.dropped(), 1);
+
+    // Draining the secondary channel makes room again.
+    assert_eq!(rx.recv(), Ok(0));
+    assert_eq!(it.next(), Some(3));
+    assert_eq!(it.dropped(), 1);
+    assert_eq!(it.next(), Some(4));
+    assert_eq!(it.dropped(), 2);
+    assert_eq!(it.next(), None);
+
+    assert_eq!(rx.iter().collect::<Vec<_>>(), [1, 3]);
+}
+
+#[test]
+fn take_fanout_secondary_disconnected() {
+    for &policy in &[FanoutPolicy::Block, FanoutPolicy::DropNewest] {
+        let (tx, rx) = sync_channel(1);
+        drop(rx);
+        let mut it = (0..10).take_fanout(3, tx, policy);
+        assert_eq!(it.by_ref().collect::<Vec<_>>(), [0, 1, 2]);
+        assert_eq!(it.dropped(), 3);
+    }
+}
+
+#[test]
+fn take_recording_replays_yields() {
+    let words = ["alpha", "beta", "gamma", "delta"];
+    let mut it = words.iter().map(|w| w.to_uppercase()).take_recording(3);
+    let yielded: Vec<_> = it.by_ref().collect();
+    assert_eq!(yielded, ["ALPHA", "BETA", "GAMMA"]);
+    assert_eq!(it.replay().collect::<Vec<_>>(), yielded);
+    // Replaying doesn't use up the recording.
+    assert_eq!(it.replay().collect::<Vec<_>>(), yielded);
+    assert_eq!(it.recorded(), &yielded[..]);
+    assert_eq!(it.into_recorded(), yielded);
+}
+
+#[test]
+fn take_recording_short_source() {
+    let mut it = (0..2).take_recording(5);
+    assert_eq!(it.size_hint(), (2, Some(2)));
+    assert_eq!(it.by_ref().count(), 2);
+    assert_eq!(it.next(), None);
+    assert_eq!(it.replay().collect::<Vec<_>>(), [0, 1]);
+}
+
+#[test]
+fn take_cached_transforms_each_key_once() {
+    let mut calls = Vec::new();
+    let keys = [3, 1, 3, 3, 2, 1, 2, 3, 9];
+    let out: Vec<_> = keys
+        .iter()
+        .take_cached(
+            8,
+            |&&k| k,
+            |&k| {
+                calls.push(k);
+                k * 10
+            },
+            3,
+        )
+        .collect();
+    assert_eq!(out, [30, 10, 30, 30, 20, 10, 20, 30]);
+    assert_eq!(calls, [3, 1, 2]);
+}
+
+#[test]
+fn take_cached_evicts_least_recently_used() {
+    let mut calls = Vec::new();
+    // With room for two keys, seeing 3 evicts 2 because 1 was used more
+    // recently, and seeing 2 again then evicts 3.
+    let keys = [1, 2, 1, 3, 1, 2, 1];
+    let out: Vec<_> = keys
+        .iter()
+        .take_cached(
+            usize::MAX,
+            |&&k| k,
+            |&k| {
+                calls.push(k);
+                -k
+            },
+            2,
+        )
+        .collect();
+    assert_eq!(out, [-1, -2, -1, -3, -1, -2, -1]);
+    assert_eq!(calls, [1, 2, 3, 2]);
+
+    // A capacity of zero turns caching off.
+    let mut calls = 0;
+    let n = [1, 1, 1].iter().take_cached(2, |&&k| k, |_| calls += 1, 0).count();
+    assert_eq!((n, calls), (2, 2));
+}
+
+#[test]
+fn take_frames_fixed_width_prefixes() {
+    let cases: [(PrefixKind, &[u8]); 4] = [
+        (PrefixKind::U16Be, &[0, 3, b'a', b'b', b'c', 0, 0, 0, 1, b'd']),
+        (PrefixKind::U16Le, &[3, 0, b'a', b'b', b'c', 0, 0, 1, 0, b'd']),
+        (PrefixKind::U32Be, &[0, 0, 0, 3, b'a', b'b', b'c', 0, 0, 0, 0, 0, 0, 0, 1, b'd']),
+        (PrefixKind::U32Le, &[3, 0, 0, 0, b'a', b'b', b'c', 0, 0, 0, 0, 1, 0, 0, 0, b'd']),
+    ];
+    for &(prefix, bytes) in &cases {
+        let mut frames = bytes.iter().copied().take_frames(10, prefix);
+        assert_eq!(frames.by_ref().collect::<Vec<_>>(), [&b"abc"[..], b"", b"d"]);
+        assert!(frames.remainder().is_empty());
+
+        // Stopping at `n` leaves the rest of the stream alone.
+        let mut iter = bytes.iter().copied();
+        assert_eq!(iter.by_ref().take_frames(1, prefix).count(), 1);
+        assert_eq!(iter.take_frames(10, prefix).count(), 2);
+    }
+}
+
+#[test]
+fn take_frames_varint() {
+    let long = vec![7; 300];
+    let mut bytes = vec![0, 2, b'o', b'k'];
+    // 300 is 0b10_0101100, which encodes as [0b1_0101100, 0b10].
+    bytes.extend_from_slice(&[0xac, 0x02]);
+    bytes.extend_from_slice(&long);
+    let frames: Vec<_> = bytes.into_iter().take_frames(5, PrefixKind::Varint).collect();
+    assert_eq!(frames, [Vec::new(), b"ok".to_vec(), long]);
+}
+
+#[test]
+fn take_frames_truncated() {
+    // Truncated in the middle of the payload.
+    let bytes = [0, 1, b'x', 0, 4, b'a', b'b'];
+    let mut frames = bytes.iter().copied().take_frames(10, PrefixKind::U16Be);
+    assert_eq!(frames.next(), Some(b"x".to_vec()));
+    assert_eq!(frames.next(), None);
+    assert_eq!(frames.remainder(), [0, 4, b'a', b'b']);
+    assert_eq!(frames.next(), None);
+
+    // Truncated in the middle of the prefix.
+    let bytes = [1, b'x', 0x80];
+    let mut frames = bytes.iter().copied().take_frames(10, PrefixKind::Varint);
+    assert_eq!(frames.by_ref().collect::<Vec<_>>(), [b"x"]);
+    assert_eq!(frames.remainder(), [0x80]);
+
+    // A varint that overflows a `u64` is treated the same way.
+    let bytes = [0xff; 12];
+    let mut frames = bytes.iter().copied().take_frames(10, PrefixKind::Varint);
+    assert_eq!(frames.next(), None);
+    assert_eq!(frames.remainder(), [0xff; 10]);
+}
+
+#[test]
+fn take_microbatched_flushes_on_size() {
+    let batches: Vec<_> = (0..).take_microbatched(8, 3, Duration::from_secs(60)).collect();
+    assert_eq!(batches, [vec![0, 1, 2], vec![3, 4, 5], vec![6, 7]]);
+
+    // The last batch is flushed when the source runs out, too.
+    let mut it = (0..4).take_microbatched(10, 3, Duration::from_secs(60));
+    assert_eq!(it.size_hint(), (1, Some(4)));
+    assert_eq!(it.next(), Some(vec![0, 1, 2]));
+    assert_eq!(it.next(), Some(vec![3]));
+    assert_eq!(it.next(), None);
+
+    // Huge caps don't turn into huge allocations.
+    let batches: Vec<_> =
+        (0..3).take_microbatched(usize::MAX, usize::MAX, Duration::from_secs(60)).collect();
+    assert_eq!(batches, [vec![0, 1, 2]]);
+}
+
+#[test]
+fn take_microbatched_flushes_on_delay() {
+    let slow = (0..).inspect(|_| thread::sleep(Duration::from_millis(20)));
+    let batches: Vec<_> = slow.take_microbatched(10, 100, Duration::from_millis(50)).collect();
+
+    // Elements arrive about every 20ms, so no batch can get anywhere near 100
+    // elements before it is 50ms old. Sleeps may overshoot on a loaded
+    // machine, which only makes the batches smaller.
+    assert!(batches.len() > 1, "{:?}", batches);
+    assert!(batches.iter().all(|batch| !batch.is_empty() && batch.len() < 10), "{:?}", batches);
+    let flattened: Vec<_> = batches.into_iter().flatten().collect();
+    assert_eq!(flattened, (0..10).collect::<Vec<_>>());
+}
+
+#[test]
+#[should_panic(expected = "batch size must be non-zero")]
+fn take_microbatched_zero_batch_size() {
+    let _ = (0..).take_microbatched(10, 0, Duration::from_secs(1));
+}
+
+#[test]
+fn take_approx_dedup_never_repeats() {
+    // Every key comes up ten times, but is only ever yielded once.
+    let stream = (0..10).flat_map(|_| 0..100);
+    let v: Vec<_> = stream.take_approx_dedup(1000, |&x| x, 100, 0.01).collect();
+    let mut sorted = v.clone();
+    sorted.sort();
+    sorted.dedup();
+    assert_eq!(sorted.len(), v.len());
+
+    // The limit counts the yielded elements only.
+    let v: Vec<_> = [1, 1, 2, 1, 3, 2, 4].iter().take_approx_dedup(3, |&&x| x, 10, 1e-9).collect();
+    assert_eq!(v, [&1, &2, &3]);
+}
+
+#[test]
+fn take_approx_dedup_false_positive_rate() {
+    // With all keys distinct, every skipped element is a false positive. At
+    // a rate of at most 1%, at most about 10 out of 1000 are expected to be
+    // skipped; three times that would be a one-in-a-million event.
+    let v: Vec<_> = (0..1000).take_approx_dedup(1000, |&x| x, 1000, 0.01).collect();
+    assert!(v.len() > 970, "{} false positives", 1000 - v.len());
+    assert!(v.windows(2).all(|w| w[0] < w[1]));
+}
+
+#[test]
+#[should_panic(expected = "false positive rate must be between 0 and 1")]
+fn take_approx_dedup_invalid_rate() {
+    let _ = (0..).take_approx_dedup(10, |&x| x, 10, 1.0);
+}
+
+#[test]
+fn take_cancellable_reports_reason() {
+    use crate::sync::atomic::AtomicBool;
+    use crate::sync::Arc;
+
+    let never = || Arc::new(AtomicBool::new(false));
+
+    let mut it = (0..10).take_cancellable(3, never());
+    assert_eq!(it.by_ref().collect::<Vec<_>>(), [0, 1, 2]);
+    assert_eq!(it.stop_reason(), Some(StopReason::Limit));
+
+    let mut it = (0..2).take_cancellable(3, never());
+    assert_eq!(it.stop_reason(), None);
+    assert_eq!(it.by_ref().collect::<Vec<_>>(), [0, 1]);
+    assert_eq!(it.stop_reason(), Some(StopReason::Exhausted));
+
+    // A flag set up front stops the iterator before it pulls anything.
+    let mut source = 0..10;
+    let mut it = source.by_ref().take_cancellable(3, Arc::new(AtomicBool::new(true)));
+    assert_eq!(it.next(), None);
+    assert_eq!(it.stop_reason(), Some(StopReason::Cancelled));
+    assert_eq!(source.next(), Some(0));
+}
+
+#[test]
+fn take_cancellable_from_another_thread() {
+    use crate::sync::atomic::{AtomicBool, Ordering};
+    use crate::sync::Arc;
+
+    let cancel = Arc::new(AtomicBool::new(false));
+    let (tx, rx) = channel();
+    let worker = {
+        let cancel = cancel.clone();
+        thread::spawn(move || {
+            let mut it = (0..).take_cancellable(usize::MAX, cancel);
+            for x in it.by_ref() {
+                if x == 5 {
+                    tx.send(()).unwrap();
+                }
+                thread::sleep(Duration::from_millis(1));
+            }
+            it.stop_reason()
+        })
+    };
+
+    // Cancel partway through, once the worker is known to be running.
+    rx.recv().unwrap();
+    cancel.store(true, Ordering::Relaxed);
+    assert_eq!(worker.join().unwrap(), Some(StopReason::Cancelled));
+
+    // Clearing the flag doesn't bring a stopped iterator back.
+    let cancel = Arc::new(AtomicBool::new(true));
+    let mut it = (0..).take_cancellable(10, cancel.clone());
+    assert_eq!(it.next(), None);
+    cancel.store(false, Ordering::Relaxed);
+    assert_eq!(it.next(), None);
+}
+
+#[test]
+fn take_tee_write_captures_consumed_prefix() {
+    let mut sink = Vec::new();
+    let mut source = b"hello, world".iter().copied();
+    let mut it = source.by_ref().take_tee_write(5, &mut sink);
+    assert_eq!(it.len(), 5);
+    assert_eq!(it.by_ref().collect::<Vec<u8>>(), b"hello");
+    assert!(it.error().is_none());
+    drop(it);
+    assert_eq!(sink, b"hello");
+    assert_eq!(source.next(), Some(b','));
+
+    // A short source is written out in full once it runs out.
+    let mut it = b"hi".iter().copied().take_tee_write(5, Vec::new());
+    assert_eq!(it.by_ref().count(), 2);
+    assert_eq!(it.into_sink().unwrap(), b"hi");
+}
+
+#[test]
+fn take_tee_write_reports_write_errors() {
+    use crate::io::{self, Write};
+
+    struct Broken;
+
+    impl Write for Broken {
+        fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::Other, "broken sink"))
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    // Iteration carries on regardless, and the error is kept for later.
+    let mut it = (0..10).take_tee_write(4, Broken);
+    assert_eq!(it.by_ref().collect::<Vec<u8>>(), [0, 1, 2, 3]);
+    assert_eq!(it.error().map(io::Error::kind), Some(io::ErrorKind::Other));
+    assert!(it.into_sink().is_err());
+}
+
+#[test]
+fn take_prefetched_preserves_order() {
+    let v: Vec<_> = (0..1000).take_prefetched(600, 8).collect();
+    assert_eq!(v, (0..600).collect::<Vec<_>>());
+
+    // A short source simply runs out, however deep the prefetch.
+    let v: Vec<_> = (0..3).take_prefetched(10, 100).collect();
+    assert_eq!(v, [0, 1, 2]);
+
+    // Without a buffer the worker hands over one element at a time.
+    let mut it = (0..).take_prefetched(2, 0);
+    assert_eq!(it.next(), Some(0));
+    assert_eq!(it.next(), Some(1));
+    assert_eq!(it.next(), None);
+    assert_eq!(it.next(), None);
+}
+
+#[test]
+fn take_prefetched_worker_stops_on_drop() {
+    use crate::sync::mpsc::RecvTimeoutError;
+
+    // The closure owns `alive`, so it is dropped along with the underlying
+    // iterator when the worker thread exits.
+    let (alive, worker_gone) = channel::<()>();
+    let mut it = (0..)
+        .map(move |x| {
+            let _alive = &alive;
+            x
+        })
+        .take_prefetched(usize::MAX, 4);
+    assert_eq!(it.next(), Some(0));
+    drop(it);
+    assert_eq!(
+        worker_gone.recv_timeout(Duration::from_secs(60)),
+        Err(RecvTimeoutError::Disconnected)
+    );
+}
+
+#[test]
+fn take_rolling_median_matches_brute_force() {
+    fn brute_force(data: &[u32], n: usize, window: usize) -> Vec<u32> {
+        (1..=n.min(data.len()))
+            .map(|end| {
+                let mut w = data[end.saturating_sub(window)..end].to_vec();
+                w.sort();
+                w[(w.len() - 1) / 2]
+            })
+            .collect()
+    }
+
+    // A sequence with plenty of repeated values, which end up on both sides
+    // of the split.
+    let mut x = 7u32;
+    let data: Vec<u32> = (0..200)
+        .map(|_| {
+            x = x.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            (x >> 16) % 20
+        })
+        .collect();
+    for &window in &[1, 2, 3, 4, 7, 16, 250] {
+        for &n in &[0, 1, 5, 150, 500] {
+            let medians: Vec<u32> = data.iter().copied().take_rolling_median(n, window).collect();
+            assert_eq!(medians, brute_force(&data, n, window), "n = {}, window = {}", n, window);
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "window size must be non-zero")]
+fn take_rolling_median_zero_window() {
+    let _ = (0..10).take_rolling_median(5, 0);
+}
+
+#[test]
+fn take_delayed_sleeps_before_each_item() {
+    let start = Instant::now();
+    let v: Vec<_> = (0..).take_delayed(3, |_| Duration::from_millis(5)).collect();
+    assert_eq!(v, [0, 1, 2]);
+    assert!(start.elapsed() >= Duration::from_millis(15), "took {:?}", start.elapsed());
+}
+
+#[test]
+fn take_delayed_passes_items_to_delay() {
+    let mut seen = Vec::new();
+    let v: Vec<_> = (0..2)
+        .take_delayed(5, |&x| {
+            seen.push(x);
+            Duration::ZERO
+        })
+        .collect();
+    assert_eq!(v, [0, 1]);
+    assert_eq!(seen, [0, 1]);
+}
+
+#[test]
+fn take_scheduled_batches_follows_schedule() {
+    let batches: Vec<_> = (0..6).take_scheduled_batches(10, [2, 3, 1]).collect();
+    assert_eq!(batches, [vec![0, 1], vec![2, 3, 4], vec![5]]);
+
+    // The limit cuts the last batch short, as does the source running out.
+    let batches: Vec<_> = (0..).take_scheduled_batches(4, [2, 3, 1]).collect();
+    assert_eq!(batches, [vec![0, 1], vec![2, 3]]);
+    let batches: Vec<_> = (0..4).take_scheduled_batches(10, [3, 3]).collect();
+    assert_eq!(batches, [vec![0, 1, 2], vec![3]]);
+
+    // Zero sizes are skipped.
+    let batches: Vec<_> = (0..).take_scheduled_batches(3, [0, 2, 0, 1]).collect();
+    assert_eq!(batches, [vec![0, 1], vec![2]]);
+
+    // Huge sizes don't turn into huge allocations.
+    let batches: Vec<_> = (0..3).take_scheduled_batches(usize::MAX, [usize::MAX]).collect();
+    assert_eq!(batches, [vec![0, 1, 2]]);
+}
+
+#[test]
+fn take_scheduled_batches_schedule_runs_out() {
+    let mut source = 0..10;
+    let mut it = source.by_ref().take_scheduled_batches(10, [2, 1]);
+    assert_eq!(it.size_hint(), (0, Some(2)));
+    assert_eq!(it.next(), Some(vec![0, 1]));
+    assert_eq!(it.next(), Some(vec![2]));
+    assert_eq!(it.next(), None);
+    assert_eq!(it.next(), None);
+
+    // What's left over stays in the source.
+    assert_eq!(source.next(), Some(3));
+}
+
+#[test]
+fn take_permitted_bounds_in_flight() {
+    use crate::sync::atomic::{AtomicUsize, Ordering};
+    use crate::sync::{Arc, Semaphore};
+
+    let sem = Arc::new(Semaphore::new(2));
+    let in_flight = Arc::new(AtomicUsize::new(0));
+    let max_in_flight = Arc::new(AtomicUsize::new(0));
+
+    let mut handles = Vec::new();
+    for _ in (0..8).take_permitted(6, sem.clone()) {
+        let (sem, in_flight, max_in_flight) =
+            (sem.clone(), in_flight.clone(), max_in_flight.clone());
+        handles.push(thread::spawn(move || {
+            let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            max_in_flight.fetch_max(now, Ordering::SeqCst);
+            thread::sleep(Duration::from_millis(10));
+            in_flight.fetch_sub(1, Ordering::SeqCst);
+            sem.release();
+        }));
+    }
+    assert_eq!(handles.len(), 6);
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert!(max_in_flight.load(Ordering::SeqCst) <= 2);
+
+    // Every permit has been given back.
+    assert!(sem.try_acquire());
+    assert!(sem.try_acquire());
+    assert!(!sem.try_acquire());
+}
+
+#[test]
+fn take_balanced_follows_greedy() {
+    let weights = [7, 2, 5, 9, 1, 4, 4, 6];
+    let buckets: Vec<_> = weights.iter().copied().take_balanced(10, 3, |&w| w).collect();
+
+    // Replay the greedy by hand: each weight goes to the lightest bucket so
+    // far, the first one on ties.
+    let mut expected = vec![Vec::new(); 3];
+    let mut totals = [0u64; 3];
+    for &w in &weights {
+        let i = (0..3).min_by_key(|&i| totals[i]).unwrap();
+        expected[i].push(w);
+        totals[i] += w;
+    }
+    assert_eq!(buckets, expected);
+    assert_eq!(buckets, [vec![7, 4], vec![2, 9], vec![5, 1, 4, 6]]);
+
+    // Every element was placed exactly once.
+    let mut placed: Vec<_> = buckets.concat();
+    placed.sort();
+    let mut all = weights.to_vec();
+    all.sort();
+    assert_eq!(placed, all);
+}
+
+#[test]
+fn take_balanced_limits_and_empty_buckets() {
+    let mut source = 0..10u64;
+    let mut it = source.by_ref().take_balanced(2, 4, |&x| x + 1);
+    assert_eq!(it.len(), 4);
+    assert_eq!(it.next(), Some(vec![0]));
+    assert_eq!(it.len(), 3);
+    assert_eq!(it.collect::<Vec<_>>(), [vec![1], vec![], vec![]]);
+
+    // What's left over stays in the source.
+    assert_eq!(source.next(), Some(2));
+}
+
+#[test]
+fn take_bytes_bounded_budget_trips_first() {
+    let records = ["abc", "de", "fghij", "k"];
+    let mut source = records.iter();
+    let mut it = source.by_ref().take_bytes_bounded(10, 8, |r| r.len());
+    assert_eq!(it.next(), Some(&"abc"));
+    assert_eq!(it.next(), Some(&"de"));
+    // "fghij" would bring the total to 10 bytes. It is taken from the source
+    // and kept aside, and the small record after it is not yielded either.
+    assert_eq!(it.next(), None);
+    assert_eq!(it.next(), None);
+    assert_eq!(it.into_rejected(), Some(&"fghij"));
+    assert_eq!(source.next(), Some(&"k"));
+
+    // Filling the budget exactly is fine.
+    let batch: Vec<_> = records.iter().take_bytes_bounded(10, 5, |r| r.len()).collect();
+    assert_eq!(batch, [&"abc", &"de"]);
+}
+
+#[test]
+fn take_bytes_bounded_count_trips_first() {
+    let mut source = 0..10;
+    let mut it = source.by_ref().take_bytes_bounded(3, 1000, |_| 8);
+    assert_eq!(it.by_ref().collect::<Vec<_>>(), [0, 1, 2]);
+    assert_eq!(it.into_rejected(), None);
+    // Nothing was pulled past the count.
+    assert_eq!(source.next(), Some(3));
+}
@@ -443,8 +443,6 @@
 #[allow(deprecated, deprecated_in_future)]
 pub use core::isize;
 #[stable(feature = "rust1", since = "1.0.0")]
-pub use core::iter;
-#[stable(feature = "rust1", since = "1.0.0")]
 pub use core::marker;
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use core::mem;
@@ -495,6 +493,7 @@
 pub mod ffi;
 pub mod fs;
 pub mod io;
+pub mod iter;
 pub mod net;
 pub mod num;
 pub mod os;
@@ -627,6 +627,10 @@ fn flush(&mut self) -> io::Result<()> {
     }
 }
 
+/// Allows extension traits within `std`.
+#[unstable(feature = "sealed", issue = "none")]
+impl crate::sealed::Sealed for TcpStream {}
+
 impl AsInner<net_imp::TcpStream> for TcpStream {
     fn as_inner(&self) -> &net_imp::TcpStream {
         &self.0
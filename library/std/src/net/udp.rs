@@ -779,6 +779,10 @@ pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
     }
 }
 
+/// Allows extension traits within `std`.
+#[unstable(feature = "sealed", issue = "none")]
+impl crate::sealed::Sealed for UdpSocket {}
+
 impl AsInner<net_imp::UdpSocket> for UdpSocket {
     fn as_inner(&self) -> &net_imp::UdpSocket {
         &self.0
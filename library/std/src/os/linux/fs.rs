@@ -2,7 +2,13 @@
 
 #![stable(feature = "metadata_ext", since = "1.1.0")]
 
-use crate::fs::Metadata;
+#[cfg(test)]
+mod tests;
+
+use crate::fs::{File, Metadata};
+use crate::io;
+use crate::os::unix::io::{AsRawFd, RawFd};
+use crate::sealed::Sealed;
 use crate::sys_common::AsInner;
 
 #[allow(deprecated)]
@@ -378,3 +384,247 @@ fn st_blocks(&self) -> u64 {
         self.as_inner().as_inner().st_blocks as u64
     }
 }
+
+/// The inode flag marking a file as immutable (`chattr +i`).
+///
+/// An immutable file cannot be modified, renamed, linked to or deleted, and
+/// its metadata cannot be changed, even by root. Setting or clearing this flag
+/// requires the `CAP_LINUX_IMMUTABLE` capability.
+#[unstable(feature = "linux_inode_flags", issue = "none")]
+pub const FS_IMMUTABLE_FL: u32 = 0x0000_0010;
+
+/// The inode flag marking a file as append-only (`chattr +a`).
+///
+/// An append-only file can only be opened for writing in append mode and
+/// cannot be renamed, linked to or deleted. Setting or clearing this flag
+/// requires the `CAP_LINUX_IMMUTABLE` capability.
+#[unstable(feature = "linux_inode_flags", issue = "none")]
+pub const FS_APPEND_FL: u32 = 0x0000_0020;
+
+/// Linux-specific extensions to [`fs::File`].
+///
+/// This trait is sealed: it cannot be implemented outside the standard library.
+/// This is so that future additional methods are not breaking changes.
+///
+/// [`fs::File`]: crate::fs::File
+#[unstable(feature = "linux_inode_flags", issue = "none")]
+pub trait FileExt: Sealed {
+    /// Returns the inode attribute flags of this file, as shown by `lsattr`.
+    ///
+    /// This uses the `FS_IOC_GETFLAGS` ioctl. The result is a combination of
+    /// `FS_*_FL` bits such as [`FS_IMMUTABLE_FL`] and [`FS_APPEND_FL`].
+    ///
+    /// # Errors
+    ///
+    /// Only some filesystems, such as ext4, XFS and Btrfs, support inode flags.
+    /// On others this returns an error of kind
+    /// [`Unsupported`](io::ErrorKind::Unsupported).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(linux_inode_flags)]
+    /// use std::fs::File;
+    /// use std::os::linux::fs::{FileExt, FS_IMMUTABLE_FL};
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let f = File::open("/etc/passwd")?;
+    ///     if f.inode_flags()? & FS_IMMUTABLE_FL != 0 {
+    ///         println!("file is immutable");
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    fn inode_flags(&self) -> io::Result<u32>;
+
+    /// Replaces the inode attribute flags of this file, as `chattr` does.
+    ///
+    /// This uses the `FS_IOC_SETFLAGS` ioctl. All flags are replaced, so to
+    /// change a single flag, read the current ones with [`inode_flags`] first.
+    ///
+    /// [`inode_flags`]: FileExt::inode_flags
+    ///
+    /// # Errors
+    ///
+    /// Changing [`FS_IMMUTABLE_FL`] or [`FS_APPEND_FL`] requires the
+    /// `CAP_LINUX_IMMUTABLE` capability; without it this returns an error of
+    /// kind [`PermissionDenied`](io::ErrorKind::PermissionDenied). Filesystems
+    /// without inode flags return an error of kind
+    /// [`Unsupported`](io::ErrorKind::Unsupported).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(linux_inode_flags)]
+    /// use std::fs::File;
+    /// use std::os::linux::fs::{FileExt, FS_APPEND_FL};
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let f = File::open("audit.log")?;
+    ///     f.set_inode_flags(f.inode_flags()? | FS_APPEND_FL)?;
+    ///     Ok(())
+    /// }
+    /// ```
+    fn set_inode_flags(&self, flags: u32) -> io::Result<()>;
+
+    /// Deallocates `len` bytes of this file starting at `offset`, leaving a
+    /// hole that reads as zeros.
+    ///
+    /// This uses `fallocate` with `FALLOC_FL_PUNCH_HOLE | FALLOC_FL_KEEP_SIZE`,
+    /// so the length of the file stays the same even if the range reaches past
+    /// its end. Filesystems only free whole blocks: the parts of the range that
+    /// cover a block only partially are zeroed instead.
+    ///
+    /// # Errors
+    ///
+    /// Filesystems that cannot punch holes return an error of kind
+    /// [`Unsupported`](io::ErrorKind::Unsupported). An `offset` or `len`
+    /// larger than `i64::MAX` returns an error of kind
+    /// [`InvalidInput`](io::ErrorKind::InvalidInput).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(linux_inode_flags, linux_punch_hole)]
+    /// use std::fs::OpenOptions;
+    /// use std::os::linux::fs::FileExt;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let f = OpenOptions::new().write(true).open("segment.log")?;
+    ///     // The first megabyte has been compacted away.
+    ///     f.punch_hole(0, 1 << 20)?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "linux_punch_hole", issue = "none")]
+    fn punch_hole(&self, offset: u64, len: u64) -> io::Result<()>;
+}
+
+#[unstable(feature = "linux_inode_flags", issue = "none")]
+impl FileExt for File {
+    fn inode_flags(&self) -> io::Result<u32> {
+        self.as_inner().inode_flags()
+    }
+
+    fn set_inode_flags(&self, flags: u32) -> io::Result<()> {
+        self.as_inner().set_inode_flags(flags)
+    }
+
+    fn punch_hole(&self, offset: u64, len: u64) -> io::Result<()> {
+        self.as_inner().punch_hole(offset, len)
+    }
+}
+
+/// A file opened for direct I/O, which checks the alignment of each read and
+/// write before it is issued.
+///
+/// With `O_DIRECT`, reads and writes bypass the page cache and go straight to
+/// the device, which requires the address and length of the buffer as well as
+/// the file offset to be multiples of the device's block size. The kernel
+/// rejects misaligned requests with a bare `EINVAL`, so `DirectFile` checks
+/// them up front instead, and returns an error of kind
+/// [`InvalidInput`](io::ErrorKind::InvalidInput) saying which of the three is
+/// misaligned.
+///
+/// The alignment is the logical block size for a block device, as reported by
+/// the `BLKSSZGET` ioctl, and the block size of the filesystem, as reported by
+/// `fstatvfs`, for a file. The latter may be stricter than what the device
+/// needs, but is always enough.
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(linux_direct_io)]
+/// use std::fs::File;
+/// use std::os::linux::fs::DirectFile;
+///
+/// fn main() -> std::io::Result<()> {
+///     let file = DirectFile::new(File::open("data.db")?)?;
+///     let align = file.alignment();
+///
+///     // Carve a suitably aligned block out of a larger buffer.
+///     let mut storage = vec![0; 2 * align];
+///     let start = storage.as_ptr().align_offset(align);
+///     let block = &mut storage[start..start + align];
+///     let n = file.read_at(block, 0)?;
+///     println!("read {} bytes bypassing the page cache", n);
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+#[unstable(feature = "linux_direct_io", issue = "none")]
+pub struct DirectFile {
+    file: File,
+    alignment: usize,
+}
+
+impl DirectFile {
+    /// Turns on `O_DIRECT` for `file`, unless it was already opened with it,
+    /// and determines the alignment it needs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind [`Unsupported`](io::ErrorKind::Unsupported)
+    /// if the filesystem of `file` doesn't support direct I/O.
+    #[unstable(feature = "linux_direct_io", issue = "none")]
+    pub fn new(file: File) -> io::Result<DirectFile> {
+        let alignment = file.as_inner().enable_direct_io()?;
+        Ok(DirectFile { file, alignment })
+    }
+
+    /// Returns the alignment, in bytes, that the address and length of the
+    /// buffers and the offsets passed to [`read_at`] and [`write_at`] must
+    /// have.
+    ///
+    /// [`read_at`]: DirectFile::read_at
+    /// [`write_at`]: DirectFile::write_at
+    #[unstable(feature = "linux_direct_io", issue = "none")]
+    pub fn alignment(&self) -> usize {
+        self.alignment
+    }
+
+    /// Reads a number of bytes starting from a given offset, bypassing the
+    /// page cache.
+    ///
+    /// This works like [`FileExt::read_at`], but first checks that `buf` and
+    /// `offset` are aligned as required by [`alignment`].
+    ///
+    /// [`FileExt::read_at`]: crate::os::unix::fs::FileExt::read_at
+    /// [`alignment`]: DirectFile::alignment
+    #[unstable(feature = "linux_direct_io", issue = "none")]
+    pub fn read_at(&self, buf: &mut [u8], offset: u64) -> io::Result<usize> {
+        self.file.as_inner().read_direct_at(buf, offset, self.alignment)
+    }
+
+    /// Writes a number of bytes starting from a given offset, bypassing the
+    /// page cache.
+    ///
+    /// This works like [`FileExt::write_at`], but first checks that `buf` and
+    /// `offset` are aligned as required by [`alignment`].
+    ///
+    /// [`FileExt::write_at`]: crate::os::unix::fs::FileExt::write_at
+    /// [`alignment`]: DirectFile::alignment
+    #[unstable(feature = "linux_direct_io", issue = "none")]
+    pub fn write_at(&self, buf: &[u8], offset: u64) -> io::Result<usize> {
+        self.file.as_inner().write_direct_at(buf, offset, self.alignment)
+    }
+
+    /// Gets a reference to the underlying file.
+    #[unstable(feature = "linux_direct_io", issue = "none")]
+    pub fn get_ref(&self) -> &File {
+        &self.file
+    }
+
+    /// Unwraps the underlying file, which keeps `O_DIRECT` turned on.
+    #[unstable(feature = "linux_direct_io", issue = "none")]
+    pub fn into_inner(self) -> File {
+        self.file
+    }
+}
+
+#[unstable(feature = "linux_direct_io", issue = "none")]
+impl AsRawFd for DirectFile {
+    fn as_raw_fd(&self) -> RawFd {
+        self.file.as_raw_fd()
+    }
+}
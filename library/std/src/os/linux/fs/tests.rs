@@ -0,0 +1,112 @@
+use super::*;
+use crate::fs::{self, OpenOptions};
+use crate::io::{ErrorKind, Write};
+use crate::sys_common::io::test::tmpdir;
+
+// Not every filesystem the tests run on (tmpfs in particular) supports inode
+// flags or hole punching, and changing the interesting inode flags needs
+// `CAP_LINUX_IMMUTABLE`.
+macro_rules! or_skip {
+    ($e:expr) => {
+        match $e {
+            Err(ref e)
+                if e.kind() == ErrorKind::Unsupported
+                    || e.kind() == ErrorKind::PermissionDenied =>
+            {
+                return;
+            }
+            other => other,
+        }
+    };
+}
+
+#[test]
+fn inode_flags_default() {
+    let tmpdir = tmpdir();
+    let f = File::create(tmpdir.join("file")).unwrap();
+    let flags = or_skip!(f.inode_flags()).unwrap();
+    assert_eq!(flags & (FS_IMMUTABLE_FL | FS_APPEND_FL), 0);
+}
+
+#[test]
+fn inode_flags_append_only() {
+    let tmpdir = tmpdir();
+    let path = tmpdir.join("file");
+    let f = File::create(&path).unwrap();
+    let flags = or_skip!(f.inode_flags()).unwrap();
+    or_skip!(f.set_inode_flags(flags | FS_APPEND_FL)).unwrap();
+    assert_ne!(f.inode_flags().unwrap() & FS_APPEND_FL, 0);
+
+    // Only appending writes are allowed now.
+    assert!(OpenOptions::new().write(true).open(&path).is_err());
+    let mut appender = OpenOptions::new().append(true).open(&path).unwrap();
+    appender.write_all(b"entry").unwrap();
+
+    // Clear the flag again so the temporary directory can be removed.
+    f.set_inode_flags(flags).unwrap();
+    assert_eq!(f.inode_flags().unwrap() & FS_APPEND_FL, 0);
+    assert_eq!(fs::read(&path).unwrap(), b"entry");
+}
+
+#[test]
+fn direct_io_checks_alignment() {
+    let tmpdir = tmpdir();
+    let path = tmpdir.join("file");
+    fs::write(&path, vec![7; 1 << 16]).unwrap();
+    let file = OpenOptions::new().read(true).write(true).open(&path).unwrap();
+    let file = or_skip!(DirectFile::new(file)).unwrap();
+    let align = file.alignment();
+    assert!(align.is_power_of_two(), "{}", align);
+
+    let mut storage = vec![0; 3 * align];
+    let start = storage.as_ptr().align_offset(align);
+    let block = &mut storage[start..start + 2 * align];
+
+    // Aligned requests go through.
+    assert_eq!(file.read_at(block, align as u64).unwrap(), 2 * align);
+    assert!(block.iter().all(|&b| b == 7));
+    block.iter_mut().for_each(|b| *b = 9);
+    assert_eq!(file.write_at(&block[..align], 0).unwrap(), align);
+    let contents = fs::read(&path).unwrap();
+    assert!(contents[..align].iter().all(|&b| b == 9));
+    assert_eq!(contents[align], 7);
+
+    // Misaligned ones are rejected before they reach the kernel.
+    let err = file.read_at(&mut storage[start + 1..start + 1 + align], 0).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    assert!(err.to_string().contains("address"), "{}", err);
+    let err = file.write_at(&storage[start..start + align - 1], 0).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    assert!(err.to_string().contains("length"), "{}", err);
+    let err = file.read_at(&mut storage[start..start + align], 1).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    assert!(err.to_string().contains("offset"), "{}", err);
+}
+
+#[test]
+fn punch_hole_deallocates_range() {
+    const MIB: usize = 1 << 20;
+    let tmpdir = tmpdir();
+    let path = tmpdir.join("file");
+    fs::write(&path, vec![0xaa; MIB]).unwrap();
+    let f = OpenOptions::new().read(true).write(true).open(&path).unwrap();
+    f.sync_all().unwrap();
+    let blocks = f.metadata().unwrap().st_blocks();
+
+    or_skip!(f.punch_hole(MIB as u64 / 4, MIB as u64 / 2)).unwrap();
+    f.sync_all().unwrap();
+    assert_eq!(f.metadata().unwrap().len(), MIB as u64);
+    assert!(f.metadata().unwrap().st_blocks() < blocks);
+
+    // The kernel reports the punched range as a hole.
+    let fd = f.as_raw_fd();
+    let hole = unsafe { libc::lseek(fd, 0, libc::SEEK_HOLE) };
+    assert_eq!(hole, MIB as libc::off_t / 4);
+    let data = unsafe { libc::lseek(fd, hole, libc::SEEK_DATA) };
+    assert_eq!(data, 3 * MIB as libc::off_t / 4);
+
+    let contents = fs::read(&path).unwrap();
+    assert!(contents[..MIB / 4].iter().all(|&b| b == 0xaa));
+    assert!(contents[MIB / 4..3 * MIB / 4].iter().all(|&b| b == 0));
+    assert!(contents[3 * MIB / 4..].iter().all(|&b| b == 0xaa));
+}
@@ -2,9 +2,13 @@
 
 #![stable(feature = "rust1", since = "1.0.0")]
 
+#[cfg(all(test, not(target_os = "emscripten")))]
+mod tests;
+
 use super::platform::fs::MetadataExt as _;
 use crate::fs::{self, OpenOptions, Permissions};
 use crate::io;
+use crate::os::unix::io::{AsRawFd, RawFd};
 use crate::path::Path;
 use crate::sys;
 use crate::sys_common::{AsInner, AsInnerMut, FromInner};
@@ -206,6 +210,57 @@ fn write_all_at(&self, mut buf: &[u8], mut offset: u64) -> io::Result<()> {
         }
         Ok(())
     }
+
+    /// Flushes the given byte range of this file to disk.
+    ///
+    /// This is a more targeted alternative to [`File::sync_data`] for
+    /// workloads that only touched part of a large file, such as appending to
+    /// a log. `offset` and `len` select the range to flush, with a `len` of
+    /// zero meaning "up to the end of the file". `flags` is a combination of
+    /// the raw `SYNC_FILE_RANGE_WAIT_BEFORE`, `SYNC_FILE_RANGE_WRITE` and
+    /// `SYNC_FILE_RANGE_WAIT_AFTER` bits, passed through unchanged.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// On Linux this calls `sync_file_range`. That only writes out dirty data
+    /// pages: it does not flush file metadata or the disk's write cache, so on
+    /// its own it does not guarantee the data survives a crash.
+    ///
+    /// On other platforms there is no range-based equivalent, so `offset`,
+    /// `len` and `flags` are ignored and the whole file is flushed as with
+    /// [`File::sync_all`]. This is always at least as durable as the Linux
+    /// behavior, but may be much slower.
+    ///
+    /// [`File::sync_data`]: fs::File::sync_data
+    /// [`File::sync_all`]: fs::File::sync_all
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(unix_sync_range)]
+    /// use std::fs::OpenOptions;
+    /// use std::io;
+    /// use std::os::unix::prelude::FileExt;
+    ///
+    /// // SYNC_FILE_RANGE_WAIT_BEFORE | SYNC_FILE_RANGE_WRITE | SYNC_FILE_RANGE_WAIT_AFTER
+    /// const SYNC_ALL_FLAGS: u32 = 1 | 2 | 4;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let file = OpenOptions::new().append(true).open("journal")?;
+    ///     let offset = file.metadata()?.len();
+    ///     file.write_all_at(b"record", offset)?;
+    ///     file.sync_range(offset, 6, SYNC_ALL_FLAGS)?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "unix_sync_range", issue = "none")]
+    fn sync_range(&self, offset: u64, len: u64, flags: u32) -> io::Result<()> {
+        let _ = (offset, len, flags);
+        Err(io::Error::new_const(
+            io::ErrorKind::Unsupported,
+            &"sync_range is not implemented for this type",
+        ))
+    }
 }
 
 #[stable(feature = "file_offset", since = "1.15.0")]
@@ -216,6 +271,9 @@ fn read_at(&self, buf: &mut [u8], offset: u64) -> io::Result<usize> {
     fn write_at(&self, buf: &[u8], offset: u64) -> io::Result<usize> {
         self.as_inner().write_at(buf, offset)
     }
+    fn sync_range(&self, offset: u64, len: u64, flags: u32) -> io::Result<()> {
+        self.as_inner().sync_range(offset, len, flags)
+    }
 }
 
 /// Unix-specific extensions to [`fs::Permissions`].
@@ -910,3 +968,457 @@ fn mode(&mut self, mode: u32) -> &mut fs::DirBuilder {
 pub fn chroot<P: AsRef<Path>>(dir: P) -> io::Result<()> {
     sys::fs::chroot(dir.as_ref())
 }
+
+/// Renames `from` to `to`, failing if `to` already exists.
+///
+/// Unlike [`fs::rename`], this never replaces an existing file at `to`; the
+/// check and the rename happen atomically, so there is no window in which a
+/// concurrently created `to` could be clobbered.
+///
+/// # Platform-specific behavior
+///
+/// This function currently corresponds to `renameat2` with the
+/// `RENAME_NOREPLACE` flag on Linux. On other platforms, on kernels older
+/// than 3.15, and on filesystems that do not support the flag, an error of
+/// kind [`io::ErrorKind::Unsupported`] is returned rather than silently
+/// falling back to a clobbering rename.
+///
+/// # Errors
+///
+/// If `to` exists, an error of kind [`io::ErrorKind::AlreadyExists`] is
+/// returned.
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(unix_rename_flags)]
+/// use std::os::unix::fs;
+///
+/// fn main() -> std::io::Result<()> {
+///     fs::rename_noreplace("config.tmp", "config.toml")?;
+///     Ok(())
+/// }
+/// ```
+#[unstable(feature = "unix_rename_flags", issue = "none")]
+pub fn rename_noreplace<P: AsRef<Path>, Q: AsRef<Path>>(from: P, to: Q) -> io::Result<()> {
+    sys::fs::rename_noreplace(from.as_ref(), to.as_ref())
+}
+
+/// Atomically exchanges the paths `a` and `b`.
+///
+/// Both paths must exist. After a successful call, `a` refers to what `b`
+/// referred to and vice versa. They may be of different types, for example
+/// one a directory and the other a file.
+///
+/// # Platform-specific behavior
+///
+/// This function currently corresponds to `renameat2` with the
+/// `RENAME_EXCHANGE` flag on Linux. On other platforms, on kernels older
+/// than 3.15, and on filesystems that do not support the flag, an error of
+/// kind [`io::ErrorKind::Unsupported`] is returned.
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(unix_rename_flags)]
+/// use std::os::unix::fs;
+///
+/// fn main() -> std::io::Result<()> {
+///     fs::rename_exchange("current", "staged")?;
+///     Ok(())
+/// }
+/// ```
+#[unstable(feature = "unix_rename_flags", issue = "none")]
+pub fn rename_exchange<P: AsRef<Path>, Q: AsRef<Path>>(a: P, b: Q) -> io::Result<()> {
+    sys::fs::rename_exchange(a.as_ref(), b.as_ref())
+}
+
+/// Reads the target of the symbolic link at `path` into `buf`, returning the
+/// length of the target.
+///
+/// This is like [`fs::read_link`], but instead of allocating a new
+/// [`PathBuf`] for every call it writes the raw bytes of the target into a
+/// caller-provided buffer. Any previous contents of `buf` are discarded, and
+/// its capacity is reused, so a directory walker that resolves many links
+/// with the same buffer only allocates when it meets a target longer than
+/// any seen before.
+///
+/// [`PathBuf`]: crate::path::PathBuf
+///
+/// # Platform-specific behavior
+///
+/// This function currently corresponds to the `readlink` function on Unix.
+/// As `readlink` truncates the target to fit the buffer without saying so, a
+/// target that fills the whole buffer is read again into a larger one.
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(unix_read_link_into)]
+/// use std::os::unix::fs;
+///
+/// fn main() -> std::io::Result<()> {
+///     let mut buf = Vec::with_capacity(4096);
+///     for link in &["/usr/bin/cc", "/usr/bin/c++"] {
+///         let len = fs::read_link_into(link, &mut buf)?;
+///         println!("{} -> {}", link, String::from_utf8_lossy(&buf[..len]));
+///     }
+///     Ok(())
+/// }
+/// ```
+#[unstable(feature = "unix_read_link_into", issue = "none")]
+pub fn read_link_into<P: AsRef<Path>>(path: P, buf: &mut Vec<u8>) -> io::Result<usize> {
+    sys::fs::read_link_into(path.as_ref(), buf)
+}
+
+/// Filesystem-level statistics, as returned by [`statvfs`].
+///
+/// All sizes are in bytes. Counts that the filesystem does not track, such as
+/// inodes on some network filesystems, are reported as zero.
+#[derive(Clone, Copy, Debug)]
+#[unstable(feature = "unix_statvfs", issue = "none")]
+pub struct FsStats(sys::fs::FsStats);
+
+impl FsStats {
+    /// Returns the preferred I/O block size of the filesystem.
+    #[unstable(feature = "unix_statvfs", issue = "none")]
+    pub fn block_size(&self) -> u64 {
+        self.0.block_size
+    }
+
+    /// Returns the total size of the filesystem.
+    #[unstable(feature = "unix_statvfs", issue = "none")]
+    pub fn total_bytes(&self) -> u64 {
+        self.0.total_bytes
+    }
+
+    /// Returns the amount of free space, including space reserved for the
+    /// superuser.
+    #[unstable(feature = "unix_statvfs", issue = "none")]
+    pub fn free_bytes(&self) -> u64 {
+        self.0.free_bytes
+    }
+
+    /// Returns the amount of free space available to unprivileged users.
+    ///
+    /// This is the figure to check before a large write, as it excludes any
+    /// space reserved for the superuser.
+    #[unstable(feature = "unix_statvfs", issue = "none")]
+    pub fn available_bytes(&self) -> u64 {
+        self.0.available_bytes
+    }
+
+    /// Returns the total number of inodes.
+    #[unstable(feature = "unix_statvfs", issue = "none")]
+    pub fn total_inodes(&self) -> u64 {
+        self.0.total_inodes
+    }
+
+    /// Returns the number of free inodes, including those reserved for the
+    /// superuser.
+    #[unstable(feature = "unix_statvfs", issue = "none")]
+    pub fn free_inodes(&self) -> u64 {
+        self.0.free_inodes
+    }
+
+    /// Returns the number of free inodes available to unprivileged users.
+    #[unstable(feature = "unix_statvfs", issue = "none")]
+    pub fn available_inodes(&self) -> u64 {
+        self.0.available_inodes
+    }
+}
+
+/// Returns statistics about the filesystem containing `path`.
+///
+/// `path` can be any file or directory on the filesystem of interest.
+///
+/// # Platform-specific behavior
+///
+/// This function currently corresponds to the `statvfs` function on Unix.
+/// Byte counts are derived from the block counts and the fragment size it
+/// reports. Redox and VxWorks return an error of kind
+/// [`io::ErrorKind::Unsupported`].
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(unix_statvfs)]
+/// use std::os::unix::fs;
+///
+/// fn main() -> std::io::Result<()> {
+///     let stats = fs::statvfs("/var/tmp")?;
+///     if stats.available_bytes() < 1 << 30 {
+///         eprintln!("less than 1 GiB left on /var/tmp");
+///     }
+///     Ok(())
+/// }
+/// ```
+#[unstable(feature = "unix_statvfs", issue = "none")]
+pub fn statvfs<P: AsRef<Path>>(path: P) -> io::Result<FsStats> {
+    sys::fs::statvfs(path.as_ref()).map(FsStats)
+}
+
+/// How [`reflink_or_copy`] copied a file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[unstable(feature = "unix_reflink", issue = "none")]
+pub enum ReflinkResult {
+    /// The destination is a copy-on-write clone that shares its data with
+    /// the source until either of them is modified.
+    Reflinked,
+    /// The contents of the source were copied in full.
+    Copied,
+}
+
+/// Copies the contents of one file to another, as a copy-on-write clone if
+/// the filesystem supports it.
+///
+/// A clone is made almost instantly and takes up no extra space until either
+/// file is modified. If the filesystem can't clone `from` to `to`, such as
+/// when it lacks support for it or the two paths are on different
+/// filesystems, this falls back to a full copy as done by [`fs::copy`]
+/// instead of failing. The returned [`ReflinkResult`] tells which of the two
+/// happened. Either way, the permission bits of `from` are copied to `to`.
+///
+/// # Platform-specific behavior
+///
+/// This function currently uses the `FICLONE` ioctl on Linux and Android,
+/// which btrfs and XFS support, and `fclonefileat` on macOS and iOS, which
+/// APFS supports. On macOS and iOS, a clone is only made if `to` doesn't
+/// exist yet. Other platforms always make a full copy.
+///
+/// # Errors
+///
+/// This function returns an error in the same cases as [`fs::copy`].
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(unix_reflink)]
+/// use std::os::unix::fs::{self, ReflinkResult};
+///
+/// fn main() -> std::io::Result<()> {
+///     match fs::reflink_or_copy("disk.img", "disk-snapshot.img")? {
+///         ReflinkResult::Reflinked => println!("snapshot shares its data with disk.img"),
+///         ReflinkResult::Copied => println!("snapshot is a full copy"),
+///     }
+///     Ok(())
+/// }
+/// ```
+#[unstable(feature = "unix_reflink", issue = "none")]
+pub fn reflink_or_copy<P: AsRef<Path>, Q: AsRef<Path>>(
+    from: P,
+    to: Q,
+) -> io::Result<ReflinkResult> {
+    let reflinked = sys::fs::reflink_or_copy(from.as_ref(), to.as_ref())?;
+    Ok(if reflinked { ReflinkResult::Reflinked } else { ReflinkResult::Copied })
+}
+
+/// Creates a new FIFO special file, also known as a named pipe, at `path`.
+///
+/// `mode` gives the permissions of the new FIFO, which are further restricted
+/// by the process's umask, as for a regular file. An error of kind
+/// [`io::ErrorKind::AlreadyExists`] is returned if something already exists
+/// at `path`.
+///
+/// # Opening a FIFO
+///
+/// A FIFO is opened with [`File::open`] or [`OpenOptions`] like any other
+/// file, but opening it is a rendezvous: by default, opening the read end
+/// blocks until some process opens the write end, and vice versa.
+///
+/// To open without waiting, pass `O_NONBLOCK` to
+/// [`OpenOptionsExt::custom_flags`]. Opening the read end then succeeds right
+/// away, even with no writer. Opening the write end succeeds if there is a
+/// reader, and otherwise fails with `ENXIO`, so a writer that must not block
+/// should open only once the reader is known to be there. The file stays in
+/// nonblocking mode afterwards, so reads and writes that cannot make progress
+/// fail with [`io::ErrorKind::WouldBlock`] instead of waiting.
+///
+/// [`File::open`]: fs::File::open
+///
+/// # Platform-specific behavior
+///
+/// This function currently corresponds to the `mkfifo` function on Unix.
+///
+/// # Examples
+///
+/// ```no_run
+/// # #![feature(rustc_private)]
+/// #![feature(unix_mkfifo)]
+/// extern crate libc;
+/// use std::fs::OpenOptions;
+/// use std::io::Read;
+/// use std::os::unix::fs::{self, OpenOptionsExt};
+///
+/// fn main() -> std::io::Result<()> {
+///     fs::mkfifo("/tmp/requests", 0o600)?;
+///     let mut requests =
+///         OpenOptions::new().read(true).custom_flags(libc::O_NONBLOCK).open("/tmp/requests")?;
+///     let mut buf = [0; 512];
+///     match requests.read(&mut buf) {
+///         Ok(n) => println!("read {} bytes", n),
+///         Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => println!("nothing yet"),
+///         Err(e) => return Err(e),
+///     }
+///     Ok(())
+/// }
+/// ```
+#[unstable(feature = "unix_mkfifo", issue = "none")]
+pub fn mkfifo<P: AsRef<Path>>(path: P, mode: u32) -> io::Result<()> {
+    sys::fs::mkfifo(path.as_ref(), mode)
+}
+
+/// A handle to an open directory, relative to which files can be opened,
+/// created and removed without ever leaving it.
+///
+/// Paths given to the `*_at` methods are resolved one component at a time
+/// relative to this directory, and none of the components are allowed to be
+/// symbolic links. Absolute paths and paths containing `..` are rejected with
+/// an error of kind [`io::ErrorKind::InvalidInput`]. Together, this ensures
+/// that path resolution never leaves the directory, even if an attacker
+/// controls both the path and the contents of the directory. Note that this
+/// does not stop the directory from containing hard links to files elsewhere,
+/// or mount points, which are followed like any other directory.
+///
+/// The directory is held open by descriptor, so it keeps referring to the
+/// same directory even if that is renamed or replaced afterwards.
+///
+/// # Platform-specific behavior
+///
+/// This type currently corresponds to a descriptor opened with
+/// `O_DIRECTORY`, with the methods using `openat`, `mkdirat`, `unlinkat`,
+/// `linkat`, `symlinkat` and `fdopendir`. Redox and VxWorks return an error of kind
+/// [`io::ErrorKind::Unsupported`].
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(unix_dir_fd)]
+/// use std::fs::OpenOptions;
+/// use std::io::Write;
+/// use std::os::unix::fs::Dir;
+///
+/// fn main() -> std::io::Result<()> {
+///     let root = Dir::open("/srv/uploads")?;
+///     root.mkdir_at("incoming", 0o755)?;
+///     let mut opts = OpenOptions::new();
+///     opts.write(true).create_new(true);
+///     root.open_at("incoming/report.txt", &opts)?.write_all(b"hello")?;
+///
+///     // Both of these are rejected without touching the filesystem.
+///     assert!(root.open_at("../etc/passwd", &opts).is_err());
+///     assert!(root.open_at("/etc/passwd", &opts).is_err());
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+#[unstable(feature = "unix_dir_fd", issue = "none")]
+pub struct Dir(sys::fs::DirFd);
+
+impl Dir {
+    /// Opens the directory at `path`.
+    ///
+    /// Unlike the paths given to the other methods, `path` is resolved as
+    /// usual, following any symbolic links.
+    #[unstable(feature = "unix_dir_fd", issue = "none")]
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Dir> {
+        sys::fs::DirFd::open(path.as_ref()).map(Dir)
+    }
+
+    /// Opens the file at `path` within this directory with the options
+    /// specified by `opts`.
+    ///
+    /// If the file itself is a symbolic link, this fails instead of opening
+    /// the target of the link.
+    #[unstable(feature = "unix_dir_fd", issue = "none")]
+    pub fn open_at<P: AsRef<Path>>(&self, path: P, opts: &OpenOptions) -> io::Result<fs::File> {
+        self.0.open_at(path.as_ref(), opts.as_inner()).map(fs::File::from_inner)
+    }
+
+    /// Opens the subdirectory at `path` within this directory.
+    ///
+    /// The returned `Dir` is confined to the subdirectory in turn.
+    #[unstable(feature = "unix_dir_fd", issue = "none")]
+    pub fn open_dir_at<P: AsRef<Path>>(&self, path: P) -> io::Result<Dir> {
+        self.0.open_dir_at(path.as_ref()).map(Dir)
+    }
+
+    /// Creates a new directory at `path` within this directory.
+    ///
+    /// `mode` gives the permissions of the new directory, which are further
+    /// restricted by the process's umask. Its parent must already exist.
+    #[unstable(feature = "unix_dir_fd", issue = "none")]
+    pub fn mkdir_at<P: AsRef<Path>>(&self, path: P, mode: u32) -> io::Result<()> {
+        self.0.mkdir_at(path.as_ref(), mode)
+    }
+
+    /// Removes the file at `path` within this directory.
+    ///
+    /// A symbolic link is removed itself, not its target. Directories
+    /// cannot be removed this way.
+    #[unstable(feature = "unix_dir_fd", issue = "none")]
+    pub fn unlink_at<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        self.0.unlink_at(path.as_ref())
+    }
+
+    /// Creates a new hard link at `link` within this directory, pointing to
+    /// the file at `original` within this directory.
+    ///
+    /// Both paths are confined to this directory like all others. Unlike
+    /// checking the paths first and then calling [`fs::hard_link`], this
+    /// leaves no window in which a directory along either path could be
+    /// swapped for a symbolic link leading elsewhere.
+    ///
+    /// If `original` is itself a symbolic link, the new link points to the
+    /// symbolic link, unless `follow_symlinks` is `true`, in which case it
+    /// points to the target of the symbolic link instead. That target is
+    /// resolved as usual, so it may well lie outside of this directory.
+    #[unstable(feature = "unix_dir_fd", issue = "none")]
+    pub fn link_at<P: AsRef<Path>, Q: AsRef<Path>>(
+        &self,
+        original: P,
+        link: Q,
+        follow_symlinks: bool,
+    ) -> io::Result<()> {
+        self.0.link_at(original.as_ref(), link.as_ref(), follow_symlinks)
+    }
+
+    /// Creates a new symbolic link at `link` within this directory, pointing
+    /// to `original`.
+    ///
+    /// As with any symbolic link, `original` is taken relative to the
+    /// directory containing the link. It must be a relative path that
+    /// doesn't lead out of this directory, such as `../file` for a link in a
+    /// subdirectory, or an error of kind [`io::ErrorKind::InvalidInput`] is
+    /// returned. This check only looks at the path itself, not at what it
+    /// refers to. As with [`link_at`], `link` is created without any window
+    /// in which it could be redirected outside of this directory.
+    ///
+    /// [`link_at`]: Dir::link_at
+    #[unstable(feature = "unix_dir_fd", issue = "none")]
+    pub fn symlink_at<P: AsRef<Path>, Q: AsRef<Path>>(
+        &self,
+        original: P,
+        link: Q,
+    ) -> io::Result<()> {
+        self.0.symlink_at(original.as_ref(), link.as_ref())
+    }
+
+    /// Returns an iterator over the entries of this directory.
+    ///
+    /// The paths of the entries are built from the path this directory was
+    /// opened with, so they are only meaningful as long as that still names
+    /// this directory. Each call reads the directory from the start.
+    #[unstable(feature = "unix_dir_fd", issue = "none")]
+    pub fn read_dir(&self) -> io::Result<fs::ReadDir> {
+        self.0.readdir().map(fs::ReadDir::from_inner)
+    }
+}
+
+#[unstable(feature = "unix_dir_fd", issue = "none")]
+impl AsRawFd for Dir {
+    #[inline]
+    fn as_raw_fd(&self) -> RawFd {
+        self.0.fd().raw()
+    }
+}
@@ -0,0 +1,294 @@
+use super::*;
+use crate::io::ErrorKind;
+use crate::sys_common::io::test::tmpdir;
+
+macro_rules! or_unsupported {
+    ($e:expr) => {
+        match $e {
+            Err(ref e) if e.kind() == ErrorKind::Unsupported => return,
+            other => other,
+        }
+    };
+}
+
+#[test]
+fn rename_noreplace_fails_if_target_exists() {
+    let tmpdir = tmpdir();
+    let from = tmpdir.join("from");
+    let to = tmpdir.join("to");
+    fs::write(&from, b"from").unwrap();
+    fs::write(&to, b"to").unwrap();
+
+    let err = or_unsupported!(rename_noreplace(&from, &to)).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::AlreadyExists);
+    assert_eq!(fs::read(&from).unwrap(), b"from");
+    assert_eq!(fs::read(&to).unwrap(), b"to");
+
+    fs::remove_file(&to).unwrap();
+    rename_noreplace(&from, &to).unwrap();
+    assert!(!from.exists());
+    assert_eq!(fs::read(&to).unwrap(), b"from");
+}
+
+#[test]
+fn rename_exchange_swaps_contents() {
+    let tmpdir = tmpdir();
+    let a = tmpdir.join("a");
+    let b = tmpdir.join("b");
+    fs::write(&a, b"aaa").unwrap();
+    fs::write(&b, b"bb").unwrap();
+
+    or_unsupported!(rename_exchange(&a, &b)).unwrap();
+    assert_eq!(fs::read(&a).unwrap(), b"bb");
+    assert_eq!(fs::read(&b).unwrap(), b"aaa");
+}
+
+#[test]
+fn sync_range_after_write() {
+    let tmpdir = tmpdir();
+    let path = tmpdir.join("journal");
+    let file = fs::OpenOptions::new().read(true).write(true).create(true).open(&path).unwrap();
+
+    file.write_all_at(b"header", 0).unwrap();
+    file.write_all_at(b"record", 4096).unwrap();
+    // SYNC_FILE_RANGE_WAIT_BEFORE | SYNC_FILE_RANGE_WRITE | SYNC_FILE_RANGE_WAIT_AFTER
+    file.sync_range(4096, 6, 1 | 2 | 4).unwrap();
+    // A zero length flushes through to the end of the file.
+    file.sync_range(0, 0, 2).unwrap();
+
+    let mut buf = [0; 6];
+    file.read_exact_at(&mut buf, 4096).unwrap();
+    assert_eq!(&buf, b"record");
+}
+
+#[test]
+fn statvfs_tmpdir() {
+    let tmpdir = tmpdir();
+    let stats = or_unsupported!(statvfs(tmpdir.path())).unwrap();
+    assert!(stats.block_size() > 0);
+    assert!(stats.total_bytes() > 0);
+    assert!(stats.free_bytes() <= stats.total_bytes());
+    assert!(stats.available_bytes() <= stats.total_bytes());
+    assert!(stats.free_inodes() <= stats.total_inodes());
+
+    let err = statvfs(tmpdir.join("missing")).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::NotFound);
+}
+
+#[test]
+fn read_link_into_reuses_and_grows_buffer() {
+    let tmpdir = tmpdir();
+    let target = "a".repeat(300);
+    let link = tmpdir.join("link");
+    symlink(&target, &link).unwrap();
+
+    let mut buf = Vec::with_capacity(1024);
+    let ptr = buf.as_ptr();
+    assert_eq!(read_link_into(&link, &mut buf).unwrap(), 300);
+    assert_eq!(buf, target.as_bytes());
+    assert_eq!(buf.as_ptr(), ptr);
+
+    // A buffer that can't hold the whole target must not come back truncated.
+    let mut buf = b"leftover".to_vec();
+    buf.shrink_to_fit();
+    assert_eq!(read_link_into(&link, &mut buf).unwrap(), 300);
+    assert_eq!(buf, target.as_bytes());
+
+    let err = read_link_into(tmpdir.join("missing"), &mut buf).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::NotFound);
+}
+
+#[test]
+fn mkfifo_passes_bytes_nonblocking() {
+    use crate::io::{Read, Write};
+
+    let tmpdir = tmpdir();
+    let path = tmpdir.join("fifo");
+    mkfifo(&path, 0o600).unwrap();
+    assert!(fs::metadata(&path).unwrap().file_type().is_fifo());
+
+    let err = mkfifo(&path, 0o600).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::AlreadyExists);
+
+    // Without a reader, a nonblocking open of the write end fails...
+    let nonblocking_write =
+        || OpenOptions::new().write(true).custom_flags(libc::O_NONBLOCK).open(&path);
+    assert_eq!(nonblocking_write().unwrap_err().raw_os_error(), Some(libc::ENXIO));
+
+    // ...but the read end can be opened without a writer, and once it is
+    // open the write end can be too.
+    let mut reader =
+        OpenOptions::new().read(true).custom_flags(libc::O_NONBLOCK).open(&path).unwrap();
+    let mut buf = [0; 4];
+    assert_eq!(reader.read(&mut buf).unwrap(), 0);
+    let mut writer = nonblocking_write().unwrap();
+    assert_eq!(reader.read(&mut buf).unwrap_err().kind(), ErrorKind::WouldBlock);
+
+    writer.write_all(b"!").unwrap();
+    assert_eq!(reader.read(&mut buf).unwrap(), 1);
+    assert_eq!(buf[0], b'!');
+}
+
+#[test]
+fn dir_fd_stays_within_directory() {
+    use crate::io::{Read, Write};
+
+    let tmpdir = tmpdir();
+    let root = tmpdir.join("root");
+    fs::create_dir(&root).unwrap();
+    fs::write(tmpdir.join("secret"), b"secret").unwrap();
+    let dir = or_unsupported!(Dir::open(&root)).unwrap();
+
+    dir.mkdir_at("sub", 0o755).unwrap();
+    let mut opts = OpenOptions::new();
+    opts.write(true).create_new(true);
+    dir.open_at("sub/./file", &opts).unwrap().write_all(b"inside").unwrap();
+    assert_eq!(fs::read(root.join("sub/file")).unwrap(), b"inside");
+
+    let mut contents = String::new();
+    let sub = dir.open_dir_at("sub").unwrap();
+    sub.open_at("file", OpenOptions::new().read(true))
+        .unwrap()
+        .read_to_string(&mut contents)
+        .unwrap();
+    assert_eq!(contents, "inside");
+    let names: Vec<_> = sub.read_dir().unwrap().map(|e| e.unwrap().file_name()).collect();
+    assert_eq!(names, ["file"]);
+    assert_eq!(dir.read_dir().unwrap().count(), 1);
+
+    // Paths that could name something outside the directory are rejected.
+    let read = OpenOptions::new().read(true).clone();
+    for path in &["../secret", "sub/../../secret", "/etc/passwd", "", "."] {
+        let err = dir.open_at(path, &read).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput, "{:?}", path);
+    }
+    assert_eq!(dir.mkdir_at("../escaped", 0o755).unwrap_err().kind(), ErrorKind::InvalidInput);
+    assert_eq!(dir.unlink_at("../secret").unwrap_err().kind(), ErrorKind::InvalidInput);
+    assert!(tmpdir.join("secret").exists());
+
+    // So are symlinks, whether they are the last component or not.
+    symlink(tmpdir.join("secret"), root.join("link")).unwrap();
+    symlink(tmpdir.path(), root.join("dirlink")).unwrap();
+    assert!(dir.open_at("link", &read).is_err());
+    assert!(dir.open_at("dirlink/secret", &read).is_err());
+    assert!(dir.open_dir_at("dirlink").is_err());
+
+    // Removing a symlink removes the link, not its target.
+    dir.unlink_at("link").unwrap();
+    dir.unlink_at("sub/file").unwrap();
+    assert!(!root.join("link").exists());
+    assert!(!root.join("sub/file").exists());
+    assert!(tmpdir.join("secret").exists());
+}
+
+#[test]
+fn dir_fd_links_stay_within_directory() {
+    let tmpdir = tmpdir();
+    let root = tmpdir.join("root");
+    fs::create_dir(&root).unwrap();
+    fs::write(tmpdir.join("secret"), b"secret").unwrap();
+    let dir = or_unsupported!(Dir::open(&root)).unwrap();
+
+    dir.mkdir_at("sub", 0o755).unwrap();
+    fs::write(root.join("file"), b"inside").unwrap();
+
+    dir.link_at("file", "sub/hard", false).unwrap();
+    assert_eq!(fs::read(root.join("sub/hard")).unwrap(), b"inside");
+    let (file, hard) = (fs::metadata(root.join("file")), fs::metadata(root.join("sub/hard")));
+    assert_eq!(file.unwrap().ino(), hard.unwrap().ino());
+
+    dir.symlink_at("../file", "sub/soft").unwrap();
+    assert_eq!(fs::read_link(root.join("sub/soft")).unwrap(), Path::new("../file"));
+    assert_eq!(fs::read(root.join("sub/soft")).unwrap(), b"inside");
+
+    // Hard links to a symlink point to the symlink itself unless asked to
+    // follow it.
+    dir.link_at("sub/soft", "sub/soft2", false).unwrap();
+    assert!(fs::symlink_metadata(root.join("sub/soft2")).unwrap().file_type().is_symlink());
+    dir.link_at("sub/soft", "hard2", true).unwrap();
+    assert!(fs::symlink_metadata(root.join("hard2")).unwrap().is_file());
+
+    // Neither end may be outside the directory, and neither may a symlink
+    // target.
+    let escapes: &[(&str, &str)] =
+        &[("../secret", "stolen"), ("file", "../planted"), ("/etc/passwd", "stolen")];
+    for &(original, link) in escapes {
+        let err = dir.link_at(original, link, false).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput, "{} -> {}", link, original);
+    }
+    for &target in &["../secret", "sub/../../secret", "/etc/passwd"] {
+        let err = dir.symlink_at(target, "stolen").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput, "{}", target);
+    }
+    let err = dir.symlink_at("../../secret", "sub/stolen").unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    assert!(!root.join("stolen").exists());
+    assert!(!tmpdir.join("planted").exists());
+}
+
+#[test]
+fn reflink_or_copy_copies_contents_and_permissions() {
+    use crate::os::unix::fs::PermissionsExt;
+
+    let tmpdir = tmpdir();
+    let from = tmpdir.join("from");
+    let to = tmpdir.join("to");
+    fs::write(&from, b"hello, reflink").unwrap();
+    fs::set_permissions(&from, Permissions::from_mode(0o640)).unwrap();
+    fs::write(&to, b"a longer file that gets replaced").unwrap();
+
+    // Whether the filesystem of the temporary directory can clone files
+    // isn't known here, but either way the result must be a full copy.
+    reflink_or_copy(&from, &to).unwrap();
+    assert_eq!(fs::read(&to).unwrap(), b"hello, reflink");
+    assert_eq!(fs::metadata(&to).unwrap().permissions().mode() & 0o777, 0o640);
+
+    // The clone doesn't change with the original.
+    fs::write(&from, b"changed").unwrap();
+    assert_eq!(fs::read(&to).unwrap(), b"hello, reflink");
+
+    let err = reflink_or_copy(tmpdir.join("missing"), tmpdir.join("other")).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::NotFound);
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn reflink_or_copy_falls_back_without_clone_support() {
+    // tmpfs can't clone files, and a clone from another filesystem isn't
+    // possible either, so this always takes the fallback path.
+    let shm = Path::new("/dev/shm");
+    if !shm.is_dir() {
+        return;
+    }
+    let tmpdir = tmpdir();
+    let from = tmpdir.join("from");
+    fs::write(&from, b"copied in full").unwrap();
+    let to = shm.join(format!("rust-reflink-test-{}", crate::process::id()));
+
+    let result = reflink_or_copy(&from, &to);
+    let contents = fs::read(&to);
+    let _ = fs::remove_file(&to);
+    assert_eq!(result.unwrap(), ReflinkResult::Copied);
+    assert_eq!(contents.unwrap(), b"copied in full");
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn reflink_or_copy_clones_on_supporting_filesystem() {
+    // Set `RUST_TEST_REFLINK_DIR` to a directory on btrfs or XFS to check
+    // that a clone is made there.
+    let dir = match crate::env::var_os("RUST_TEST_REFLINK_DIR") {
+        Some(dir) => crate::path::PathBuf::from(dir),
+        None => return,
+    };
+    let from = dir.join(format!("rust-reflink-from-{}", crate::process::id()));
+    let to = dir.join(format!("rust-reflink-to-{}", crate::process::id()));
+    fs::write(&from, b"shared extents").unwrap();
+
+    let result = reflink_or_copy(&from, &to);
+    let contents = fs::read(&to);
+    let _ = fs::remove_file(&from);
+    let _ = fs::remove_file(&to);
+    assert_eq!(result.unwrap(), ReflinkResult::Reflinked);
+    assert_eq!(contents.unwrap(), b"shared extents");
+}
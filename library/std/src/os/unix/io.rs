@@ -193,3 +193,40 @@ fn as_raw_fd(&self) -> RawFd {
         libc::STDERR_FILENO
     }
 }
+
+/// Turns line buffering of the standard error stream on or off.
+///
+/// Standard error is unbuffered by default, so that everything written to it
+/// reaches the file descriptor right away and survives a crash. The flip side
+/// is that every write is a system call, and formatting a single line with
+/// [`eprintln!`] can take several writes. With buffering turned on, writes to
+/// [`io::stderr`] are collected and written out once per line instead, or
+/// sooner when a partial line outgrows the buffer.
+///
+/// Buffered output is flushed when a newline is written, when
+/// [`Write::flush`] is called on [`io::stderr`], when buffering is turned off
+/// again, and when the program exits normally, including through
+/// [`process::exit`]. A partial line still in the buffer is lost if the
+/// program is killed or aborts, so only turn this on when losing the tail of
+/// the output in a crash is acceptable.
+///
+/// Turning buffering off returns any error from flushing the buffer.
+///
+/// [`Write::flush`]: io::Write::flush
+/// [`process::exit`]: crate::process::exit
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(unix_stderr_buffering)]
+/// use std::os::unix::io::set_stderr_buffered;
+///
+/// set_stderr_buffered(true).unwrap();
+/// for i in 0..1000 {
+///     eprintln!("event={} status=ok", i);
+/// }
+/// ```
+#[unstable(feature = "unix_stderr_buffering", issue = "none")]
+pub fn set_stderr_buffered(buffered: bool) -> io::Result<()> {
+    sys::stdio::set_stderr_buffered(buffered)
+}
@@ -74,6 +74,9 @@ mod platform {
 pub mod raw;
 pub mod thread;
 
+#[unstable(feature = "unix_uname", issue = "none")]
+pub mod uname;
+
 #[unstable(feature = "peer_credentials_unix_socket", issue = "42839", reason = "unstable")]
 #[cfg(any(
     target_os = "android",
@@ -82,7 +85,9 @@ mod platform {
     target_os = "freebsd",
     target_os = "ios",
     target_os = "macos",
-    target_os = "openbsd"
+    target_os = "openbsd",
+    target_os = "solaris",
+    target_os = "illumos"
 ))]
 pub mod ucred;
 
@@ -108,6 +108,83 @@ pub(super) fn send_vectored_with_ancillary_to(
     }
 }
 
+#[cfg(any(doc, target_os = "android", target_os = "linux",))]
+pub(super) fn send_vectored_with_fds_and_creds(
+    socket: &Socket,
+    bufs: &[IoSlice<'_>],
+    fds: &[RawFd],
+    creds: Option<&SocketCred>,
+) -> io::Result<usize> {
+    let mut capacity = 0;
+    if !fds.is_empty() {
+        capacity += cmsg_space::<RawFd>(fds.len())?;
+    }
+    if creds.is_some() {
+        capacity += cmsg_space::<libc::ucred>(1)?;
+    }
+
+    let mut buffer = vec![0; capacity];
+    let mut ancillary = SocketAncillary::new(&mut buffer);
+    // The buffer is sized exactly for these messages, so adding them cannot fail.
+    if !fds.is_empty() {
+        assert!(ancillary.add_fds(fds));
+    }
+    if let Some(creds) = creds {
+        assert!(ancillary.add_creds(crate::slice::from_ref(creds)));
+    }
+    send_vectored_with_ancillary_to(socket, None, bufs, &mut ancillary)
+}
+
+#[cfg(any(doc, target_os = "android", target_os = "linux",))]
+pub(super) fn recv_vectored_with_fds_and_creds(
+    socket: &Socket,
+    bufs: &mut [IoSliceMut<'_>],
+    max_fds: usize,
+) -> io::Result<ReceivedMessage> {
+    let capacity = cmsg_space::<RawFd>(max_fds)? + cmsg_space::<libc::ucred>(1)?;
+    let mut buffer = vec![0; capacity];
+    let mut ancillary = SocketAncillary::new(&mut buffer);
+    let (len, truncated, _) = recv_vectored_with_ancillary_from(socket, bufs, &mut ancillary)?;
+
+    let mut fds = Vec::new();
+    let mut creds = None;
+    // Anything that did arrive is collected even if the control buffer was
+    // truncated: those descriptors are already installed in this process and
+    // would leak if they were not handed to the caller.
+    for message in ancillary.messages() {
+        match message {
+            Ok(AncillaryData::ScmRights(rights)) => fds.extend(rights),
+            Ok(AncillaryData::ScmCredentials(mut cred)) => {
+                if let Some(cred) = cred.next() {
+                    creds = Some(cred);
+                }
+            }
+            Err(_) => {}
+        }
+    }
+
+    Ok(ReceivedMessage {
+        len,
+        truncated,
+        fds,
+        creds,
+        ancillary_truncated: ancillary.truncated(),
+    })
+}
+
+/// Returns the control buffer space needed for one message holding `count`
+/// values of type `T`.
+#[cfg(any(doc, target_os = "android", target_os = "linux",))]
+fn cmsg_space<T>(count: usize) -> io::Result<usize> {
+    count
+        .checked_mul(size_of::<T>())
+        .and_then(|len| u32::try_from(len).ok())
+        .map(|len| unsafe { libc::CMSG_SPACE(len) as usize })
+        .ok_or_else(|| {
+            io::Error::new_const(io::ErrorKind::InvalidInput, &"too much ancillary data")
+        })
+}
+
 fn add_to_ancillary_data<T>(
     buffer: &mut [u8],
     length: &mut usize,
@@ -288,6 +365,80 @@ pub fn get_gid(&self) -> libc::gid_t {
     }
 }
 
+/// A message received together with its file descriptors and credentials.
+///
+/// This is returned by the `recv_vectored_with_fds_and_creds` methods of
+/// [`UnixStream`] and [`UnixDatagram`].
+///
+/// [`UnixStream`]: super::UnixStream
+/// [`UnixDatagram`]: super::UnixDatagram
+#[cfg(any(doc, target_os = "android", target_os = "linux",))]
+#[unstable(feature = "unix_socket_fds_and_creds", issue = "none")]
+pub struct ReceivedMessage {
+    len: usize,
+    truncated: bool,
+    fds: Vec<RawFd>,
+    creds: Option<SocketCred>,
+    ancillary_truncated: bool,
+}
+
+#[cfg(any(doc, target_os = "android", target_os = "linux",))]
+impl ReceivedMessage {
+    /// Returns the number of bytes of data received.
+    #[unstable(feature = "unix_socket_fds_and_creds", issue = "none")]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no data was received.
+    #[unstable(feature = "unix_socket_fds_and_creds", issue = "none")]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns `true` if the data did not fit in the given buffers and the
+    /// rest of the datagram was discarded.
+    #[unstable(feature = "unix_socket_fds_and_creds", issue = "none")]
+    pub fn truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// Returns the file descriptors received with the message.
+    ///
+    /// These descriptors are open in this process and the caller is
+    /// responsible for closing them.
+    #[unstable(feature = "unix_socket_fds_and_creds", issue = "none")]
+    pub fn fds(&self) -> &[RawFd] {
+        &self.fds
+    }
+
+    /// Consumes the message, returning the received file descriptors.
+    #[unstable(feature = "unix_socket_fds_and_creds", issue = "none")]
+    pub fn into_fds(self) -> Vec<RawFd> {
+        self.fds
+    }
+
+    /// Returns the credentials received with the message, if any.
+    ///
+    /// Credentials are only received if `SO_PASSCRED` is enabled on the
+    /// receiving socket.
+    #[unstable(feature = "unix_socket_fds_and_creds", issue = "none")]
+    pub fn creds(&self) -> Option<&SocketCred> {
+        self.creds.as_ref()
+    }
+
+    /// Returns `true` if the control buffer was too small for all of the
+    /// ancillary data sent with the message.
+    ///
+    /// The kernel discards whatever does not fit, including file descriptors
+    /// beyond the `max_fds` passed when receiving. Any descriptors that did
+    /// fit are still returned by [`fds`](ReceivedMessage::fds).
+    #[unstable(feature = "unix_socket_fds_and_creds", issue = "none")]
+    pub fn ancillary_truncated(&self) -> bool {
+        self.ancillary_truncated
+    }
+}
+
 /// This control message contains file descriptors.
 ///
 /// The level is equal to `SOL_SOCKET` and the type is equal to `SCM_RIGHTS`.
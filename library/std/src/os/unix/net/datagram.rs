@@ -9,6 +9,11 @@
     target_os = "openbsd",
 ))]
 use super::{recv_vectored_with_ancillary_from, send_vectored_with_ancillary_to, SocketAncillary};
+#[cfg(any(doc, target_os = "android", target_os = "linux",))]
+use super::{
+    recv_vectored_with_fds_and_creds, send_vectored_with_fds_and_creds, ReceivedMessage,
+    SocketCred,
+};
 use super::{sockaddr_un, SocketAddr};
 #[cfg(any(
     target_os = "android",
@@ -22,11 +27,18 @@
 use crate::io::{IoSlice, IoSliceMut};
 use crate::net::Shutdown;
 use crate::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
+#[cfg(any(
+    target_os = "android",
+    target_os = "linux",
+    target_os = "solaris",
+    target_os = "illumos"
+))]
+use crate::os::unix::ucred::{self, UCred};
 use crate::path::Path;
 use crate::sys::cvt;
 use crate::sys::net::Socket;
 use crate::sys_common::{AsInner, FromInner, IntoInner};
-use crate::time::Duration;
+use crate::time::{Duration, SystemTime, UNIX_EPOCH};
 use crate::{fmt, io};
 
 #[cfg(any(
@@ -256,6 +268,47 @@ pub fn peer_addr(&self) -> io::Result<SocketAddr> {
         SocketAddr::new(|addr, len| unsafe { libc::getpeername(*self.0.as_inner(), addr, len) })
     }
 
+    /// Gets the peer credentials for this Unix datagram socket.
+    ///
+    /// The credentials are those of the process that created the other end
+    /// of the socket, as with [`UnixStream::peer_cred`].
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// On Linux and Android only sockets created by [`pair`] carry their
+    /// peer's credentials. A socket connected with [`connect`] has none, and
+    /// this returns an error for it.
+    ///
+    /// The BSDs, including macOS and iOS, only record the credentials of
+    /// connection-oriented sockets, so this method is not available there.
+    ///
+    /// [`UnixStream::peer_cred`]: super::UnixStream::peer_cred
+    /// [`pair`]: UnixDatagram::pair
+    /// [`connect`]: UnixDatagram::connect
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(peer_credentials_unix_socket)]
+    /// use std::os::unix::net::UnixDatagram;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let (sock, _peer) = UnixDatagram::pair()?;
+    ///     let peer_cred = sock.peer_cred().expect("Couldn't get peer credentials");
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "peer_credentials_unix_socket", issue = "42839", reason = "unstable")]
+    #[cfg(any(
+        target_os = "android",
+        target_os = "linux",
+        target_os = "solaris",
+        target_os = "illumos"
+    ))]
+    pub fn peer_cred(&self) -> io::Result<UCred> {
+        ucred::peer_cred(self)
+    }
+
     fn recv_from_flags(
         &self,
         buf: &mut [u8],
@@ -327,6 +380,74 @@ pub fn recv(&self, buf: &mut [u8]) -> io::Result<usize> {
         self.0.read(buf)
     }
 
+    /// Turns the `SO_TIMESTAMPNS` option of this socket on or off.
+    ///
+    /// While it is on, the kernel takes a timestamp of every datagram as it
+    /// arrives, which [`recv_with_timestamp`] then reports. Datagrams that
+    /// were already queued when it was turned on may carry no timestamp, or
+    /// one that doesn't reflect their arrival.
+    ///
+    /// [`recv_with_timestamp`]: UnixDatagram::recv_with_timestamp
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This method is currently only supported on Linux and Android. Other
+    /// platforms return an error of kind [`io::ErrorKind::Unsupported`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(unix_recv_timestamp)]
+    /// use std::os::unix::net::UnixDatagram;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let sock = UnixDatagram::bind("/path/to/the/socket")?;
+    ///     sock.set_timestamping(true)?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "unix_recv_timestamp", issue = "none")]
+    pub fn set_timestamping(&self, enable: bool) -> io::Result<()> {
+        self.0.set_timestamping(enable)
+    }
+
+    /// Receives a single datagram, along with the time at which the kernel
+    /// received it.
+    ///
+    /// This works like [`recv`], but also reads the timestamp from the
+    /// `SCM_TIMESTAMPNS` control message, which the kernel attaches once
+    /// timestamping has been turned on with [`set_timestamping`]. The
+    /// timestamp is `None` if the kernel didn't attach one to the datagram.
+    ///
+    /// [`recv`]: UnixDatagram::recv
+    /// [`set_timestamping`]: UnixDatagram::set_timestamping
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This method is currently only supported on Linux and Android. Other
+    /// platforms return an error of kind [`io::ErrorKind::Unsupported`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(unix_recv_timestamp)]
+    /// use std::os::unix::net::UnixDatagram;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let sock = UnixDatagram::bind("/path/to/the/socket")?;
+    ///     sock.set_timestamping(true)?;
+    ///     let mut buf = [0; 64];
+    ///     let (len, received_at) = sock.recv_with_timestamp(&mut buf)?;
+    ///     println!("{} bytes received at {:?}", len, received_at);
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "unix_recv_timestamp", issue = "none")]
+    pub fn recv_with_timestamp(&self, buf: &mut [u8]) -> io::Result<(usize, Option<SystemTime>)> {
+        let (len, timestamp) = self.0.recv_with_timestamp(buf)?;
+        Ok((len, timestamp.map(|t| UNIX_EPOCH + t)))
+    }
+
     /// Receives data and ancillary data from socket.
     ///
     /// On success, returns the number of bytes read, if the data was truncated and the address from whence the msg came.
@@ -594,6 +715,88 @@ pub fn send_vectored_with_ancillary(
         send_vectored_with_ancillary_to(&self.0, None, bufs, ancillary)
     }
 
+    /// Sends data, file descriptors and credentials on the socket in a single
+    /// `sendmsg` call.
+    ///
+    /// This is a shorthand for building a [`SocketAncillary`] holding an
+    /// `SCM_RIGHTS` message with `fds` (if not empty) and an `SCM_CREDENTIALS`
+    /// message with `creds` (if given), and passing it to
+    /// [`send_vectored_with_ancillary`]. The control buffer is allocated at
+    /// exactly the required size.
+    ///
+    /// On success, returns the number of bytes written.
+    ///
+    /// [`send_vectored_with_ancillary`]: UnixDatagram::send_vectored_with_ancillary
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(unix_socket_ancillary_data, unix_socket_fds_and_creds)]
+    /// use std::io::IoSlice;
+    /// use std::os::unix::io::AsRawFd;
+    /// use std::os::unix::net::{SocketCred, UnixDatagram};
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let socket = UnixDatagram::bind("/tmp/sock")?;
+    ///     let mut creds = SocketCred::new();
+    ///     creds.set_pid(std::process::id() as i32);
+    ///     let fds = [std::io::stdin().as_raw_fd()];
+    ///     socket.send_vectored_with_fds_and_creds(&[IoSlice::new(b"hello")], &fds, Some(&creds))?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(any(doc, target_os = "android", target_os = "linux",))]
+    #[unstable(feature = "unix_socket_fds_and_creds", issue = "none")]
+    pub fn send_vectored_with_fds_and_creds(
+        &self,
+        bufs: &[IoSlice<'_>],
+        fds: &[RawFd],
+        creds: Option<&SocketCred>,
+    ) -> io::Result<usize> {
+        send_vectored_with_fds_and_creds(&self.0, bufs, fds, creds)
+    }
+
+    /// Receives data together with any file descriptors and credentials sent
+    /// with it, in a single `recvmsg` call.
+    ///
+    /// Room is made for up to `max_fds` file descriptors and one set of
+    /// credentials. If the sender passed more than that, the kernel discards
+    /// the excess and [`ReceivedMessage::ancillary_truncated`] returns `true`;
+    /// the descriptors that did arrive are still returned so they can be
+    /// closed. Credentials are only received if [`set_passcred`] was enabled
+    /// on this socket.
+    ///
+    /// [`set_passcred`]: UnixDatagram::set_passcred
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(unix_socket_ancillary_data, unix_socket_fds_and_creds)]
+    /// use std::io::IoSliceMut;
+    /// use std::os::unix::net::UnixDatagram;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let socket = UnixDatagram::bind("/tmp/sock")?;
+    ///     socket.set_passcred(true)?;
+    ///     let mut buf = [0; 64];
+    ///     let msg = socket.recv_vectored_with_fds_and_creds(&mut [IoSliceMut::new(&mut buf)], 4)?;
+    ///     println!("{} bytes, fds {:?}", msg.len(), msg.fds());
+    ///     if let Some(creds) = msg.creds() {
+    ///         println!("from pid {}", creds.get_pid());
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(any(doc, target_os = "android", target_os = "linux",))]
+    #[unstable(feature = "unix_socket_fds_and_creds", issue = "none")]
+    pub fn recv_vectored_with_fds_and_creds(
+        &self,
+        bufs: &mut [IoSliceMut<'_>],
+        max_fds: usize,
+    ) -> io::Result<ReceivedMessage> {
+        recv_vectored_with_fds_and_creds(&self.0, bufs, max_fds)
+    }
+
     /// Sets the read timeout for the socket.
     ///
     /// If the provided value is [`None`], then [`recv`] and [`recv_from`] calls will
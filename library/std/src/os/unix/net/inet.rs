@@ -0,0 +1,504 @@
+//! Unix-specific extensions to the TCP and UDP types in [`std::net`].
+//!
+//! [`std::net`]: crate::net
+
+use crate::fmt;
+use crate::io;
+use crate::net::{SocketAddr, TcpStream, UdpSocket};
+use crate::sealed::Sealed;
+use crate::sys::net::{tcp_info, Socket};
+use crate::sys_common::{net as net_imp, AsInner, FromInner};
+use crate::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Unix-specific extensions to [`TcpStream`].
+///
+/// This trait is sealed: it cannot be implemented outside the standard library.
+/// This is so that future additional methods are not breaking changes.
+#[unstable(feature = "unix_socket_buffer_size", issue = "none")]
+pub trait TcpStreamExt: Sealed {
+    /// Sets the size of the kernel receive buffer for this socket, in bytes.
+    ///
+    /// This sets the `SO_RCVBUF` option. Values larger than `i32::MAX` are
+    /// clamped to `i32::MAX`, and the kernel may further clamp the value to
+    /// a system-wide limit.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// On Linux the kernel doubles the requested value to leave room for
+    /// bookkeeping overhead, and [`recv_buffer_size`] reports the doubled
+    /// value. Do not expect the getter to return exactly what was set.
+    ///
+    /// [`recv_buffer_size`]: TcpStreamExt::recv_buffer_size
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(unix_socket_buffer_size)]
+    /// use std::net::TcpStream;
+    /// use std::os::unix::net::TcpStreamExt;
+    ///
+    /// let stream = TcpStream::connect("127.0.0.1:8080").expect("Couldn't connect to the server");
+    /// stream.set_recv_buffer_size(4 * 1024 * 1024).expect("set_recv_buffer_size call failed");
+    /// ```
+    #[unstable(feature = "unix_socket_buffer_size", issue = "none")]
+    fn set_recv_buffer_size(&self, size: usize) -> io::Result<()>;
+
+    /// Gets the size of the kernel receive buffer for this socket, in bytes.
+    ///
+    /// This reads the `SO_RCVBUF` option. On Linux this is twice the value
+    /// passed to [`set_recv_buffer_size`].
+    ///
+    /// [`set_recv_buffer_size`]: TcpStreamExt::set_recv_buffer_size
+    #[unstable(feature = "unix_socket_buffer_size", issue = "none")]
+    fn recv_buffer_size(&self) -> io::Result<usize>;
+
+    /// Sets the size of the kernel send buffer for this socket, in bytes.
+    ///
+    /// This sets the `SO_SNDBUF` option. Values larger than `i32::MAX` are
+    /// clamped to `i32::MAX`. As with [`set_recv_buffer_size`], Linux doubles
+    /// the requested value.
+    ///
+    /// [`set_recv_buffer_size`]: TcpStreamExt::set_recv_buffer_size
+    #[unstable(feature = "unix_socket_buffer_size", issue = "none")]
+    fn set_send_buffer_size(&self, size: usize) -> io::Result<()>;
+
+    /// Gets the size of the kernel send buffer for this socket, in bytes.
+    ///
+    /// This reads the `SO_SNDBUF` option. On Linux this is twice the value
+    /// passed to [`set_send_buffer_size`].
+    ///
+    /// [`set_send_buffer_size`]: TcpStreamExt::set_send_buffer_size
+    #[unstable(feature = "unix_socket_buffer_size", issue = "none")]
+    fn send_buffer_size(&self) -> io::Result<usize>;
+
+    /// Opens a TCP connection to `remote` from the local address `local`.
+    ///
+    /// This binds the socket to `local` before connecting, which lets a client
+    /// on a multi-homed host choose the source IP address of the connection.
+    /// Passing port 0 in `local` lets the system pick an ephemeral port.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// On Linux, if the port in `local` is 0, the `IP_BIND_ADDRESS_NO_PORT`
+    /// option is set before binding. This defers choosing the ephemeral port
+    /// until the connection is made, so the same port can be shared by
+    /// connections to different destinations instead of exhausting the port
+    /// range. On kernels older than 4.2, and on other platforms, the socket is
+    /// simply bound and then connected, which reserves the port at bind time.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(unix_socket_buffer_size, unix_tcp_connect_from)]
+    /// use std::net::TcpStream;
+    /// use std::os::unix::net::TcpStreamExt;
+    ///
+    /// let local = "192.168.1.20:0".parse().unwrap();
+    /// let remote = "192.168.1.1:80".parse().unwrap();
+    /// let stream = TcpStream::connect_from(local, remote).expect("Couldn't connect");
+    /// ```
+    #[unstable(feature = "unix_tcp_connect_from", issue = "none")]
+    fn connect_from(local: SocketAddr, remote: SocketAddr) -> io::Result<TcpStream>;
+
+    /// Opens a TCP connection to whichever of `addrs` answers first.
+    ///
+    /// This follows the "Happy Eyeballs" algorithm of RFC 8305. Addresses are
+    /// tried in an order that alternates between IPv6 and IPv4, starting with
+    /// the family of the first address in `addrs`. A new attempt is started
+    /// every 250 milliseconds, or immediately when an earlier attempt fails,
+    /// while earlier attempts keep running. The first attempt to connect wins
+    /// and all others are abandoned, so a host whose preferred address family
+    /// is broken connects over the other family after a short delay instead
+    /// of waiting for a full connect timeout.
+    ///
+    /// `timeout` bounds the whole operation. If no attempt succeeds within
+    /// it, an error of kind [`TimedOut`](io::ErrorKind::TimedOut) is
+    /// returned. If every attempt fails, the error from the last failure is
+    /// returned.
+    ///
+    /// An error of kind [`InvalidInput`](io::ErrorKind::InvalidInput) is
+    /// returned if `addrs` is empty or if `timeout` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(unix_socket_buffer_size, unix_tcp_connect_any)]
+    /// use std::net::{TcpStream, ToSocketAddrs};
+    /// use std::os::unix::net::TcpStreamExt;
+    /// use std::time::Duration;
+    ///
+    /// let addrs: Vec<_> = "example.com:80".to_socket_addrs().unwrap().collect();
+    /// let stream = TcpStream::connect_any(&addrs, Duration::from_secs(10))
+    ///     .expect("Couldn't connect to the server");
+    /// ```
+    #[unstable(feature = "unix_tcp_connect_any", issue = "none")]
+    fn connect_any(addrs: &[SocketAddr], timeout: Duration) -> io::Result<TcpStream>;
+
+    /// Sets how long transmitted data may remain unacknowledged before the
+    /// connection is forcibly closed.
+    ///
+    /// This sets the `TCP_USER_TIMEOUT` option. Unlike keepalive, which only
+    /// probes an idle connection, it bounds how long the kernel keeps
+    /// retransmitting when the peer stops acknowledging in the middle of a
+    /// transfer, so a connection to a peer that has gone dark fails within
+    /// roughly `timeout` instead of after many minutes of retries. Once it
+    /// expires, pending and subsequent operations fail with
+    /// [`TimedOut`](io::ErrorKind::TimedOut).
+    ///
+    /// The timeout is applied with millisecond precision. A nonzero timeout
+    /// shorter than a millisecond is rounded up to one millisecond, and one
+    /// too long to represent is clamped to `i32::MAX` milliseconds, which is
+    /// the largest value the kernel accepts. A zero timeout restores the
+    /// system default behavior.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This is only supported on Linux. Other platforms return an error of
+    /// kind [`Unsupported`](io::ErrorKind::Unsupported).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(unix_socket_buffer_size, unix_tcp_user_timeout)]
+    /// use std::net::TcpStream;
+    /// use std::os::unix::net::TcpStreamExt;
+    /// use std::time::Duration;
+    ///
+    /// let stream = TcpStream::connect("127.0.0.1:8080").expect("Couldn't connect to the server");
+    /// stream.set_tcp_user_timeout(Duration::from_secs(10)).expect("set_tcp_user_timeout failed");
+    /// ```
+    #[unstable(feature = "unix_tcp_user_timeout", issue = "none")]
+    fn set_tcp_user_timeout(&self, timeout: Duration) -> io::Result<()>;
+
+    /// Gets the value of the `TCP_USER_TIMEOUT` option on this socket.
+    ///
+    /// A zero duration means the system default behavior is in effect.
+    ///
+    /// For more information about this option, see
+    /// [`TcpStreamExt::set_tcp_user_timeout`].
+    #[unstable(feature = "unix_tcp_user_timeout", issue = "none")]
+    fn tcp_user_timeout(&self) -> io::Result<Duration>;
+
+    /// Returns the kernel's statistics for this connection, such as its
+    /// round-trip time, congestion window and retransmission counts.
+    ///
+    /// This reads the `TCP_INFO` option. The statistics are a snapshot taken
+    /// at the time of the call.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This is only supported on Linux. Other platforms return an error of
+    /// kind [`Unsupported`](io::ErrorKind::Unsupported).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(unix_socket_buffer_size, unix_tcp_info)]
+    /// use std::net::TcpStream;
+    /// use std::os::unix::net::TcpStreamExt;
+    ///
+    /// let stream = TcpStream::connect("127.0.0.1:8080").expect("Couldn't connect to the server");
+    /// let info = stream.tcp_info().expect("tcp_info call failed");
+    /// println!("rtt: {:?}, cwnd: {} segments", info.rtt(), info.snd_cwnd());
+    /// ```
+    #[unstable(feature = "unix_tcp_info", issue = "none")]
+    fn tcp_info(&self) -> io::Result<TcpInfo>;
+}
+
+/// Statistics about a TCP connection, as returned by
+/// [`TcpStreamExt::tcp_info`].
+///
+/// Some statistics were only added in later kernel versions. Those are
+/// returned as an [`Option`], which is `None` when the running kernel doesn't
+/// report them.
+#[derive(Clone)]
+#[unstable(feature = "unix_tcp_info", issue = "none")]
+pub struct TcpInfo {
+    info: tcp_info,
+    len: usize,
+}
+
+impl TcpInfo {
+    /// Returns the state of the connection.
+    ///
+    /// This is one of the kernel's `TCP_*` states, such as `TCP_ESTABLISHED`
+    /// (1) or `TCP_CLOSE_WAIT` (8).
+    #[unstable(feature = "unix_tcp_info", issue = "none")]
+    pub fn state(&self) -> u8 {
+        self.info.tcpi_state
+    }
+
+    /// Returns the number of retransmission timeouts since data was last
+    /// acknowledged.
+    ///
+    /// This goes back to 0 as soon as the peer acknowledges data again. See
+    /// [`total_retrans`] for a running total.
+    ///
+    /// [`total_retrans`]: TcpInfo::total_retrans
+    #[unstable(feature = "unix_tcp_info", issue = "none")]
+    pub fn retransmits(&self) -> u8 {
+        self.info.tcpi_retransmits
+    }
+
+    /// Returns the total number of segments retransmitted over the lifetime
+    /// of the connection.
+    #[unstable(feature = "unix_tcp_info", issue = "none")]
+    pub fn total_retrans(&self) -> u32 {
+        self.info.tcpi_total_retrans
+    }
+
+    /// Returns the current retransmission timeout.
+    #[unstable(feature = "unix_tcp_info", issue = "none")]
+    pub fn rto(&self) -> Duration {
+        Duration::from_micros(self.info.tcpi_rto.into())
+    }
+
+    /// Returns the smoothed round-trip time.
+    #[unstable(feature = "unix_tcp_info", issue = "none")]
+    pub fn rtt(&self) -> Duration {
+        Duration::from_micros(self.info.tcpi_rtt.into())
+    }
+
+    /// Returns the variation of the round-trip time.
+    #[unstable(feature = "unix_tcp_info", issue = "none")]
+    pub fn rttvar(&self) -> Duration {
+        Duration::from_micros(self.info.tcpi_rttvar.into())
+    }
+
+    /// Returns the maximum segment size for sending, in bytes.
+    #[unstable(feature = "unix_tcp_info", issue = "none")]
+    pub fn snd_mss(&self) -> u32 {
+        self.info.tcpi_snd_mss
+    }
+
+    /// Returns the maximum segment size for receiving, in bytes.
+    #[unstable(feature = "unix_tcp_info", issue = "none")]
+    pub fn rcv_mss(&self) -> u32 {
+        self.info.tcpi_rcv_mss
+    }
+
+    /// Returns the congestion window, in segments.
+    #[unstable(feature = "unix_tcp_info", issue = "none")]
+    pub fn snd_cwnd(&self) -> u32 {
+        self.info.tcpi_snd_cwnd
+    }
+
+    /// Returns the slow start threshold, in segments.
+    #[unstable(feature = "unix_tcp_info", issue = "none")]
+    pub fn snd_ssthresh(&self) -> u32 {
+        self.info.tcpi_snd_ssthresh
+    }
+
+    /// Returns the number of segments sent but not yet acknowledged.
+    #[unstable(feature = "unix_tcp_info", issue = "none")]
+    pub fn unacked(&self) -> u32 {
+        self.info.tcpi_unacked
+    }
+
+    /// Returns the number of segments currently considered lost.
+    #[unstable(feature = "unix_tcp_info", issue = "none")]
+    pub fn lost(&self) -> u32 {
+        self.info.tcpi_lost
+    }
+
+    /// Returns the number of bytes the peer has acknowledged.
+    ///
+    /// This is `None` on kernels older than 4.1.
+    #[unstable(feature = "unix_tcp_info", issue = "none")]
+    pub fn bytes_acked(&self) -> Option<u64> {
+        self.get(&self.info.tcpi_bytes_acked)
+    }
+
+    /// Returns the number of bytes received from the peer.
+    ///
+    /// This is `None` on kernels older than 4.1.
+    #[unstable(feature = "unix_tcp_info", issue = "none")]
+    pub fn bytes_received(&self) -> Option<u64> {
+        self.get(&self.info.tcpi_bytes_received)
+    }
+
+    fn get<T: Copy>(&self, field: &T) -> Option<T> {
+        if self.info.has_field(field, self.len) { Some(*field) } else { None }
+    }
+}
+
+#[unstable(feature = "unix_tcp_info", issue = "none")]
+impl fmt::Debug for TcpInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TcpInfo")
+            .field("state", &self.state())
+            .field("retransmits", &self.retransmits())
+            .field("total_retrans", &self.total_retrans())
+            .field("rto", &self.rto())
+            .field("rtt", &self.rtt())
+            .field("rttvar", &self.rttvar())
+            .field("snd_mss", &self.snd_mss())
+            .field("rcv_mss", &self.rcv_mss())
+            .field("snd_cwnd", &self.snd_cwnd())
+            .field("snd_ssthresh", &self.snd_ssthresh())
+            .field("unacked", &self.unacked())
+            .field("lost", &self.lost())
+            .field("bytes_acked", &self.bytes_acked())
+            .field("bytes_received", &self.bytes_received())
+            .finish()
+    }
+}
+
+/// Unix-specific extensions to [`UdpSocket`].
+///
+/// This trait is sealed: it cannot be implemented outside the standard library.
+/// This is so that future additional methods are not breaking changes.
+#[unstable(feature = "unix_socket_buffer_size", issue = "none")]
+pub trait UdpSocketExt: Sealed {
+    /// Sets the size of the kernel receive buffer for this socket, in bytes.
+    ///
+    /// See [`TcpStreamExt::set_recv_buffer_size`] for details, including the
+    /// Linux doubling behavior.
+    #[unstable(feature = "unix_socket_buffer_size", issue = "none")]
+    fn set_recv_buffer_size(&self, size: usize) -> io::Result<()>;
+
+    /// Gets the size of the kernel receive buffer for this socket, in bytes.
+    ///
+    /// See [`TcpStreamExt::recv_buffer_size`] for details.
+    #[unstable(feature = "unix_socket_buffer_size", issue = "none")]
+    fn recv_buffer_size(&self) -> io::Result<usize>;
+
+    /// Sets the size of the kernel send buffer for this socket, in bytes.
+    ///
+    /// See [`TcpStreamExt::set_send_buffer_size`] for details.
+    #[unstable(feature = "unix_socket_buffer_size", issue = "none")]
+    fn set_send_buffer_size(&self, size: usize) -> io::Result<()>;
+
+    /// Gets the size of the kernel send buffer for this socket, in bytes.
+    ///
+    /// See [`TcpStreamExt::send_buffer_size`] for details.
+    #[unstable(feature = "unix_socket_buffer_size", issue = "none")]
+    fn send_buffer_size(&self) -> io::Result<usize>;
+
+    /// Turns the `SO_TIMESTAMPNS` option of this socket on or off.
+    ///
+    /// While it is on, the kernel takes a timestamp of every datagram as it
+    /// arrives, which [`recv_with_timestamp`] then reports. Datagrams that
+    /// were already queued when it was turned on may carry no timestamp, or
+    /// one that doesn't reflect their arrival, so turn it on before any
+    /// traffic is expected, such as right after binding.
+    ///
+    /// [`recv_with_timestamp`]: UdpSocketExt::recv_with_timestamp
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This method is currently only supported on Linux and Android. Other
+    /// platforms return an error of kind [`io::ErrorKind::Unsupported`].
+    #[unstable(feature = "unix_recv_timestamp", issue = "none")]
+    fn set_timestamping(&self, enable: bool) -> io::Result<()>;
+
+    /// Receives a single datagram, along with the time at which the kernel
+    /// received it.
+    ///
+    /// This works like [`UdpSocket::recv`], but also reads the timestamp from
+    /// the `SCM_TIMESTAMPNS` control message, which the kernel attaches once
+    /// timestamping has been turned on with [`set_timestamping`]. The
+    /// timestamp is taken when the datagram arrives, before it waits in the
+    /// receive queue, which makes it suitable for measuring latency. It is
+    /// `None` if the kernel didn't attach one to the datagram, as happens
+    /// while timestamping is off.
+    ///
+    /// [`set_timestamping`]: UdpSocketExt::set_timestamping
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This method is currently only supported on Linux and Android. Other
+    /// platforms return an error of kind [`io::ErrorKind::Unsupported`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(unix_socket_buffer_size, unix_recv_timestamp)]
+    /// use std::net::UdpSocket;
+    /// use std::os::unix::net::UdpSocketExt;
+    /// use std::time::SystemTime;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let socket = UdpSocket::bind("127.0.0.1:34254")?;
+    ///     socket.set_timestamping(true)?;
+    ///     let mut buf = [0; 1500];
+    ///     let (len, received_at) = socket.recv_with_timestamp(&mut buf)?;
+    ///     if let Some(Ok(delay)) = received_at.map(|t| SystemTime::now().duration_since(t)) {
+    ///         println!("{} bytes waited {:?} in the receive queue", len, delay);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "unix_recv_timestamp", issue = "none")]
+    fn recv_with_timestamp(&self, buf: &mut [u8]) -> io::Result<(usize, Option<SystemTime>)>;
+}
+
+#[unstable(feature = "unix_socket_buffer_size", issue = "none")]
+impl TcpStreamExt for TcpStream {
+    fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.as_inner().socket().set_recv_buffer_size(size)
+    }
+
+    fn recv_buffer_size(&self) -> io::Result<usize> {
+        self.as_inner().socket().recv_buffer_size()
+    }
+
+    fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.as_inner().socket().set_send_buffer_size(size)
+    }
+
+    fn send_buffer_size(&self) -> io::Result<usize> {
+        self.as_inner().socket().send_buffer_size()
+    }
+
+    fn connect_from(local: SocketAddr, remote: SocketAddr) -> io::Result<TcpStream> {
+        let sock = Socket::connect_from(&local, &remote)?;
+        Ok(TcpStream::from_inner(net_imp::TcpStream::from_inner(sock)))
+    }
+
+    fn connect_any(addrs: &[SocketAddr], timeout: Duration) -> io::Result<TcpStream> {
+        let sock = Socket::connect_any(addrs, timeout)?;
+        Ok(TcpStream::from_inner(net_imp::TcpStream::from_inner(sock)))
+    }
+
+    fn set_tcp_user_timeout(&self, timeout: Duration) -> io::Result<()> {
+        self.as_inner().socket().set_tcp_user_timeout(timeout)
+    }
+
+    fn tcp_user_timeout(&self) -> io::Result<Duration> {
+        self.as_inner().socket().tcp_user_timeout()
+    }
+
+    fn tcp_info(&self) -> io::Result<TcpInfo> {
+        let (info, len) = self.as_inner().socket().tcp_info()?;
+        Ok(TcpInfo { info, len })
+    }
+}
+
+#[unstable(feature = "unix_socket_buffer_size", issue = "none")]
+impl UdpSocketExt for UdpSocket {
+    fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.as_inner().socket().set_recv_buffer_size(size)
+    }
+
+    fn recv_buffer_size(&self) -> io::Result<usize> {
+        self.as_inner().socket().recv_buffer_size()
+    }
+
+    fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.as_inner().socket().set_send_buffer_size(size)
+    }
+
+    fn send_buffer_size(&self) -> io::Result<usize> {
+        self.as_inner().socket().send_buffer_size()
+    }
+
+    fn set_timestamping(&self, enable: bool) -> io::Result<()> {
+        self.as_inner().socket().set_timestamping(enable)
+    }
+
+    fn recv_with_timestamp(&self, buf: &mut [u8]) -> io::Result<(usize, Option<SystemTime>)> {
+        let (len, timestamp) = self.as_inner().socket().recv_with_timestamp(buf)?;
+        Ok((len, timestamp.map(|t| UNIX_EPOCH + t)))
+    }
+}
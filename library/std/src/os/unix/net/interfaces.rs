@@ -0,0 +1,72 @@
+use crate::ffi::{OsStr, OsString};
+use crate::io;
+use crate::net::IpAddr;
+use crate::sys::net;
+
+/// An IP address assigned to a local network interface, as returned by
+/// [`interface_addrs`].
+#[derive(Clone, Debug)]
+#[unstable(feature = "unix_interface_addrs", issue = "none")]
+pub struct InterfaceAddr {
+    name: OsString,
+    addr: IpAddr,
+    flags: u64,
+}
+
+impl InterfaceAddr {
+    /// Returns the name of the interface, such as `lo` or `eth0`.
+    #[unstable(feature = "unix_interface_addrs", issue = "none")]
+    pub fn name(&self) -> &OsStr {
+        &self.name
+    }
+
+    /// Returns the address assigned to the interface.
+    #[unstable(feature = "unix_interface_addrs", issue = "none")]
+    pub fn addr(&self) -> IpAddr {
+        self.addr
+    }
+
+    /// Returns the flags of the interface, such as `IFF_UP` and
+    /// `IFF_LOOPBACK`.
+    ///
+    /// These are the raw `ifa_flags` bits reported by the system. Their
+    /// values are platform-specific and can be tested against the `IFF_*`
+    /// constants of the `libc` crate. They are returned as a `u64` because
+    /// illumos has flags above bit 31, such as `IFF_IPMP`.
+    #[unstable(feature = "unix_interface_addrs", issue = "none")]
+    pub fn flags(&self) -> u64 {
+        self.flags
+    }
+}
+
+/// Returns the IPv4 and IPv6 addresses assigned to the local network
+/// interfaces.
+///
+/// An interface with several addresses, such as one with both an IPv4 and an
+/// IPv6 address, is listed once per address. Interfaces without an IP
+/// address are not listed at all, nor are link-layer addresses.
+///
+/// # Platform-specific behavior
+///
+/// This function currently corresponds to the `getifaddrs` function on
+/// Linux, Android, illumos and the BSDs, including macOS. Other platforms
+/// return an error of kind [`io::ErrorKind::Unsupported`].
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(unix_interface_addrs)]
+/// use std::os::unix::net::interface_addrs;
+///
+/// fn main() -> std::io::Result<()> {
+///     for ifa in interface_addrs()? {
+///         println!("{:?}: {}", ifa.name(), ifa.addr());
+///     }
+///     Ok(())
+/// }
+/// ```
+#[unstable(feature = "unix_interface_addrs", issue = "none")]
+pub fn interface_addrs() -> io::Result<Vec<InterfaceAddr>> {
+    let addrs = net::getifaddrs()?;
+    Ok(addrs.into_iter().map(|(name, addr, flags)| InterfaceAddr { name, addr, flags }).collect())
+}
@@ -2,7 +2,7 @@
 use crate::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
 use crate::path::Path;
 use crate::sys::cvt;
-use crate::sys::net::Socket;
+use crate::sys::net::{BoundPath, Socket};
 use crate::sys_common::{AsInner, FromInner, IntoInner};
 use crate::{fmt, io, mem};
 
@@ -38,7 +38,7 @@
 /// }
 /// ```
 #[stable(feature = "unix_socket", since = "1.10.0")]
-pub struct UnixListener(Socket);
+pub struct UnixListener(Socket, Option<BoundPath>);
 
 #[stable(feature = "unix_socket", since = "1.10.0")]
 impl fmt::Debug for UnixListener {
@@ -77,10 +77,59 @@ pub fn bind<P: AsRef<Path>>(path: P) -> io::Result<UnixListener> {
             cvt(libc::bind(*inner.as_inner(), &addr as *const _ as *const _, len as _))?;
             cvt(libc::listen(*inner.as_inner(), 128))?;
 
-            Ok(UnixListener(inner))
+            Ok(UnixListener(inner, None))
         }
     }
 
+    /// Creates a new `UnixListener` bound to the specified socket, which
+    /// removes the socket file again when it is dropped.
+    ///
+    /// Binding creates a socket file at `path`, which [`bind`] leaves behind
+    /// once the listener is gone, so that binding to the same path later fails
+    /// with [`io::ErrorKind::AddrInUse`] until the stale file is removed. A
+    /// listener created by this function removes the file itself when it is
+    /// dropped.
+    ///
+    /// The file is only removed if it is still the one created here: the
+    /// device and inode numbers of the file are recorded on bind and checked
+    /// again before removal, so a socket some other process has since bound
+    /// at the same path is left alone. This is best-effort, as the file could
+    /// still be replaced between the check and the removal, and inode numbers
+    /// can be reused once a file is removed. Nothing is removed if the process
+    /// exits without dropping the listener, nor for an unnamed socket, which
+    /// has no file.
+    ///
+    /// Handles created by [`try_clone`] don't remove the file, and
+    /// [`into_raw_fd`] gives up on removing it, so only the listener returned
+    /// here does. Once it is dropped, any clones still listening can no
+    /// longer be reached by path.
+    ///
+    /// [`bind`]: UnixListener::bind
+    /// [`try_clone`]: UnixListener::try_clone
+    /// [`into_raw_fd`]: IntoRawFd::into_raw_fd
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(unix_listener_cleanup)]
+    /// use std::os::unix::net::UnixListener;
+    /// use std::path::Path;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let listener = UnixListener::bind_with_cleanup("/path/to/the/socket")?;
+    ///     drop(listener);
+    ///     assert!(!Path::new("/path/to/the/socket").exists());
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "unix_listener_cleanup", issue = "none")]
+    pub fn bind_with_cleanup<P: AsRef<Path>>(path: P) -> io::Result<UnixListener> {
+        let path = path.as_ref();
+        let UnixListener(socket, _) = UnixListener::bind(path)?;
+        let bound = if path.as_os_str().is_empty() { None } else { Some(BoundPath::new(path)?) };
+        Ok(UnixListener(socket, bound))
+    }
+
     /// Accepts a new incoming connection to this listener.
     ///
     /// This function will block the calling thread until a new Unix connection
@@ -132,7 +181,7 @@ pub fn accept(&self) -> io::Result<(UnixStream, SocketAddr)> {
     /// ```
     #[stable(feature = "unix_socket", since = "1.10.0")]
     pub fn try_clone(&self) -> io::Result<UnixListener> {
-        self.0.duplicate().map(UnixListener)
+        self.0.duplicate().map(|socket| UnixListener(socket, None))
     }
 
     /// Returns the local socket address of this listener.
@@ -250,7 +299,7 @@ fn as_raw_fd(&self) -> RawFd {
 impl FromRawFd for UnixListener {
     #[inline]
     unsafe fn from_raw_fd(fd: RawFd) -> UnixListener {
-        UnixListener(Socket::from_inner(fd))
+        UnixListener(Socket::from_inner(fd), None)
     }
 }
 
@@ -258,6 +307,10 @@ unsafe fn from_raw_fd(fd: RawFd) -> UnixListener {
 impl IntoRawFd for UnixListener {
     #[inline]
     fn into_raw_fd(self) -> RawFd {
+        // The socket lives on, so its file has to stay too.
+        if let Some(bound) = self.1 {
+            bound.into_path();
+        }
         self.0.into_inner()
     }
 }
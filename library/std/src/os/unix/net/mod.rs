@@ -24,8 +24,29 @@
 ))]
 mod ancillary;
 mod datagram;
+mod inet;
+mod interfaces;
 mod listener;
 mod raw_fd;
+mod raw_socket;
+#[doc(cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "linux",
+    target_os = "netbsd",
+    target_os = "openbsd",
+)))]
+#[cfg(any(
+    doc,
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "linux",
+    target_os = "netbsd",
+    target_os = "openbsd",
+))]
+mod seqpacket;
 mod stream;
 #[cfg(all(test, not(target_os = "emscripten")))]
 mod tests;
@@ -46,9 +67,28 @@
 pub use self::ancillary::*;
 #[stable(feature = "unix_socket", since = "1.10.0")]
 pub use self::datagram::*;
+#[unstable(feature = "unix_socket_buffer_size", issue = "none")]
+pub use self::inet::{TcpStreamExt, UdpSocketExt};
+#[unstable(feature = "unix_tcp_info", issue = "none")]
+pub use self::inet::TcpInfo;
+#[unstable(feature = "unix_interface_addrs", issue = "none")]
+pub use self::interfaces::{interface_addrs, InterfaceAddr};
 #[stable(feature = "unix_socket", since = "1.10.0")]
 pub use self::listener::*;
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use self::raw_fd::*;
+#[unstable(feature = "unix_raw_socket", issue = "none")]
+pub use self::raw_socket::RawSocket;
+#[cfg(any(
+    doc,
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "linux",
+    target_os = "netbsd",
+    target_os = "openbsd",
+))]
+#[unstable(feature = "unix_seqpacket", issue = "none")]
+pub use self::seqpacket::{UnixSeqpacket, UnixSeqpacketListener};
 #[stable(feature = "unix_socket", since = "1.10.0")]
 pub use self::stream::*;
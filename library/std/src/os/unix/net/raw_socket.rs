@@ -0,0 +1,194 @@
+use crate::net::{IpAddr, SocketAddr};
+use crate::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
+use crate::sys::net::Socket;
+use crate::sys_common::{AsInner, FromInner, IntoInner};
+use crate::time::Duration;
+use crate::{fmt, io};
+
+/// A raw IP socket, as used by network diagnostic tools such as `ping` and
+/// `traceroute`.
+///
+/// A raw socket sends and receives whole IP payloads for a single protocol,
+/// such as ICMP, bypassing the transport layer. There are no ports: the
+/// program builds and parses the protocol headers itself.
+///
+/// Creating a raw socket is a privileged operation. On Linux it needs the
+/// `CAP_NET_RAW` capability, and on other platforms it generally needs root.
+/// Without the privilege, the constructors fail with an error of kind
+/// [`io::ErrorKind::PermissionDenied`].
+///
+/// # IP headers
+///
+/// What a raw socket sends and receives depends on the address family:
+///
+/// * On an IPv4 socket, received packets include the IP header, and sent
+///   packets do not: the kernel builds one. Setting [`set_header_included`]
+///   makes the kernel expect the IP header in sent packets as well.
+/// * On an IPv6 socket, neither sent nor received packets include the IP
+///   header.
+///
+/// [`set_header_included`]: RawSocket::set_header_included
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(unix_raw_socket)]
+/// use std::net::{IpAddr, Ipv4Addr};
+/// use std::os::unix::net::RawSocket;
+///
+/// fn main() -> std::io::Result<()> {
+///     let socket = RawSocket::new_v4(1)?; // IPPROTO_ICMP
+///
+///     // An ICMP echo request with an identifier and sequence number of 1.
+///     let request = [8, 0, 0xf7, 0xfd, 0, 1, 0, 1];
+///     socket.send_to(&request, IpAddr::V4(Ipv4Addr::LOCALHOST))?;
+///
+///     let mut buf = [0; 1500];
+///     let (len, from) = socket.recv_from(&mut buf)?;
+///     println!("{} bytes from {}, including the IP header", len, from);
+///     Ok(())
+/// }
+/// ```
+#[unstable(feature = "unix_raw_socket", issue = "none")]
+pub struct RawSocket(Socket);
+
+#[unstable(feature = "unix_raw_socket", issue = "none")]
+impl fmt::Debug for RawSocket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RawSocket").field("fd", self.0.as_inner()).finish()
+    }
+}
+
+impl RawSocket {
+    /// Creates a raw IPv4 socket for the IP protocol number `protocol`, such
+    /// as `1` for ICMP.
+    ///
+    /// This corresponds to `socket(AF_INET, SOCK_RAW, protocol)`.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`io::ErrorKind::PermissionDenied`] if the process lacks
+    /// the privilege to create raw sockets.
+    #[unstable(feature = "unix_raw_socket", issue = "none")]
+    pub fn new_v4(protocol: i32) -> io::Result<RawSocket> {
+        Socket::new_raw_ip(libc::AF_INET, protocol).map(RawSocket)
+    }
+
+    /// Creates a raw IPv6 socket for the IP protocol number `protocol`, such
+    /// as `58` for ICMPv6.
+    ///
+    /// This corresponds to `socket(AF_INET6, SOCK_RAW, protocol)`.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`io::ErrorKind::PermissionDenied`] if the process lacks
+    /// the privilege to create raw sockets.
+    #[unstable(feature = "unix_raw_socket", issue = "none")]
+    pub fn new_v6(protocol: i32) -> io::Result<RawSocket> {
+        Socket::new_raw_ip(libc::AF_INET6, protocol).map(RawSocket)
+    }
+
+    /// Sends a packet to `addr`, returning the number of bytes written.
+    ///
+    /// Unless [`set_header_included`] is set, `buf` is the payload that
+    /// follows the IP header, such as an ICMP message.
+    ///
+    /// [`set_header_included`]: RawSocket::set_header_included
+    #[unstable(feature = "unix_raw_socket", issue = "none")]
+    pub fn send_to(&self, buf: &[u8], addr: IpAddr) -> io::Result<usize> {
+        self.0.send_to(buf, &SocketAddr::new(addr, 0))
+    }
+
+    /// Receives a single packet, returning the number of bytes read and the
+    /// address it came from.
+    ///
+    /// On an IPv4 socket the packet starts with the IP header. If `buf` is
+    /// too small for the packet, the rest of it is discarded.
+    #[unstable(feature = "unix_raw_socket", issue = "none")]
+    pub fn recv_from(&self, buf: &mut [u8]) -> io::Result<(usize, IpAddr)> {
+        self.0.recv_from(buf).map(|(n, addr)| (n, addr.ip()))
+    }
+
+    /// Sets whether packets sent on this socket include their IP header.
+    ///
+    /// This sets the `IP_HDRINCL` option, which only applies to IPv4
+    /// sockets. When it is set, [`send_to`] expects `buf` to start with a
+    /// complete IP header, although the kernel may still fill in fields such
+    /// as the checksum and the packet ID.
+    ///
+    /// [`send_to`]: RawSocket::send_to
+    #[unstable(feature = "unix_raw_socket", issue = "none")]
+    pub fn set_header_included(&self, included: bool) -> io::Result<()> {
+        self.0.set_header_included(included)
+    }
+
+    /// Returns whether packets sent on this socket include their IP header.
+    ///
+    /// This reads the `IP_HDRINCL` option. See [`set_header_included`].
+    ///
+    /// [`set_header_included`]: RawSocket::set_header_included
+    #[unstable(feature = "unix_raw_socket", issue = "none")]
+    pub fn header_included(&self) -> io::Result<bool> {
+        self.0.header_included()
+    }
+
+    /// Sets the read timeout for the socket.
+    ///
+    /// If the provided value is [`None`], [`recv_from`] blocks indefinitely.
+    /// An [`Err`] is returned if the zero [`Duration`] is passed to this
+    /// method.
+    ///
+    /// [`recv_from`]: RawSocket::recv_from
+    #[unstable(feature = "unix_raw_socket", issue = "none")]
+    pub fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.0.set_timeout(timeout, libc::SO_RCVTIMEO)
+    }
+
+    /// Sets the write timeout for the socket.
+    ///
+    /// If the provided value is [`None`], [`send_to`] blocks indefinitely.
+    /// An [`Err`] is returned if the zero [`Duration`] is passed to this
+    /// method.
+    ///
+    /// [`send_to`]: RawSocket::send_to
+    #[unstable(feature = "unix_raw_socket", issue = "none")]
+    pub fn set_write_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.0.set_timeout(timeout, libc::SO_SNDTIMEO)
+    }
+
+    /// Moves the socket into or out of nonblocking mode.
+    #[unstable(feature = "unix_raw_socket", issue = "none")]
+    pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        self.0.set_nonblocking(nonblocking)
+    }
+
+    /// Returns the value of the `SO_ERROR` option.
+    #[unstable(feature = "unix_raw_socket", issue = "none")]
+    pub fn take_error(&self) -> io::Result<Option<io::Error>> {
+        self.0.take_error()
+    }
+}
+
+#[unstable(feature = "unix_raw_socket", issue = "none")]
+impl AsRawFd for RawSocket {
+    #[inline]
+    fn as_raw_fd(&self) -> RawFd {
+        *self.0.as_inner()
+    }
+}
+
+#[unstable(feature = "unix_raw_socket", issue = "none")]
+impl FromRawFd for RawSocket {
+    #[inline]
+    unsafe fn from_raw_fd(fd: RawFd) -> RawSocket {
+        RawSocket(Socket::from_inner(fd))
+    }
+}
+
+#[unstable(feature = "unix_raw_socket", issue = "none")]
+impl IntoRawFd for RawSocket {
+    #[inline]
+    fn into_raw_fd(self) -> RawFd {
+        self.0.into_inner()
+    }
+}
@@ -0,0 +1,327 @@
+use super::{sockaddr_un, SocketAddr};
+use crate::net::Shutdown;
+use crate::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
+use crate::path::Path;
+use crate::sys::cvt;
+use crate::sys::net::Socket;
+use crate::sys_common::{AsInner, FromInner, IntoInner};
+use crate::time::Duration;
+use crate::{fmt, io, mem};
+
+/// A connected Unix sequenced-packet socket.
+///
+/// A sequenced-packet socket (`SOCK_SEQPACKET`) is connection-oriented and
+/// reliable like a [`UnixStream`], but it keeps message boundaries like a
+/// [`UnixDatagram`]: each [`send`] is delivered as exactly one message, which
+/// a single [`recv`] returns whole.
+///
+/// [`UnixStream`]: super::UnixStream
+/// [`UnixDatagram`]: super::UnixDatagram
+/// [`send`]: UnixSeqpacket::send
+/// [`recv`]: UnixSeqpacket::recv
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(unix_seqpacket)]
+/// use std::os::unix::net::UnixSeqpacket;
+///
+/// fn main() -> std::io::Result<()> {
+///     let socket = UnixSeqpacket::connect("/path/to/the/socket")?;
+///     socket.send(b"hello")?;
+///     socket.send(b"world")?;
+///
+///     let mut buf = [0; 1024];
+///     let len = socket.recv(&mut buf)?;
+///     println!("reply: {:?}", &buf[..len]);
+///     Ok(())
+/// }
+/// ```
+#[unstable(feature = "unix_seqpacket", issue = "none")]
+pub struct UnixSeqpacket(Socket);
+
+#[unstable(feature = "unix_seqpacket", issue = "none")]
+impl fmt::Debug for UnixSeqpacket {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut builder = fmt.debug_struct("UnixSeqpacket");
+        builder.field("fd", self.0.as_inner());
+        if let Ok(addr) = self.local_addr() {
+            builder.field("local", &addr);
+        }
+        if let Ok(addr) = self.peer_addr() {
+            builder.field("peer", &addr);
+        }
+        builder.finish()
+    }
+}
+
+impl UnixSeqpacket {
+    /// Connects to the sequenced-packet socket named by `path`.
+    ///
+    /// The other end is a [`UnixSeqpacketListener`] bound to `path`.
+    #[unstable(feature = "unix_seqpacket", issue = "none")]
+    pub fn connect<P: AsRef<Path>>(path: P) -> io::Result<UnixSeqpacket> {
+        unsafe {
+            let inner = Socket::new_raw(libc::AF_UNIX, libc::SOCK_SEQPACKET)?;
+            let (addr, len) = sockaddr_un(path.as_ref())?;
+
+            cvt(libc::connect(*inner.as_inner(), &addr as *const _ as *const _, len))?;
+            Ok(UnixSeqpacket(inner))
+        }
+    }
+
+    /// Creates an unnamed pair of connected sockets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(unix_seqpacket)]
+    /// use std::os::unix::net::UnixSeqpacket;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let (a, b) = UnixSeqpacket::pair()?;
+    ///     a.send(b"one")?;
+    ///     a.send(b"two")?;
+    ///
+    ///     let mut buf = [0; 16];
+    ///     assert_eq!(b.recv(&mut buf)?, 3);
+    ///     assert_eq!(&buf[..3], b"one");
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "unix_seqpacket", issue = "none")]
+    pub fn pair() -> io::Result<(UnixSeqpacket, UnixSeqpacket)> {
+        let (i1, i2) = Socket::new_pair(libc::AF_UNIX, libc::SOCK_SEQPACKET)?;
+        Ok((UnixSeqpacket(i1), UnixSeqpacket(i2)))
+    }
+
+    /// Creates a new independently owned handle to the underlying socket.
+    #[unstable(feature = "unix_seqpacket", issue = "none")]
+    pub fn try_clone(&self) -> io::Result<UnixSeqpacket> {
+        self.0.duplicate().map(UnixSeqpacket)
+    }
+
+    /// Returns the socket address of the local half of this connection.
+    #[unstable(feature = "unix_seqpacket", issue = "none")]
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        SocketAddr::new(|addr, len| unsafe { libc::getsockname(*self.0.as_inner(), addr, len) })
+    }
+
+    /// Returns the socket address of the remote half of this connection.
+    #[unstable(feature = "unix_seqpacket", issue = "none")]
+    pub fn peer_addr(&self) -> io::Result<SocketAddr> {
+        SocketAddr::new(|addr, len| unsafe { libc::getpeername(*self.0.as_inner(), addr, len) })
+    }
+
+    /// Sends `buf` as a single message, returning the number of bytes sent.
+    ///
+    /// The whole message is sent or none of it is: a message too large for
+    /// the socket fails with an error instead of being split.
+    #[unstable(feature = "unix_seqpacket", issue = "none")]
+    pub fn send(&self, buf: &[u8]) -> io::Result<usize> {
+        self.0.write(buf)
+    }
+
+    /// Receives a single message, returning its length.
+    ///
+    /// If `buf` is too small for the message, the rest of it is discarded. A
+    /// return value of `0` means the peer has shut down the connection, unless
+    /// it sent an empty message.
+    #[unstable(feature = "unix_seqpacket", issue = "none")]
+    pub fn recv(&self, buf: &mut [u8]) -> io::Result<usize> {
+        self.0.read(buf)
+    }
+
+    /// Receives a single message without removing it from the queue.
+    #[unstable(feature = "unix_seqpacket", issue = "none")]
+    pub fn peek(&self, buf: &mut [u8]) -> io::Result<usize> {
+        self.0.peek(buf)
+    }
+
+    /// Sets the read timeout for the socket.
+    ///
+    /// If the provided value is [`None`], [`recv`] blocks indefinitely. An
+    /// [`Err`] is returned if the zero [`Duration`] is passed to this method.
+    ///
+    /// [`recv`]: UnixSeqpacket::recv
+    #[unstable(feature = "unix_seqpacket", issue = "none")]
+    pub fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.0.set_timeout(timeout, libc::SO_RCVTIMEO)
+    }
+
+    /// Sets the write timeout for the socket.
+    ///
+    /// If the provided value is [`None`], [`send`] blocks indefinitely. An
+    /// [`Err`] is returned if the zero [`Duration`] is passed to this method.
+    ///
+    /// [`send`]: UnixSeqpacket::send
+    #[unstable(feature = "unix_seqpacket", issue = "none")]
+    pub fn set_write_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.0.set_timeout(timeout, libc::SO_SNDTIMEO)
+    }
+
+    /// Returns the read timeout of this socket.
+    #[unstable(feature = "unix_seqpacket", issue = "none")]
+    pub fn read_timeout(&self) -> io::Result<Option<Duration>> {
+        self.0.timeout(libc::SO_RCVTIMEO)
+    }
+
+    /// Returns the write timeout of this socket.
+    #[unstable(feature = "unix_seqpacket", issue = "none")]
+    pub fn write_timeout(&self) -> io::Result<Option<Duration>> {
+        self.0.timeout(libc::SO_SNDTIMEO)
+    }
+
+    /// Moves the socket into or out of nonblocking mode.
+    #[unstable(feature = "unix_seqpacket", issue = "none")]
+    pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        self.0.set_nonblocking(nonblocking)
+    }
+
+    /// Returns the value of the `SO_ERROR` option.
+    #[unstable(feature = "unix_seqpacket", issue = "none")]
+    pub fn take_error(&self) -> io::Result<Option<io::Error>> {
+        self.0.take_error()
+    }
+
+    /// Shuts down the read, write, or both halves of this connection.
+    #[unstable(feature = "unix_seqpacket", issue = "none")]
+    pub fn shutdown(&self, how: Shutdown) -> io::Result<()> {
+        self.0.shutdown(how)
+    }
+}
+
+#[unstable(feature = "unix_seqpacket", issue = "none")]
+impl AsRawFd for UnixSeqpacket {
+    #[inline]
+    fn as_raw_fd(&self) -> RawFd {
+        *self.0.as_inner()
+    }
+}
+
+#[unstable(feature = "unix_seqpacket", issue = "none")]
+impl FromRawFd for UnixSeqpacket {
+    #[inline]
+    unsafe fn from_raw_fd(fd: RawFd) -> UnixSeqpacket {
+        UnixSeqpacket(Socket::from_inner(fd))
+    }
+}
+
+#[unstable(feature = "unix_seqpacket", issue = "none")]
+impl IntoRawFd for UnixSeqpacket {
+    #[inline]
+    fn into_raw_fd(self) -> RawFd {
+        self.0.into_inner()
+    }
+}
+
+/// A Unix sequenced-packet socket server, accepting [`UnixSeqpacket`]
+/// connections.
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(unix_seqpacket)]
+/// use std::os::unix::net::UnixSeqpacketListener;
+///
+/// fn main() -> std::io::Result<()> {
+///     let listener = UnixSeqpacketListener::bind("/path/to/the/socket")?;
+///     let (socket, _addr) = listener.accept()?;
+///
+///     let mut buf = [0; 1024];
+///     let len = socket.recv(&mut buf)?;
+///     socket.send(&buf[..len])?;
+///     Ok(())
+/// }
+/// ```
+#[unstable(feature = "unix_seqpacket", issue = "none")]
+pub struct UnixSeqpacketListener(Socket);
+
+#[unstable(feature = "unix_seqpacket", issue = "none")]
+impl fmt::Debug for UnixSeqpacketListener {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut builder = fmt.debug_struct("UnixSeqpacketListener");
+        builder.field("fd", self.0.as_inner());
+        if let Ok(addr) = self.local_addr() {
+            builder.field("local", &addr);
+        }
+        builder.finish()
+    }
+}
+
+impl UnixSeqpacketListener {
+    /// Creates a new `UnixSeqpacketListener` bound to the specified socket.
+    #[unstable(feature = "unix_seqpacket", issue = "none")]
+    pub fn bind<P: AsRef<Path>>(path: P) -> io::Result<UnixSeqpacketListener> {
+        unsafe {
+            let inner = Socket::new_raw(libc::AF_UNIX, libc::SOCK_SEQPACKET)?;
+            let (addr, len) = sockaddr_un(path.as_ref())?;
+
+            cvt(libc::bind(*inner.as_inner(), &addr as *const _ as *const _, len as _))?;
+            cvt(libc::listen(*inner.as_inner(), 128))?;
+
+            Ok(UnixSeqpacketListener(inner))
+        }
+    }
+
+    /// Accepts a new incoming connection to this listener.
+    ///
+    /// This function blocks the calling thread until a connection is
+    /// established, and returns it along with the remote peer's address.
+    #[unstable(feature = "unix_seqpacket", issue = "none")]
+    pub fn accept(&self) -> io::Result<(UnixSeqpacket, SocketAddr)> {
+        let mut storage: libc::sockaddr_un = unsafe { mem::zeroed() };
+        let mut len = mem::size_of_val(&storage) as libc::socklen_t;
+        let sock = self.0.accept(&mut storage as *mut _ as *mut _, &mut len)?;
+        let addr = SocketAddr::from_parts(storage, len)?;
+        Ok((UnixSeqpacket(sock), addr))
+    }
+
+    /// Creates a new independently owned handle to the underlying socket.
+    #[unstable(feature = "unix_seqpacket", issue = "none")]
+    pub fn try_clone(&self) -> io::Result<UnixSeqpacketListener> {
+        self.0.duplicate().map(UnixSeqpacketListener)
+    }
+
+    /// Returns the local socket address of this listener.
+    #[unstable(feature = "unix_seqpacket", issue = "none")]
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        SocketAddr::new(|addr, len| unsafe { libc::getsockname(*self.0.as_inner(), addr, len) })
+    }
+
+    /// Moves the socket into or out of nonblocking mode.
+    #[unstable(feature = "unix_seqpacket", issue = "none")]
+    pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        self.0.set_nonblocking(nonblocking)
+    }
+
+    /// Returns the value of the `SO_ERROR` option.
+    #[unstable(feature = "unix_seqpacket", issue = "none")]
+    pub fn take_error(&self) -> io::Result<Option<io::Error>> {
+        self.0.take_error()
+    }
+}
+
+#[unstable(feature = "unix_seqpacket", issue = "none")]
+impl AsRawFd for UnixSeqpacketListener {
+    #[inline]
+    fn as_raw_fd(&self) -> RawFd {
+        *self.0.as_inner()
+    }
+}
+
+#[unstable(feature = "unix_seqpacket", issue = "none")]
+impl FromRawFd for UnixSeqpacketListener {
+    #[inline]
+    unsafe fn from_raw_fd(fd: RawFd) -> UnixSeqpacketListener {
+        UnixSeqpacketListener(Socket::from_inner(fd))
+    }
+}
+
+#[unstable(feature = "unix_seqpacket", issue = "none")]
+impl IntoRawFd for UnixSeqpacketListener {
+    #[inline]
+    fn into_raw_fd(self) -> RawFd {
+        self.0.into_inner()
+    }
+}
@@ -9,6 +9,11 @@
     target_os = "openbsd",
 ))]
 use super::{recv_vectored_with_ancillary_from, send_vectored_with_ancillary_to, SocketAncillary};
+#[cfg(any(doc, target_os = "android", target_os = "linux",))]
+use super::{
+    recv_vectored_with_fds_and_creds, send_vectored_with_fds_and_creds, ReceivedMessage,
+    SocketCred,
+};
 use super::{sockaddr_un, SocketAddr};
 use crate::fmt;
 use crate::io::{self, Initializer, IoSlice, IoSliceMut};
@@ -21,7 +26,9 @@
     target_os = "freebsd",
     target_os = "ios",
     target_os = "macos",
-    target_os = "openbsd"
+    target_os = "openbsd",
+    target_os = "solaris",
+    target_os = "illumos"
 ))]
 use crate::os::unix::ucred;
 use crate::path::Path;
@@ -38,7 +45,9 @@
     target_os = "freebsd",
     target_os = "ios",
     target_os = "macos",
-    target_os = "openbsd"
+    target_os = "openbsd",
+    target_os = "solaris",
+    target_os = "illumos"
 ))]
 pub use ucred::UCred;
 
@@ -208,7 +217,9 @@ pub fn peer_addr(&self) -> io::Result<SocketAddr> {
         target_os = "freebsd",
         target_os = "ios",
         target_os = "macos",
-        target_os = "openbsd"
+        target_os = "openbsd",
+        target_os = "solaris",
+        target_os = "illumos"
     ))]
     pub fn peer_cred(&self) -> io::Result<UCred> {
         ucred::peer_cred(self)
@@ -568,6 +579,88 @@ pub fn send_vectored_with_ancillary(
     ) -> io::Result<usize> {
         send_vectored_with_ancillary_to(&self.0, None, bufs, ancillary)
     }
+
+    /// Sends data, file descriptors and credentials on the socket in a single
+    /// `sendmsg` call.
+    ///
+    /// This is a shorthand for building a [`SocketAncillary`] holding an
+    /// `SCM_RIGHTS` message with `fds` (if not empty) and an `SCM_CREDENTIALS`
+    /// message with `creds` (if given), and passing it to
+    /// [`send_vectored_with_ancillary`]. The control buffer is allocated at
+    /// exactly the required size.
+    ///
+    /// On success, returns the number of bytes written.
+    ///
+    /// [`send_vectored_with_ancillary`]: UnixStream::send_vectored_with_ancillary
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(unix_socket_ancillary_data, unix_socket_fds_and_creds)]
+    /// use std::io::IoSlice;
+    /// use std::os::unix::io::AsRawFd;
+    /// use std::os::unix::net::{SocketCred, UnixStream};
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let socket = UnixStream::connect("/tmp/sock")?;
+    ///     let mut creds = SocketCred::new();
+    ///     creds.set_pid(std::process::id() as i32);
+    ///     let fds = [std::io::stdin().as_raw_fd()];
+    ///     socket.send_vectored_with_fds_and_creds(&[IoSlice::new(b"hello")], &fds, Some(&creds))?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(any(doc, target_os = "android", target_os = "linux",))]
+    #[unstable(feature = "unix_socket_fds_and_creds", issue = "none")]
+    pub fn send_vectored_with_fds_and_creds(
+        &self,
+        bufs: &[IoSlice<'_>],
+        fds: &[RawFd],
+        creds: Option<&SocketCred>,
+    ) -> io::Result<usize> {
+        send_vectored_with_fds_and_creds(&self.0, bufs, fds, creds)
+    }
+
+    /// Receives data together with any file descriptors and credentials sent
+    /// with it, in a single `recvmsg` call.
+    ///
+    /// Room is made for up to `max_fds` file descriptors and one set of
+    /// credentials. If the sender passed more than that, the kernel discards
+    /// the excess and [`ReceivedMessage::ancillary_truncated`] returns `true`;
+    /// the descriptors that did arrive are still returned so they can be
+    /// closed. Credentials are only received if [`set_passcred`] was enabled
+    /// on this socket.
+    ///
+    /// [`set_passcred`]: UnixStream::set_passcred
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(unix_socket_ancillary_data, unix_socket_fds_and_creds)]
+    /// use std::io::IoSliceMut;
+    /// use std::os::unix::net::UnixStream;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let socket = UnixStream::connect("/tmp/sock")?;
+    ///     socket.set_passcred(true)?;
+    ///     let mut buf = [0; 64];
+    ///     let msg = socket.recv_vectored_with_fds_and_creds(&mut [IoSliceMut::new(&mut buf)], 4)?;
+    ///     println!("{} bytes, fds {:?}", msg.len(), msg.fds());
+    ///     if let Some(creds) = msg.creds() {
+    ///         println!("from pid {}", creds.get_pid());
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(any(doc, target_os = "android", target_os = "linux",))]
+    #[unstable(feature = "unix_socket_fds_and_creds", issue = "none")]
+    pub fn recv_vectored_with_fds_and_creds(
+        &self,
+        bufs: &mut [IoSliceMut<'_>],
+        max_fds: usize,
+    ) -> io::Result<ReceivedMessage> {
+        recv_vectored_with_fds_and_creds(&self.0, bufs, max_fds)
+    }
 }
 
 #[stable(feature = "unix_socket", since = "1.10.0")]
@@ -638,3 +638,384 @@ fn test_send_vectored_with_ancillary_unix_datagram() {
         unreachable!("must be ScmRights");
     }
 }
+
+#[test]
+fn tcp_buffer_sizes() {
+    use crate::net::{TcpListener, TcpStream};
+
+    let listener = or_panic!(TcpListener::bind("127.0.0.1:0"));
+    let stream = or_panic!(TcpStream::connect(or_panic!(listener.local_addr())));
+
+    or_panic!(stream.set_recv_buffer_size(64 * 1024));
+    assert!(or_panic!(stream.recv_buffer_size()) >= 64 * 1024);
+    or_panic!(stream.set_send_buffer_size(64 * 1024));
+    assert!(or_panic!(stream.send_buffer_size()) >= 64 * 1024);
+}
+
+#[test]
+fn udp_buffer_sizes() {
+    use crate::net::UdpSocket;
+
+    let sock = or_panic!(UdpSocket::bind("127.0.0.1:0"));
+
+    or_panic!(sock.set_recv_buffer_size(64 * 1024));
+    assert!(or_panic!(sock.recv_buffer_size()) >= 64 * 1024);
+    or_panic!(sock.set_send_buffer_size(64 * 1024));
+    assert!(or_panic!(sock.send_buffer_size()) >= 64 * 1024);
+
+    // Oversized requests are clamped rather than truncated to a negative `c_int`.
+    or_panic!(sock.set_recv_buffer_size(usize::MAX));
+    assert!(or_panic!(sock.recv_buffer_size()) > 0);
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn recv_with_timestamp() {
+    use crate::net::UdpSocket;
+    use crate::time::{Duration, SystemTime};
+
+    // The kernel's timestamp should fall between the send and the receive,
+    // give or take some slack for clock adjustments in between.
+    let in_range = |t: SystemTime, before: SystemTime, after: SystemTime| {
+        let slack = Duration::from_secs(1);
+        t >= before - slack && t <= after + slack
+    };
+
+    let rx = or_panic!(UdpSocket::bind("127.0.0.1:0"));
+    let tx = or_panic!(UdpSocket::bind("127.0.0.1:0"));
+    let mut buf = [0; 16];
+
+    or_panic!(rx.set_timestamping(true));
+
+    let before = SystemTime::now();
+    or_panic!(tx.send_to(b"ping", or_panic!(rx.local_addr())));
+    let (len, timestamp) = or_panic!(rx.recv_with_timestamp(&mut buf));
+    let after = SystemTime::now();
+    assert_eq!(&buf[..len], b"ping");
+    let timestamp = timestamp.expect("no timestamp received");
+    assert!(in_range(timestamp, before, after), "{:?} not in {:?}..{:?}", timestamp, before, after);
+
+    let (s1, s2) = or_panic!(UnixDatagram::pair());
+    or_panic!(s2.set_timestamping(true));
+    let before = SystemTime::now();
+    or_panic!(s1.send(b"pong"));
+    let (len, timestamp) = or_panic!(s2.recv_with_timestamp(&mut buf));
+    let after = SystemTime::now();
+    assert_eq!(&buf[..len], b"pong");
+    let timestamp = timestamp.expect("no timestamp received");
+    assert!(in_range(timestamp, before, after), "{:?} not in {:?}..{:?}", timestamp, before, after);
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn raw_socket_icmp_echo() {
+    use crate::net::{IpAddr, Ipv4Addr};
+
+    // Raw sockets need `CAP_NET_RAW`, which unprivileged test runs lack.
+    let socket = match RawSocket::new_v4(libc::IPPROTO_ICMP) {
+        Ok(socket) => socket,
+        Err(e) if e.kind() == ErrorKind::PermissionDenied => return,
+        Err(e) => panic!("failed to create raw socket: {}", e),
+    };
+    or_panic!(socket.set_read_timeout(Some(Duration::from_secs(5))));
+    assert!(!or_panic!(socket.header_included()));
+
+    // An echo request with the identifier 0x5253 and sequence number 1.
+    let mut request = [8, 0, 0, 0, 0x52, 0x53, 0, 1, b'p', b'i', b'n', b'g'];
+    let sum = request.chunks(2).map(|w| u32::from(u16::from_be_bytes([w[0], w[1]]))).sum::<u32>();
+    let sum = !((sum & 0xffff) + (sum >> 16)) as u16;
+    request[2..4].copy_from_slice(&sum.to_be_bytes());
+    let localhost = IpAddr::V4(Ipv4Addr::LOCALHOST);
+    assert_eq!(or_panic!(socket.send_to(&request, localhost)), request.len());
+
+    // The socket sees every ICMP packet that arrives, including the request
+    // itself on its way in, so look for the reply among them.
+    let mut buf = [0; 1500];
+    loop {
+        let (len, from) = or_panic!(socket.recv_from(&mut buf));
+        assert_eq!(from, localhost);
+        // Skip the IPv4 header, whose length is in the low nibble.
+        let icmp = &buf[usize::from(buf[0] & 0xf) * 4..len];
+        if icmp[0] == 0 && icmp[4..8] == request[4..8] {
+            assert_eq!(&icmp[8..], b"ping");
+            break;
+        }
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux",))]
+#[test]
+fn test_send_recv_vectored_with_fds_and_creds() {
+    use crate::os::unix::io::FromRawFd;
+
+    let (s1, s2) = or_panic!(UnixStream::pair());
+    or_panic!(s2.set_passcred(true));
+
+    let mut cred = SocketCred::new();
+    cred.set_pid(unsafe { libc::getpid() });
+    cred.set_uid(unsafe { libc::getuid() });
+    cred.set_gid(unsafe { libc::getgid() });
+
+    let (pipe_read, mut pipe_write) = or_panic!(UnixStream::pair());
+    let bufs_send = &[IoSlice::new(b"head"), IoSlice::new(b"body")][..];
+    let fds = [pipe_read.as_raw_fd()];
+    let count = or_panic!(s1.send_vectored_with_fds_and_creds(bufs_send, &fds, Some(&cred)));
+    assert_eq!(count, 8);
+
+    let mut buf = [0; 8];
+    let msg = or_panic!(s2.recv_vectored_with_fds_and_creds(&mut [IoSliceMut::new(&mut buf)], 1));
+    assert_eq!(msg.len(), 8);
+    assert_eq!(&buf, b"headbody");
+    assert!(!msg.ancillary_truncated());
+
+    let received = msg.creds().expect("no credentials received");
+    assert_eq!(received.get_pid(), cred.get_pid());
+    assert_eq!(received.get_uid(), cred.get_uid());
+    assert_eq!(received.get_gid(), cred.get_gid());
+
+    // The received descriptor refers to the same socket as the one sent.
+    let fds = msg.into_fds();
+    assert_eq!(fds.len(), 1);
+    let mut received_read = unsafe { UnixStream::from_raw_fd(fds[0]) };
+    or_panic!(pipe_write.write_all(b"fd"));
+    let mut fd_buf = [0; 2];
+    or_panic!(received_read.read_exact(&mut fd_buf));
+    assert_eq!(&fd_buf, b"fd");
+}
+
+#[test]
+fn tcp_connect_from() {
+    use crate::net::{IpAddr, Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+
+    let listener = or_panic!(TcpListener::bind("127.0.0.1:0"));
+    let remote = or_panic!(listener.local_addr());
+
+    // Linux routes all of 127.0.0.0/8 to the loopback interface, so a source
+    // address other than the listener's own can be used there.
+    let source = if cfg!(target_os = "linux") {
+        Ipv4Addr::new(127, 0, 0, 2)
+    } else {
+        Ipv4Addr::LOCALHOST
+    };
+    let local = SocketAddr::new(IpAddr::V4(source), 0);
+
+    let stream = or_panic!(TcpStream::connect_from(local, remote));
+    let (_accepted, peer) = or_panic!(listener.accept());
+    assert_eq!(or_panic!(stream.local_addr()).ip(), IpAddr::V4(source));
+    assert_ne!(or_panic!(stream.local_addr()).port(), 0);
+    assert_eq!(peer, or_panic!(stream.local_addr()));
+    assert_eq!(or_panic!(stream.peer_addr()), remote);
+}
+
+#[test]
+fn tcp_connect_any() {
+    use crate::net::{TcpListener, TcpStream};
+
+    // Nothing listens on a port that was just released, so connecting to it
+    // is refused and the next address gets tried.
+    let closed = or_panic!(or_panic!(TcpListener::bind("127.0.0.1:0")).local_addr());
+    let listener = or_panic!(TcpListener::bind("127.0.0.1:0"));
+    let live = or_panic!(listener.local_addr());
+
+    let stream = or_panic!(TcpStream::connect_any(&[closed, live], Duration::from_secs(10)));
+    assert_eq!(or_panic!(stream.peer_addr()), live);
+    let (_accepted, peer) = or_panic!(listener.accept());
+    assert_eq!(peer, or_panic!(stream.local_addr()));
+
+    let err = TcpStream::connect_any(&[closed], Duration::from_secs(10)).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::ConnectionRefused);
+    let err = TcpStream::connect_any(&[], Duration::from_secs(10)).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn tcp_user_timeout() {
+    use crate::net::{TcpListener, TcpStream};
+    use crate::time::Duration;
+
+    let listener = or_panic!(TcpListener::bind("127.0.0.1:0"));
+    let stream = or_panic!(TcpStream::connect(or_panic!(listener.local_addr())));
+
+    assert_eq!(or_panic!(stream.tcp_user_timeout()), Duration::ZERO);
+    or_panic!(stream.set_tcp_user_timeout(Duration::from_millis(1500)));
+    assert_eq!(or_panic!(stream.tcp_user_timeout()), Duration::from_millis(1500));
+
+    // Sub-millisecond timeouts don't silently turn into "use the default".
+    or_panic!(stream.set_tcp_user_timeout(Duration::from_micros(10)));
+    assert_eq!(or_panic!(stream.tcp_user_timeout()), Duration::from_millis(1));
+
+    or_panic!(stream.set_tcp_user_timeout(Duration::MAX));
+    assert_eq!(or_panic!(stream.tcp_user_timeout()), Duration::from_millis(i32::MAX as u64));
+
+    or_panic!(stream.set_tcp_user_timeout(Duration::ZERO));
+    assert_eq!(or_panic!(stream.tcp_user_timeout()), Duration::ZERO);
+}
+
+#[test]
+#[cfg(not(target_os = "linux"))]
+fn tcp_user_timeout_unsupported() {
+    use crate::net::{TcpListener, TcpStream};
+    use crate::time::Duration;
+
+    let listener = or_panic!(TcpListener::bind("127.0.0.1:0"));
+    let stream = or_panic!(TcpStream::connect(or_panic!(listener.local_addr())));
+    let err = stream.set_tcp_user_timeout(Duration::from_secs(1)).unwrap_err();
+    assert_eq!(err.kind(), crate::io::ErrorKind::Unsupported);
+}
+
+#[test]
+fn interface_addrs_include_loopback() {
+    use crate::net::{IpAddr, Ipv4Addr};
+
+    let addrs = match interface_addrs() {
+        Err(ref e) if e.kind() == ErrorKind::Unsupported => return,
+        other => or_panic!(other),
+    };
+    let loopback = addrs
+        .iter()
+        .find(|ifa| ifa.addr() == IpAddr::V4(Ipv4Addr::LOCALHOST))
+        .expect("127.0.0.1 is not assigned to any interface");
+    assert!(!loopback.name().is_empty());
+    assert_ne!(loopback.flags() & libc::IFF_LOOPBACK as u64, 0);
+    // IPv6 may be disabled, but if `::1` is there it's on a loopback interface.
+    for ifa in addrs.iter().filter(|ifa| ifa.addr().is_loopback()) {
+        assert_ne!(ifa.flags() & libc::IFF_LOOPBACK as u64, 0, "{:?}", ifa);
+    }
+}
+
+#[test]
+fn listener_bind_with_cleanup() {
+    let dir = tmpdir();
+    let socket_path = dir.path().join("sock");
+
+    let listener = or_panic!(UnixListener::bind_with_cleanup(&socket_path));
+    assert!(socket_path.exists());
+    let clone = or_panic!(listener.try_clone());
+    drop(clone);
+    assert!(socket_path.exists());
+    drop(listener);
+    assert!(!socket_path.exists());
+
+    // The path can be bound again right away.
+    let listener = or_panic!(UnixListener::bind_with_cleanup(&socket_path));
+
+    // A socket file someone else bound at the same path in the meantime is
+    // left alone. Moving the original file out of the way, rather than
+    // removing it, keeps its inode number from being reused for the new one.
+    or_panic!(crate::fs::rename(&socket_path, dir.path().join("moved")));
+    let other = or_panic!(UnixListener::bind(&socket_path));
+    drop(listener);
+    assert!(socket_path.exists());
+    drop(other);
+    assert!(socket_path.exists());
+}
+
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "linux",
+    target_os = "netbsd",
+    target_os = "openbsd",
+))]
+#[test]
+fn test_seqpacket_message_boundaries() {
+    let (a, b) = or_panic!(UnixSeqpacket::pair());
+    let msgs: [&[u8]; 3] = [b"first", b"2", &[7; 1000]];
+    for msg in &msgs {
+        assert_eq!(or_panic!(a.send(msg)), msg.len());
+    }
+
+    // Each message comes out whole, and on its own, however large the buffer.
+    let mut buf = [0; 4096];
+    for msg in &msgs {
+        let len = or_panic!(b.recv(&mut buf));
+        assert_eq!(&buf[..len], *msg);
+    }
+
+    // A message that doesn't fit is cut short rather than split in two.
+    or_panic!(a.send(b"truncated"));
+    or_panic!(a.send(b"next"));
+    let mut small = [0; 5];
+    assert_eq!(or_panic!(b.recv(&mut small)), 5);
+    assert_eq!(&small, b"trunc");
+    let len = or_panic!(b.recv(&mut buf));
+    assert_eq!(&buf[..len], b"next");
+}
+
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "linux",
+    target_os = "netbsd",
+    target_os = "openbsd",
+))]
+#[test]
+fn test_seqpacket_listener() {
+    let dir = tmpdir();
+    let path = dir.path().join("sock");
+
+    let listener = or_panic!(UnixSeqpacketListener::bind(&path));
+    let thread = thread::spawn(move || {
+        let (socket, _) = or_panic!(listener.accept());
+        let mut buf = [0; 64];
+        loop {
+            let len = or_panic!(socket.recv(&mut buf));
+            if len == 0 {
+                break;
+            }
+            or_panic!(socket.send(&buf[..len]));
+        }
+    });
+
+    let socket = or_panic!(UnixSeqpacket::connect(&path));
+    assert_eq!(Some(&*path), socket.peer_addr().unwrap().as_pathname());
+    let mut buf = [0; 64];
+    for msg in &[&b"ping"[..], b"a longer message"] {
+        or_panic!(socket.send(msg));
+        let len = or_panic!(socket.recv(&mut buf));
+        assert_eq!(&buf[..len], *msg);
+    }
+    or_panic!(socket.shutdown(crate::net::Shutdown::Write));
+    thread.join().unwrap();
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn tcp_info() {
+    use crate::net::{TcpListener, TcpStream};
+
+    const TCP_ESTABLISHED: u8 = 1;
+
+    let listener = or_panic!(TcpListener::bind("127.0.0.1:0"));
+    let mut stream = or_panic!(TcpStream::connect(or_panic!(listener.local_addr())));
+    let (mut peer, _) = or_panic!(listener.accept());
+
+    or_panic!(stream.write_all(b"ping"));
+    let mut buf = [0; 4];
+    or_panic!(peer.read_exact(&mut buf));
+
+    let info = or_panic!(stream.tcp_info());
+    assert_eq!(info.state(), TCP_ESTABLISHED, "{:?}", info);
+    assert!(info.snd_mss() > 0, "{:?}", info);
+    assert!(info.snd_cwnd() > 0, "{:?}", info);
+
+    let info = or_panic!(peer.tcp_info());
+    assert_eq!(info.state(), TCP_ESTABLISHED, "{:?}", info);
+    if let Some(received) = info.bytes_received() {
+        assert_eq!(received, 4);
+    }
+}
+
+#[test]
+#[cfg(not(target_os = "linux"))]
+fn tcp_info_unsupported() {
+    use crate::net::{TcpListener, TcpStream};
+
+    let listener = or_panic!(TcpListener::bind("127.0.0.1:0"));
+    let stream = or_panic!(TcpStream::connect(or_panic!(listener.local_addr())));
+    let err = stream.tcp_info().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Unsupported);
+}
@@ -1,10 +1,45 @@
 //! Unix-specific extensions to primitives in the `std::process` module.
+//!
+//! # `SIGPIPE`
+//!
+//! Before `main` runs, the runtime sets `SIGPIPE` to be ignored, so that
+//! writing to a closed pipe or socket fails with
+//! [`io::ErrorKind::BrokenPipe`] instead of killing the process. On Linux, a
+//! program can ask for something else by defining a `#[no_mangle]` static
+//! named `__rust_std_sigpipe` of type `u8`:
+//!
+//! * `0` ignores `SIGPIPE`. This is the default, and any value not listed
+//!   here is treated the same way.
+//! * `1` resets `SIGPIPE` to its default disposition, so that writing to a
+//!   closed pipe kills the process, as command line filters conventionally
+//!   expect.
+//! * `2` leaves `SIGPIPE` as it was inherited from the parent process.
+//!
+//! ```no_run
+//! // Die quietly when the reader of our output goes away, like `cat` does.
+//! #[no_mangle]
+//! static __rust_std_sigpipe: u8 = 1;
+//!
+//! fn main() {
+//!     loop {
+//!         println!("y");
+//!     }
+//! }
+//! ```
+//!
+//! The runtime looks the symbol up with weak linkage, so it is only seen when
+//! the standard library is linked statically into the program, which is the
+//! default. With a dynamically linked standard library (`-C prefer-dynamic`),
+//! and on every platform other than Linux, the symbol is ignored and
+//! `SIGPIPE` is always ignored. This interface is unstable and may change or
+//! be removed.
 
 #![stable(feature = "rust1", since = "1.0.0")]
 
 use crate::ffi::OsStr;
 use crate::io;
 use crate::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
+use crate::path::Path;
 use crate::process;
 use crate::sealed::Sealed;
 use crate::sys;
@@ -147,6 +182,226 @@ fn before_exec<F>(&mut self, f: F) -> &mut process::Command
     fn arg0<S>(&mut self, arg: S) -> &mut process::Command
     where
         S: AsRef<OsStr>;
+
+    /// Executes the command as a child process like [`Command::output`], but
+    /// stops collecting output once either stdout or stderr exceeds
+    /// `max_bytes`.
+    ///
+    /// This guards a supervising process against running out of memory when
+    /// a child misbehaves and writes an unbounded amount of output. The
+    /// returned flag is `true` if the cap was hit, in which case the stream
+    /// that overflowed holds exactly its first `max_bytes` bytes and the
+    /// other holds whatever had been read from it so far.
+    ///
+    /// When the cap is hit the child is killed with `SIGKILL`, and both pipes
+    /// are closed once it has been reaped, so this returns promptly even if
+    /// the child would otherwise keep writing forever. The returned
+    /// [`ExitStatus`] then reports that signal. Draining the rest of the
+    /// output instead would mean waiting for a runaway child to finish on its
+    /// own, which is exactly what this is meant to avoid.
+    ///
+    /// As with [`Command::output`], stdout and stderr are captured unless
+    /// configured otherwise, and stdin defaults to [`Stdio::null`] unless
+    /// configured otherwise.
+    ///
+    /// [`Command::output`]: process::Command::output
+    /// [`ExitStatus`]: process::ExitStatus
+    /// [`Stdio::null`]: process::Stdio::null
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(unix_output_capped)]
+    /// use std::os::unix::process::CommandExt;
+    /// use std::process::Command;
+    ///
+    /// let (output, truncated) = Command::new("yes")
+    ///     .output_capped(64 * 1024)
+    ///     .expect("failed to execute process");
+    /// assert!(truncated);
+    /// assert_eq!(output.stdout.len(), 64 * 1024);
+    /// ```
+    #[unstable(feature = "unix_output_capped", issue = "none")]
+    fn output_capped(&mut self, max_bytes: usize) -> io::Result<(process::Output, bool)>;
+
+    /// Executes the command as a child process like [`Command::output`],
+    /// feeding it `input` on its standard input.
+    ///
+    /// The child's stdin is always connected to a pipe, replacing any earlier
+    /// [`stdin`] configuration. `input` is written to it while stdout and
+    /// stderr are being collected, so a child that produces a lot of output
+    /// before it has read all of its input does not deadlock. Once all of
+    /// `input` has been written, the pipe is closed so the child sees the end
+    /// of its input.
+    ///
+    /// A child does not have to read all of its input. Programs such as
+    /// `head` or `grep -q` exit as soon as they have seen enough, and writing
+    /// the rest of `input` then fails with [`BrokenPipe`]. If
+    /// `tolerate_broken_pipe` is `true`, that just stops the writing: the
+    /// output and exit status are still collected as usual. That is
+    /// appropriate whenever the child is allowed to decide how much input it
+    /// needs, and whether it succeeded is judged from its exit status. Pass
+    /// `false` when the child is expected to consume everything, so that an
+    /// early exit surfaces as an error.
+    ///
+    /// As with [`Command::output`], stdout and stderr are captured unless
+    /// configured otherwise.
+    ///
+    /// [`Command::output`]: process::Command::output
+    /// [`stdin`]: process::Command::stdin
+    /// [`BrokenPipe`]: io::ErrorKind::BrokenPipe
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(unix_output_with_input)]
+    /// use std::os::unix::process::CommandExt;
+    /// use std::process::Command;
+    ///
+    /// let log = "a\n".repeat(1_000_000);
+    /// let output = Command::new("head")
+    ///     .arg("-n1")
+    ///     .output_with_input(log.as_bytes(), true)
+    ///     .expect("failed to execute process");
+    /// assert_eq!(output.stdout, b"a\n");
+    /// ```
+    #[unstable(feature = "unix_output_with_input", issue = "none")]
+    fn output_with_input(
+        &mut self,
+        input: &[u8],
+        tolerate_broken_pipe: bool,
+    ) -> io::Result<process::Output>;
+
+    /// Sets a resource limit for the child process, as if by `setrlimit`.
+    ///
+    /// `resource` is one of the `RLIMIT_*` constants, such as
+    /// `RLIMIT_NOFILE` or `RLIMIT_CPU`, and `soft` and `hard` are the soft and
+    /// hard limits to apply. Pass `RLIM_INFINITY` for no limit; values too
+    /// large for the platform's `rlim_t` are clamped to its maximum. Calling
+    /// this several times applies each limit in turn, so a later call for the
+    /// same resource wins.
+    ///
+    /// The limits are recorded up front and applied in the child between
+    /// `fork` and `exec`, without allocating, so this is a safe alternative to
+    /// calling `setrlimit` from [`pre_exec`]. They only affect the child: the
+    /// parent's own limits are left untouched.
+    ///
+    /// # Ordering
+    ///
+    /// Limits are applied before the child switches to the groups, group ID
+    /// and user ID requested with [`groups`], [`gid`] and [`uid`], while it
+    /// still has the parent's privileges, so a privileged parent can give an
+    /// unprivileged child a hard limit above its own. They are applied
+    /// before any [`pre_exec`] closure runs. If a limit cannot be applied,
+    /// for instance because `soft` exceeds `hard` or an unprivileged parent
+    /// tries to raise a hard limit, spawning fails with the error reported by
+    /// `setrlimit`.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// Spawning returns an error of kind
+    /// [`Unsupported`](io::ErrorKind::Unsupported) on Fuchsia and VxWorks if
+    /// any limit has been set.
+    ///
+    /// [`pre_exec`]: CommandExt::pre_exec
+    /// [`groups`]: CommandExt::groups
+    /// [`gid`]: CommandExt::gid
+    /// [`uid`]: CommandExt::uid
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # #![feature(rustc_private)]
+    /// #![feature(unix_process_rlimit)]
+    /// extern crate libc;
+    /// use std::os::unix::process::CommandExt;
+    /// use std::process::Command;
+    ///
+    /// // Give the child at most ten seconds of CPU time and 64 descriptors.
+    /// let status = Command::new("./untrusted")
+    ///     .rlimit(libc::RLIMIT_CPU as i32, 10, 10)
+    ///     .rlimit(libc::RLIMIT_NOFILE as i32, 64, 64)
+    ///     .status()
+    ///     .expect("failed to run child");
+    /// # let _ = status;
+    /// ```
+    #[unstable(feature = "unix_process_rlimit", issue = "none")]
+    fn rlimit(&mut self, resource: i32, soft: u64, hard: u64) -> &mut process::Command;
+
+    /// Runs the child in a new session, with the terminal at `path` as its
+    /// controlling terminal and as its stdin, stdout and stderr.
+    ///
+    /// This is what a terminal emulator or an `expect`-style tool needs to
+    /// run an interactive program on the slave side of a pseudo-terminal:
+    /// `path` is typically the name returned by `ptsname` for the master the
+    /// parent keeps. With a controlling terminal, the child gets job control
+    /// signals such as `SIGINT` from the terminal, and can open `/dev/tty`.
+    ///
+    /// The terminal replaces any [`stdin`], [`stdout`] and [`stderr`]
+    /// configuration, and its path is recorded up front, so that the child
+    /// does not allocate between `fork` and `exec`.
+    ///
+    /// [`stdin`]: process::Command::stdin
+    /// [`stdout`]: process::Command::stdout
+    /// [`stderr`]: process::Command::stderr
+    ///
+    /// # Ordering
+    ///
+    /// Only the leader of a session without a controlling terminal can
+    /// acquire one, so the child first starts a new session with `setsid`.
+    /// It then opens `path`, makes it its controlling terminal with the
+    /// `TIOCSCTTY` ioctl, and duplicates it onto its standard streams. This
+    /// all happens right after the standard streams are set up, and before
+    /// the resource limits, groups and IDs requested with [`rlimit`],
+    /// [`groups`], [`gid`] and [`uid`] are applied, so the terminal is opened
+    /// with the parent's privileges. It also happens before any [`pre_exec`]
+    /// closure runs, which therefore already sees the new session and
+    /// terminal. If any step fails, spawning fails with its error.
+    ///
+    /// [`rlimit`]: CommandExt::rlimit
+    /// [`groups`]: CommandExt::groups
+    /// [`gid`]: CommandExt::gid
+    /// [`uid`]: CommandExt::uid
+    /// [`pre_exec`]: CommandExt::pre_exec
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This is supported on Linux, Android, macOS, iOS and the BSDs. On other
+    /// platforms, spawning returns an error of kind
+    /// [`Unsupported`](io::ErrorKind::Unsupported) if a controlling terminal
+    /// has been set.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # #![feature(rustc_private)]
+    /// #![feature(unix_process_controlling_tty)]
+    /// extern crate libc;
+    /// use std::ffi::{CStr, OsStr};
+    /// use std::fs::File;
+    /// use std::os::unix::ffi::OsStrExt;
+    /// use std::os::unix::io::FromRawFd;
+    /// use std::os::unix::process::CommandExt;
+    /// use std::process::Command;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let (master, slave) = unsafe {
+    ///         let fd = libc::posix_openpt(libc::O_RDWR | libc::O_NOCTTY);
+    ///         assert!(fd >= 0 && libc::grantpt(fd) == 0 && libc::unlockpt(fd) == 0);
+    ///         let name = CStr::from_ptr(libc::ptsname(fd)).to_bytes().to_vec();
+    ///         (File::from_raw_fd(fd), name)
+    ///     };
+    ///     let mut child = Command::new("vi").controlling_tty(OsStr::from_bytes(&slave)).spawn()?;
+    ///     // Talk to `vi` through `master`...
+    ///     # drop(master);
+    ///     child.wait()?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "unix_process_controlling_tty", issue = "none")]
+    fn controlling_tty<P>(&mut self, path: P) -> &mut process::Command
+    where
+        P: AsRef<Path>;
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
@@ -199,6 +454,74 @@ fn arg0<S>(&mut self, arg: S) -> &mut process::Command
         self.as_inner_mut().set_arg_0(arg.as_ref());
         self
     }
+
+    fn output_capped(&mut self, max_bytes: usize) -> io::Result<(process::Output, bool)> {
+        let inner = self.as_inner_mut().spawn(sys::process::Stdio::MakePipe, false)?;
+        let mut child = process::Child::from_inner(inner);
+        drop(child.stdin.take());
+
+        let (mut stdout, mut stderr) = (Vec::new(), Vec::new());
+        let out = child.stdout.take().map(IntoInner::into_inner);
+        let err = child.stderr.take().map(IntoInner::into_inner);
+        let truncated = sys::pipe::read2_capped(
+            out.as_ref(),
+            &mut stdout,
+            err.as_ref(),
+            &mut stderr,
+            max_bytes,
+        )?;
+        if truncated {
+            // The child may already have exited on its own, so a failure to
+            // kill it is not an error.
+            let _ = child.kill();
+        }
+
+        // Only close the pipes once the child is gone, as a child that is
+        // still writing would otherwise die of `SIGPIPE` instead.
+        let status = child.wait()?;
+        drop((out, err));
+        Ok((process::Output { status, stdout, stderr }, truncated))
+    }
+
+    fn output_with_input(
+        &mut self,
+        input: &[u8],
+        tolerate_broken_pipe: bool,
+    ) -> io::Result<process::Output> {
+        self.as_inner_mut().stdin(sys::process::Stdio::MakePipe);
+        let inner = self.as_inner_mut().spawn(sys::process::Stdio::MakePipe, true)?;
+        let mut child = process::Child::from_inner(inner);
+
+        let (mut stdout, mut stderr) = (Vec::new(), Vec::new());
+        let stdin = child.stdin.take().map(IntoInner::into_inner);
+        let out = child.stdout.take().map(IntoInner::into_inner);
+        let err = child.stderr.take().map(IntoInner::into_inner);
+        sys::pipe::write_read2(
+            stdin,
+            input,
+            tolerate_broken_pipe,
+            out,
+            &mut stdout,
+            err,
+            &mut stderr,
+        )?;
+
+        let status = child.wait()?;
+        Ok(process::Output { status, stdout, stderr })
+    }
+
+    fn rlimit(&mut self, resource: i32, soft: u64, hard: u64) -> &mut process::Command {
+        self.as_inner_mut().rlimit(resource, soft, hard);
+        self
+    }
+
+    fn controlling_tty<P>(&mut self, path: P) -> &mut process::Command
+    where
+        P: AsRef<Path>,
+    {
+        self.as_inner_mut().controlling_tty(path.as_ref().as_os_str());
+        self
+    }
 }
 
 /// Unix-specific extensions to [`process::ExitStatus`] and
@@ -317,6 +640,63 @@ fn into_raw(self) -> i32 {
     }
 }
 
+/// Unix-specific extensions to [`process::Child`].
+///
+/// This trait is sealed: it cannot be implemented outside the standard library.
+/// This is so that future additional methods are not breaking changes.
+#[unstable(feature = "unix_wait_nowait", issue = "none")]
+pub trait ChildExt: Sealed {
+    /// Waits for the child to exit and returns its status, without reaping it.
+    ///
+    /// This uses `waitid` with `WNOWAIT`, so the child stays a zombie after
+    /// this returns: its pid is not released and a later call to
+    /// [`Child::wait`] or [`Child::try_wait`] (or a `waitpid` from another
+    /// part of the program) still observes and reaps the same exit status.
+    /// It is the caller's responsibility to make sure the child is eventually
+    /// reaped.
+    ///
+    /// If the child has already been reaped through [`Child::wait`] or
+    /// [`Child::try_wait`], the status recorded then is returned.
+    ///
+    /// # Interaction with `SIGCHLD`
+    ///
+    /// If `SIGCHLD` is ignored (its disposition is `SIG_IGN`) or installed
+    /// with `SA_NOCLDWAIT`, the kernel reaps children automatically and this
+    /// returns an error with the raw OS error `ECHILD`, just like
+    /// [`Child::wait`] would. Likewise, a `SIGCHLD` handler that reaps
+    /// children on its own may do so before or after this call observes the
+    /// exit.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This is only supported on Linux. Other platforms return an error of
+    /// kind [`Unsupported`](io::ErrorKind::Unsupported).
+    ///
+    /// [`Child::wait`]: process::Child::wait
+    /// [`Child::try_wait`]: process::Child::try_wait
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(unix_wait_nowait)]
+    /// use std::os::unix::process::ChildExt;
+    /// use std::process::Command;
+    ///
+    /// let mut child = Command::new("true").spawn().expect("failed to spawn");
+    /// let peeked = child.peek_exit_status().expect("failed to peek");
+    /// // The child has not been reaped yet, so a normal wait still works.
+    /// assert_eq!(child.wait().expect("failed to wait"), peeked);
+    /// ```
+    fn peek_exit_status(&self) -> io::Result<process::ExitStatus>;
+}
+
+#[unstable(feature = "unix_wait_nowait", issue = "none")]
+impl ChildExt for process::Child {
+    fn peek_exit_status(&self) -> io::Result<process::ExitStatus> {
+        self.as_inner().peek_wait().map(process::ExitStatus::from_inner)
+    }
+}
+
 #[stable(feature = "process_extensions", since = "1.2.0")]
 impl FromRawFd for process::Stdio {
     #[inline]
@@ -8,9 +8,12 @@
 
 use libc::{gid_t, pid_t, uid_t};
 
+#[cfg(test)]
+mod tests;
+
 /// Credentials for a UNIX process for credentials passing.
 #[unstable(feature = "peer_credentials_unix_socket", issue = "42839", reason = "unstable")]
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct UCred {
     /// The UID part of the peer credential. This is the effective UID of the process at the domain
     /// socket's endpoint.
@@ -23,6 +26,10 @@ pub struct UCred {
     /// discover the PID exists, this field will be populated to the PID of the process at the
     /// domain socket's endpoint. Otherwise, it will be set to None.
     pub pid: Option<pid_t>,
+    /// The supplementary groups of the process at the domain socket's endpoint. This field is
+    /// optional because not every platform can report it. It is currently only populated on
+    /// Linux and Android, with kernel 4.13 or later, and is None everywhere else.
+    pub groups: Option<Vec<gid_t>>,
 }
 
 #[cfg(any(target_os = "android", target_os = "linux"))]
@@ -34,15 +41,19 @@ pub struct UCred {
 #[cfg(any(target_os = "macos", target_os = "ios",))]
 pub use self::impl_mac::peer_cred;
 
+#[cfg(any(target_os = "solaris", target_os = "illumos"))]
+pub use self::impl_solaris::peer_cred;
+
 #[cfg(any(target_os = "linux", target_os = "android"))]
 pub mod impl_linux {
     use super::UCred;
     use crate::os::unix::io::AsRawFd;
-    use crate::os::unix::net::UnixStream;
     use crate::{io, mem};
-    use libc::{c_void, getsockopt, socklen_t, ucred, SOL_SOCKET, SO_PEERCRED};
+    use libc::{
+        c_int, c_void, getsockopt, gid_t, socklen_t, ucred, uid_t, SOL_SOCKET, SO_PEERCRED,
+    };
 
-    pub fn peer_cred(socket: &UnixStream) -> io::Result<UCred> {
+    pub fn peer_cred<S: AsRawFd>(socket: &S) -> io::Result<UCred> {
         let ucred_size = mem::size_of::<ucred>();
 
         // Trivial sanity checks.
@@ -61,10 +72,65 @@ pub fn peer_cred(socket: &UnixStream) -> io::Result<UCred> {
                 &mut ucred_size,
             );
 
-            if ret == 0 && ucred_size as usize == mem::size_of::<ucred>() {
-                Ok(UCred { uid: ucred.uid, gid: ucred.gid, pid: Some(ucred.pid) })
-            } else {
-                Err(io::Error::last_os_error())
+            if ret != 0 || ucred_size as usize != mem::size_of::<ucred>() {
+                return Err(io::Error::last_os_error());
+            }
+
+            // Only sockets made by `socketpair` or connected streams record their peer's
+            // credentials. Others, such as a datagram socket connected with `connect`, report
+            // a PID of 0 and IDs of -1 instead of failing.
+            if ucred.pid == 0 && ucred.uid == uid_t::MAX {
+                return Err(io::Error::new_const(
+                    io::ErrorKind::Other,
+                    &"no peer credentials are available for this socket",
+                ));
+            }
+
+            let groups = peer_groups(socket)?;
+            Ok(UCred { uid: ucred.uid, gid: ucred.gid, pid: Some(ucred.pid), groups })
+        }
+    }
+
+    // Not every version of `libc` has this one.
+    #[cfg(not(target_arch = "sparc64"))]
+    const SO_PEERGROUPS: c_int = 59;
+    #[cfg(target_arch = "sparc64")]
+    const SO_PEERGROUPS: c_int = 0x3d;
+
+    /// Reads the peer's supplementary groups with `SO_PEERGROUPS`, or returns `None` on kernels
+    /// older than 4.13, which don't have it.
+    fn peer_groups<S: AsRawFd>(socket: &S) -> io::Result<Option<Vec<gid_t>>> {
+        // Most processes are in a handful of groups. If the buffer is too small, the kernel
+        // fails with `ERANGE` and says how much room it needs.
+        let mut groups: Vec<gid_t> = Vec::with_capacity(16);
+        loop {
+            let mut size = (groups.capacity() * mem::size_of::<gid_t>()) as socklen_t;
+            let ret = unsafe {
+                getsockopt(
+                    socket.as_raw_fd(),
+                    SOL_SOCKET,
+                    SO_PEERGROUPS,
+                    groups.as_mut_ptr() as *mut c_void,
+                    &mut size,
+                )
+            };
+            if ret == 0 {
+                unsafe { groups.set_len(size as usize / mem::size_of::<gid_t>()) };
+                return Ok(Some(groups));
+            }
+            let err = io::Error::last_os_error();
+            match err.raw_os_error() {
+                Some(libc::ERANGE) => {
+                    let needed = size as usize / mem::size_of::<gid_t>();
+                    // Guard against looping forever should the kernel not
+                    // ask for more than there already is.
+                    if needed <= groups.capacity() {
+                        return Err(err);
+                    }
+                    groups.reserve_exact(needed);
+                }
+                Some(libc::ENOPROTOOPT) => return Ok(None),
+                _ => return Err(err),
             }
         }
     }
@@ -75,10 +141,9 @@ pub mod impl_bsd {
     use super::UCred;
     use crate::io;
     use crate::os::unix::io::AsRawFd;
-    use crate::os::unix::net::UnixStream;
 
-    pub fn peer_cred(socket: &UnixStream) -> io::Result<UCred> {
-        let mut cred = UCred { uid: 1, gid: 1, pid: None };
+    pub fn peer_cred<S: AsRawFd>(socket: &S) -> io::Result<UCred> {
+        let mut cred = UCred { uid: 1, gid: 1, pid: None, groups: None };
         unsafe {
             let ret = libc::getpeereid(socket.as_raw_fd(), &mut cred.uid, &mut cred.gid);
 
@@ -95,12 +160,11 @@ pub fn peer_cred(socket: &UnixStream) -> io::Result<UCred> {
 pub mod impl_mac {
     use super::UCred;
     use crate::os::unix::io::AsRawFd;
-    use crate::os::unix::net::UnixStream;
     use crate::{io, mem};
     use libc::{c_void, getpeereid, getsockopt, pid_t, socklen_t, LOCAL_PEERPID, SOL_LOCAL};
 
-    pub fn peer_cred(socket: &UnixStream) -> io::Result<UCred> {
-        let mut cred = UCred { uid: 1, gid: 1, pid: None };
+    pub fn peer_cred<S: AsRawFd>(socket: &S) -> io::Result<UCred> {
+        let mut cred = UCred { uid: 1, gid: 1, pid: None, groups: None };
         unsafe {
             let ret = getpeereid(socket.as_raw_fd(), &mut cred.uid, &mut cred.gid);
 
@@ -128,3 +192,45 @@ pub fn peer_cred(socket: &UnixStream) -> io::Result<UCred> {
         }
     }
 }
+
+#[cfg(any(target_os = "solaris", target_os = "illumos"))]
+pub mod impl_solaris {
+    use super::UCred;
+    use crate::io;
+    use crate::os::unix::io::AsRawFd;
+    use crate::ptr;
+    use libc::{c_int, gid_t, pid_t, uid_t};
+
+    // `ucred_t` is opaque, and only ever handled through a pointer.
+    #[allow(non_camel_case_types)]
+    enum ucred_t {}
+
+    extern "C" {
+        fn getpeerucred(fd: c_int, ucred: *mut *mut ucred_t) -> c_int;
+        fn ucred_geteuid(ucred: *const ucred_t) -> uid_t;
+        fn ucred_getegid(ucred: *const ucred_t) -> gid_t;
+        fn ucred_getpid(ucred: *const ucred_t) -> pid_t;
+        fn ucred_free(ucred: *mut ucred_t);
+    }
+
+    pub fn peer_cred<S: AsRawFd>(socket: &S) -> io::Result<UCred> {
+        // `getpeerucred` allocates the `ucred_t` itself when passed a null pointer.
+        let mut ucred: *mut ucred_t = ptr::null_mut();
+        unsafe {
+            let ret = getpeerucred(socket.as_raw_fd(), &mut ucred);
+
+            if ret != 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            let uid = ucred_geteuid(ucred);
+            let gid = ucred_getegid(ucred);
+            // The PID is unavailable, and reported as -1, when the peer is in another zone.
+            let pid = ucred_getpid(ucred);
+            ucred_free(ucred);
+
+            let pid = if pid == -1 { None } else { Some(pid) };
+            Ok(UCred { uid, gid, pid, groups: None })
+        }
+    }
+}
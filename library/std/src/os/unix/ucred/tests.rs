@@ -1,4 +1,4 @@
-use crate::os::unix::net::UnixStream;
+use crate::os::unix::net::{UnixDatagram, UnixStream};
 use libc::{getegid, geteuid, getpid};
 
 #[test]
@@ -9,7 +9,9 @@
     target_os = "freebsd",
     target_os = "ios",
     target_os = "macos",
-    target_os = "openbsd"
+    target_os = "openbsd",
+    target_os = "solaris",
+    target_os = "illumos"
 ))]
 fn test_socket_pair() {
     // Create two connected sockets and get their peer credentials. They should be equal.
@@ -26,7 +28,7 @@ fn test_socket_pair() {
 
 #[test]
 #[cfg(any(target_os = "linux", target_os = "ios", target_os = "macos",))]
-fn test_socket_pair_pids(arg: Type) -> RetType {
+fn test_socket_pair_pids() {
     // Create two connected sockets and get their peer credentials.
     let (sock_a, sock_b) = UnixStream::pair().unwrap();
     let (cred_a, cred_b) = (sock_a.peer_cred().unwrap(), sock_b.peer_cred().unwrap());
@@ -36,3 +38,46 @@ fn test_socket_pair_pids(arg: Type) -> RetType {
     assert_eq!(cred_a.pid, Some(pid));
     assert_eq!(cred_b.pid, Some(pid));
 }
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn test_datagram_pair() {
+    let (sock_a, sock_b) = UnixDatagram::pair().unwrap();
+    let (cred_a, cred_b) = (sock_a.peer_cred().unwrap(), sock_b.peer_cred().unwrap());
+    assert_eq!(cred_a, cred_b);
+
+    let uid = unsafe { geteuid() };
+    assert_eq!(cred_a.uid, uid);
+}
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn test_datagram_connect_has_no_cred() {
+    use crate::sys_common::io::test::tmpdir;
+
+    let dir = tmpdir();
+    let path = dir.path().join("sock");
+    let _server = UnixDatagram::bind(&path).unwrap();
+    let client = UnixDatagram::unbound().unwrap();
+    client.connect(&path).unwrap();
+    assert!(client.peer_cred().is_err());
+}
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn test_socket_pair_groups() {
+    let (sock_a, _sock_b) = UnixStream::pair().unwrap();
+    let cred = sock_a.peer_cred().unwrap();
+
+    // Kernels older than 4.13 can't report the groups.
+    let mut groups = match cred.groups {
+        Some(groups) => groups,
+        None => return,
+    };
+    let mut expected = vec![0; unsafe { libc::getgroups(0, crate::ptr::null_mut()) } as usize];
+    let len = unsafe { libc::getgroups(expected.len() as libc::c_int, expected.as_mut_ptr()) };
+    expected.truncate(len as usize);
+    groups.sort();
+    expected.sort();
+    assert_eq!(groups, expected);
+}
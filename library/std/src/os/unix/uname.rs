@@ -0,0 +1,71 @@
+//! Unix system identification, as reported by `uname(2)`.
+
+#[cfg(test)]
+mod tests;
+
+use crate::ffi::OsStr;
+use crate::io;
+use crate::sys;
+
+/// The identification of the running system, as returned by [`uname`].
+///
+/// Each field is reported as the kernel provides it, without any
+/// interpretation. The values are only meaningful for diagnostics or coarse
+/// platform checks: their format is not standardized across systems.
+#[derive(Clone, Debug)]
+#[unstable(feature = "unix_uname", issue = "none")]
+pub struct UtsName(sys::os::UtsName);
+
+impl UtsName {
+    /// Returns the name of the operating system, such as `Linux` or `FreeBSD`.
+    #[unstable(feature = "unix_uname", issue = "none")]
+    pub fn sysname(&self) -> &OsStr {
+        &self.0.sysname
+    }
+
+    /// Returns the network node name of this machine.
+    #[unstable(feature = "unix_uname", issue = "none")]
+    pub fn nodename(&self) -> &OsStr {
+        &self.0.nodename
+    }
+
+    /// Returns the release of the operating system, such as `5.10.0-8-amd64`.
+    #[unstable(feature = "unix_uname", issue = "none")]
+    pub fn release(&self) -> &OsStr {
+        &self.0.release
+    }
+
+    /// Returns the version of the operating system.
+    ///
+    /// This is usually a build string, such as a build number and date,
+    /// rather than a version number.
+    #[unstable(feature = "unix_uname", issue = "none")]
+    pub fn version(&self) -> &OsStr {
+        &self.0.version
+    }
+
+    /// Returns the hardware type of this machine, such as `x86_64`.
+    #[unstable(feature = "unix_uname", issue = "none")]
+    pub fn machine(&self) -> &OsStr {
+        &self.0.machine
+    }
+}
+
+/// Returns the identification of the running system.
+///
+/// This calls `uname(2)`, which describes the kernel the program runs on,
+/// not the one it was built for.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(unix_uname)]
+/// use std::os::unix::uname::uname;
+///
+/// let uts = uname().expect("uname failed");
+/// println!("running on {:?} {:?}", uts.sysname(), uts.release());
+/// ```
+#[unstable(feature = "unix_uname", issue = "none")]
+pub fn uname() -> io::Result<UtsName> {
+    sys::os::uname().map(UtsName)
+}
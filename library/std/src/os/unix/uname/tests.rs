@@ -0,0 +1,28 @@
+use super::uname;
+
+#[test]
+fn uname_sysname() {
+    let uts = uname().unwrap();
+    assert!(!uts.sysname().is_empty());
+    assert!(!uts.release().is_empty());
+    assert!(!uts.machine().is_empty());
+
+    let expected = if cfg!(any(target_os = "linux", target_os = "android")) {
+        "Linux"
+    } else if cfg!(any(target_os = "macos", target_os = "ios")) {
+        "Darwin"
+    } else if cfg!(target_os = "freebsd") {
+        "FreeBSD"
+    } else if cfg!(target_os = "netbsd") {
+        "NetBSD"
+    } else if cfg!(target_os = "openbsd") {
+        "OpenBSD"
+    } else if cfg!(target_os = "dragonfly") {
+        "DragonFly"
+    } else if cfg!(any(target_os = "solaris", target_os = "illumos")) {
+        "SunOS"
+    } else {
+        return;
+    };
+    assert_eq!(uts.sysname(), expected);
+}
@@ -169,6 +169,8 @@
 pub use self::poison::{LockResult, PoisonError, TryLockError, TryLockResult};
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use self::rwlock::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+#[unstable(feature = "semaphore", issue = "none")]
+pub use self::semaphore::{Semaphore, SemaphoreGuard};
 
 pub mod mpsc;
 
@@ -178,3 +180,4 @@
 mod once;
 mod poison;
 mod rwlock;
+mod semaphore;
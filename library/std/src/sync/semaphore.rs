@@ -0,0 +1,143 @@
+#[cfg(test)]
+mod tests;
+
+use crate::fmt;
+use crate::sync::{Condvar, Mutex};
+
+/// A counting semaphore, which limits how many threads can hold one of its
+/// permits at a time.
+///
+/// A semaphore starts out with a number of permits. [`acquire`] takes one,
+/// blocking until one is available, and [`release`] gives one back. Permits
+/// are not tied to a thread: one thread can acquire a permit and another
+/// release it.
+///
+/// A permit needed only for a scope is better taken with [`access`], which
+/// gives it back when the returned guard is dropped.
+///
+/// The semaphore is kept minimal. It is public because
+/// [`IteratorExt::take_permitted`] shares one with the code that consumes its
+/// items, and that code has to give the permits back.
+///
+/// [`acquire`]: Semaphore::acquire
+/// [`release`]: Semaphore::release
+/// [`access`]: Semaphore::access
+/// [`IteratorExt::take_permitted`]: crate::iter::IteratorExt::take_permitted
+///
+/// # Examples
+///
+/// ```
+/// #![feature(semaphore)]
+/// use std::sync::{Arc, Semaphore};
+/// use std::thread;
+///
+/// // At most two of the threads below run their critical section at once.
+/// let sem = Arc::new(Semaphore::new(2));
+/// let handles: Vec<_> = (0..5)
+///     .map(|i| {
+///         let sem = Arc::clone(&sem);
+///         thread::spawn(move || {
+///             let _permit = sem.access();
+///             println!("thread {} has a permit", i);
+///         })
+///     })
+///     .collect();
+/// for handle in handles {
+///     handle.join().unwrap();
+/// }
+/// ```
+#[unstable(feature = "semaphore", issue = "none")]
+pub struct Semaphore {
+    permits: Mutex<usize>,
+    cvar: Condvar,
+}
+
+#[unstable(feature = "semaphore", issue = "none")]
+impl fmt::Debug for Semaphore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Semaphore").finish_non_exhaustive()
+    }
+}
+
+impl Semaphore {
+    /// Creates a new semaphore with `permits` permits available.
+    #[unstable(feature = "semaphore", issue = "none")]
+    pub fn new(permits: usize) -> Semaphore {
+        Semaphore { permits: Mutex::new(permits), cvar: Condvar::new() }
+    }
+
+    /// Takes a permit, blocking the current thread until one is available.
+    ///
+    /// The permit is held until it is given back with [`release`].
+    ///
+    /// [`release`]: Semaphore::release
+    #[unstable(feature = "semaphore", issue = "none")]
+    pub fn acquire(&self) {
+        let mut permits = self.permits.lock().unwrap();
+        while *permits == 0 {
+            permits = self.cvar.wait(permits).unwrap();
+        }
+        *permits -= 1;
+    }
+
+    /// Takes a permit, blocking the current thread until one is available,
+    /// and returns a guard that gives it back when dropped.
+    #[unstable(feature = "semaphore", issue = "none")]
+    pub fn access(&self) -> SemaphoreGuard<'_> {
+        self.acquire();
+        SemaphoreGuard { sem: self }
+    }
+
+    /// Takes a permit if one is available, without blocking.
+    ///
+    /// Returns whether a permit was taken.
+    #[unstable(feature = "semaphore", issue = "none")]
+    pub fn try_acquire(&self) -> bool {
+        let mut permits = self.permits.lock().unwrap();
+        if *permits == 0 {
+            false
+        } else {
+            *permits -= 1;
+            true
+        }
+    }
+
+    /// Gives back a permit, waking up a thread blocked in [`acquire`] if
+    /// there is one.
+    ///
+    /// This doesn't check that the permit was ever acquired: releasing more
+    /// permits than were acquired raises the number of permits available.
+    ///
+    /// [`acquire`]: Semaphore::acquire
+    #[unstable(feature = "semaphore", issue = "none")]
+    pub fn release(&self) {
+        *self.permits.lock().unwrap() += 1;
+        self.cvar.notify_one();
+    }
+}
+
+/// An RAII guard holding a permit of a [`Semaphore`]. The permit is given
+/// back when the guard is dropped.
+///
+/// This structure is created by the [`access`] method on [`Semaphore`].
+///
+/// [`access`]: Semaphore::access
+#[must_use = "if unused the permit is given back immediately"]
+#[unstable(feature = "semaphore", issue = "none")]
+pub struct SemaphoreGuard<'a> {
+    sem: &'a Semaphore,
+}
+
+#[unstable(feature = "semaphore", issue = "none")]
+impl fmt::Debug for SemaphoreGuard<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SemaphoreGuard").finish_non_exhaustive()
+    }
+}
+
+#[unstable(feature = "semaphore", issue = "none")]
+impl Drop for SemaphoreGuard<'_> {
+    fn drop(&mut self) {
+        self.sem.release();
+    }
+}
@@ -0,0 +1,44 @@
+use crate::sync::mpsc::{channel, TryRecvError};
+use crate::sync::{Arc, Semaphore};
+use crate::thread;
+
+#[test]
+fn try_acquire() {
+    let sem = Semaphore::new(2);
+    assert!(sem.try_acquire());
+    assert!(sem.try_acquire());
+    assert!(!sem.try_acquire());
+    sem.release();
+    assert!(sem.try_acquire());
+}
+
+#[test]
+fn access_releases_on_drop() {
+    let sem = Semaphore::new(1);
+    {
+        let _permit = sem.access();
+        assert!(!sem.try_acquire());
+    }
+    assert!(sem.try_acquire());
+}
+
+#[test]
+#[cfg_attr(target_os = "emscripten", ignore)]
+fn acquire_blocks_until_release() {
+    let sem = Arc::new(Semaphore::new(1));
+    let (tx, rx) = channel();
+
+    sem.acquire();
+    let sem2 = sem.clone();
+    let handle = thread::spawn(move || {
+        sem2.acquire();
+        tx.send(()).unwrap();
+    });
+
+    // The thread can't have got the permit yet.
+    assert!(matches!(rx.try_recv(), Err(TryRecvError::Empty)));
+    sem.release();
+    rx.recv().unwrap();
+    handle.join().unwrap();
+    assert!(!sem.try_acquire());
+}
@@ -17,6 +17,7 @@
 
 use crate::intrinsics;
 use crate::os::raw::c_char;
+use crate::sync::atomic::{AtomicU64, Ordering};
 
 pub mod alloc;
 pub mod args;
@@ -34,6 +35,9 @@
 pub mod os;
 #[path = "../unix/path.rs"]
 pub mod path;
+// hermit-abi offers neither pipes nor local socket pairs to back an
+// `AnonPipe` with, and the only users of one, child processes, are
+// unsupported as well.
 #[path = "../unsupported/pipe.rs"]
 pub mod pipe;
 #[path = "../unsupported/process.rs"]
@@ -46,6 +50,9 @@
 pub mod thread_local_key;
 pub mod time;
 
+#[cfg(test)]
+mod tests;
+
 use crate::io::ErrorKind;
 pub use crate::sys_common::os_str_bytes as os_str;
 
@@ -84,9 +91,31 @@ pub fn abort_internal() -> ! {
     }
 }
 
-// FIXME: just a workaround to test the system
 pub fn hashmap_random_keys() -> (u64, u64) {
-    (1, 2)
+    (random_u64(), random_u64())
+}
+
+fn random_u64() -> u64 {
+    let mut value = 0;
+    // This fails if the processor has no hardware random number generator.
+    if unsafe { abi::secure_rand64(&mut value) } == 0 {
+        return value;
+    }
+
+    // Without one, mix the clock with a counter. That is easy to predict,
+    // but at least differs between maps, unlike fixed keys.
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+    let mut now = abi::timespec { tv_sec: 0, tv_nsec: 0 };
+    let _ = unsafe { abi::clock_gettime(abi::CLOCK_MONOTONIC, &mut now) };
+    let nanos = (now.tv_sec as u64).wrapping_mul(1_000_000_000).wrapping_add(now.tv_nsec as u64);
+
+    // The SplitMix64 finalizer, so that every input bit affects every output
+    // bit.
+    let mut x = nanos ^ count.wrapping_mul(0x9e3779b97f4a7c15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d049bb133111eb);
+    x ^ (x >> 31)
 }
 
 // This function is needed by the panic runtime. The symbol is named in
@@ -141,6 +170,10 @@ pub fn decode_error_kind(errno: i32) -> ErrorKind {
         x if x == 103 as i32 => ErrorKind::ConnectionAborted,
         x if x == 111 as i32 => ErrorKind::ConnectionRefused,
         x if x == 104 as i32 => ErrorKind::ConnectionReset,
+        x if x == 102 as i32 => ErrorKind::ConnectionReset,
+        x if x == 113 as i32 => ErrorKind::HostUnreachable,
+        x if x == 101 as i32 => ErrorKind::NetworkUnreachable,
+        x if x == 100 as i32 => ErrorKind::NetworkDown,
         x if x == 17 as i32 => ErrorKind::AlreadyExists,
         x if x == 4 as i32 => ErrorKind::Interrupted,
         x if x == 22 as i32 => ErrorKind::InvalidInput,
@@ -149,6 +182,10 @@ pub fn decode_error_kind(errno: i32) -> ErrorKind {
         x if x == 1 as i32 => ErrorKind::PermissionDenied,
         x if x == 32 as i32 => ErrorKind::BrokenPipe,
         x if x == 110 as i32 => ErrorKind::TimedOut,
+        x if x == 28 as i32 => ErrorKind::StorageFull,
+        x if x == 122 as i32 => ErrorKind::QuotaExceeded,
+        x if x == 27 as i32 => ErrorKind::FileTooLarge,
+        x if x == 30 as i32 => ErrorKind::ReadOnlyFilesystem,
         _ => ErrorKind::Other,
     }
 }
@@ -0,0 +1,10 @@
+use super::hashmap_random_keys;
+
+#[test]
+fn hashmap_random_keys_differ() {
+    let first = hashmap_random_keys();
+    let second = hashmap_random_keys();
+    assert_ne!(first, (1, 2));
+    assert_ne!(first, second);
+    assert_ne!(first.0, first.1);
+}
@@ -239,8 +239,21 @@ pub fn set_cloexec(&self) -> io::Result<()> {
         }
     }
 
-    #[cfg(target_os = "linux")]
-    pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+    /// Sets or clears `O_NONBLOCK` with `ioctl(FIONBIO)`.
+    ///
+    /// Unlike the `fcntl` path, which reads the file status flags and writes
+    /// them back, this changes the single flag in one call. That avoids both
+    /// the extra syscall and the window in which a concurrent `F_SETFL` on the
+    /// same open file description, for instance the other end of a self-pipe
+    /// shared with a signal handler or a child process, could be overwritten.
+    ///
+    /// `set_nonblocking` uses this on Linux, where `FIONBIO` has always been
+    /// supported for every kind of descriptor. Elsewhere it is available as an
+    /// explicit alternative for callers that know `fcntl` is problematic for
+    /// their descriptor; some platforms only honor `FIONBIO` for sockets and
+    /// terminals, in which case this returns the `ioctl` error.
+    #[allow(dead_code)] // not used on all platforms
+    pub fn set_nonblocking_ioctl(&self, nonblocking: bool) -> io::Result<()> {
         unsafe {
             let v = nonblocking as c_int;
             cvt(libc::ioctl(self.fd, libc::FIONBIO, &v))?;
@@ -248,8 +261,9 @@ pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
         }
     }
 
-    #[cfg(not(target_os = "linux"))]
-    pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+    /// Sets or clears `O_NONBLOCK` with `fcntl(F_SETFL)`.
+    #[allow(dead_code)] // not used on all platforms
+    pub fn set_nonblocking_fcntl(&self, nonblocking: bool) -> io::Result<()> {
         unsafe {
             let previous = cvt(libc::fcntl(self.fd, libc::F_GETFL))?;
             let new = if nonblocking {
@@ -264,6 +278,16 @@ pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
         }
     }
 
+    #[cfg(target_os = "linux")]
+    pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        self.set_nonblocking_ioctl(nonblocking)
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        self.set_nonblocking_fcntl(nonblocking)
+    }
+
     pub fn duplicate(&self) -> io::Result<FileDesc> {
         // We want to atomically duplicate this file descriptor and set the
         // CLOEXEC flag, and currently that's done via F_DUPFD_CLOEXEC. This
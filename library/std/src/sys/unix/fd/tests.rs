@@ -7,3 +7,28 @@ fn limit_vector_count() {
     let bufs = (0..1500).map(|_| IoSlice::new(&[])).collect::<Vec<_>>();
     assert!(stdout.write_vectored(&bufs).is_ok());
 }
+
+#[test]
+#[cfg(target_os = "linux")]
+fn set_nonblocking_ioctl_on_pipe() {
+    use crate::io::ErrorKind;
+    use crate::sys::pipe::anon_pipe;
+
+    let (reader, writer) = anon_pipe().unwrap();
+    let (reader, writer) = (reader.into_fd(), writer.into_fd());
+    reader.set_nonblocking_ioctl(true).unwrap();
+
+    let mut buf = [0; 4];
+    assert_eq!(reader.read(&mut buf).unwrap_err().kind(), ErrorKind::WouldBlock);
+
+    writer.write(b"ping").unwrap();
+    assert_eq!(reader.read(&mut buf).unwrap(), 4);
+    assert_eq!(&buf, b"ping");
+
+    // The flag set through the ioctl is the same one `fcntl` reports.
+    let flags = unsafe { libc::fcntl(reader.raw(), libc::F_GETFL) };
+    assert_ne!(flags & libc::O_NONBLOCK, 0);
+    reader.set_nonblocking_ioctl(false).unwrap();
+    let flags = unsafe { libc::fcntl(reader.raw(), libc::F_GETFL) };
+    assert_eq!(flags & libc::O_NONBLOCK, 0);
+}
@@ -244,6 +244,16 @@ pub struct DirBuilder {
     mode: mode_t,
 }
 
+/// A directory opened with `O_DIRECTORY`, which the `*at` methods resolve
+/// paths relative to.
+// Never constructed on Redox and VxWorks, where opening one is unsupported.
+#[cfg_attr(any(target_os = "redox", target_os = "vxworks"), allow(dead_code))]
+pub struct DirFd {
+    fd: FileDesc,
+    // Only used for the paths of the entries returned by `readdir`.
+    root: PathBuf,
+}
+
 cfg_has_statx! {{
     impl FileAttr {
         fn from_stat64(stat: stat64) -> Self {
@@ -421,6 +431,21 @@ fn from_inner(mode: u32) -> FilePermissions {
     }
 }
 
+impl ReadDir {
+    fn new(inner: InnerReadDir) -> ReadDir {
+        ReadDir {
+            inner: Arc::new(inner),
+            #[cfg(not(any(
+                target_os = "solaris",
+                target_os = "illumos",
+                target_os = "fuchsia",
+                target_os = "redox",
+            )))]
+            end_of_stream: false,
+        }
+    }
+}
+
 impl fmt::Debug for ReadDir {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         // This will only be called from std::fs::ReadDir, which will add a "ReadDir()" frame.
@@ -811,6 +836,170 @@ unsafe fn os_datasync(fd: c_int) -> c_int {
         }
     }
 
+    #[cfg(target_os = "linux")]
+    pub fn sync_range(&self, offset: u64, len: u64, flags: u32) -> io::Result<()> {
+        use crate::convert::TryInto;
+        let offset: off64_t =
+            offset.try_into().map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        let len: off64_t =
+            len.try_into().map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        cvt_r(|| unsafe { libc::sync_file_range(self.0.raw(), offset, len, flags as libc::c_uint) })
+            .map(drop)
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn sync_range(&self, _offset: u64, _len: u64, _flags: u32) -> io::Result<()> {
+        // There is no way to flush only part of a file here, so flush all of it.
+        self.fsync()
+    }
+
+    #[cfg(target_os = "linux")]
+    pub fn inode_flags(&self) -> io::Result<u32> {
+        // `FS_IOC_GETFLAGS` is `_IOR('f', 1, long)`, and the direction bits of
+        // `_IOR` are encoded differently on a few architectures. The kernel
+        // only ever reads and writes an `int` through it, despite the `long`.
+        #[cfg(any(
+            target_arch = "mips",
+            target_arch = "mips64",
+            target_arch = "powerpc",
+            target_arch = "powerpc64",
+            target_arch = "sparc64"
+        ))]
+        const IOC_READ: u32 = 0x4000_0000;
+        #[cfg(not(any(
+            target_arch = "mips",
+            target_arch = "mips64",
+            target_arch = "powerpc",
+            target_arch = "powerpc64",
+            target_arch = "sparc64"
+        )))]
+        const IOC_READ: u32 = 0x8000_0000;
+        const FS_IOC_GETFLAGS: u32 =
+            IOC_READ | (mem::size_of::<libc::c_long>() as u32) << 16 | 0x6601;
+
+        let mut flags: c_int = 0;
+        cvt(unsafe { libc::ioctl(self.0.raw(), FS_IOC_GETFLAGS as _, &mut flags) })
+            .map_err(inode_flags_error)?;
+        Ok(flags as u32)
+    }
+
+    #[cfg(target_os = "linux")]
+    pub fn set_inode_flags(&self, flags: u32) -> io::Result<()> {
+        // `FS_IOC_SETFLAGS` is `_IOW('f', 2, long)`; see `inode_flags`.
+        #[cfg(any(
+            target_arch = "mips",
+            target_arch = "mips64",
+            target_arch = "powerpc",
+            target_arch = "powerpc64",
+            target_arch = "sparc64"
+        ))]
+        const IOC_WRITE: u32 = 0x8000_0000;
+        #[cfg(not(any(
+            target_arch = "mips",
+            target_arch = "mips64",
+            target_arch = "powerpc",
+            target_arch = "powerpc64",
+            target_arch = "sparc64"
+        )))]
+        const IOC_WRITE: u32 = 0x4000_0000;
+        const FS_IOC_SETFLAGS: u32 =
+            IOC_WRITE | (mem::size_of::<libc::c_long>() as u32) << 16 | 0x6602;
+
+        let flags = flags as c_int;
+        cvt(unsafe { libc::ioctl(self.0.raw(), FS_IOC_SETFLAGS as _, &flags) })
+            .map(drop)
+            .map_err(inode_flags_error)
+    }
+
+    #[cfg(target_os = "linux")]
+    pub fn punch_hole(&self, offset: u64, len: u64) -> io::Result<()> {
+        use crate::convert::TryInto;
+        let offset: off64_t =
+            offset.try_into().map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        let len: off64_t =
+            len.try_into().map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        // Without `FALLOC_FL_KEEP_SIZE` the kernel rejects the request, as
+        // punching a hole never changes the length of the file.
+        let mode = libc::FALLOC_FL_PUNCH_HOLE | libc::FALLOC_FL_KEEP_SIZE;
+        cvt_r(|| unsafe { libc::fallocate64(self.0.raw(), mode, offset, len) }).map(drop).map_err(
+            |err| match err.raw_os_error() {
+                Some(libc::EOPNOTSUPP) => io::Error::new_const(
+                    io::ErrorKind::Unsupported,
+                    &"punching holes is not supported by this filesystem",
+                ),
+                _ => err,
+            },
+        )
+    }
+
+    /// Turns on `O_DIRECT` for this file, returning the alignment that the
+    /// buffers and offsets of its reads and writes need from now on.
+    #[cfg(target_os = "linux")]
+    pub fn enable_direct_io(&self) -> io::Result<usize> {
+        // `BLKSSZGET` is `_IO(0x12, 104)`, and the direction bits of `_IO`
+        // are encoded differently on a few architectures.
+        #[cfg(any(
+            target_arch = "mips",
+            target_arch = "mips64",
+            target_arch = "powerpc",
+            target_arch = "powerpc64",
+            target_arch = "sparc64"
+        ))]
+        const BLKSSZGET: u32 = 0x2000_1268;
+        #[cfg(not(any(
+            target_arch = "mips",
+            target_arch = "mips64",
+            target_arch = "powerpc",
+            target_arch = "powerpc64",
+            target_arch = "sparc64"
+        )))]
+        const BLKSSZGET: u32 = 0x1268;
+
+        let fd = self.0.raw();
+        let flags = cvt(unsafe { libc::fcntl(fd, libc::F_GETFL) })?;
+        if flags & libc::O_DIRECT == 0 {
+            cvt(unsafe { libc::fcntl(fd, libc::F_SETFL, flags | libc::O_DIRECT) }).map_err(
+                |err| match err.raw_os_error() {
+                    Some(libc::EINVAL) => io::Error::new_const(
+                        io::ErrorKind::Unsupported,
+                        &"direct I/O is not supported by this filesystem",
+                    ),
+                    _ => err,
+                },
+            )?;
+        }
+
+        // A block device reports its logical block size, which is what direct
+        // I/O has to be aligned to. For a file, the block size of its
+        // filesystem is a multiple of that of the device underneath.
+        let align = if self.file_attr()?.file_type().is(libc::S_IFBLK) {
+            let mut size: c_int = 0;
+            cvt(unsafe { libc::ioctl(fd, BLKSSZGET as _, &mut size) })?;
+            size as usize
+        } else {
+            let mut buf: libc::statvfs = unsafe { mem::zeroed() };
+            cvt(unsafe { libc::fstatvfs(fd, &mut buf) })?;
+            buf.f_bsize as usize
+        };
+        Ok(align.max(1))
+    }
+
+    /// Reads at `offset` with `O_DIRECT`, after checking that the buffer and
+    /// the offset have the `align`ment returned by `enable_direct_io`.
+    #[cfg(target_os = "linux")]
+    pub fn read_direct_at(&self, buf: &mut [u8], offset: u64, align: usize) -> io::Result<usize> {
+        check_direct_io_alignment(buf.as_ptr(), buf.len(), offset, align)?;
+        self.read_at(buf, offset)
+    }
+
+    /// Writes at `offset` with `O_DIRECT`, after checking that the buffer and
+    /// the offset have the `align`ment returned by `enable_direct_io`.
+    #[cfg(target_os = "linux")]
+    pub fn write_direct_at(&self, buf: &[u8], offset: u64, align: usize) -> io::Result<usize> {
+        check_direct_io_alignment(buf.as_ptr(), buf.len(), offset, align)?;
+        self.write_at(buf, offset)
+    }
+
     pub fn truncate(&self, size: u64) -> io::Result<()> {
         #[cfg(target_os = "android")]
         return crate::sys::android::ftruncate64(self.0.raw(), size);
@@ -892,6 +1081,48 @@ pub fn set_permissions(&self, perm: FilePermissions) -> io::Result<()> {
     }
 }
 
+/// Checks the alignment that direct I/O needs up front, as the kernel only
+/// fails with `EINVAL` without saying what's wrong.
+#[cfg(target_os = "linux")]
+fn check_direct_io_alignment(
+    ptr: *const u8,
+    len: usize,
+    offset: u64,
+    align: usize,
+) -> io::Result<()> {
+    if ptr as usize % align != 0 {
+        Err(io::Error::new_const(
+            io::ErrorKind::InvalidInput,
+            &"buffer address is not aligned to the direct I/O block size",
+        ))
+    } else if len % align != 0 {
+        Err(io::Error::new_const(
+            io::ErrorKind::InvalidInput,
+            &"buffer length is not a multiple of the direct I/O block size",
+        ))
+    } else if offset % align as u64 != 0 {
+        Err(io::Error::new_const(
+            io::ErrorKind::InvalidInput,
+            &"file offset is not a multiple of the direct I/O block size",
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+// Filesystems without inode flags (tmpfs, NFS, ...) either reject the ioctl
+// outright or report that the operation is not supported.
+#[cfg(target_os = "linux")]
+fn inode_flags_error(err: io::Error) -> io::Error {
+    match err.raw_os_error() {
+        Some(libc::ENOTTY) | Some(libc::EOPNOTSUPP) => io::Error::new_const(
+            io::ErrorKind::Unsupported,
+            &"inode flags are not supported by this filesystem",
+        ),
+        _ => err,
+    }
+}
+
 impl DirBuilder {
     pub fn new() -> DirBuilder {
         DirBuilder { mode: 0o777 }
@@ -912,6 +1143,199 @@ fn cstr(path: &Path) -> io::Result<CString> {
     Ok(CString::new(path.as_os_str().as_bytes())?)
 }
 
+#[cfg(not(any(target_os = "redox", target_os = "vxworks")))]
+impl DirFd {
+    const OPEN_FLAGS: c_int = libc::O_RDONLY | libc::O_DIRECTORY | libc::O_CLOEXEC;
+
+    pub fn open(p: &Path) -> io::Result<DirFd> {
+        let root = p.to_path_buf();
+        let p = cstr(p)?;
+        let fd = cvt_r(|| unsafe { open64(p.as_ptr(), DirFd::OPEN_FLAGS) })?;
+        Ok(DirFd { fd: FileDesc::new(fd), root })
+    }
+
+    /// Opens every directory leading up to the last component of `p`, one
+    /// component at a time and without following symlinks, so that the
+    /// walk can never leave this directory. Returns the innermost directory
+    /// opened, or `None` if that is this one, along with the last component.
+    fn resolve(&self, p: &Path) -> io::Result<(Option<FileDesc>, CString)> {
+        use crate::path::Component;
+
+        let mut parent: Option<FileDesc> = None;
+        let mut last: Option<CString> = None;
+        for component in p.components() {
+            let name = match component {
+                Component::Normal(name) => CString::new(name.as_bytes())?,
+                Component::CurDir => continue,
+                Component::ParentDir | Component::RootDir | Component::Prefix(_) => {
+                    return Err(io::Error::new_const(
+                        io::ErrorKind::InvalidInput,
+                        &"path must be relative and stay within the directory",
+                    ));
+                }
+            };
+            if let Some(dir) = last.replace(name) {
+                let at = parent.as_ref().unwrap_or(&self.fd).raw();
+                let flags = DirFd::OPEN_FLAGS | libc::O_NOFOLLOW;
+                let fd = cvt_r(|| unsafe { libc::openat(at, dir.as_ptr(), flags) })?;
+                parent = Some(FileDesc::new(fd));
+            }
+        }
+        match last {
+            Some(name) => Ok((parent, name)),
+            None => {
+                Err(io::Error::new_const(io::ErrorKind::InvalidInput, &"path has no file name"))
+            }
+        }
+    }
+
+    pub fn open_at(&self, p: &Path, opts: &OpenOptions) -> io::Result<File> {
+        let (parent, name) = self.resolve(p)?;
+        let at = parent.as_ref().unwrap_or(&self.fd).raw();
+        let flags = libc::O_CLOEXEC
+            | libc::O_NOFOLLOW
+            | opts.get_access_mode()?
+            | opts.get_creation_mode()?
+            | (opts.custom_flags as c_int & !libc::O_ACCMODE);
+        // See `File::open_c` for why the mode is passed as a `c_int`.
+        let fd = cvt_r(|| unsafe { libc::openat(at, name.as_ptr(), flags, opts.mode as c_int) })?;
+        Ok(File(FileDesc::new(fd)))
+    }
+
+    pub fn open_dir_at(&self, p: &Path) -> io::Result<DirFd> {
+        let (parent, name) = self.resolve(p)?;
+        let at = parent.as_ref().unwrap_or(&self.fd).raw();
+        let flags = DirFd::OPEN_FLAGS | libc::O_NOFOLLOW;
+        let fd = cvt_r(|| unsafe { libc::openat(at, name.as_ptr(), flags) })?;
+        Ok(DirFd { fd: FileDesc::new(fd), root: self.root.join(p) })
+    }
+
+    pub fn mkdir_at(&self, p: &Path, mode: u32) -> io::Result<()> {
+        let (parent, name) = self.resolve(p)?;
+        let at = parent.as_ref().unwrap_or(&self.fd).raw();
+        cvt(unsafe { libc::mkdirat(at, name.as_ptr(), mode as mode_t) })?;
+        Ok(())
+    }
+
+    pub fn unlink_at(&self, p: &Path) -> io::Result<()> {
+        let (parent, name) = self.resolve(p)?;
+        let at = parent.as_ref().unwrap_or(&self.fd).raw();
+        cvt(unsafe { libc::unlinkat(at, name.as_ptr(), 0) })?;
+        Ok(())
+    }
+
+    pub fn link_at(&self, original: &Path, link: &Path, follow: bool) -> io::Result<()> {
+        let (original_parent, original_name) = self.resolve(original)?;
+        let (link_parent, link_name) = self.resolve(link)?;
+        let original_at = original_parent.as_ref().unwrap_or(&self.fd).raw();
+        let link_at = link_parent.as_ref().unwrap_or(&self.fd).raw();
+        let flags = if follow { libc::AT_SYMLINK_FOLLOW } else { 0 };
+        cvt(unsafe {
+            libc::linkat(original_at, original_name.as_ptr(), link_at, link_name.as_ptr(), flags)
+        })?;
+        Ok(())
+    }
+
+    pub fn symlink_at(&self, original: &Path, link: &Path) -> io::Result<()> {
+        use crate::path::Component;
+
+        let (parent, name) = self.resolve(link)?;
+
+        // The target is resolved relative to the directory holding the link,
+        // so it may climb out of that one, but not out of this one.
+        let mut depth = link.components().filter(|c| matches!(c, Component::Normal(_))).count() - 1;
+        for component in original.components() {
+            match component {
+                Component::Normal(_) => depth += 1,
+                Component::CurDir => {}
+                Component::ParentDir if depth > 0 => depth -= 1,
+                _ => {
+                    return Err(io::Error::new_const(
+                        io::ErrorKind::InvalidInput,
+                        &"symlink target must be relative and stay within the directory",
+                    ));
+                }
+            }
+        }
+
+        let original = cstr(original)?;
+        let at = parent.as_ref().unwrap_or(&self.fd).raw();
+        cvt(unsafe { libc::symlinkat(original.as_ptr(), at, name.as_ptr()) })?;
+        Ok(())
+    }
+
+    pub fn readdir(&self) -> io::Result<ReadDir> {
+        // Open the directory afresh rather than duplicating the descriptor,
+        // so that each stream has its own position.
+        let dot = b".\0".as_ptr() as *const libc::c_char;
+        let fd = cvt_r(|| unsafe { libc::openat(self.fd.raw(), dot, DirFd::OPEN_FLAGS) })?;
+        let fd = FileDesc::new(fd);
+        let ptr = unsafe { libc::fdopendir(fd.raw()) };
+        if ptr.is_null() {
+            return Err(Error::last_os_error());
+        }
+        // The stream owns the descriptor now and closes it in `closedir`.
+        mem::forget(fd);
+        Ok(ReadDir::new(InnerReadDir { dirp: Dir(ptr), root: self.root.clone() }))
+    }
+
+    pub fn fd(&self) -> &FileDesc {
+        &self.fd
+    }
+}
+
+#[cfg(any(target_os = "redox", target_os = "vxworks"))]
+impl DirFd {
+    fn unsupported<T>() -> io::Result<T> {
+        Err(io::Error::new_const(
+            io::ErrorKind::Unsupported,
+            &"directory descriptors are not supported on this platform",
+        ))
+    }
+
+    pub fn open(_p: &Path) -> io::Result<DirFd> {
+        DirFd::unsupported()
+    }
+
+    pub fn open_at(&self, _p: &Path, _opts: &OpenOptions) -> io::Result<File> {
+        DirFd::unsupported()
+    }
+
+    pub fn open_dir_at(&self, _p: &Path) -> io::Result<DirFd> {
+        DirFd::unsupported()
+    }
+
+    pub fn mkdir_at(&self, _p: &Path, _mode: u32) -> io::Result<()> {
+        DirFd::unsupported()
+    }
+
+    pub fn unlink_at(&self, _p: &Path) -> io::Result<()> {
+        DirFd::unsupported()
+    }
+
+    pub fn link_at(&self, _original: &Path, _link: &Path, _follow: bool) -> io::Result<()> {
+        DirFd::unsupported()
+    }
+
+    pub fn symlink_at(&self, _original: &Path, _link: &Path) -> io::Result<()> {
+        DirFd::unsupported()
+    }
+
+    pub fn readdir(&self) -> io::Result<ReadDir> {
+        DirFd::unsupported()
+    }
+
+    pub fn fd(&self) -> &FileDesc {
+        &self.fd
+    }
+}
+
+impl fmt::Debug for DirFd {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Dir").field("fd", &self.fd.raw()).field("path", &self.root).finish()
+    }
+}
+
 impl FromInner<c_int> for File {
     fn from_inner(fd: c_int) -> File {
         File(FileDesc::new(fd))
@@ -1004,17 +1428,7 @@ pub fn readdir(p: &Path) -> io::Result<ReadDir> {
         if ptr.is_null() {
             Err(Error::last_os_error())
         } else {
-            let inner = InnerReadDir { dirp: Dir(ptr), root };
-            Ok(ReadDir {
-                inner: Arc::new(inner),
-                #[cfg(not(any(
-                    target_os = "solaris",
-                    target_os = "illumos",
-                    target_os = "fuchsia",
-                    target_os = "redox",
-                )))]
-                end_of_stream: false,
-            })
+            Ok(ReadDir::new(InnerReadDir { dirp: Dir(ptr), root }))
         }
     }
 }
@@ -1032,6 +1446,60 @@ pub fn rename(old: &Path, new: &Path) -> io::Result<()> {
     Ok(())
 }
 
+pub fn rename_noreplace(old: &Path, new: &Path) -> io::Result<()> {
+    rename_with_flags(old, new, RENAME_NOREPLACE)
+}
+
+pub fn rename_exchange(old: &Path, new: &Path) -> io::Result<()> {
+    rename_with_flags(old, new, RENAME_EXCHANGE)
+}
+
+const RENAME_NOREPLACE: libc::c_uint = 1 << 0;
+const RENAME_EXCHANGE: libc::c_uint = 1 << 1;
+
+#[cfg(target_os = "linux")]
+fn rename_with_flags(old: &Path, new: &Path, flags: libc::c_uint) -> io::Result<()> {
+    syscall! {
+        fn renameat2(
+            olddirfd: c_int,
+            oldpath: *const libc::c_char,
+            newdirfd: c_int,
+            newpath: *const libc::c_char,
+            flags: libc::c_uint
+        ) -> c_int
+    }
+
+    let old = cstr(old)?;
+    let new = cstr(new)?;
+    let r = cvt(unsafe {
+        renameat2(libc::AT_FDCWD, old.as_ptr(), libc::AT_FDCWD, new.as_ptr(), flags)
+    });
+    match r {
+        Ok(_) => Ok(()),
+        // Kernels prior to 3.15 lack `renameat2` entirely, and filesystems
+        // which do not implement the requested flag report `EINVAL`. Either
+        // way we must not fall back to a plain `rename`, which would clobber
+        // the target.
+        Err(ref e) if e.raw_os_error() == Some(libc::ENOSYS) => Err(io::Error::new_const(
+            io::ErrorKind::Unsupported,
+            &"renameat2 is not supported by this kernel",
+        )),
+        Err(ref e) if e.raw_os_error() == Some(libc::EINVAL) => Err(io::Error::new_const(
+            io::ErrorKind::Unsupported,
+            &"the requested rename flags are not supported by this filesystem",
+        )),
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn rename_with_flags(_old: &Path, _new: &Path, _flags: libc::c_uint) -> io::Result<()> {
+    Err(io::Error::new_const(
+        io::ErrorKind::Unsupported,
+        &"renaming with flags is not supported on this platform",
+    ))
+}
+
 pub fn set_perm(p: &Path, perm: FilePermissions) -> io::Result<()> {
     let p = cstr(p)?;
     cvt_r(|| unsafe { libc::chmod(p.as_ptr(), perm.mode) })?;
@@ -1045,10 +1513,29 @@ pub fn rmdir(p: &Path) -> io::Result<()> {
 }
 
 pub fn readlink(p: &Path) -> io::Result<PathBuf> {
+    let mut buf = Vec::with_capacity(256);
+    read_link_into(p, &mut buf)?;
+    buf.shrink_to_fit();
+    Ok(PathBuf::from(OsString::from_vec(buf)))
+}
+
+/// Reads the target of the symlink at `p` into `buf`, replacing its contents,
+/// and returns the length of the target.
+///
+/// `readlink` silently truncates the target to the size of the buffer it is
+/// given and does not report the full length, so a result that fills the
+/// whole buffer may have been cut short. In that case the buffer is grown and
+/// the call retried, until a read leaves room to spare. The spare capacity of
+/// `buf` is reused, so a buffer that is already large enough is never
+/// reallocated.
+pub fn read_link_into(p: &Path, buf: &mut Vec<u8>) -> io::Result<usize> {
     let c_path = cstr(p)?;
     let p = c_path.as_ptr();
 
-    let mut buf = Vec::with_capacity(256);
+    buf.clear();
+    if buf.capacity() == 0 {
+        buf.reserve(256);
+    }
 
     loop {
         let buf_read =
@@ -1059,9 +1546,7 @@ pub fn readlink(p: &Path) -> io::Result<PathBuf> {
         }
 
         if buf_read != buf.capacity() {
-            buf.shrink_to_fit();
-
-            return Ok(PathBuf::from(OsString::from_vec(buf)));
+            return Ok(buf_read);
         }
 
         // Trigger the internal buffer resizing logic of `Vec` by requiring
@@ -1200,28 +1685,154 @@ pub fn copy(from: &Path, to: &Path) -> io::Result<u64> {
     io::copy(&mut reader, &mut writer)
 }
 
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "ios"
+)))]
+pub fn reflink_or_copy(from: &Path, to: &Path) -> io::Result<bool> {
+    // There is no way to clone a file here.
+    copy(from, to).map(|_| false)
+}
+
 #[cfg(any(target_os = "linux", target_os = "android"))]
 pub fn copy(from: &Path, to: &Path) -> io::Result<u64> {
     let (mut reader, reader_metadata) = open_from(from)?;
-    let max_len = u64::MAX;
     let (mut writer, _) = open_to_and_set_permissions(to, reader_metadata)?;
+    copy_open_files(&mut reader, &mut writer)
+}
 
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn copy_open_files(reader: &mut crate::fs::File, writer: &mut crate::fs::File) -> io::Result<u64> {
     use super::kernel_copy::{copy_regular_files, CopyResult};
 
+    let max_len = u64::MAX;
     match copy_regular_files(reader.as_raw_fd(), writer.as_raw_fd(), max_len) {
         CopyResult::Ended(bytes) => Ok(bytes),
         CopyResult::Error(e, _) => Err(e),
-        CopyResult::Fallback(written) => match io::copy::generic_copy(&mut reader, &mut writer) {
+        CopyResult::Fallback(written) => match io::copy::generic_copy(reader, writer) {
             Ok(bytes) => Ok(bytes + written),
             Err(e) => Err(e),
         },
     }
 }
 
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn reflink_or_copy(from: &Path, to: &Path) -> io::Result<bool> {
+    // `FICLONE` is `_IOW(0x94, 9, int)`, and the direction bits of `_IOW` are
+    // encoded differently on a few architectures.
+    #[cfg(any(
+        target_arch = "mips",
+        target_arch = "mips64",
+        target_arch = "powerpc",
+        target_arch = "powerpc64",
+        target_arch = "sparc64"
+    ))]
+    const FICLONE: u32 = 0x8004_9409;
+    #[cfg(not(any(
+        target_arch = "mips",
+        target_arch = "mips64",
+        target_arch = "powerpc",
+        target_arch = "powerpc64",
+        target_arch = "sparc64"
+    )))]
+    const FICLONE: u32 = 0x4004_9409;
+
+    let (mut reader, reader_metadata) = open_from(from)?;
+    let (mut writer, _) = open_to_and_set_permissions(to, reader_metadata)?;
+
+    // Share the extents of `from` if the filesystem supports it, as btrfs
+    // and XFS do.
+    match cvt(unsafe { libc::ioctl(writer.as_raw_fd(), FICLONE as _, reader.as_raw_fd()) }) {
+        Ok(_) => return Ok(true),
+        Err(err) => match err.raw_os_error() {
+            // The filesystem can't share extents at all (EOPNOTSUPP, or
+            // ENOTTY if it doesn't know the ioctl), or not between these two
+            // files, because they are on different filesystems (EXDEV) or one
+            // of them isn't a regular file (EINVAL). A full copy still works
+            // in all of these cases.
+            Some(libc::EOPNOTSUPP)
+            | Some(libc::ENOTTY)
+            | Some(libc::ENOSYS)
+            | Some(libc::EXDEV)
+            | Some(libc::EINVAL) => {}
+            _ => return Err(err),
+        },
+    }
+
+    copy_open_files(&mut reader, &mut writer)?;
+    Ok(false)
+}
+
 #[cfg(any(target_os = "macos", target_os = "ios"))]
 pub fn copy(from: &Path, to: &Path) -> io::Result<u64> {
+    let (reader, reader_metadata) = open_from(from)?;
+
+    // Opportunistically attempt to create a copy-on-write clone of `from`.
+    if clone_file(&reader, to)? {
+        return Ok(reader_metadata.len());
+    }
+    fcopyfile_to(reader, reader_metadata, to)
+}
+
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+pub fn reflink_or_copy(from: &Path, to: &Path) -> io::Result<bool> {
+    let (reader, reader_metadata) = open_from(from)?;
+    if clone_file(&reader, to)? {
+        return Ok(true);
+    }
+    fcopyfile_to(reader, reader_metadata, to)?;
+    Ok(false)
+}
+
+/// Creates `to` as a copy-on-write clone of `reader` using `fclonefileat`.
+/// Returns `false` if that is not possible and a regular copy should be made
+/// instead.
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+fn clone_file(reader: &crate::fs::File, to: &Path) -> io::Result<bool> {
     use crate::sync::atomic::{AtomicBool, Ordering};
 
+    // MacOS prior to 10.12 don't support `fclonefileat`
+    // We store the availability in a global to avoid unnecessary syscalls
+    static HAS_FCLONEFILEAT: AtomicBool = AtomicBool::new(true);
+    syscall! {
+        fn fclonefileat(
+            srcfd: libc::c_int,
+            dst_dirfd: libc::c_int,
+            dst: *const libc::c_char,
+            flags: libc::c_int
+        ) -> libc::c_int
+    }
+
+    if !HAS_FCLONEFILEAT.load(Ordering::Relaxed) {
+        return Ok(false);
+    }
+    let to = cstr(to)?;
+    match cvt(unsafe { fclonefileat(reader.as_raw_fd(), libc::AT_FDCWD, to.as_ptr(), 0) }) {
+        Ok(_) => Ok(true),
+        Err(err) => match err.raw_os_error() {
+            // `fclonefileat` will fail on non-APFS volumes, if the
+            // destination already exists, or if the source and destination
+            // are on different devices. In all these cases `fcopyfile`
+            // should succeed.
+            Some(libc::ENOTSUP) | Some(libc::EEXIST) | Some(libc::EXDEV) => Ok(false),
+            Some(libc::ENOSYS) => {
+                HAS_FCLONEFILEAT.store(false, Ordering::Relaxed);
+                Ok(false)
+            }
+            _ => Err(err),
+        },
+    }
+}
+
+/// Copies `reader` to `to` using `fcopyfile`.
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+fn fcopyfile_to(
+    reader: crate::fs::File,
+    reader_metadata: crate::fs::Metadata,
+    to: &Path,
+) -> io::Result<u64> {
     const COPYFILE_ACL: u32 = 1 << 0;
     const COPYFILE_STAT: u32 = 1 << 1;
     const COPYFILE_XATTR: u32 = 1 << 2;
@@ -1267,41 +1878,6 @@ fn drop(&mut self) {
         }
     }
 
-    // MacOS prior to 10.12 don't support `fclonefileat`
-    // We store the availability in a global to avoid unnecessary syscalls
-    static HAS_FCLONEFILEAT: AtomicBool = AtomicBool::new(true);
-    syscall! {
-        fn fclonefileat(
-            srcfd: libc::c_int,
-            dst_dirfd: libc::c_int,
-            dst: *const libc::c_char,
-            flags: libc::c_int
-        ) -> libc::c_int
-    }
-
-    let (reader, reader_metadata) = open_from(from)?;
-
-    // Opportunistically attempt to create a copy-on-write clone of `from`
-    // using `fclonefileat`.
-    if HAS_FCLONEFILEAT.load(Ordering::Relaxed) {
-        let to = cstr(to)?;
-        let clonefile_result =
-            cvt(unsafe { fclonefileat(reader.as_raw_fd(), libc::AT_FDCWD, to.as_ptr(), 0) });
-        match clonefile_result {
-            Ok(_) => return Ok(reader_metadata.len()),
-            Err(err) => match err.raw_os_error() {
-                // `fclonefileat` will fail on non-APFS volumes, if the
-                // destination already exists, or if the source and destination
-                // are on different devices. In all these cases `fcopyfile`
-                // should succeed.
-                Some(libc::ENOTSUP) | Some(libc::EEXIST) | Some(libc::EXDEV) => (),
-                Some(libc::ENOSYS) => HAS_FCLONEFILEAT.store(false, Ordering::Relaxed),
-                _ => return Err(err),
-            },
-        }
-    }
-
-    // Fall back to using `fcopyfile` if `fclonefileat` does not succeed.
     let (writer, writer_metadata) = open_to_and_set_permissions(to, reader_metadata)?;
 
     // We ensure that `FreeOnDrop` never contains a null pointer so it is
@@ -1329,9 +1905,60 @@ fn fclonefileat(
     Ok(bytes_copied as u64)
 }
 
+#[derive(Clone, Copy, Debug)]
+pub struct FsStats {
+    pub block_size: u64,
+    pub total_bytes: u64,
+    pub free_bytes: u64,
+    pub available_bytes: u64,
+    pub total_inodes: u64,
+    pub free_inodes: u64,
+    pub available_inodes: u64,
+}
+
+#[cfg(not(any(target_os = "redox", target_os = "vxworks")))]
+pub fn statvfs(p: &Path) -> io::Result<FsStats> {
+    #[cfg(all(target_os = "linux", target_env = "gnu"))]
+    use libc::statvfs64;
+    #[cfg(not(all(target_os = "linux", target_env = "gnu")))]
+    use libc::statvfs as statvfs64;
+
+    let p = cstr(p)?;
+    let mut buf: statvfs64 = unsafe { mem::zeroed() };
+    cvt(unsafe { statvfs64(p.as_ptr(), &mut buf) })?;
+
+    // Block counts are in units of the fragment size, which some systems
+    // leave as zero to mean "same as the block size".
+    let fragment_size = if buf.f_frsize != 0 { buf.f_frsize as u64 } else { buf.f_bsize as u64 };
+    let bytes = |blocks| fragment_size.saturating_mul(blocks);
+    Ok(FsStats {
+        block_size: buf.f_bsize as u64,
+        total_bytes: bytes(buf.f_blocks as u64),
+        free_bytes: bytes(buf.f_bfree as u64),
+        available_bytes: bytes(buf.f_bavail as u64),
+        total_inodes: buf.f_files as u64,
+        free_inodes: buf.f_ffree as u64,
+        available_inodes: buf.f_favail as u64,
+    })
+}
+
+#[cfg(any(target_os = "redox", target_os = "vxworks"))]
+pub fn statvfs(_p: &Path) -> io::Result<FsStats> {
+    Err(io::Error::new_const(
+        io::ErrorKind::Unsupported,
+        &"statvfs is not supported on this platform",
+    ))
+}
+
 #[cfg(not(any(target_os = "fuchsia", target_os = "vxworks")))]
 pub fn chroot(dir: &Path) -> io::Result<()> {
     let dir = cstr(dir)?;
     cvt(unsafe { libc::chroot(dir.as_ptr()) })?;
     Ok(())
 }
+
+pub fn mkfifo(p: &Path, mode: u32) -> io::Result<()> {
+    let p = cstr(p)?;
+    cvt(unsafe { libc::mkfifo(p.as_ptr(), mode as libc::mode_t) })?;
+    Ok(())
+}
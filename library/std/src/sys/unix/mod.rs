@@ -44,6 +44,9 @@
 
 pub use crate::sys_common::os_str_bytes as os_str;
 
+#[cfg(test)]
+mod tests;
+
 // SAFETY: must be called only once during runtime initialization.
 // NOTE: this is not guaranteed to run, for example when Rust code is called externally.
 pub unsafe fn init(argc: isize, argv: *const *const u8) {
@@ -58,7 +61,8 @@ pub unsafe fn init(argc: isize, argv: *const *const u8) {
     // want!
     //
     // Hence, we set SIGPIPE to ignore when the program starts up in order
-    // to prevent this problem.
+    // to prevent this problem, unless the program asked for something else
+    // (see the `sigpipe` module).
     reset_sigpipe();
 
     stack_overflow::init();
@@ -110,19 +114,100 @@ unsafe fn sanitize_standard_fds() {
                         }
                     }
                 }
+            } else if #[cfg(target_os = "fuchsia")] {
+                // fdio implements poll in userspace, by waiting on the kernel
+                // object behind each descriptor. A descriptor that isn't bound
+                // has no object to wait on, so rather than rely on how fdio
+                // reports that, look the descriptors up in fdio's table, which
+                // `fcntl(F_GETFD)` does without leaving the process. Fuchsia
+                // has no /dev/null either, so bind a null object from fdio to
+                // the closed descriptors instead.
+                use crate::sys::os::errno;
+                extern "C" {
+                    fn fdio_null_create() -> *mut libc::c_void;
+                    fn fdio_bind_to_fd(
+                        io: *mut libc::c_void,
+                        fd: libc::c_int,
+                        starting_fd: libc::c_int,
+                    ) -> libc::c_int;
+                }
+                for fd in 0..3 {
+                    if libc::fcntl(fd, libc::F_GETFD) == -1 && errno() == libc::EBADF {
+                        let null = fdio_null_create();
+                        if null.is_null() || fdio_bind_to_fd(null, fd, 0) != fd {
+                            libc::abort();
+                        }
+                    }
+                }
+            } else if #[cfg(target_os = "vxworks")] {
+                // The poll of VxWorks is not documented to flag a closed
+                // descriptor with POLLNVAL rather than failing altogether, in
+                // which case it couldn't tell which of the three is closed. Ask
+                // about each descriptor on its own, as on Darwin. The null
+                // device of VxWorks is /null.
+                use crate::sys::os::errno;
+                for fd in 0..3 {
+                    if libc::fcntl(fd, libc::F_GETFD) == -1 && errno() == libc::EBADF {
+                        if libc::open("/null\0".as_ptr().cast(), libc::O_RDWR, 0) == -1 {
+                            libc::abort();
+                        }
+                    }
+                }
             }
         }
     }
 
     unsafe fn reset_sigpipe() {
         #[cfg(not(any(target_os = "emscripten", target_os = "fuchsia")))]
-        assert!(signal(libc::SIGPIPE, libc::SIG_IGN) != libc::SIG_ERR);
+        {
+            let handler = match sigpipe::requested() {
+                sigpipe::INHERIT => return,
+                sigpipe::DEFAULT => libc::SIG_DFL,
+                _ => libc::SIG_IGN,
+            };
+            assert!(signal(libc::SIGPIPE, handler) != libc::SIG_ERR);
+        }
+    }
+}
+
+/// What the runtime does with `SIGPIPE` during [`init`].
+///
+/// A program picks one of the values below by defining a `#[no_mangle]`
+/// static `__rust_std_sigpipe: u8`, as documented in `std::os::unix::process`.
+/// The runtime only holds a weak reference to that symbol, so programs that
+/// don't define it get [`IGNORE`]. Weak linkage is only dependable on Linux
+/// (see `sys::unix::weak`), so other targets always get [`IGNORE`] as well.
+#[cfg(not(any(target_os = "emscripten", target_os = "fuchsia")))]
+pub mod sigpipe {
+    /// Set `SIGPIPE` to `SIG_IGN`, so that writing to a closed pipe or socket
+    /// fails with `EPIPE` instead of killing the process. This is the default,
+    /// and any value not listed here is treated the same way.
+    pub const IGNORE: u8 = 0;
+    /// Set `SIGPIPE` to `SIG_DFL`, so that writing to a closed pipe kills the
+    /// process, as command line filters conventionally expect.
+    pub const DEFAULT: u8 = 1;
+    /// Leave `SIGPIPE` as it was inherited from the parent process.
+    pub const INHERIT: u8 = 2;
+
+    #[cfg(target_os = "linux")]
+    pub(super) unsafe fn requested() -> u8 {
+        extern "C" {
+            #[linkage = "extern_weak"]
+            static __rust_std_sigpipe: *const u8;
+        }
+        if __rust_std_sigpipe.is_null() { IGNORE } else { *__rust_std_sigpipe }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub(super) unsafe fn requested() -> u8 {
+        IGNORE
     }
 }
 
 // SAFETY: must be called only once during runtime cleanup.
 // NOTE: this is not guaranteed to run, for example when the program aborts.
 pub unsafe fn cleanup() {
+    stdio::cleanup();
     args::cleanup();
     stack_overflow::cleanup();
 }
@@ -135,13 +220,16 @@ pub unsafe fn cleanup() {
 pub fn decode_error_kind(errno: i32) -> ErrorKind {
     match errno as libc::c_int {
         libc::ECONNREFUSED => ErrorKind::ConnectionRefused,
-        libc::ECONNRESET => ErrorKind::ConnectionReset,
+        libc::ECONNRESET | libc::ENETRESET => ErrorKind::ConnectionReset,
         libc::EPERM | libc::EACCES => ErrorKind::PermissionDenied,
         libc::EPIPE => ErrorKind::BrokenPipe,
         libc::ENOTCONN => ErrorKind::NotConnected,
         libc::ECONNABORTED => ErrorKind::ConnectionAborted,
         libc::EADDRNOTAVAIL => ErrorKind::AddrNotAvailable,
         libc::EADDRINUSE => ErrorKind::AddrInUse,
+        libc::EHOSTUNREACH => ErrorKind::HostUnreachable,
+        libc::ENETUNREACH => ErrorKind::NetworkUnreachable,
+        libc::ENETDOWN => ErrorKind::NetworkDown,
         libc::ENOENT => ErrorKind::NotFound,
         libc::EINTR => ErrorKind::Interrupted,
         libc::EINVAL => ErrorKind::InvalidInput,
@@ -149,11 +237,17 @@ pub fn decode_error_kind(errno: i32) -> ErrorKind {
         libc::EEXIST => ErrorKind::AlreadyExists,
         libc::ENOSYS => ErrorKind::Unsupported,
         libc::ENOMEM => ErrorKind::OutOfMemory,
+        libc::ENOSPC => ErrorKind::StorageFull,
+        libc::EDQUOT => ErrorKind::QuotaExceeded,
+        libc::EFBIG => ErrorKind::FileTooLarge,
+        libc::EROFS => ErrorKind::ReadOnlyFilesystem,
 
         // These two constants can have the same value on some systems,
         // but different values on others, so we can't use a match
         // clause
         x if x == libc::EAGAIN || x == libc::EWOULDBLOCK => ErrorKind::WouldBlock,
+        // Same as above: Linux, for one, defines `ENOTSUP` as `EOPNOTSUPP`.
+        x if x == libc::ENOTSUP || x == libc::EOPNOTSUPP => ErrorKind::Unsupported,
 
         _ => ErrorKind::Other,
     }
@@ -195,6 +289,13 @@ pub fn cvt_r<T, F>(mut f: F) -> crate::io::Result<T>
     }
 }
 
+/// Like [`cvt`], for functions such as `mmap` that report errors by returning
+/// `MAP_FAILED` (a pointer with all bits set) instead of `-1`.
+#[allow(dead_code)] // not used on all platforms
+pub fn cvt_mmap(ptr: *mut libc::c_void) -> crate::io::Result<*mut libc::c_void> {
+    if ptr == libc::MAP_FAILED { Err(crate::io::Error::last_os_error()) } else { Ok(ptr) }
+}
+
 pub fn cvt_nz(error: libc::c_int) -> crate::io::Result<()> {
     if error == 0 {
         Ok(())
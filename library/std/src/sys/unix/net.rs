@@ -1,15 +1,20 @@
+#[cfg(test)]
+mod tests;
+
 use crate::cmp;
-use crate::ffi::CStr;
+use crate::ffi::{CStr, OsString};
 use crate::io::{self, IoSlice, IoSliceMut};
 use crate::mem;
-use crate::net::{Shutdown, SocketAddr};
+use crate::net::{IpAddr, Shutdown, SocketAddr};
+use crate::path::{Path, PathBuf};
+use crate::ptr;
 use crate::str;
 use crate::sys::fd::FileDesc;
 use crate::sys_common::net::{getsockopt, setsockopt, sockaddr_to_addr};
 use crate::sys_common::{AsInner, FromInner, IntoInner};
 use crate::time::{Duration, Instant};
 
-use libc::{c_int, c_void, size_t, sockaddr, socklen_t, EAI_SYSTEM, MSG_PEEK};
+use libc::{c_int, c_short, c_void, size_t, sockaddr, socklen_t, EAI_SYSTEM, MSG_PEEK};
 
 pub use crate::sys::{cvt, cvt_r};
 
@@ -20,6 +25,74 @@
 
 pub struct Socket(FileDesc);
 
+/// The leading part of Linux's `struct tcp_info`, up to the fields added in
+/// kernel 4.1.
+///
+/// The kernel struct keeps growing at the end. Newer kernels only fill in as
+/// much of it as fits in the buffer they are given, and older kernels fill in
+/// less than all of it, so the length they report has to be checked before
+/// reading any of the later fields.
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[allow(non_camel_case_types)]
+pub struct tcp_info {
+    pub tcpi_state: u8,
+    pub tcpi_ca_state: u8,
+    pub tcpi_retransmits: u8,
+    pub tcpi_probes: u8,
+    pub tcpi_backoff: u8,
+    pub tcpi_options: u8,
+    pub tcpi_snd_rcv_wscale: u8,
+    pub tcpi_flags: u8,
+
+    pub tcpi_rto: u32,
+    pub tcpi_ato: u32,
+    pub tcpi_snd_mss: u32,
+    pub tcpi_rcv_mss: u32,
+
+    pub tcpi_unacked: u32,
+    pub tcpi_sacked: u32,
+    pub tcpi_lost: u32,
+    pub tcpi_retrans: u32,
+    pub tcpi_fackets: u32,
+
+    pub tcpi_last_data_sent: u32,
+    pub tcpi_last_ack_sent: u32,
+    pub tcpi_last_data_recv: u32,
+    pub tcpi_last_ack_recv: u32,
+
+    pub tcpi_pmtu: u32,
+    pub tcpi_rcv_ssthresh: u32,
+    pub tcpi_rtt: u32,
+    pub tcpi_rttvar: u32,
+    pub tcpi_snd_ssthresh: u32,
+    pub tcpi_snd_cwnd: u32,
+    pub tcpi_advmss: u32,
+    pub tcpi_reordering: u32,
+
+    pub tcpi_rcv_rtt: u32,
+    pub tcpi_rcv_space: u32,
+
+    pub tcpi_total_retrans: u32,
+
+    // Linux 3.15
+    pub tcpi_pacing_rate: u64,
+    pub tcpi_max_pacing_rate: u64,
+
+    // Linux 4.1
+    pub tcpi_bytes_acked: u64,
+    pub tcpi_bytes_received: u64,
+}
+
+impl tcp_info {
+    /// Returns whether `field`, which must be a field of `self`, lies within
+    /// the first `len` bytes.
+    pub fn has_field<T>(&self, field: &T, len: usize) -> bool {
+        let start = field as *const T as usize - self as *const tcp_info as usize;
+        start + mem::size_of::<T>() <= len
+    }
+}
+
 pub fn init() {}
 
 pub fn cvt_gai(err: c_int) -> io::Result<()> {
@@ -43,6 +116,66 @@ pub fn cvt_gai(err: c_int) -> io::Result<()> {
     ))
 }
 
+/// Waits until `fd` is ready for any of the `poll` events in `events`,
+/// returning the events that were reported, or `None` if `timeout` elapses
+/// first. Like `poll`, this may report `POLLERR`, `POLLHUP` or `POLLNVAL`
+/// even if they are not in `events`.
+///
+/// The deadline is fixed on entry. If `poll` is interrupted by a signal, it is
+/// called again with only the time that is left, so signals neither cut the
+/// wait short nor stretch it out. The remaining time is rounded up to whole
+/// milliseconds, so the wait never ends before the deadline.
+pub fn wait_readable(
+    fd: c_int,
+    events: c_short,
+    timeout: Duration,
+) -> io::Result<Option<c_short>> {
+    let mut pollfd = libc::pollfd { fd, events, revents: 0 };
+    // A timeout too long to represent as a deadline is as good as infinite.
+    let deadline = Instant::now().checked_add(timeout);
+
+    loop {
+        let ms = match deadline {
+            Some(deadline) => {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                let ms = (remaining.as_nanos() + 999_999) / 1_000_000;
+                cmp::min(ms, c_int::MAX as u128) as c_int
+            }
+            None => -1,
+        };
+
+        match unsafe { libc::poll(&mut pollfd, 1, ms) } {
+            -1 => {
+                let err = io::Error::last_os_error();
+                if err.kind() != io::ErrorKind::Interrupted {
+                    return Err(err);
+                }
+            }
+            // A timeout clamped to `c_int::MAX` milliseconds can end before
+            // the deadline, in which case we keep waiting.
+            0 if deadline.map_or(false, |deadline| Instant::now() >= deadline) => {
+                return Ok(None);
+            }
+            0 => {}
+            _ => return Ok(Some(pollfd.revents)),
+        }
+    }
+}
+
+/// Works out how a non-blocking `connect` ended, from the `poll` events that
+/// reported it done and the error pending on the socket.
+fn connect_outcome(revents: c_short, error: Option<io::Error>) -> io::Result<()> {
+    match error {
+        // linux returns POLLOUT|POLLERR|POLLHUP for refused connections (!), so
+        // readiness alone doesn't mean the connection was made.
+        Some(e) => Err(e),
+        None if revents & libc::POLLHUP != 0 => {
+            Err(io::Error::new_const(io::ErrorKind::Other, &"no error set after POLLHUP"))
+        }
+        None => Ok(()),
+    }
+}
+
 impl Socket {
     pub fn new(addr: &SocketAddr, ty: c_int) -> io::Result<Socket> {
         let fam = match *addr {
@@ -53,6 +186,16 @@ pub fn new(addr: &SocketAddr, ty: c_int) -> io::Result<Socket> {
     }
 
     pub fn new_raw(fam: c_int, ty: c_int) -> io::Result<Socket> {
+        Socket::new_with_protocol(fam, ty, 0)
+    }
+
+    /// Creates a `SOCK_RAW` socket for the IP protocol `protocol`, such as
+    /// `IPPROTO_ICMP`. This needs `CAP_NET_RAW` on Linux, or root elsewhere.
+    pub fn new_raw_ip(fam: c_int, protocol: c_int) -> io::Result<Socket> {
+        Socket::new_with_protocol(fam, libc::SOCK_RAW, protocol)
+    }
+
+    fn new_with_protocol(fam: c_int, ty: c_int, protocol: c_int) -> io::Result<Socket> {
         unsafe {
             cfg_if::cfg_if! {
                 if #[cfg(any(
@@ -67,10 +210,10 @@ pub fn new_raw(fam: c_int, ty: c_int) -> io::Result<Socket> {
                     // On platforms that support it we pass the SOCK_CLOEXEC
                     // flag to atomically create the socket and set it as
                     // CLOEXEC. On Linux this was added in 2.6.27.
-                    let fd = cvt(libc::socket(fam, ty | libc::SOCK_CLOEXEC, 0))?;
+                    let fd = cvt(libc::socket(fam, ty | libc::SOCK_CLOEXEC, protocol))?;
                     Ok(Socket(FileDesc::new(fd)))
                 } else {
-                    let fd = cvt(libc::socket(fam, ty, 0))?;
+                    let fd = cvt(libc::socket(fam, ty, protocol))?;
                     let fd = FileDesc::new(fd);
                     fd.set_cloexec()?;
                     let socket = Socket(fd);
@@ -136,8 +279,6 @@ pub fn connect_timeout(&self, addr: &SocketAddr, timeout: Duration) -> io::Resul
             Err(e) => return Err(e),
         }
 
-        let mut pollfd = libc::pollfd { fd: self.0.raw(), events: libc::POLLOUT, revents: 0 };
-
         if timeout.as_secs() == 0 && timeout.subsec_nanos() == 0 {
             return Err(io::Error::new_const(
                 io::ErrorKind::InvalidInput,
@@ -145,52 +286,175 @@ pub fn connect_timeout(&self, addr: &SocketAddr, timeout: Duration) -> io::Resul
             ));
         }
 
-        let start = Instant::now();
-
-        loop {
-            let elapsed = start.elapsed();
-            if elapsed >= timeout {
+        let revents = match wait_readable(self.0.raw(), libc::POLLOUT, timeout)? {
+            Some(revents) => revents,
+            None => {
                 return Err(io::Error::new_const(io::ErrorKind::TimedOut, &"connection timed out"));
             }
+        };
+        connect_outcome(revents, self.take_error()?)
+    }
+
+    pub fn connect_from(local: &SocketAddr, remote: &SocketAddr) -> io::Result<Socket> {
+        let sock = Socket::new(remote, libc::SOCK_STREAM)?;
+
+        // Binding to port 0 normally reserves an ephemeral port right away,
+        // which must then be unique across *all* connections from that
+        // address. With `IP_BIND_ADDRESS_NO_PORT` the port is only picked at
+        // `connect` time, when it just has to be unique for the full 4-tuple.
+        // Kernels before 4.2 don't know the option; plain `bind` still works
+        // there, so ignore any error.
+        #[cfg(target_os = "linux")]
+        if local.port() == 0 {
+            let _ = setsockopt(&sock, libc::IPPROTO_IP, libc::IP_BIND_ADDRESS_NO_PORT, 1 as c_int);
+        }
+
+        let (addrp, len) = local.into_inner();
+        cvt(unsafe { libc::bind(sock.0.raw(), addrp, len) })?;
+        let (addrp, len) = remote.into_inner();
+        cvt_r(|| unsafe { libc::connect(sock.0.raw(), addrp, len) })?;
+        Ok(sock)
+    }
+
+    pub fn connect_any(addrs: &[SocketAddr], timeout: Duration) -> io::Result<Socket> {
+        // The "Connection Attempt Delay" recommended by RFC 8305.
+        const ATTEMPT_DELAY: Duration = Duration::from_millis(250);
 
-            let timeout = timeout - elapsed;
-            let mut timeout = timeout
-                .as_secs()
-                .saturating_mul(1_000)
-                .saturating_add(timeout.subsec_nanos() as u64 / 1_000_000);
-            if timeout == 0 {
-                timeout = 1;
+        let first_family = match addrs.first() {
+            Some(addr) => addr.is_ipv6(),
+            None => {
+                return Err(io::Error::new_const(
+                    io::ErrorKind::InvalidInput,
+                    &"could not resolve to any addresses",
+                ));
             }
+        };
+        if timeout == Duration::ZERO {
+            return Err(io::Error::new_const(
+                io::ErrorKind::InvalidInput,
+                &"cannot set a 0 duration timeout",
+            ));
+        }
+
+        // Alternate between address families, starting with the preferred
+        // family of the first address, so that a broken family only ever
+        // costs one attempt delay.
+        let (preferred, other): (Vec<&SocketAddr>, Vec<&SocketAddr>) =
+            addrs.iter().partition(|addr| addr.is_ipv6() == first_family);
+        let mut order = Vec::with_capacity(addrs.len());
+        let (mut preferred, mut other) = (preferred.into_iter(), other.into_iter());
+        while preferred.len() + other.len() != 0 {
+            order.extend(preferred.next());
+            order.extend(other.next());
+        }
+        let mut order = order.into_iter();
 
-            let timeout = cmp::min(timeout, c_int::MAX as u64) as c_int;
+        let start = Instant::now();
+        let mut next_attempt = start;
+        let mut pending: Vec<Socket> = Vec::new();
+        let mut last_err = None;
 
-            match unsafe { libc::poll(&mut pollfd, 1, timeout) } {
-                -1 => {
-                    let err = io::Error::last_os_error();
-                    if err.kind() != io::ErrorKind::Interrupted {
-                        return Err(err);
+        loop {
+            let now = Instant::now();
+            if now >= next_attempt || pending.is_empty() {
+                match order.next() {
+                    Some(addr) => {
+                        next_attempt = now + ATTEMPT_DELAY;
+                        match Socket::begin_connect(addr) {
+                            Ok((sock, true)) => {
+                                sock.set_nonblocking(false)?;
+                                return Ok(sock);
+                            }
+                            Ok((sock, false)) => pending.push(sock),
+                            Err(e) => {
+                                // Move on to the next address without waiting.
+                                last_err = Some(e);
+                                next_attempt = now;
+                                continue;
+                            }
+                        }
                     }
-                }
-                0 => {}
-                _ => {
-                    // linux returns POLLOUT|POLLERR|POLLHUP for refused connections (!), so look
-                    // for POLLHUP rather than read readiness
-                    if pollfd.revents & libc::POLLHUP != 0 {
-                        let e = self.take_error()?.unwrap_or_else(|| {
+                    None if pending.is_empty() => {
+                        return Err(last_err.unwrap_or_else(|| {
                             io::Error::new_const(
                                 io::ErrorKind::Other,
-                                &"no error set after POLLHUP",
+                                &"all connection attempts failed",
                             )
-                        });
-                        return Err(e);
+                        }));
                     }
+                    None => {}
+                }
+            }
+
+            let elapsed = now.saturating_duration_since(start);
+            if elapsed >= timeout {
+                return Err(io::Error::new_const(io::ErrorKind::TimedOut, &"connection timed out"));
+            }
+            let mut wait = timeout - elapsed;
+            if order.len() != 0 {
+                wait = cmp::min(wait, next_attempt.saturating_duration_since(now));
+            }
+            let wait = cmp::max(wait.as_millis(), 1);
+            let wait = cmp::min(wait, c_int::MAX as u128) as c_int;
+
+            let mut fds: Vec<libc::pollfd> = pending
+                .iter()
+                .map(|sock| libc::pollfd { fd: sock.0.raw(), events: libc::POLLOUT, revents: 0 })
+                .collect();
+            if unsafe { libc::poll(fds.as_mut_ptr(), fds.len() as libc::nfds_t, wait) } == -1 {
+                let err = io::Error::last_os_error();
+                if err.kind() != io::ErrorKind::Interrupted {
+                    return Err(err);
+                }
+                continue;
+            }
 
-                    return Ok(());
+            let mut i = 0;
+            while i < pending.len() {
+                let revents = fds[i].revents;
+                if revents == 0 {
+                    i += 1;
+                    continue;
+                }
+                // As in `connect_timeout`, a refused connection can be
+                // reported as POLLOUT|POLLERR|POLLHUP, so check for errors
+                // before treating the socket as connected.
+                match pending[i].take_error() {
+                    Ok(None) if revents & libc::POLLHUP == 0 => {
+                        // Dropping the other attempts closes their sockets.
+                        let sock = pending.swap_remove(i);
+                        sock.set_nonblocking(false)?;
+                        return Ok(sock);
+                    }
+                    Ok(None) => {
+                        last_err = Some(io::Error::new_const(
+                            io::ErrorKind::Other,
+                            &"no error set after POLLHUP",
+                        ))
+                    }
+                    Ok(Some(e)) | Err(e) => last_err = Some(e),
                 }
+                pending.remove(i);
+                fds.remove(i);
+                // A failed attempt makes way for the next one right away.
+                next_attempt = now;
             }
         }
     }
 
+    /// Starts a nonblocking connection attempt to `addr`, returning the socket
+    /// and whether it is already connected.
+    fn begin_connect(addr: &SocketAddr) -> io::Result<(Socket, bool)> {
+        let sock = Socket::new(addr, libc::SOCK_STREAM)?;
+        sock.set_nonblocking(true)?;
+        let (addrp, len) = addr.into_inner();
+        match cvt(unsafe { libc::connect(sock.0.raw(), addrp, len) }) {
+            Ok(_) => Ok((sock, true)),
+            Err(ref e) if e.raw_os_error() == Some(libc::EINPROGRESS) => Ok((sock, false)),
+            Err(e) => Err(e),
+        }
+    }
+
     pub fn accept(&self, storage: *mut sockaddr, len: *mut socklen_t) -> io::Result<Socket> {
         // Unfortunately the only known way right now to accept a socket and
         // atomically set the CLOEXEC flag is to use the `accept4` syscall on
@@ -272,6 +536,14 @@ pub fn recv_from(&self, buf: &mut [u8]) -> io::Result<(usize, SocketAddr)> {
         self.recv_from_with_flags(buf, 0)
     }
 
+    pub fn send_to(&self, buf: &[u8], dst: &SocketAddr) -> io::Result<usize> {
+        let (dstp, dstlen) = dst.into_inner();
+        let n = cvt(unsafe {
+            libc::sendto(self.0.raw(), buf.as_ptr() as *const c_void, buf.len(), 0, dstp, dstlen)
+        })?;
+        Ok(n as usize)
+    }
+
     #[cfg(any(
         target_os = "android",
         target_os = "dragonfly",
@@ -286,6 +558,59 @@ pub fn recv_msg(&self, msg: &mut libc::msghdr) -> io::Result<usize> {
         Ok(n as usize)
     }
 
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn set_timestamping(&self, enable: bool) -> io::Result<()> {
+        setsockopt(self, libc::SOL_SOCKET, libc::SO_TIMESTAMPNS, enable as c_int)
+    }
+
+    #[cfg(not(any(target_os = "android", target_os = "linux")))]
+    pub fn set_timestamping(&self, _enable: bool) -> io::Result<()> {
+        Err(io::Error::new_const(
+            io::ErrorKind::Unsupported,
+            &"receive timestamps are only supported on Linux and Android",
+        ))
+    }
+
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn recv_with_timestamp(&self, buf: &mut [u8]) -> io::Result<(usize, Option<Duration>)> {
+        // Room for a single `SCM_TIMESTAMPNS` message, aligned for `cmsghdr`.
+        let mut control = [0u64; 8];
+        let mut iov = libc::iovec { iov_base: buf.as_mut_ptr() as *mut c_void, iov_len: buf.len() };
+        let mut msg: libc::msghdr = unsafe { mem::zeroed() };
+        msg.msg_iov = &mut iov;
+        msg.msg_iovlen = 1;
+        msg.msg_control = control.as_mut_ptr() as *mut c_void;
+        msg.msg_controllen = mem::size_of_val(&control) as _;
+        let n = self.recv_msg(&mut msg)?;
+
+        let mut timestamp = None;
+        unsafe {
+            let mut cmsg = libc::CMSG_FIRSTHDR(&msg);
+            while !cmsg.is_null() {
+                if (*cmsg).cmsg_level == libc::SOL_SOCKET
+                    && (*cmsg).cmsg_type == libc::SCM_TIMESTAMPNS
+                {
+                    let ts = ptr::read_unaligned(libc::CMSG_DATA(cmsg) as *const libc::timespec);
+                    // A clock set before 1970 has no representation as a
+                    // duration since the epoch, so report no timestamp then.
+                    if ts.tv_sec >= 0 {
+                        timestamp = Some(Duration::new(ts.tv_sec as u64, ts.tv_nsec as u32));
+                    }
+                }
+                cmsg = libc::CMSG_NXTHDR(&msg, cmsg);
+            }
+        }
+        Ok((n, timestamp))
+    }
+
+    #[cfg(not(any(target_os = "android", target_os = "linux")))]
+    pub fn recv_with_timestamp(&self, _buf: &mut [u8]) -> io::Result<(usize, Option<Duration>)> {
+        Err(io::Error::new_const(
+            io::ErrorKind::Unsupported,
+            &"receive timestamps are only supported on Linux and Android",
+        ))
+    }
+
     pub fn peek_from(&self, buf: &mut [u8]) -> io::Result<(usize, SocketAddr)> {
         self.recv_from_with_flags(buf, MSG_PEEK)
     }
@@ -376,6 +701,113 @@ pub fn nodelay(&self) -> io::Result<bool> {
         Ok(raw != 0)
     }
 
+    pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        let size = cmp::min(size, i32::MAX as usize) as c_int;
+        setsockopt(self, libc::SOL_SOCKET, libc::SO_RCVBUF, size)
+    }
+
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        let raw: c_int = getsockopt(self, libc::SOL_SOCKET, libc::SO_RCVBUF)?;
+        Ok(raw as usize)
+    }
+
+    pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        let size = cmp::min(size, i32::MAX as usize) as c_int;
+        setsockopt(self, libc::SOL_SOCKET, libc::SO_SNDBUF, size)
+    }
+
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        let raw: c_int = getsockopt(self, libc::SOL_SOCKET, libc::SO_SNDBUF)?;
+        Ok(raw as usize)
+    }
+
+    #[cfg(target_os = "linux")]
+    pub fn set_tcp_user_timeout(&self, timeout: Duration) -> io::Result<()> {
+        // The kernel rejects values that don't fit in a signed `int`, and a
+        // value of zero means "use the system default", so a nonzero timeout
+        // below one millisecond is rounded up rather than down.
+        let millis = match timeout.as_millis() {
+            0 if timeout != Duration::ZERO => 1,
+            millis => cmp::min(millis, c_int::MAX as u128) as libc::c_uint,
+        };
+        setsockopt(self, libc::IPPROTO_TCP, libc::TCP_USER_TIMEOUT, millis)
+    }
+
+    #[cfg(target_os = "linux")]
+    pub fn tcp_user_timeout(&self) -> io::Result<Duration> {
+        let millis: libc::c_uint = getsockopt(self, libc::IPPROTO_TCP, libc::TCP_USER_TIMEOUT)?;
+        Ok(Duration::from_millis(millis as u64))
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn set_tcp_user_timeout(&self, _timeout: Duration) -> io::Result<()> {
+        Err(io::Error::new_const(
+            io::ErrorKind::Unsupported,
+            &"TCP_USER_TIMEOUT is only supported on Linux",
+        ))
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn tcp_user_timeout(&self) -> io::Result<Duration> {
+        Err(io::Error::new_const(
+            io::ErrorKind::Unsupported,
+            &"TCP_USER_TIMEOUT is only supported on Linux",
+        ))
+    }
+
+    #[cfg(target_os = "linux")]
+    pub fn tcp_info(&self) -> io::Result<(tcp_info, usize)> {
+        // Not `getsockopt`, which insists on the kernel filling in exactly
+        // the size of the struct.
+        unsafe {
+            let mut info: tcp_info = mem::zeroed();
+            let mut len = mem::size_of::<tcp_info>() as socklen_t;
+            cvt(libc::getsockopt(
+                *self.as_inner(),
+                libc::IPPROTO_TCP,
+                libc::TCP_INFO,
+                &mut info as *mut _ as *mut _,
+                &mut len,
+            ))?;
+            Ok((info, len as usize))
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn tcp_info(&self) -> io::Result<(tcp_info, usize)> {
+        Err(io::Error::new_const(
+            io::ErrorKind::Unsupported,
+            &"TCP_INFO is only supported on Linux",
+        ))
+    }
+
+    #[cfg(not(target_os = "vxworks"))]
+    pub fn set_header_included(&self, included: bool) -> io::Result<()> {
+        setsockopt(self, libc::IPPROTO_IP, libc::IP_HDRINCL, included as c_int)
+    }
+
+    #[cfg(not(target_os = "vxworks"))]
+    pub fn header_included(&self) -> io::Result<bool> {
+        let raw: c_int = getsockopt(self, libc::IPPROTO_IP, libc::IP_HDRINCL)?;
+        Ok(raw != 0)
+    }
+
+    #[cfg(target_os = "vxworks")]
+    pub fn set_header_included(&self, _included: bool) -> io::Result<()> {
+        Err(io::Error::new_const(
+            io::ErrorKind::Unsupported,
+            &"IP_HDRINCL is not supported on this platform",
+        ))
+    }
+
+    #[cfg(target_os = "vxworks")]
+    pub fn header_included(&self) -> io::Result<bool> {
+        Err(io::Error::new_const(
+            io::ErrorKind::Unsupported,
+            &"IP_HDRINCL is not supported on this platform",
+        ))
+    }
+
     #[cfg(any(target_os = "android", target_os = "linux",))]
     pub fn set_passcred(&self, passcred: bool) -> io::Result<()> {
         setsockopt(self, libc::SOL_SOCKET, libc::SO_PASSCRED, passcred as libc::c_int)
@@ -401,6 +833,8 @@ pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
     }
 
     pub fn take_error(&self) -> io::Result<Option<io::Error>> {
+        // Reading `SO_ERROR` also resets it, so each pending error is only
+        // reported once.
         let raw: c_int = getsockopt(self, libc::SOL_SOCKET, libc::SO_ERROR)?;
         if raw == 0 {
             Ok(None)
@@ -428,6 +862,129 @@ fn into_inner(self) -> c_int {
     }
 }
 
+/// The path of a socket file created by binding a Unix socket, which is
+/// removed again when this is dropped.
+pub struct BoundPath {
+    path: PathBuf,
+    dev: u64,
+    ino: u64,
+}
+
+impl BoundPath {
+    /// Records `path`, which a socket has just been bound to. The socket file
+    /// is told apart by its device and inode numbers, so that a different file
+    /// put at the same path in the meantime is left alone on drop.
+    pub fn new(path: &Path) -> io::Result<BoundPath> {
+        let (dev, ino) = file_id(path)?;
+        Ok(BoundPath { path: path.to_path_buf(), dev, ino })
+    }
+
+    /// Gives up on removing the socket file, returning its path.
+    pub fn into_path(self) -> PathBuf {
+        let this = mem::ManuallyDrop::new(self);
+        // SAFETY: `this` is never used or dropped again.
+        unsafe { ptr::read(&this.path) }
+    }
+}
+
+impl Drop for BoundPath {
+    fn drop(&mut self) {
+        // Another process may have replaced the socket file since, typically
+        // by removing it and binding a socket of its own at the same path, in
+        // which case the file is no longer ours to remove. That can still
+        // happen between the check and the removal, so this is best-effort.
+        if file_id(&self.path).ok() == Some((self.dev, self.ino)) {
+            let _ = crate::sys::fs::unlink(&self.path);
+        }
+    }
+}
+
+fn file_id(path: &Path) -> io::Result<(u64, u64)> {
+    let attr = crate::sys::fs::lstat(path)?;
+    let stat = attr.as_inner();
+    Ok((stat.st_dev as u64, stat.st_ino as u64))
+}
+
+/// Lists the IP addresses of the local network interfaces, as
+/// `(interface name, address, interface flags)`.
+///
+/// An interface with several addresses appears once for each of them.
+/// Entries for link-layer addresses (`AF_PACKET` on Linux, `AF_LINK` on the
+/// BSDs) and for interfaces without an address are skipped.
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd",
+    target_os = "dragonfly",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "illumos",
+))]
+pub fn getifaddrs() -> io::Result<Vec<(OsString, IpAddr, u64)>> {
+    use crate::net::{Ipv4Addr, Ipv6Addr};
+    use crate::os::unix::ffi::OsStringExt;
+
+    // Frees the list however we leave this function.
+    struct IfAddrs(*mut libc::ifaddrs);
+
+    impl Drop for IfAddrs {
+        fn drop(&mut self) {
+            unsafe { libc::freeifaddrs(self.0) }
+        }
+    }
+
+    let mut head = ptr::null_mut();
+    cvt(unsafe { libc::getifaddrs(&mut head) })?;
+    let list = IfAddrs(head);
+
+    let mut addrs = Vec::new();
+    let mut next = list.0;
+    while let Some(ifa) = unsafe { next.as_ref() } {
+        next = ifa.ifa_next;
+        let addr = match unsafe { ifa.ifa_addr.as_ref() } {
+            Some(addr) => addr,
+            None => continue,
+        };
+        let ip = match addr.sa_family as c_int {
+            libc::AF_INET => {
+                let sin = unsafe { &*(addr as *const sockaddr as *const libc::sockaddr_in) };
+                IpAddr::V4(Ipv4Addr::from(u32::from_be(sin.sin_addr.s_addr)))
+            }
+            libc::AF_INET6 => {
+                let sin6 = unsafe { &*(addr as *const sockaddr as *const libc::sockaddr_in6) };
+                IpAddr::V6(Ipv6Addr::from(sin6.sin6_addr.s6_addr))
+            }
+            _ => continue,
+        };
+        // illumos declares the flags as a `u64`, with flags of its own above
+        // bit 31, while everyone else uses a `c_uint`.
+        let flags = u64::from(ifa.ifa_flags);
+        let name = unsafe { CStr::from_ptr(ifa.ifa_name) };
+        addrs.push((OsString::from_vec(name.to_bytes().to_vec()), ip, flags));
+    }
+    Ok(addrs)
+}
+
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd",
+    target_os = "dragonfly",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "illumos",
+)))]
+pub fn getifaddrs() -> io::Result<Vec<(OsString, IpAddr, u64)>> {
+    Err(io::Error::new_const(
+        io::ErrorKind::Unsupported,
+        &"getifaddrs is not supported on this platform",
+    ))
+}
+
 // In versions of glibc prior to 2.26, there's a bug where the DNS resolver
 // will cache the contents of /etc/resolv.conf, so changes to that file on disk
 // can be ignored by a long-running program. That can break DNS lookups on e.g.
@@ -0,0 +1,97 @@
+use super::*;
+use crate::net::TcpListener;
+
+#[test]
+fn take_error_after_refused_connect() {
+    // Find a local port that nobody is listening on.
+    let addr = {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        listener.local_addr().unwrap()
+    };
+
+    let sock = Socket::new(&addr, libc::SOCK_STREAM).unwrap();
+    sock.set_nonblocking(true).unwrap();
+    assert!(sock.take_error().unwrap().is_none());
+
+    let (addrp, len) = addr.into_inner();
+    match cvt(unsafe { libc::connect(sock.0.raw(), addrp, len) }) {
+        Err(ref e) if e.raw_os_error() == Some(libc::EINPROGRESS) => {}
+        // Some systems refuse loopback connections synchronously, in which
+        // case nothing is left pending on the socket.
+        Err(ref e) if e.kind() == io::ErrorKind::ConnectionRefused => return,
+        other => panic!("unexpected connect result: {:?}", other),
+    }
+
+    let mut pollfd = libc::pollfd { fd: sock.0.raw(), events: libc::POLLOUT, revents: 0 };
+    cvt_r(|| unsafe { libc::poll(&mut pollfd, 1, 10_000) }).unwrap();
+    assert_ne!(pollfd.revents, 0);
+
+    let err = sock.take_error().unwrap().expect("no error pending after a refused connect");
+    assert_eq!(err.kind(), io::ErrorKind::ConnectionRefused);
+    // Reading the error cleared it.
+    assert!(sock.take_error().unwrap().is_none());
+}
+
+#[test]
+fn wait_readable_timeout_and_ready() {
+    let (a, b) = Socket::new_pair(libc::AF_UNIX, libc::SOCK_STREAM).unwrap();
+
+    let start = Instant::now();
+    assert_eq!(wait_readable(a.0.raw(), libc::POLLIN, Duration::from_millis(50)).unwrap(), None);
+    assert!(start.elapsed() >= Duration::from_millis(50));
+    assert_eq!(wait_readable(a.0.raw(), libc::POLLIN, Duration::ZERO).unwrap(), None);
+
+    b.write(b"x").unwrap();
+    assert_eq!(wait_readable(a.0.raw(), libc::POLLIN, Duration::ZERO).unwrap(), Some(libc::POLLIN));
+    let ready = wait_readable(a.0.raw(), libc::POLLIN, Duration::from_secs(10)).unwrap();
+    assert_eq!(ready, Some(libc::POLLIN));
+}
+
+#[test]
+fn wait_readable_retries_after_eintr() {
+    use crate::sync::atomic::{AtomicUsize, Ordering};
+    use crate::thread;
+
+    static SIGNALS: AtomicUsize = AtomicUsize::new(0);
+    extern "C" fn on_signal(_: c_int) {
+        SIGNALS.fetch_add(1, Ordering::SeqCst);
+    }
+
+    // Without `SA_RESTART`, a signal makes a blocked `poll` fail with EINTR.
+    let mut action: libc::sigaction = unsafe { mem::zeroed() };
+    action.sa_sigaction = on_signal as extern "C" fn(c_int) as libc::sighandler_t;
+    let mut old: libc::sigaction = unsafe { mem::zeroed() };
+    cvt(unsafe { libc::sigaction(libc::SIGUSR2, &action, &mut old) }).unwrap();
+
+    let (a, b) = Socket::new_pair(libc::AF_UNIX, libc::SOCK_STREAM).unwrap();
+    // `pthread_t` is a pointer on some platforms, so it is passed as `usize`
+    // to be `Send`.
+    let waiter = unsafe { libc::pthread_self() } as usize;
+    let start = Instant::now();
+    let t = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(100));
+        unsafe { libc::pthread_kill(waiter as libc::pthread_t, libc::SIGUSR2) };
+        thread::sleep(Duration::from_millis(100));
+        b.write(b"x").unwrap();
+    });
+
+    let ready = wait_readable(a.0.raw(), libc::POLLIN, Duration::from_secs(10));
+    t.join().unwrap();
+    cvt(unsafe { libc::sigaction(libc::SIGUSR2, &old, crate::ptr::null_mut()) }).unwrap();
+
+    assert_eq!(ready.unwrap(), Some(libc::POLLIN));
+    assert_eq!(SIGNALS.load(Ordering::SeqCst), 1);
+    assert!(start.elapsed() >= Duration::from_millis(200));
+}
+
+#[test]
+fn connect_outcome_hangup_without_error() {
+    // A hang-up with no pending error must not pass for a connection.
+    let err = connect_outcome(libc::POLLOUT | libc::POLLHUP, None).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::Other);
+    assert!(connect_outcome(libc::POLLOUT, None).is_ok());
+
+    let refused = io::Error::from_raw_os_error(libc::ECONNREFUSED);
+    let err = connect_outcome(libc::POLLOUT | libc::POLLERR | libc::POLLHUP, Some(refused));
+    assert_eq!(err.unwrap_err().kind(), io::ErrorKind::ConnectionRefused);
+}
@@ -2,7 +2,7 @@
 
 #![allow(unused_imports)] // lots of cfg code here
 
-#[cfg(all(test, target_env = "gnu"))]
+#[cfg(test)]
 mod tests;
 
 use crate::os::unix::prelude::*;
@@ -73,7 +73,8 @@ pub fn errno() -> i32 {
 }
 
 /// Sets the platform-specific value of errno
-#[cfg(all(not(target_os = "linux"), not(target_os = "dragonfly"), not(target_os = "vxworks")))] // needed for readdir and syscall!
+// needed for readdir, syscall! and confstr
+#[cfg(not(any(target_os = "dragonfly", target_os = "vxworks")))]
 #[allow(dead_code)] // but not all target cfgs actually end up using it
 pub fn set_errno(e: i32) {
     unsafe { *errno_location() = e as c_int }
@@ -492,6 +493,12 @@ pub unsafe fn environ() -> *mut *const *const c_char {
     ptr::addr_of_mut!(environ)
 }
 
+// Reading the environment only takes the read side of this lock, so any number
+// of threads can call `getenv` or iterate `environ` at once. Only `setenv` and
+// `unsetenv` take the write side, since they may reallocate `environ` and free
+// the strings readers are looking at. This only protects accesses made through
+// `std`: a raw `libc::setenv` from elsewhere in the process bypasses the lock
+// and can still race with readers here.
 static ENV_LOCK: StaticRWLock = StaticRWLock::new();
 
 pub fn env_read_lock() -> RWLockReadGuard {
@@ -573,6 +580,313 @@ pub fn page_size() -> usize {
     unsafe { libc::sysconf(libc::_SC_PAGESIZE) as usize }
 }
 
+#[derive(Clone, Debug)]
+pub struct UtsName {
+    pub sysname: OsString,
+    pub nodename: OsString,
+    pub release: OsString,
+    pub version: OsString,
+    pub machine: OsString,
+}
+
+pub fn uname() -> io::Result<UtsName> {
+    let mut buf: libc::utsname = unsafe { mem::zeroed() };
+    // Some platforms return a positive value on success, so only treat -1 as
+    // an error.
+    cvt(unsafe { libc::uname(&mut buf) })?;
+    Ok(UtsName {
+        sysname: utsname_field(&buf.sysname),
+        nodename: utsname_field(&buf.nodename),
+        release: utsname_field(&buf.release),
+        version: utsname_field(&buf.version),
+        machine: utsname_field(&buf.machine),
+    })
+}
+
+/// Converts a `utsname` field to an `OsString`.
+///
+/// The fields are fixed-size arrays that are normally NUL-terminated, but
+/// some platforms fill a field completely when the value is exactly as long
+/// as the array. Scanning only within the array, rather than handing it to
+/// `CStr::from_ptr`, keeps such a field from being read past its end.
+fn utsname_field(field: &[c_char]) -> OsString {
+    let bytes = unsafe { slice::from_raw_parts(field.as_ptr() as *const u8, field.len()) };
+    let len = memchr::memchr(0, bytes).unwrap_or(bytes.len());
+    OsStringExt::from_vec(bytes[..len].to_vec())
+}
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd",
+    target_os = "dragonfly",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "solaris",
+    target_os = "illumos",
+))]
+#[allow(dead_code)] // sys isn't exported yet
+pub fn confstr(name: c_int) -> io::Result<OsString> {
+    // Part of POSIX, but not bound by the `libc` crate on most of these
+    // platforms.
+    extern "C" {
+        fn confstr(name: c_int, buf: *mut c_char, len: libc::size_t) -> libc::size_t;
+    }
+
+    let mut buf: Vec<u8> = Vec::with_capacity(TMPBUF_SZ);
+    loop {
+        // `confstr` truncates the value to fit the buffer it is given, but
+        // always returns the size needed for the whole value, including the
+        // NUL terminator. A return larger than the buffer therefore means the
+        // value was cut short, so grow the buffer to that size and ask again.
+        //
+        // A return of 0 means either that `name` is invalid, which sets
+        // errno, or that it has no value, which leaves errno alone.
+        set_errno(0);
+        let len = unsafe { confstr(name, buf.as_mut_ptr() as *mut c_char, buf.capacity()) };
+        if len == 0 {
+            return match errno() {
+                0 => Ok(OsString::new()),
+                e => Err(io::Error::from_raw_os_error(e)),
+            };
+        }
+        if len <= buf.capacity() {
+            unsafe { buf.set_len(len - 1) };
+            return Ok(OsString::from_vec(buf));
+        }
+        buf.reserve(len);
+    }
+}
+
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd",
+    target_os = "dragonfly",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "solaris",
+    target_os = "illumos",
+)))]
+#[allow(dead_code)] // sys isn't exported yet
+pub fn confstr(_name: c_int) -> io::Result<OsString> {
+    Err(io::Error::new_const(
+        io::ErrorKind::Unsupported,
+        &"confstr is not supported on this platform",
+    ))
+}
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd",
+    target_os = "dragonfly",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "solaris",
+    target_os = "illumos",
+))]
+#[allow(dead_code)] // sys isn't exported yet
+pub fn loadavg() -> io::Result<[f64; 3]> {
+    let mut avg = [0.0; 3];
+    // `getloadavg` returns the number of samples it filled in, or -1 if the
+    // load average could not be obtained, without necessarily setting errno.
+    let n = unsafe { libc::getloadavg(avg.as_mut_ptr(), avg.len() as c_int) };
+    if n < avg.len() as c_int {
+        return Err(io::Error::new_const(
+            io::ErrorKind::Other,
+            &"the load average could not be obtained",
+        ));
+    }
+    Ok(avg)
+}
+
+#[cfg(target_os = "android")]
+#[allow(dead_code)] // sys isn't exported yet
+pub fn loadavg() -> io::Result<[f64; 3]> {
+    // Bionic only gained `getloadavg` in API level 29, so read the values
+    // from procfs instead, which is where it gets them from anyway. The
+    // file looks like `0.20 0.18 0.12 1/80 11206`.
+    let contents = crate::fs::read_to_string("/proc/loadavg")?;
+    let mut fields = contents.split_ascii_whitespace().map(str::parse::<f64>);
+    match (fields.next(), fields.next(), fields.next()) {
+        (Some(Ok(one)), Some(Ok(five)), Some(Ok(fifteen))) => Ok([one, five, fifteen]),
+        _ => Err(io::Error::new_const(
+            io::ErrorKind::InvalidData,
+            &"unexpected contents in /proc/loadavg",
+        )),
+    }
+}
+
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd",
+    target_os = "dragonfly",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "solaris",
+    target_os = "illumos",
+)))]
+#[allow(dead_code)] // sys isn't exported yet
+pub fn loadavg() -> io::Result<[f64; 3]> {
+    Err(io::Error::new_const(
+        io::ErrorKind::Unsupported,
+        &"the load average is not available on this platform",
+    ))
+}
+
+// The `libc` crate only has these `prctl` options for Linux so far.
+#[cfg(target_os = "linux")]
+use libc::{PR_GET_DUMPABLE, PR_SET_DUMPABLE, PR_SET_NAME};
+#[cfg(target_os = "android")]
+const PR_GET_DUMPABLE: c_int = 3;
+#[cfg(target_os = "android")]
+const PR_SET_DUMPABLE: c_int = 4;
+#[cfg(target_os = "android")]
+const PR_SET_NAME: c_int = 15;
+
+/// Sets the name of the calling thread, which is also the name of the
+/// process shown by `ps` and in `/proc/self/comm` when called from the main
+/// thread.
+///
+/// The kernel keeps at most 15 bytes of the name, so a longer name is cut
+/// short, at a character boundary so that it stays valid UTF-8.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[allow(dead_code)] // sys isn't exported yet
+pub fn set_process_name(name: &str) -> io::Result<()> {
+    const TASK_COMM_LEN: usize = 16;
+
+    if name.as_bytes().contains(&0) {
+        return Err(io::Error::new_const(
+            io::ErrorKind::InvalidInput,
+            &"nul byte found in provided data",
+        ));
+    }
+    let mut len = name.len().min(TASK_COMM_LEN - 1);
+    while !name.is_char_boundary(len) {
+        len -= 1;
+    }
+    let mut buf = [0u8; TASK_COMM_LEN];
+    buf[..len].copy_from_slice(&name.as_bytes()[..len]);
+    cvt(unsafe { libc::prctl(PR_SET_NAME, buf.as_ptr() as libc::c_ulong, 0, 0, 0) })?;
+    Ok(())
+}
+
+/// Sets whether the process can dump core, and whether other processes of
+/// the same user can attach to it with `ptrace` or read its memory.
+///
+/// The kernel clears this flag when the process changes its credentials,
+/// such as when it drops privileges, but a process holding secrets may want
+/// to clear it on its own as well.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[allow(dead_code)] // sys isn't exported yet
+pub fn set_dumpable(dumpable: bool) -> io::Result<()> {
+    cvt(unsafe { libc::prctl(PR_SET_DUMPABLE, dumpable as libc::c_ulong, 0, 0, 0) })?;
+    Ok(())
+}
+
+/// Returns whether the process can dump core, as set by [`set_dumpable`].
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[allow(dead_code)] // sys isn't exported yet
+pub fn dumpable() -> io::Result<bool> {
+    // The result is 0 if the process isn't dumpable, and 1 or 2 (dumpable
+    // by root only, as set through `suid_dumpable`) if it is.
+    let dumpable = cvt(unsafe { libc::prctl(PR_GET_DUMPABLE, 0, 0, 0, 0) })?;
+    Ok(dumpable != 0)
+}
+
+/// Reads the raw value of the sysctl `name`, such as `hw.ncpu`.
+///
+/// OpenBSD has no `sysctlbyname`, so it is left out.
+#[cfg(any(
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd",
+    target_os = "dragonfly",
+    target_os = "netbsd",
+))]
+#[allow(dead_code)] // sys isn't exported yet
+pub fn sysctl_by_name(name: &str) -> io::Result<Vec<u8>> {
+    let name = CString::new(name).map_err(|_| {
+        io::Error::new_const(io::ErrorKind::InvalidInput, &"nul byte found in provided data")
+    })?;
+    let mut buf: Vec<u8> = Vec::new();
+    loop {
+        // Ask for the size of the value first. It may grow before the second
+        // call reads it, which then fails with `ENOMEM`, so ask again.
+        let mut len = 0;
+        cvt(unsafe {
+            libc::sysctlbyname(name.as_ptr(), ptr::null_mut(), &mut len, ptr::null_mut(), 0)
+        })?;
+        buf.reserve(len);
+        let mut len = buf.capacity();
+        let res = cvt(unsafe {
+            libc::sysctlbyname(
+                name.as_ptr(),
+                buf.as_mut_ptr() as *mut c_void,
+                &mut len,
+                ptr::null_mut(),
+                0,
+            )
+        });
+        match res {
+            Ok(_) => {
+                unsafe { buf.set_len(len) };
+                return Ok(buf);
+            }
+            Err(e) if e.raw_os_error() == Some(libc::ENOMEM) => {}
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Reads the sysctl `name`, which must hold a 32-bit integer.
+#[cfg(any(
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd",
+    target_os = "dragonfly",
+    target_os = "netbsd",
+))]
+#[allow(dead_code)] // sys isn't exported yet
+pub fn sysctl_u32(name: &str) -> io::Result<u32> {
+    let value = sysctl_by_name(name)?;
+    if value.len() != 4 {
+        return Err(io::Error::new_const(
+            io::ErrorKind::InvalidData,
+            &"the sysctl value is not a 32-bit integer",
+        ));
+    }
+    let mut bytes = [0; 4];
+    bytes.copy_from_slice(&value);
+    Ok(u32::from_ne_bytes(bytes))
+}
+
+/// Reads the sysctl `name`, which must hold a string, such as
+/// `kern.ostype`.
+#[cfg(any(
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd",
+    target_os = "dragonfly",
+    target_os = "netbsd",
+))]
+#[allow(dead_code)] // sys isn't exported yet
+pub fn sysctl_string(name: &str) -> io::Result<OsString> {
+    let mut value = sysctl_by_name(name)?;
+    // Strings come with their NUL terminator.
+    if let Some(nul) = memchr::memchr(0, &value) {
+        value.truncate(nul);
+    }
+    Ok(OsString::from_vec(value))
+}
+
 pub fn temp_dir() -> PathBuf {
     crate::env::var_os("TMPDIR").map(PathBuf::from).unwrap_or_else(|| {
         if cfg!(target_os = "android") {
@@ -1,12 +1,14 @@
 use super::*;
 
 #[test]
+#[cfg(all(target_env = "gnu", not(target_os = "vxworks")))]
 fn test_glibc_version() {
     // This mostly just tests that the weak linkage doesn't panic wildly...
     glibc_version();
 }
 
 #[test]
+#[cfg(all(target_env = "gnu", not(target_os = "vxworks")))]
 fn test_parse_glibc_version() {
     let cases = [
         ("0.0", Some((0, 0))),
@@ -21,3 +23,148 @@ fn test_parse_glibc_version() {
         assert_eq!(parsed, parse_glibc_version(version_str));
     }
 }
+
+#[test]
+fn test_utsname_field() {
+    let field = [b'L' as c_char, b'i' as c_char, 0, b'x' as c_char];
+    assert_eq!(utsname_field(&field), "Li");
+
+    // A field that fills its whole array has no terminator at all.
+    let field = [b'B' as c_char, b'S' as c_char, b'D' as c_char];
+    assert_eq!(utsname_field(&field), "BSD");
+
+    assert_eq!(utsname_field(&[0; 4]), "");
+}
+
+#[test]
+#[cfg(any(
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd",
+    target_os = "dragonfly",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "solaris",
+    target_os = "illumos",
+))]
+fn test_confstr() {
+    // `libc` only has `_CS_PATH` for some of these platforms.
+    #[cfg(target_os = "linux")]
+    const _CS_PATH: c_int = 0;
+    #[cfg(any(target_os = "solaris", target_os = "illumos"))]
+    const _CS_PATH: c_int = 65;
+    #[cfg(not(any(target_os = "linux", target_os = "solaris", target_os = "illumos")))]
+    const _CS_PATH: c_int = 1;
+
+    let path = confstr(_CS_PATH).unwrap();
+    assert!(!path.is_empty());
+    assert!(path.as_bytes().starts_with(b"/"), "{:?}", path);
+
+    let err = confstr(-1).unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::EINVAL));
+}
+
+#[test]
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd",
+    target_os = "dragonfly",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "solaris",
+    target_os = "illumos",
+)))]
+fn test_confstr_unsupported() {
+    assert_eq!(confstr(0).unwrap_err().kind(), io::ErrorKind::Unsupported);
+}
+
+#[test]
+fn test_loadavg() {
+    let avg = match loadavg() {
+        Err(e) if e.kind() == io::ErrorKind::Unsupported => return,
+        result => result.unwrap(),
+    };
+    for &x in &avg {
+        assert!(x.is_finite() && x >= 0.0, "{:?}", avg);
+    }
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_set_process_name() {
+    // The name belongs to the calling thread, so use a thread of our own.
+    crate::thread::spawn(|| {
+        let comm = || crate::fs::read_to_string("/proc/thread-self/comm").unwrap();
+
+        set_process_name("rust-test").unwrap();
+        assert_eq!(comm(), "rust-test\n");
+
+        // Long names are cut short to 15 bytes, without splitting the `é`
+        // that straddles the limit.
+        set_process_name("a-rather-long-name").unwrap();
+        assert_eq!(comm(), "a-rather-long-n\n");
+        set_process_name("a-rather-long-é").unwrap();
+        assert_eq!(comm(), "a-rather-long-\n");
+        set_process_name("a-rather-longé").unwrap();
+        assert_eq!(comm(), "a-rather-longé\n");
+
+        let err = set_process_name("nul\0byte").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert_eq!(comm(), "a-rather-longé\n");
+    })
+    .join()
+    .unwrap();
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_set_dumpable() {
+    use crate::env;
+    use crate::process::Command;
+
+    // The flag belongs to the whole process, so flip it in a fresh copy of
+    // the test binary rather than under the feet of concurrent tests.
+    if env::var_os("RUST_TEST_SET_DUMPABLE").is_none() {
+        let status = Command::new(env::current_exe().unwrap())
+            .arg("test_set_dumpable")
+            .arg("--test-threads=1")
+            .env("RUST_TEST_SET_DUMPABLE", "1")
+            .status()
+            .expect("failed to run the test binary");
+        assert!(status.success(), "{}", status);
+        return;
+    }
+
+    let was_dumpable = dumpable().unwrap();
+    set_dumpable(false).unwrap();
+    assert!(!dumpable().unwrap());
+    set_dumpable(true).unwrap();
+    assert!(dumpable().unwrap());
+    set_dumpable(was_dumpable).unwrap();
+}
+
+#[test]
+#[cfg(any(
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd",
+    target_os = "dragonfly",
+    target_os = "netbsd",
+))]
+fn test_sysctl() {
+    let ncpu = sysctl_u32("hw.ncpu").unwrap();
+    assert!(ncpu >= 1, "{}", ncpu);
+    assert_eq!(sysctl_by_name("hw.ncpu").unwrap(), ncpu.to_ne_bytes());
+
+    let ostype = sysctl_string("kern.ostype").unwrap();
+    assert!(!ostype.is_empty());
+    assert!(!ostype.as_bytes().contains(&0), "{:?}", ostype);
+
+    // A string is not an integer.
+    assert_eq!(sysctl_u32("kern.ostype").unwrap_err().kind(), io::ErrorKind::InvalidData);
+    assert_eq!(sysctl_by_name("no.such.sysctl").unwrap_err().raw_os_error(), Some(libc::ENOENT));
+    assert_eq!(sysctl_by_name("hw\0ncpu").unwrap_err().kind(), io::ErrorKind::InvalidInput);
+}
@@ -120,3 +120,145 @@ fn read(fd: &FileDesc, dst: &mut Vec<u8>) -> Result<bool, io::Error> {
         }
     }
 }
+
+/// Like `read2`, but gives up once either pipe has produced more than `cap`
+/// bytes.
+///
+/// Either pipe may be absent, in which case it is treated as already closed.
+/// Returns `true` if reading stopped because of the cap, in which case the
+/// offending buffer has been truncated to `cap` bytes and both pipes have been
+/// left undrained. The pipes are only borrowed, so that the caller can stop
+/// the writer before closing them, rather than have it die of `SIGPIPE`.
+pub fn read2_capped(
+    p1: Option<&AnonPipe>,
+    v1: &mut Vec<u8>,
+    p2: Option<&AnonPipe>,
+    v2: &mut Vec<u8>,
+    cap: usize,
+) -> io::Result<bool> {
+    let p1 = p1.map(AnonPipe::fd);
+    let p2 = p2.map(AnonPipe::fd);
+
+    // A negative fd makes `poll` ignore that entry, which is how both absent
+    // pipes and pipes that have hit EOF are taken out of the loop.
+    let mut fds: [libc::pollfd; 2] = unsafe { mem::zeroed() };
+    for (pfd, fd) in fds.iter_mut().zip([&p1, &p2]) {
+        pfd.fd = -1;
+        pfd.events = libc::POLLIN;
+        if let Some(fd) = fd {
+            fd.set_nonblocking(true)?;
+            pfd.fd = fd.raw();
+        }
+    }
+
+    let mut buf = [0; 8 * 1024];
+    while fds[0].fd >= 0 || fds[1].fd >= 0 {
+        cvt_r(|| unsafe { libc::poll(fds.as_mut_ptr(), 2, -1) })?;
+
+        for (pfd, (fd, dst)) in fds.iter_mut().zip([(&p1, &mut *v1), (&p2, &mut *v2)]) {
+            let fd = match fd {
+                Some(fd) if pfd.fd >= 0 && pfd.revents != 0 => fd,
+                _ => continue,
+            };
+            match fd.read(&mut buf) {
+                Ok(0) => pfd.fd = -1,
+                Ok(n) => {
+                    dst.extend_from_slice(&buf[..n]);
+                    if dst.len() > cap {
+                        dst.truncate(cap);
+                        return Ok(true);
+                    }
+                }
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => {}
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+    }
+    Ok(false)
+}
+
+/// Like `read2`, but also writes `input` to `stdin` while reading, then closes
+/// it so the child sees EOF.
+///
+/// Writing and reading happen in the same `poll` loop, so a child that fills
+/// its output pipes before it has read all of its input cannot deadlock
+/// against us. Any of the pipes may be absent, in which case it is treated as
+/// already closed.
+///
+/// If the child closes its end of `stdin` before reading all of `input`, the
+/// write fails with `EPIPE`. With `tolerate_broken_pipe` set, that just stops
+/// the writing and the output is still collected to the end; otherwise the
+/// error is returned.
+pub fn write_read2(
+    stdin: Option<AnonPipe>,
+    input: &[u8],
+    tolerate_broken_pipe: bool,
+    p1: Option<AnonPipe>,
+    v1: &mut Vec<u8>,
+    p2: Option<AnonPipe>,
+    v2: &mut Vec<u8>,
+) -> io::Result<()> {
+    let mut stdin = stdin.map(AnonPipe::into_fd);
+    let p1 = p1.map(AnonPipe::into_fd);
+    let p2 = p2.map(AnonPipe::into_fd);
+
+    // As in `read2_capped`, a negative fd makes `poll` ignore that entry.
+    let mut fds: [libc::pollfd; 3] = unsafe { mem::zeroed() };
+    fds[0].events = libc::POLLOUT;
+    for (pfd, fd) in fds.iter_mut().zip([&stdin, &p1, &p2]) {
+        pfd.fd = -1;
+        if let Some(fd) = fd {
+            fd.set_nonblocking(true)?;
+            pfd.fd = fd.raw();
+        }
+    }
+    fds[1].events = libc::POLLIN;
+    fds[2].events = libc::POLLIN;
+
+    let mut input = input;
+    if input.is_empty() {
+        stdin = None;
+        fds[0].fd = -1;
+    }
+
+    let mut buf = [0; 8 * 1024];
+    while fds.iter().any(|pfd| pfd.fd >= 0) {
+        cvt_r(|| unsafe { libc::poll(fds.as_mut_ptr(), 3, -1) })?;
+
+        if let Some(fd) = &stdin {
+            if fds[0].revents != 0 {
+                match fd.write(input) {
+                    Ok(n) => input = &input[n..],
+                    Err(e) if e.kind() == io::ErrorKind::WouldBlock => {}
+                    Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                    Err(e) if e.kind() == io::ErrorKind::BrokenPipe && tolerate_broken_pipe => {
+                        input = &[];
+                    }
+                    Err(e) => return Err(e),
+                }
+                if input.is_empty() {
+                    // Closing the pipe is what tells the child there is no
+                    // more input.
+                    stdin = None;
+                    fds[0].fd = -1;
+                }
+            }
+        }
+
+        for (pfd, (fd, dst)) in fds[1..].iter_mut().zip([(&p1, &mut *v1), (&p2, &mut *v2)]) {
+            let fd = match fd {
+                Some(fd) if pfd.fd >= 0 && pfd.revents != 0 => fd,
+                _ => continue,
+            };
+            match fd.read(&mut buf) {
+                Ok(0) => pfd.fd = -1,
+                Ok(n) => dst.extend_from_slice(&buf[..n]),
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => {}
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+    }
+    Ok(())
+}
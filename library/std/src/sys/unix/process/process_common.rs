@@ -76,6 +76,8 @@ pub struct Command {
     saw_nul: bool,
     closures: Vec<Box<dyn FnMut() -> io::Result<()> + Send + Sync>>,
     groups: Option<Box<[gid_t]>>,
+    rlimits: Vec<(c_int, u64, u64)>,
+    controlling_tty: Option<CString>,
     stdin: Option<Stdio>,
     stdout: Option<Stdio>,
     stderr: Option<Stdio>,
@@ -138,6 +140,8 @@ pub fn new(program: &OsStr) -> Command {
             saw_nul,
             closures: Vec::new(),
             groups: None,
+            rlimits: Vec::new(),
+            controlling_tty: None,
             stdin: None,
             stdout: None,
             stderr: None,
@@ -176,6 +180,12 @@ pub fn gid(&mut self, id: gid_t) {
     pub fn groups(&mut self, groups: &[gid_t]) {
         self.groups = Some(Box::from(groups));
     }
+    pub fn rlimit(&mut self, resource: c_int, soft: u64, hard: u64) {
+        self.rlimits.push((resource, soft, hard));
+    }
+    pub fn controlling_tty(&mut self, path: &OsStr) {
+        self.controlling_tty = Some(os2c(path, &mut self.saw_nul));
+    }
 
     pub fn saw_nul(&self) -> bool {
         self.saw_nul
@@ -223,6 +233,14 @@ pub fn get_gid(&self) -> Option<gid_t> {
     pub fn get_groups(&self) -> Option<&[gid_t]> {
         self.groups.as_deref()
     }
+    #[allow(dead_code)]
+    pub fn get_rlimits(&self) -> &[(c_int, u64, u64)] {
+        &self.rlimits
+    }
+    #[allow(dead_code)]
+    pub fn get_controlling_tty(&self) -> Option<&CStr> {
+        self.controlling_tty.as_deref()
+    }
 
     pub fn get_closures(&mut self) -> &mut Vec<Box<dyn FnMut() -> io::Result<()> + Send + Sync>> {
         &mut self.closures
@@ -28,6 +28,18 @@ pub fn spawn(
                 &"nul byte found in provided data",
             ));
         }
+        if !self.get_rlimits().is_empty() {
+            return Err(io::Error::new_const(
+                io::ErrorKind::Unsupported,
+                &"resource limits for child processes are not supported on this platform",
+            ));
+        }
+        if self.get_controlling_tty().is_some() {
+            return Err(io::Error::new_const(
+                io::ErrorKind::Unsupported,
+                &"controlling terminals for child processes are not supported on this platform",
+            ));
+        }
 
         let (ours, theirs) = self.setup_io(default, needs_stdin)?;
 
@@ -231,6 +243,13 @@ pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
         }
         Ok(Some(ExitStatus(proc_info.return_code)))
     }
+
+    pub fn peek_wait(&self) -> io::Result<ExitStatus> {
+        Err(io::Error::new_const(
+            io::ErrorKind::Unsupported,
+            &"waiting without reaping is not supported on this platform",
+        ))
+    }
 }
 
 #[derive(PartialEq, Eq, Clone, Copy, Debug)]
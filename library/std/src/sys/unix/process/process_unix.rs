@@ -1,3 +1,4 @@
+use crate::cmp;
 use crate::convert::{TryFrom, TryInto};
 use crate::fmt;
 use crate::io::{self, Error, ErrorKind};
@@ -7,6 +8,7 @@
 use crate::ptr;
 use crate::sys;
 use crate::sys::cvt;
+use crate::sys::fd::FileDesc;
 use crate::sys::process::process_common::*;
 
 #[cfg(target_os = "vxworks")]
@@ -187,8 +189,19 @@ unsafe fn do_exec(
             cvt_r(|| libc::dup2(fd, libc::STDERR_FILENO))?;
         }
 
+        if let Some(tty) = self.get_controlling_tty() {
+            set_controlling_tty(tty)?;
+        }
+
         #[cfg(not(target_os = "l4re"))]
         {
+            // Resource limits go first: once privileges have been dropped
+            // below, an unprivileged child could no longer raise a hard limit.
+            for &(resource, soft, hard) in self.get_rlimits() {
+                let clamp = |v: u64| cmp::min(v, libc::rlim_t::MAX as u64) as libc::rlim_t;
+                let limit = libc::rlimit { rlim_cur: clamp(soft), rlim_max: clamp(hard) };
+                cvt(libc::setrlimit(resource as _, &limit))?;
+            }
             if let Some(_g) = self.get_groups() {
                 //FIXME: Redox kernel does not support setgroups yet
                 #[cfg(not(target_os = "redox"))]
@@ -300,6 +313,8 @@ fn posix_spawn(
             || (self.env_saw_path() && !self.program_is_path())
             || !self.get_closures().is_empty()
             || self.get_groups().is_some()
+            || !self.get_rlimits().is_empty()
+            || self.get_controlling_tty().is_some()
         {
             return Ok(None);
         }
@@ -425,6 +440,56 @@ fn drop(&mut self) {
     }
 }
 
+/// Makes the terminal at `tty` the controlling terminal of the child, and
+/// its stdin, stdout and stderr. This runs between `fork` and `exec`, so it
+/// only makes async-signal-safe calls.
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd",
+    target_os = "dragonfly",
+    target_os = "netbsd",
+    target_os = "openbsd",
+))]
+unsafe fn set_controlling_tty(tty: &crate::ffi::CStr) -> io::Result<()> {
+    use crate::sys::cvt_r;
+
+    // Only the leader of a session without a controlling terminal can
+    // acquire one, and the child inherited its parent's session, so it has
+    // to start its own first.
+    cvt(libc::setsid())?;
+    // `O_NOCTTY` leaves acquiring the terminal to `TIOCSCTTY`, as opening it
+    // only does so implicitly on some platforms.
+    let fd = cvt_r(|| libc::open(tty.as_ptr(), libc::O_RDWR | libc::O_NOCTTY))?;
+    cvt(libc::ioctl(fd, libc::TIOCSCTTY as _, 0))?;
+    for &target in &[libc::STDIN_FILENO, libc::STDOUT_FILENO, libc::STDERR_FILENO] {
+        cvt_r(|| libc::dup2(fd, target))?;
+    }
+    if fd > libc::STDERR_FILENO {
+        libc::close(fd);
+    }
+    Ok(())
+}
+
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd",
+    target_os = "dragonfly",
+    target_os = "netbsd",
+    target_os = "openbsd",
+)))]
+unsafe fn set_controlling_tty(_tty: &crate::ffi::CStr) -> io::Result<()> {
+    Err(io::Error::new_const(
+        ErrorKind::Unsupported,
+        &"controlling terminals for child processes are not supported on this platform",
+    ))
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // Processes
 ////////////////////////////////////////////////////////////////////////////////
@@ -478,6 +543,46 @@ pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
             Ok(Some(ExitStatus::new(status)))
         }
     }
+
+    #[cfg(target_os = "linux")]
+    pub fn peek_wait(&self) -> io::Result<ExitStatus> {
+        use crate::sys::cvt_r;
+        if let Some(status) = self.status {
+            return Ok(status);
+        }
+        let mut info: libc::siginfo_t = unsafe { mem::zeroed() };
+        cvt_r(|| unsafe {
+            libc::waitid(
+                libc::P_PID,
+                self.pid as libc::id_t,
+                &mut info,
+                libc::WEXITED | libc::WNOWAIT,
+            )
+        })?;
+        // `waitid` reports the event as a `si_code` and `si_status` pair rather
+        // than as a wait status, so re-encode it the way `waitpid` would have.
+        let status = unsafe { info.si_status() };
+        let status = match info.si_code {
+            libc::CLD_EXITED => (status & 0xff) << 8,
+            libc::CLD_KILLED => status & 0x7f,
+            libc::CLD_DUMPED => (status & 0x7f) | 0x80,
+            _ => {
+                return Err(Error::new_const(
+                    ErrorKind::Other,
+                    &"waitid reported an event other than process exit",
+                ));
+            }
+        };
+        Ok(ExitStatus::new(status))
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn peek_wait(&self) -> io::Result<ExitStatus> {
+        Err(Error::new_const(
+            ErrorKind::Unsupported,
+            &"waiting without reaping is not supported on this platform",
+        ))
+    }
 }
 
 /// Unix exit statuses
@@ -555,12 +660,12 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
             write!(f, "exit status: {}", code)
         } else if let Some(signal) = self.signal() {
             if self.core_dumped() {
-                write!(f, "signal: {} (core dumped)", signal)
+                write!(f, "signal: {} (core dumped)", DisplaySignal(signal))
             } else {
-                write!(f, "signal: {}", signal)
+                write!(f, "signal: {}", DisplaySignal(signal))
             }
         } else if let Some(signal) = self.stopped_signal() {
-            write!(f, "stopped (not terminated) by signal: {}", signal)
+            write!(f, "stopped (not terminated) by signal: {}", DisplaySignal(signal))
         } else if self.continued() {
             write!(f, "continued (WIFCONTINUED)")
         } else {
@@ -569,6 +674,52 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     }
 }
 
+/// The signals that are given by name when an `ExitStatus` is displayed.
+static SIGNAL_NAMES: &[(c_int, &str)] = &[
+    (libc::SIGHUP, "SIGHUP"),
+    (libc::SIGINT, "SIGINT"),
+    (libc::SIGQUIT, "SIGQUIT"),
+    (libc::SIGILL, "SIGILL"),
+    (libc::SIGTRAP, "SIGTRAP"),
+    (libc::SIGABRT, "SIGABRT"),
+    (libc::SIGBUS, "SIGBUS"),
+    (libc::SIGFPE, "SIGFPE"),
+    (libc::SIGKILL, "SIGKILL"),
+    (libc::SIGUSR1, "SIGUSR1"),
+    (libc::SIGSEGV, "SIGSEGV"),
+    (libc::SIGUSR2, "SIGUSR2"),
+    (libc::SIGPIPE, "SIGPIPE"),
+    (libc::SIGALRM, "SIGALRM"),
+    (libc::SIGTERM, "SIGTERM"),
+    (libc::SIGCHLD, "SIGCHLD"),
+    (libc::SIGCONT, "SIGCONT"),
+    (libc::SIGSTOP, "SIGSTOP"),
+    (libc::SIGTSTP, "SIGTSTP"),
+    (libc::SIGTTIN, "SIGTTIN"),
+    (libc::SIGTTOU, "SIGTTOU"),
+    (libc::SIGURG, "SIGURG"),
+    (libc::SIGXCPU, "SIGXCPU"),
+    (libc::SIGXFSZ, "SIGXFSZ"),
+    (libc::SIGVTALRM, "SIGVTALRM"),
+    (libc::SIGPROF, "SIGPROF"),
+    (libc::SIGWINCH, "SIGWINCH"),
+    (libc::SIGIO, "SIGIO"),
+    (libc::SIGSYS, "SIGSYS"),
+];
+
+/// Displays a signal number along with its name, such as `SIGSEGV (11)`, or
+/// just the number if the signal is not a common one.
+struct DisplaySignal(c_int);
+
+impl fmt::Display for DisplaySignal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match SIGNAL_NAMES.iter().find(|&&(signal, _)| signal == self.0) {
+            Some((_, name)) => write!(f, "{} ({})", name, self.0),
+            None => write!(f, "{}", self.0),
+        }
+    }
+}
+
 #[derive(PartialEq, Eq, Clone, Copy, Debug)]
 pub struct ExitStatusError(NonZero_c_int);
 
@@ -584,6 +735,239 @@ pub fn code(self) -> Option<NonZeroI32> {
     }
 }
 
+/// A descriptor that becomes readable when a child process exits, so that
+/// child exits can be waited for with `poll` or `epoll` along with other I/O,
+/// rather than with a `SIGCHLD` handler writing to a self-pipe.
+///
+/// This is a `signalfd` for `SIGCHLD`, which only receives the signal while
+/// it is blocked. [`ChildExitFd::new`] blocks it in the calling thread, and
+/// threads spawned afterwards inherit that mask, but any thread that already
+/// exists must block it too: the kernel hands the signal to a thread that
+/// doesn't block it if there is one, and the default disposition discards it
+/// without the descriptor ever becoming readable. Children spawned through
+/// `Command` start with an empty signal mask, so the blocked `SIGCHLD` is not
+/// passed on to them.
+///
+/// The standard library installs no `SIGCHLD` handler of its own, and
+/// `Child::wait` just calls `waitpid` on its own pid. [`ChildExitFd::reap`],
+/// however, reaps every child that has exited, including children that are
+/// still owned by a `Child`: waiting on those afterwards fails with `ECHILD`.
+#[allow(dead_code)] // sys isn't exported yet
+pub struct ChildExitFd {
+    fd: FileDesc,
+}
+
+#[allow(dead_code)] // sys isn't exported yet
+impl ChildExitFd {
+    /// Blocks `SIGCHLD` in the calling thread and creates the descriptor,
+    /// which is non-blocking and closed on exec.
+    ///
+    /// Children that exited before this was called have not raised a signal
+    /// the descriptor can see, so call [`ChildExitFd::reap`] once right away
+    /// to collect them.
+    #[cfg(target_os = "linux")]
+    pub fn new() -> io::Result<ChildExitFd> {
+        use crate::mem::MaybeUninit;
+        use crate::sys::cvt_nz;
+
+        unsafe {
+            let mut set = MaybeUninit::<libc::sigset_t>::uninit();
+            cvt(sigemptyset(set.as_mut_ptr()))?;
+            cvt(sigaddset(set.as_mut_ptr(), libc::SIGCHLD))?;
+            cvt_nz(libc::pthread_sigmask(libc::SIG_BLOCK, set.as_ptr(), ptr::null_mut()))?;
+            let flags = libc::SFD_CLOEXEC | libc::SFD_NONBLOCK;
+            let fd = cvt(libc::signalfd(-1, set.as_ptr(), flags))?;
+            Ok(ChildExitFd { fd: FileDesc::new(fd) })
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn new() -> io::Result<ChildExitFd> {
+        Err(Error::new_const(
+            ErrorKind::Unsupported,
+            &"signalfd-based child reaping is not supported on this platform",
+        ))
+    }
+
+    /// The descriptor to wait on for readability.
+    pub fn fd(&self) -> &FileDesc {
+        &self.fd
+    }
+
+    /// Reaps every child that has exited, returning their pids and exit
+    /// statuses. This never blocks, and returns an empty list if no child has
+    /// exited since the last call.
+    ///
+    /// Pending signals of the same kind are merged, so a single notification
+    /// may stand for any number of exits, and its `ssi_pid` can't be trusted
+    /// to name them all. The pending notifications are consumed first, and
+    /// the children are then reaped with `waitpid` until none is left, so
+    /// that a child exiting in between raises a new notification instead of
+    /// being missed.
+    pub fn reap(&self) -> io::Result<Vec<(pid_t, ExitStatus)>> {
+        // Reads return whole `signalfd_siginfo` records, 128 bytes each.
+        let mut buf = [0u8; 8 * 128];
+        loop {
+            match self.fd.read(&mut buf) {
+                Ok(n) if n == buf.len() => {}
+                Ok(_) => break,
+                Err(ref e) if e.kind() == ErrorKind::WouldBlock => break,
+                Err(ref e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        let mut reaped = Vec::new();
+        loop {
+            let mut status = 0 as c_int;
+            match unsafe { libc::waitpid(-1, &mut status, libc::WNOHANG) } {
+                0 => break,
+                -1 => match sys::os::errno() {
+                    libc::ECHILD => break,
+                    libc::EINTR => {}
+                    errno => return Err(Error::from_raw_os_error(errno)),
+                },
+                pid => reaped.push((pid, ExitStatus::new(status))),
+            }
+        }
+        Ok(reaped)
+    }
+}
+
+/// Options for [`daemonize`]. Every option is enabled by default.
+#[derive(Clone, Debug)]
+#[allow(dead_code)] // sys isn't exported yet
+pub struct DaemonOptions {
+    chdir_root: bool,
+    reset_umask: bool,
+    redirect_stdio: bool,
+}
+
+#[allow(dead_code)] // sys isn't exported yet
+impl DaemonOptions {
+    pub fn new() -> DaemonOptions {
+        DaemonOptions { chdir_root: true, reset_umask: true, redirect_stdio: true }
+    }
+
+    /// Whether the daemon changes its working directory to `/`, so that it
+    /// doesn't keep the filesystem it was started from busy and prevent it
+    /// from being unmounted.
+    pub fn chdir_root(&mut self, chdir_root: bool) -> &mut DaemonOptions {
+        self.chdir_root = chdir_root;
+        self
+    }
+
+    /// Whether the daemon's umask is reset to 0, so that the permissions it
+    /// asks for when creating files are used as given instead of being
+    /// restricted by whatever umask it happened to inherit.
+    pub fn reset_umask(&mut self, reset_umask: bool) -> &mut DaemonOptions {
+        self.reset_umask = reset_umask;
+        self
+    }
+
+    /// Whether the standard input, output and error of the daemon are
+    /// redirected to `/dev/null`, so that it doesn't hold on to the terminal
+    /// it was started from, and reading or writing them doesn't fail once
+    /// that terminal is gone.
+    pub fn redirect_stdio(&mut self, redirect_stdio: bool) -> &mut DaemonOptions {
+        self.redirect_stdio = redirect_stdio;
+        self
+    }
+}
+
+/// Turns the calling process into a daemon, configured by `opts`.
+///
+/// This forks twice. The first child starts a new session with `setsid`,
+/// which detaches it from the controlling terminal, then forks the daemon and
+/// exits right away, so that the daemon is not a session leader and can never
+/// acquire a controlling terminal again. The daemon is thereby reparented to
+/// init, or to the nearest subreaper, which reaps it once it exits.
+///
+/// This only ever returns `Ok` in the daemon. The original process reaps the
+/// first child, so that no zombie is left behind, waits for the daemon to
+/// finish setting itself up and then exits with status 0 through `_exit`,
+/// like `daemon(3)` does. If anything fails in either child, that child
+/// exits instead, and the error is returned in the original process.
+///
+/// Only the calling thread survives a fork, and any lock another thread held
+/// at that point stays locked forever in the daemon, so this should be called
+/// before any thread is spawned. For the same reason, only async-signal-safe
+/// functions are used until this returns. Nothing is flushed: unwritten data
+/// in buffers like that of `io::stdout` is carried over into the daemon
+/// alone, and ends up in `/dev/null` if its output is redirected.
+#[allow(dead_code)] // sys isn't exported yet
+pub fn daemonize(opts: &DaemonOptions) -> io::Result<()> {
+    use crate::sys::cvt_r;
+
+    // Failures in the children are reported over this pipe as a big-endian
+    // errno. It reaches end of file once the first child has exited and the
+    // daemon has closed its end, which it only does after setting itself up.
+    let (input, output) = sys::pipe::anon_pipe()?;
+    let pid = cvt(unsafe { libc::fork() })?;
+
+    if pid == 0 {
+        drop(input);
+        match unsafe { daemon_child(opts) } {
+            Ok(()) => return Ok(()),
+            Err(err) => {
+                let errno = err.raw_os_error().unwrap_or(libc::EINVAL).to_be_bytes();
+                // pipe I/O up to PIPE_BUF bytes should be atomic
+                let _ = output.write(&errno);
+                unsafe { libc::_exit(1) }
+            }
+        }
+    }
+
+    drop(output);
+    let mut status = 0 as c_int;
+    cvt_r(|| unsafe { libc::waitpid(pid, &mut status, 0) })?;
+
+    let mut errno = [0; 4];
+    loop {
+        match input.read(&mut errno) {
+            Ok(0) => unsafe { libc::_exit(0) },
+            Ok(4) => return Err(Error::from_raw_os_error(i32::from_be_bytes(errno))),
+            Err(ref e) if e.kind() == ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+            Ok(..) => {
+                return Err(Error::new_const(
+                    ErrorKind::Other,
+                    &"short read on the daemonize status pipe",
+                ));
+            }
+        }
+    }
+}
+
+/// The part of [`daemonize`] that runs in the first child. This only
+/// returns `Ok` in the daemon, as the first child exits once it has forked.
+unsafe fn daemon_child(opts: &DaemonOptions) -> io::Result<()> {
+    use crate::sys::cvt_r;
+
+    cvt(libc::setsid())?;
+    if cvt(libc::fork())? != 0 {
+        libc::_exit(0);
+    }
+
+    if opts.chdir_root {
+        cvt(libc::chdir(b"/\0".as_ptr() as *const libc::c_char))?;
+    }
+    if opts.reset_umask {
+        libc::umask(0);
+    }
+    if opts.redirect_stdio {
+        let null = b"/dev/null\0".as_ptr() as *const libc::c_char;
+        let fd = cvt_r(|| libc::open(null, libc::O_RDWR))?;
+        for &target in &[libc::STDIN_FILENO, libc::STDOUT_FILENO, libc::STDERR_FILENO] {
+            cvt_r(|| libc::dup2(fd, target))?;
+        }
+        if fd > libc::STDERR_FILENO {
+            libc::close(fd);
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 #[path = "process_unix/tests.rs"]
 mod tests;
@@ -14,8 +14,11 @@ fn exitstatus_display_tests() {
 
     let t = |v, s| assert_eq!(s, format!("{}", <ExitStatus as ExitStatusExt>::from_raw(v)));
 
-    t(0x0000f, "signal: 15");
-    t(0x0008b, "signal: 11 (core dumped)");
+    t(0x0000f, "signal: SIGTERM (15)");
+    t(0x0008b, "signal: SIGSEGV (11) (core dumped)");
+    t(0x00009, "signal: SIGKILL (9)");
+    t(0x00086, "signal: SIGABRT (6) (core dumped)");
+    t(0x00002, "signal: SIGINT (2)");
     t(0x00000, "exit status: 0");
     t(0x0ff00, "exit status: 255");
 
@@ -24,7 +27,9 @@ fn exitstatus_display_tests() {
     // The purpose of this test is to test our string formatting, not our understanding of the wait
     // status magic numbers.  So restrict these to Linux.
     if cfg!(target_os = "linux") {
-        t(0x0137f, "stopped (not terminated) by signal: 19");
+        t(0x0137f, "stopped (not terminated) by signal: SIGSTOP (19)");
+        // Real-time signals have no name of their own.
+        t(0x00022, "signal: 34");
         t(0x0ffff, "continued (WIFCONTINUED)");
     }
 
@@ -55,3 +60,282 @@ fn test_command_fork_no_unwind() {
     let signal = status.signal().expect("expected child process to die of signal");
     assert!(signal == libc::SIGABRT || signal == libc::SIGILL || signal == libc::SIGTRAP);
 }
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_peek_exit_status_leaves_child_reapable() {
+    use crate::os::unix::process::ChildExt;
+
+    let mut child = Command::new("sh").arg("-c").arg("exit 3").spawn().expect("failed to spawn");
+    let peeked = child.peek_exit_status().expect("failed to peek exit status");
+    assert_eq!(peeked.code(), Some(3));
+
+    // Peeking again still sees the zombie.
+    assert_eq!(child.peek_exit_status().expect("failed to peek exit status"), peeked);
+
+    let status = child.wait().expect("child was reaped by the peek");
+    assert_eq!(status, peeked);
+}
+
+#[test]
+#[cfg_attr(any(target_os = "vxworks", target_os = "emscripten"), ignore)]
+fn test_output_capped_truncates_runaway_child() {
+    use crate::time::{Duration, Instant};
+
+    let start = Instant::now();
+    let (output, truncated) = Command::new("sh")
+        .arg("-c")
+        .arg("while :; do echo 0123456789abcdef; done")
+        .output_capped(4096)
+        .expect("failed to run child");
+    assert!(truncated);
+    assert_eq!(output.stdout.len(), 4096);
+    assert!(output.stdout.starts_with(b"0123456789abcdef\n"));
+    assert_eq!(output.status.signal(), Some(libc::SIGKILL));
+    assert!(start.elapsed() < Duration::from_secs(30));
+}
+
+#[test]
+#[cfg_attr(any(target_os = "vxworks", target_os = "emscripten"), ignore)]
+fn test_output_capped_under_cap() {
+    let (output, truncated) = Command::new("sh")
+        .arg("-c")
+        .arg("echo out; echo err >&2; exit 7")
+        .output_capped(4096)
+        .expect("failed to run child");
+    assert!(!truncated);
+    assert_eq!(output.stdout, b"out\n");
+    assert_eq!(output.stderr, b"err\n");
+    assert_eq!(output.status.code(), Some(7));
+}
+
+#[test]
+#[cfg_attr(any(target_os = "vxworks", target_os = "emscripten"), ignore)]
+fn test_output_with_input_tolerates_early_exit() {
+    // Far more than fits in a pipe buffer, so the child exits long before
+    // all of it has been written.
+    let input = vec![b'x'; 4 * 1024 * 1024];
+
+    let output = Command::new("sh")
+        .arg("-c")
+        .arg("head -c 4; echo done >&2")
+        .output_with_input(&input, true)
+        .expect("failed to run child");
+    assert!(output.status.success());
+    assert_eq!(output.stdout, b"xxxx");
+    assert_eq!(output.stderr, b"done\n");
+
+    let err = Command::new("sh")
+        .arg("-c")
+        .arg("head -c 4")
+        .output_with_input(&input, false)
+        .unwrap_err();
+    assert_eq!(err.kind(), crate::io::ErrorKind::BrokenPipe);
+}
+
+#[test]
+#[cfg_attr(any(target_os = "vxworks", target_os = "emscripten"), ignore)]
+fn test_output_with_input_feeds_stdin() {
+    // Echoing the input back while it is still being written only works if
+    // writing and reading are interleaved.
+    let input: Vec<u8> = (0..1024 * 1024).map(|i| (i % 251) as u8).collect();
+    let output = Command::new("cat").output_with_input(&input, false).expect("failed to run child");
+    assert!(output.status.success());
+    assert_eq!(output.stdout, input);
+}
+
+#[test]
+#[cfg_attr(any(target_os = "vxworks", target_os = "emscripten"), ignore)]
+fn test_rlimit_nofile() {
+    let limit = libc::RLIMIT_NOFILE as i32;
+
+    let output = Command::new("sh")
+        .arg("-c")
+        .arg("ulimit -n")
+        .rlimit(limit, 8, 8)
+        .output()
+        .expect("failed to run child");
+    assert!(output.status.success());
+    assert_eq!(output.stdout, b"8\n");
+
+    // Opening descriptors 3 through 8 needs more than 8 descriptors, so the
+    // redirections fail and take the shell down with them.
+    let open_many = "exec 3</dev/null 4</dev/null 5</dev/null 6</dev/null 7</dev/null 8</dev/null";
+    let status = Command::new("sh")
+        .arg("-c")
+        .arg(open_many)
+        .rlimit(limit, 8, 8)
+        .stderr(crate::process::Stdio::null())
+        .status()
+        .expect("failed to run child");
+    assert!(!status.success());
+
+    // Without the limit the same command is fine.
+    let status = Command::new("sh").arg("-c").arg(open_many).status().unwrap();
+    assert!(status.success());
+}
+
+#[test]
+#[cfg_attr(any(target_os = "vxworks", target_os = "emscripten"), ignore)]
+fn test_rlimit_soft_above_hard_fails_spawn() {
+    let err = Command::new("true")
+        .rlimit(libc::RLIMIT_NOFILE as i32, 16, 8)
+        .spawn()
+        .expect_err("spawn succeeded with soft limit above hard limit");
+    assert_eq!(err.raw_os_error(), Some(libc::EINVAL));
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_controlling_tty() {
+    use crate::ffi::{CStr, OsStr};
+    use crate::fs::File;
+    use crate::io::Read;
+    use crate::os::unix::ffi::OsStrExt;
+    use crate::os::unix::io::FromRawFd;
+
+    let (mut master, slave) = unsafe {
+        let fd = libc::posix_openpt(libc::O_RDWR | libc::O_NOCTTY);
+        assert!(fd >= 0, "posix_openpt failed: {}", crate::io::Error::last_os_error());
+        let master = File::from_raw_fd(fd);
+        assert_eq!(libc::grantpt(fd), 0);
+        assert_eq!(libc::unlockpt(fd), 0);
+        let mut buf = [0 as libc::c_char; 64];
+        assert_eq!(libc::ptsname_r(fd, buf.as_mut_ptr(), buf.len()), 0);
+        (master, CStr::from_ptr(buf.as_ptr()).to_bytes().to_vec())
+    };
+
+    // Opening `/dev/tty` only succeeds in a process with a controlling
+    // terminal, and the test harness may well not have one to inherit.
+    let status = Command::new("sh")
+        .arg("-c")
+        .arg(": </dev/tty && echo ok")
+        .controlling_tty(OsStr::from_bytes(&slave))
+        .status()
+        .expect("failed to run child");
+    assert!(status.success());
+
+    // Once the last descriptor of the slave side is closed, reading the
+    // master fails with `EIO` instead of reporting end of file.
+    let mut output = Vec::new();
+    let mut buf = [0; 64];
+    loop {
+        match master.read(&mut buf) {
+            Ok(0) | Err(_) => break,
+            Ok(n) => output.extend_from_slice(&buf[..n]),
+        }
+    }
+    assert_eq!(output, b"ok\r\n");
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_daemonize_detaches() {
+    use super::{daemonize, DaemonOptions};
+    use crate::convert::TryInto;
+    use crate::sys::pipe::anon_pipe;
+
+    // Daemonize a forked copy of the test process rather than the test harness
+    // itself. The harness is multithreaded, so only async-signal-safe code may
+    // run in the children.
+    let (report_in, report_out) = anon_pipe().unwrap();
+    let (go_in, go_out) = anon_pipe().unwrap();
+    let pid = unsafe { libc::fork() };
+    assert!(pid >= 0);
+    if pid == 0 {
+        if daemonize(&DaemonOptions::new()).is_err() {
+            unsafe { libc::_exit(2) };
+        }
+        // Wait for the test to reap the process that called `daemonize`, by
+        // which point the first child has been reaped as well.
+        let _ = go_in.read(&mut [0]);
+        let mut buf = [0; 1];
+        let stdin_eof = unsafe { libc::read(libc::STDIN_FILENO, buf.as_mut_ptr().cast(), 1) };
+        let tty = unsafe { libc::open(b"/dev/tty\0".as_ptr().cast(), libc::O_RDWR) };
+        let tty_errno = if tty < 0 { crate::sys::os::errno() } else { 0 };
+        let report = unsafe {
+            [libc::getpid(), libc::getppid(), libc::getsid(0), stdin_eof as i32, tty_errno]
+        };
+        let mut bytes = [0; 20];
+        for (chunk, value) in bytes.chunks_mut(4).zip(&report) {
+            chunk.copy_from_slice(&value.to_ne_bytes());
+        }
+        let _ = report_out.write(&bytes);
+        unsafe { libc::_exit(0) };
+    }
+
+    drop(report_out);
+    drop(go_in);
+    let mut status = 0;
+    assert_eq!(unsafe { libc::waitpid(pid, &mut status, 0) }, pid);
+    assert_eq!(status, 0, "daemonize failed");
+    go_out.write(&[1]).unwrap();
+    drop(go_out);
+
+    let mut bytes = [0; 20];
+    let mut len = 0;
+    while len < bytes.len() {
+        match report_in.read(&mut bytes[len..]).unwrap() {
+            0 => panic!("the daemon only reported {} bytes", len),
+            n => len += n,
+        }
+    }
+    let report: Vec<i32> =
+        bytes.chunks(4).map(|chunk| i32::from_ne_bytes(chunk.try_into().unwrap())).collect();
+    let (daemon, parent, session, stdin_eof, tty_errno) =
+        (report[0], report[1], report[2], report[3], report[4]);
+
+    // The daemon is in a new session, led by the first child, which has
+    // exited, so the daemon has been reparented to init or a subreaper.
+    let test = unsafe { libc::getpid() };
+    assert_ne!(session, unsafe { libc::getsid(0) });
+    assert_ne!(session, daemon);
+    assert!(parent != session && parent != pid && parent != test, "parent is {}", parent);
+    // It has no controlling terminal, and reads end of file from stdin.
+    assert_eq!(tty_errno, libc::ENXIO);
+    assert_eq!(stdin_eof, 0);
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_child_exit_fd_reaps_children() {
+    use super::ChildExitFd;
+    use crate::collections::HashMap;
+    use crate::env;
+
+    // `SIGCHLD` must be blocked in every thread for the descriptor to see it,
+    // and reaping every child would steal those of concurrent tests, so run
+    // in a fresh copy of the test binary where this is the only thread.
+    if env::var_os("RUST_TEST_CHILD_EXIT_FD").is_none() {
+        let status = Command::new(env::current_exe().unwrap())
+            .arg("test_child_exit_fd_reaps_children")
+            .arg("--test-threads=1")
+            .env("RUST_TEST_CHILD_EXIT_FD", "1")
+            .status()
+            .expect("failed to run the test binary");
+        assert!(status.success(), "{}", status);
+        return;
+    }
+
+    let exits = ChildExitFd::new().expect("failed to create signalfd");
+    assert!(exits.reap().unwrap().is_empty());
+
+    let mut expected = HashMap::new();
+    for code in 1..=3 {
+        let child = Command::new("sh").arg("-c").arg(format!("exit {}", code)).spawn().unwrap();
+        // Dropping the `Child` leaves the reaping to the descriptor.
+        expected.insert(child.id() as libc::pid_t, code);
+    }
+
+    let mut reaped = HashMap::new();
+    while reaped.len() < expected.len() {
+        let mut pfd = libc::pollfd { fd: exits.fd().raw(), events: libc::POLLIN, revents: 0 };
+        let n = unsafe { libc::poll(&mut pfd, 1, 30_000) };
+        assert_eq!(n, 1, "no child exit was reported");
+        for (pid, status) in exits.reap().unwrap() {
+            assert!(reaped.insert(pid, status.code().unwrap()).is_none());
+        }
+    }
+    assert_eq!(reaped, expected);
+    assert!(exits.reap().unwrap().is_empty());
+}
@@ -29,6 +29,18 @@ pub fn spawn(
                 &"nul byte found in provided data",
             ));
         }
+        if !self.get_rlimits().is_empty() {
+            return Err(io::Error::new_const(
+                ErrorKind::Unsupported,
+                &"resource limits for child processes are not supported on this platform",
+            ));
+        }
+        if self.get_controlling_tty().is_some() {
+            return Err(io::Error::new_const(
+                ErrorKind::Unsupported,
+                &"controlling terminals for child processes are not supported on this platform",
+            ));
+        }
         let (ours, theirs) = self.setup_io(default, needs_stdin)?;
         let mut p = Process { pid: 0, status: None };
 
@@ -175,6 +187,13 @@ pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
             Ok(Some(ExitStatus::new(status)))
         }
     }
+
+    pub fn peek_wait(&self) -> io::Result<ExitStatus> {
+        Err(Error::new_const(
+            ErrorKind::Unsupported,
+            &"waiting without reaping is not supported on this platform",
+        ))
+    }
 }
 
 /// Unix exit statuses
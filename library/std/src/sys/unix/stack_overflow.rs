@@ -44,13 +44,13 @@ mod imp {
     use crate::ptr;
     use crate::thread;
 
-    use libc::MAP_FAILED;
     use libc::{mmap, munmap};
     use libc::{sigaction, sighandler_t, SA_ONSTACK, SA_SIGINFO, SIGBUS, SIG_DFL};
     use libc::{sigaltstack, SIGSTKSZ, SS_DISABLE};
     use libc::{MAP_ANON, MAP_PRIVATE, PROT_NONE, PROT_READ, PROT_WRITE, SIGSEGV};
 
     use crate::sync::atomic::{AtomicBool, AtomicPtr, Ordering};
+    use crate::sys::unix::cvt_mmap;
     use crate::sys::unix::os::page_size;
     use crate::sys_common::thread_info;
 
@@ -143,17 +143,17 @@ pub unsafe fn cleanup() {
     }
 
     unsafe fn get_stackp() -> *mut libc::c_void {
-        let stackp = mmap(
+        let stackp = match cvt_mmap(mmap(
             ptr::null_mut(),
             SIGSTKSZ + page_size(),
             PROT_READ | PROT_WRITE,
             MAP_PRIVATE | MAP_ANON,
             -1,
             0,
-        );
-        if stackp == MAP_FAILED {
-            panic!("failed to allocate an alternative stack: {}", io::Error::last_os_error());
-        }
+        )) {
+            Ok(stackp) => stackp,
+            Err(e) => panic!("failed to allocate an alternative stack: {}", e),
+        };
         let guard_result = libc::mprotect(stackp, page_size(), PROT_NONE);
         if guard_result != 0 {
             panic!("failed to set up alternative stack guard page: {}", io::Error::last_os_error());
@@ -1,6 +1,12 @@
+#[cfg(test)]
+mod tests;
+
 use crate::io::{self, IoSlice, IoSliceMut};
 use crate::mem::ManuallyDrop;
+use crate::sync::atomic::{AtomicBool, Ordering};
 use crate::sys::fd::FileDesc;
+use crate::sys_common::memchr;
+use crate::sys_common::mutex::StaticMutex;
 
 pub struct Stdin(());
 pub struct Stdout(());
@@ -58,13 +64,60 @@ pub const fn new() -> Stderr {
     }
 }
 
+// Whether writes to stderr go through `STDERR_BUF`. Off unless a program asks for it, so that
+// by default everything reaches the file descriptor before a crash can lose it.
+static STDERR_BUFFERED: AtomicBool = AtomicBool::new(false);
+static STDERR_LOCK: StaticMutex = StaticMutex::new();
+// Only accessed while holding `STDERR_LOCK`.
+static mut STDERR_BUF: LineBuffer = LineBuffer::new();
+
+/// Turns buffering of stderr on or off. Turning it off flushes whatever is still buffered.
+pub fn set_stderr_buffered(buffered: bool) -> io::Result<()> {
+    unsafe {
+        // The flag only changes under the lock, and the flush happens before the lock is released,
+        // so no write can slip in between and reach the descriptor ahead of the buffered output.
+        let _guard = STDERR_LOCK.lock();
+        STDERR_BUFFERED.store(buffered, Ordering::Relaxed);
+        if buffered { Ok(()) } else { STDERR_BUF.flush(&mut RawStderr) }
+    }
+}
+
+fn flush_stderr() -> io::Result<()> {
+    unsafe {
+        let _guard = STDERR_LOCK.lock();
+        STDERR_BUF.flush(&mut RawStderr)
+    }
+}
+
+/// Flushes buffered stderr output at exit.
+pub fn cleanup() {
+    let _ = flush_stderr();
+}
+
 impl io::Write for Stderr {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        ManuallyDrop::new(FileDesc::new(libc::STDERR_FILENO)).write(buf)
+        if STDERR_BUFFERED.load(Ordering::Relaxed) {
+            unsafe {
+                let _guard = STDERR_LOCK.lock();
+                // Buffering may have been turned off, and the buffer flushed, since the check
+                // above.
+                if STDERR_BUFFERED.load(Ordering::Relaxed) {
+                    STDERR_BUF.write(&mut RawStderr, buf)
+                } else {
+                    RawStderr.write(buf)
+                }
+            }
+        } else {
+            RawStderr.write(buf)
+        }
     }
 
     fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
-        ManuallyDrop::new(FileDesc::new(libc::STDERR_FILENO)).write_vectored(bufs)
+        if STDERR_BUFFERED.load(Ordering::Relaxed) {
+            io::default_write_vectored(|buf| self.write(buf), bufs)
+        } else {
+            RawStderr.write_vectored(bufs)
+        }
     }
 
     #[inline]
@@ -72,11 +125,118 @@ fn is_write_vectored(&self) -> bool {
         true
     }
 
+    fn flush(&mut self) -> io::Result<()> {
+        flush_stderr()
+    }
+}
+
+struct RawStderr;
+
+impl io::Write for RawStderr {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        ManuallyDrop::new(FileDesc::new(libc::STDERR_FILENO)).write(buf)
+    }
+
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        ManuallyDrop::new(FileDesc::new(libc::STDERR_FILENO)).write_vectored(bufs)
+    }
+
     fn flush(&mut self) -> io::Result<()> {
         Ok(())
     }
 }
 
+const STDERR_BUF_SIZE: usize = 1024;
+
+/// A line buffer in the style of `LineWriter`, kept in a fixed-size array so that it can live in
+/// a static and never allocates.
+///
+/// Complete lines are written out as soon as they arrive, together with anything already
+/// buffered, and only a trailing partial line is held back. A write that doesn't fit in the
+/// buffer goes straight through.
+struct LineBuffer {
+    buf: [u8; STDERR_BUF_SIZE],
+    len: usize,
+}
+
+impl LineBuffer {
+    const fn new() -> LineBuffer {
+        LineBuffer { buf: [0; STDERR_BUF_SIZE], len: 0 }
+    }
+
+    /// Like `Write::write`, this may accept only part of `data`. Once bytes have been accepted
+    /// they are never dropped: if writing them out fails, they stay buffered and the error is
+    /// reported by the next write or flush instead.
+    fn write<W: io::Write>(&mut self, out: &mut W, data: &[u8]) -> io::Result<usize> {
+        let (lines, tail) = match memchr::memrchr(b'\n', data) {
+            Some(i) => data.split_at(i + 1),
+            None => {
+                if !self.push(data) {
+                    self.flush(out)?;
+                    if !self.push(data) {
+                        return out.write(data);
+                    }
+                }
+                return Ok(data.len());
+            }
+        };
+        if self.push(lines) {
+            // The lines are buffered, so they count as written even if the flush fails.
+            if self.flush(out).is_err() {
+                return Ok(lines.len());
+            }
+        } else {
+            self.flush(out)?;
+            let written = out.write(lines)?;
+            if written < lines.len() {
+                return Ok(written);
+            }
+        }
+        // A tail too long for the buffer is left to the next call, which writes it straight
+        // through.
+        if self.push(tail) { Ok(data.len()) } else { Ok(lines.len()) }
+    }
+
+    /// Appends `data` if there is room for all of it.
+    fn push(&mut self, data: &[u8]) -> bool {
+        match self.buf.get_mut(self.len..self.len + data.len()) {
+            Some(dst) => {
+                dst.copy_from_slice(data);
+                self.len += data.len();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Writes out everything buffered. Whatever can't be written stays at the front of the
+    /// buffer.
+    fn flush<W: io::Write>(&mut self, out: &mut W) -> io::Result<()> {
+        let mut written = 0;
+        let mut ret = Ok(());
+        while written < self.len {
+            match out.write(&self.buf[written..self.len]) {
+                Ok(0) => {
+                    ret = Err(io::Error::new_const(
+                        io::ErrorKind::WriteZero,
+                        &"failed to write the buffered data",
+                    ));
+                    break;
+                }
+                Ok(n) => written += n,
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => {
+                    ret = Err(e);
+                    break;
+                }
+            }
+        }
+        self.buf.copy_within(written..self.len, 0);
+        self.len -= written;
+        ret
+    }
+}
+
 pub fn is_ebadf(err: &io::Error) -> bool {
     err.raw_os_error() == Some(libc::EBADF as i32)
 }
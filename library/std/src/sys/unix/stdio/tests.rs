@@ -0,0 +1,105 @@
+use super::LineBuffer;
+use crate::io::{self, Write};
+
+/// Stands in for the stderr file descriptor, recording each write call.
+#[derive(Default)]
+struct CountingFd {
+    writes: usize,
+    written: Vec<u8>,
+}
+
+impl Write for CountingFd {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.writes += 1;
+        self.written.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn line_buffer_coalesces_writes() {
+    let mut fd = CountingFd::default();
+    let mut buf = LineBuffer::new();
+
+    // What `eprintln!` does: one write for each piece of the formatted line.
+    for piece in &["level=", "info", " msg=", "started", "\n"] {
+        buf.write(&mut fd, piece.as_bytes()).unwrap();
+    }
+    assert_eq!(fd.writes, 1);
+    assert_eq!(fd.written, b"level=info msg=started\n");
+
+    // A partial line is held back until its newline arrives.
+    buf.write(&mut fd, b"one\ntw").unwrap();
+    assert_eq!(fd.writes, 2);
+    buf.write(&mut fd, b"o\nthree").unwrap();
+    assert_eq!(fd.writes, 3);
+    assert_eq!(fd.written, b"level=info msg=started\none\ntwo\n");
+    buf.flush(&mut fd).unwrap();
+    assert_eq!(fd.writes, 4);
+    assert_eq!(fd.written, b"level=info msg=started\none\ntwo\nthree");
+
+    // Nothing to flush, nothing written.
+    buf.flush(&mut fd).unwrap();
+    assert_eq!(fd.writes, 4);
+}
+
+#[test]
+fn line_buffer_overflow() {
+    let mut fd = CountingFd::default();
+    let mut buf = LineBuffer::new();
+
+    // A line too long for the buffer still comes out whole and in order.
+    let long = vec![b'x'; super::STDERR_BUF_SIZE * 2];
+    buf.write(&mut fd, b"start ").unwrap();
+    buf.write(&mut fd, &long).unwrap();
+    buf.write(&mut fd, b" end\n").unwrap();
+    let mut expected = b"start ".to_vec();
+    expected.extend_from_slice(&long);
+    expected.extend_from_slice(b" end\n");
+    assert_eq!(fd.written, expected);
+}
+
+/// Fails every write while `failing` is set.
+#[derive(Default)]
+struct FlakyFd {
+    failing: bool,
+    written: Vec<u8>,
+}
+
+impl Write for FlakyFd {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.failing {
+            return Err(io::Error::from_raw_os_error(libc::EIO));
+        }
+        self.written.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn line_buffer_keeps_data_on_error() {
+    let mut fd = FlakyFd::default();
+    let mut buf = LineBuffer::new();
+
+    buf.write(&mut fd, b"one ").unwrap();
+    fd.failing = true;
+    // The line is accepted into the buffer, so the failed flush isn't reported yet...
+    assert_eq!(buf.write(&mut fd, b"two\nthree").unwrap(), 4);
+    // ...but the next write has to flush first, and fails without accepting anything.
+    let long = vec![b'x'; super::STDERR_BUF_SIZE];
+    assert_eq!(buf.write(&mut fd, &long).unwrap_err().raw_os_error(), Some(libc::EIO));
+    assert!(buf.flush(&mut fd).is_err());
+
+    // Nothing was lost along the way.
+    fd.failing = false;
+    buf.write(&mut fd, b"three\n").unwrap();
+    assert_eq!(fd.written, b"one two\nthree\n");
+}
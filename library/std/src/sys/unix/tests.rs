@@ -0,0 +1,45 @@
+use super::{cvt_mmap, decode_error_kind};
+use crate::io::ErrorKind;
+use crate::ptr;
+
+#[test]
+fn cvt_mmap_maps_map_failed_to_error() {
+    unsafe {
+        let len = 4096;
+        let addr = cvt_mmap(libc::mmap(
+            ptr::null_mut(),
+            len,
+            libc::PROT_READ | libc::PROT_WRITE,
+            libc::MAP_PRIVATE | libc::MAP_ANON,
+            -1,
+            0,
+        ))
+        .unwrap();
+        assert_eq!(libc::munmap(addr, len), 0);
+
+        // Mapping zero bytes is never allowed.
+        let err = cvt_mmap(libc::mmap(
+            ptr::null_mut(),
+            0,
+            libc::PROT_READ,
+            libc::MAP_PRIVATE | libc::MAP_ANON,
+            -1,
+            0,
+        ))
+        .unwrap_err();
+        assert_eq!(err.raw_os_error(), Some(libc::EINVAL));
+    }
+}
+
+#[test]
+fn decode_error_kind_aliased_errnos() {
+    // Whether or not each pair shares a value on this target, both of its
+    // members decode to the same kind.
+    assert_eq!(decode_error_kind(libc::EAGAIN), ErrorKind::WouldBlock);
+    assert_eq!(decode_error_kind(libc::EWOULDBLOCK), ErrorKind::WouldBlock);
+    assert_eq!(decode_error_kind(libc::ENOTSUP), ErrorKind::Unsupported);
+    assert_eq!(decode_error_kind(libc::EOPNOTSUPP), ErrorKind::Unsupported);
+    if cfg!(target_os = "linux") {
+        assert_eq!(libc::ENOTSUP, libc::EOPNOTSUPP);
+    }
+}
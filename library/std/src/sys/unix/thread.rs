@@ -229,11 +229,12 @@ pub unsafe fn init() -> Option<Guard> {
 #[cfg_attr(test, allow(dead_code))]
 pub mod guard {
     use libc::{mmap, mprotect};
-    use libc::{MAP_ANON, MAP_FAILED, MAP_FIXED, MAP_PRIVATE, PROT_NONE, PROT_READ, PROT_WRITE};
+    use libc::{MAP_ANON, MAP_FIXED, MAP_PRIVATE, PROT_NONE, PROT_READ, PROT_WRITE};
 
     use crate::io;
     use crate::ops::Range;
     use crate::sync::atomic::{AtomicUsize, Ordering};
+    use crate::sys::cvt_mmap;
     use crate::sys::os;
 
     // This is initialized in init() and only read from after
@@ -367,16 +368,18 @@ pub unsafe fn init() -> Option<Guard> {
             // read/write permissions and only then mprotect() it to
             // no permissions at all. See issue #50313.
             let stackaddr = get_stack_start_aligned()?;
-            let result = mmap(
+            let result = cvt_mmap(mmap(
                 stackaddr,
                 page_size,
                 PROT_READ | PROT_WRITE,
                 MAP_PRIVATE | MAP_ANON | MAP_FIXED,
                 -1,
                 0,
-            );
-            if result != stackaddr || result == MAP_FAILED {
-                panic!("failed to allocate a guard page: {}", io::Error::last_os_error());
+            ));
+            match result {
+                Ok(addr) if addr == stackaddr => {}
+                Ok(addr) => panic!("failed to allocate a guard page: mapped at {:p}", addr),
+                Err(e) => panic!("failed to allocate a guard page: {}", e),
             }
 
             let result = mprotect(stackaddr, page_size, PROT_NONE);
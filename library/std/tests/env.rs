@@ -138,3 +138,48 @@ fn var_to_os_string(var: Result<String, VarError>) -> Option<OsString> {
         }
     }
 }
+
+#[test]
+#[cfg_attr(target_os = "emscripten", ignore)]
+fn test_env_concurrent_readers_and_writers() {
+    use std::sync::Arc;
+    use std::thread;
+
+    let names: Arc<Vec<OsString>> = Arc::new((0..4).map(|_| make_rand_name()).collect());
+    for n in names.iter() {
+        set_var(n, "initial");
+    }
+
+    let readers: Vec<_> = (0..8)
+        .map(|_| {
+            let names = Arc::clone(&names);
+            thread::spawn(move || {
+                for _ in 0..1000 {
+                    for n in names.iter() {
+                        // Every read sees either the initial value or a complete write.
+                        let v = var_os(n).expect("variable disappeared");
+                        assert!(v == "initial" || v.to_str().unwrap().starts_with("updated-"));
+                    }
+                }
+            })
+        })
+        .collect();
+
+    let writer = {
+        let names = Arc::clone(&names);
+        thread::spawn(move || {
+            for i in 0..100 {
+                set_var(&names[i % names.len()], format!("updated-{}", i));
+            }
+        })
+    };
+
+    for reader in readers {
+        reader.join().unwrap();
+    }
+    writer.join().unwrap();
+
+    for n in names.iter() {
+        remove_var(n);
+    }
+}
@@ -0,0 +1,43 @@
+//! Checks that a program can ask the runtime to leave `SIGPIPE` at its
+//! default disposition, in which case writing to a closed pipe kills it.
+
+#![cfg(target_os = "linux")]
+#![feature(rustc_private)]
+
+extern crate libc;
+
+use std::env;
+use std::io::{self, Write};
+use std::os::unix::process::ExitStatusExt;
+use std::process::{Command, Stdio};
+
+// Picks the "default" behavior; see `std::os::unix::process`.
+#[no_mangle]
+static __rust_std_sigpipe: u8 = 1;
+
+#[test]
+fn default_sigpipe_kills_on_closed_pipe() {
+    if env::var_os("RUST_TEST_SIGPIPE_CHILD").is_some() {
+        // Wait for the parent to close the read end, then write until the
+        // signal arrives.
+        let mut stdout = io::stdout();
+        loop {
+            if let Err(e) = stdout.write_all(&[0; 4096]) {
+                panic!("write failed instead of raising SIGPIPE: {}", e);
+            }
+        }
+    }
+
+    let mut child = Command::new(env::current_exe().unwrap())
+        .arg("default_sigpipe_kills_on_closed_pipe")
+        .arg("--exact")
+        .env("RUST_TEST_SIGPIPE_CHILD", "1")
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+    drop(child.stdout.take());
+
+    let status = child.wait().unwrap();
+    assert_eq!(status.signal(), Some(libc::SIGPIPE), "{}", status);
+}